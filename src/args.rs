@@ -1,11 +1,10 @@
-use std::collections::HashMap;
-
 use codemap::{Span, Spanned};
+use indexmap::IndexMap;
 
 use crate::{
     common::Identifier,
     error::SassResult,
-    value::Value,
+    value::{ArgList, Value},
     {Cow, Token},
 };
 
@@ -35,7 +34,7 @@ impl FuncArgs {
 }
 
 #[derive(Debug, Clone)]
-pub(crate) struct CallArgs(pub HashMap<CallArg, SassResult<Spanned<Value>>>, pub Span);
+pub(crate) struct CallArgs(pub IndexMap<CallArg, SassResult<Spanned<Value>>>, pub Span);
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub(crate) enum CallArg {
@@ -61,7 +60,7 @@ impl CallArg {
 
 impl CallArgs {
     pub fn new(span: Span) -> Self {
-        CallArgs(HashMap::new(), span)
+        CallArgs(IndexMap::new(), span)
     }
 
     pub fn to_css_string(self, is_compressed: bool) -> SassResult<Spanned<String>> {
@@ -101,14 +100,14 @@ impl CallArgs {
     ///
     /// Removes the argument
     pub fn get_named<T: Into<Identifier>>(&mut self, val: T) -> Option<SassResult<Spanned<Value>>> {
-        self.0.remove(&CallArg::Named(val.into()))
+        self.0.shift_remove(&CallArg::Named(val.into()))
     }
 
     /// Get a positional argument by 0-indexed position
     ///
     /// Removes the argument
     pub fn get_positional(&mut self, val: usize) -> Option<SassResult<Spanned<Value>>> {
-        self.0.remove(&CallArg::Positional(val))
+        self.0.shift_remove(&CallArg::Positional(val))
     }
 
     pub fn get<T: Into<Identifier>>(
@@ -238,4 +237,27 @@ impl CallArgs {
 
         Ok(vals)
     }
+
+    /// Collect the remaining arguments into the value of a rest argument such
+    /// as `$args...`, keeping arguments passed by name as its keywords
+    pub fn into_arg_list(self) -> SassResult<ArgList> {
+        let mut positional = Vec::new();
+        let mut keywords = IndexMap::new();
+
+        for (arg, value) in self.0 {
+            match arg {
+                CallArg::Positional(idx) => positional.push((idx, value?)),
+                CallArg::Named(name) => {
+                    keywords.insert(name, value?);
+                }
+            }
+        }
+
+        positional.sort_by_key(|(idx, ..)| *idx);
+
+        Ok(ArgList::new(
+            positional.into_iter().map(|(.., value)| value).collect(),
+            keywords,
+        ))
+    }
 }
@@ -0,0 +1,45 @@
+/// The query of an `@at-root` rule, such as `(without: media)`, which
+/// determines the enclosing rules its contents are moved outside of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AtRootQuery {
+    /// Whether the named rules are kept (`with`) rather than excluded (`without`)
+    pub include: bool,
+
+    /// The lowercase names of at-rules, as well as the special values `rule`,
+    /// for style rules, and `all`
+    pub names: Vec<String>,
+}
+
+impl AtRootQuery {
+    fn has_name(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name)
+    }
+
+    fn all(&self) -> bool {
+        self.has_name("all")
+    }
+
+    pub fn excludes_style_rules(&self) -> bool {
+        (self.all() || self.has_name("rule")) != self.include
+    }
+
+    /// Whether the contents should be moved outside of an enclosing at-rule
+    /// named `name`
+    pub fn excludes_name(&self, name: &str) -> bool {
+        (self.all() || self.has_name(name)) != self.include
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `@at-root` without a query behaves as `@at-root (without: rule)`
+impl Default for AtRootQuery {
+    fn default() -> Self {
+        Self {
+            include: false,
+            names: vec!["rule".to_owned()],
+        }
+    }
+}
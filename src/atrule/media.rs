@@ -1,12 +1,12 @@
-#![allow(dead_code)]
 use std::fmt;
 
-use crate::{parse::Stmt, selector::Selector};
+use crate::{parse::Stmt, selector::Selector, utils::is_name};
 
 #[derive(Debug, Clone)]
 pub(crate) struct MediaRule {
+    #[allow(dead_code)]
     pub super_selector: Selector,
-    pub query: String,
+    pub queries: Vec<MediaQuery>,
     pub body: Vec<Stmt>,
 }
 
@@ -24,13 +24,12 @@ pub(crate) struct MediaQuery {
 
     /// Feature queries, including parentheses.
     pub features: Vec<String>,
+
+    /// Whether `self.features` are joined by `and` rather than `or`
+    pub conjunction: bool,
 }
 
 impl MediaQuery {
-    pub fn is_condition(&self) -> bool {
-        self.modifier.is_none() && self.media_type.is_none()
-    }
-
     pub fn matches_all_types(&self) -> bool {
         self.media_type.is_none()
             || self
@@ -39,16 +38,62 @@ impl MediaQuery {
                 .map_or(false, |v| v.to_ascii_lowercase() == "all")
     }
 
-    pub fn condition(features: Vec<String>) -> Self {
+    pub fn condition(features: Vec<String>, conjunction: bool) -> Self {
         Self {
             modifier: None,
             media_type: None,
             features,
+            conjunction,
+        }
+    }
+
+    fn media_type(media_type: String, modifier: Option<String>, features: Vec<String>) -> Self {
+        Self {
+            modifier,
+            media_type: Some(media_type),
+            features,
+            conjunction: true,
         }
     }
 
+    /// Parse a comma-separated list of plain CSS media queries, such as the
+    /// query of a `@media` rule once its interpolation has been resolved
+    pub fn parse_list(query: &str) -> Result<Vec<Self>, &'static str> {
+        MediaQueryParser {
+            chars: query.chars().collect(),
+            cursor: 0,
+        }
+        .parse()
+    }
+
+    /// Merge every query in `queries1` with every query in `queries2`, as
+    /// required for nested `@media` rules.
+    ///
+    /// Returns `None` if any pair of queries can't be represented as a single
+    /// query. Pairs that can never match are omitted, so the result is empty
+    /// when nothing could match both lists.
+    pub fn merge_lists(queries1: &[Self], queries2: &[Self]) -> Option<Vec<Self>> {
+        let mut merged = Vec::new();
+
+        for query1 in queries1 {
+            for query2 in queries2 {
+                match query1.merge(query2) {
+                    MediaQueryMergeResult::Empty => continue,
+                    MediaQueryMergeResult::Unrepresentable => return None,
+                    MediaQueryMergeResult::Success(query) => merged.push(query),
+                }
+            }
+        }
+
+        Some(merged)
+    }
+
     #[allow(clippy::if_not_else)]
     fn merge(&self, other: &Self) -> MediaQueryMergeResult {
+        if !self.conjunction || !other.conjunction {
+            return MediaQueryMergeResult::Unrepresentable;
+        }
+
         let this_modifier = self.modifier.as_ref().map(|m| m.to_ascii_lowercase());
         let this_type = self.media_type.as_ref().map(|m| m.to_ascii_lowercase());
         let other_modifier = other.modifier.as_ref().map(|m| m.to_ascii_lowercase());
@@ -61,6 +106,7 @@ impl MediaQuery {
                     .chain(&other.features)
                     .cloned()
                     .collect(),
+                true,
             ));
         }
 
@@ -69,7 +115,7 @@ impl MediaQuery {
         let features;
 
         if (this_modifier.as_deref() == Some("not")) != (other_modifier.as_deref() == Some("not")) {
-            if this_modifier == other_modifier {
+            if this_type == other_type {
                 let negative_features = if this_modifier.as_deref() == Some("not") {
                     &self.features
                 } else {
@@ -200,6 +246,7 @@ impl MediaQuery {
                 other.modifier.clone()
             },
             features,
+            conjunction: true,
         })
     }
 }
@@ -207,7 +254,7 @@ impl MediaQuery {
 impl fmt::Display for MediaQuery {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(modifier) = &self.modifier {
-            f.write_str(modifier)?;
+            write!(f, "{} ", modifier)?;
         }
         if let Some(media_type) = &self.media_type {
             f.write_str(media_type)?;
@@ -215,7 +262,11 @@ impl fmt::Display for MediaQuery {
                 f.write_str(" and ")?;
             }
         }
-        f.write_str(&self.features.join(" and "))
+        f.write_str(
+            &self
+                .features
+                .join(if self.conjunction { " and " } else { " or " }),
+        )
     }
 }
 
@@ -225,3 +276,261 @@ enum MediaQueryMergeResult {
     Unrepresentable,
     Success(MediaQuery),
 }
+
+/// A parser for plain CSS media queries, which follows the grammar of
+/// [Media Queries Level 4](https://drafts.csswg.org/mediaqueries-4/#mq-syntax)
+/// only as far as is needed to merge queries
+struct MediaQueryParser {
+    chars: Vec<char>,
+    cursor: usize,
+}
+
+impl MediaQueryParser {
+    fn parse(mut self) -> Result<Vec<MediaQuery>, &'static str> {
+        let mut queries = Vec::new();
+
+        loop {
+            self.whitespace();
+            queries.push(self.media_query()?);
+            self.whitespace();
+
+            if !self.scan_char(',') {
+                break;
+            }
+        }
+
+        if self.cursor != self.chars.len() {
+            return Err("expected no more input.");
+        }
+
+        Ok(queries)
+    }
+
+    fn media_query(&mut self) -> Result<MediaQuery, &'static str> {
+        if self.peek() == Some('(') {
+            let mut conditions = vec![self.media_in_parens()?];
+            self.whitespace();
+
+            let mut conjunction = true;
+
+            if self.scan_identifier("and") {
+                self.expect_whitespace()?;
+                conditions.append(&mut self.media_logic_sequence("and")?);
+            } else if self.scan_identifier("or") {
+                self.expect_whitespace()?;
+                conjunction = false;
+                conditions.append(&mut self.media_logic_sequence("or")?);
+            }
+
+            return Ok(MediaQuery::condition(conditions, conjunction));
+        }
+
+        let identifier1 = self.identifier()?;
+
+        if identifier1.eq_ignore_ascii_case("not") {
+            self.expect_whitespace()?;
+
+            // for example, `not (color)`
+            if !self.looking_at_identifier() {
+                let condition = format!("(not {})", self.media_in_parens()?);
+                return Ok(MediaQuery::condition(vec![condition], true));
+            }
+        }
+
+        self.whitespace();
+
+        if !self.looking_at_identifier() {
+            return Ok(MediaQuery::media_type(identifier1, None, Vec::new()));
+        }
+
+        let identifier2 = self.identifier()?;
+
+        let (modifier, media_type) = if identifier2.eq_ignore_ascii_case("and") {
+            self.expect_whitespace()?;
+            (None, identifier1)
+        } else {
+            self.whitespace();
+
+            if !self.scan_identifier("and") {
+                return Ok(MediaQuery::media_type(
+                    identifier2,
+                    Some(identifier1),
+                    Vec::new(),
+                ));
+            }
+
+            self.expect_whitespace()?;
+            (Some(identifier1), identifier2)
+        };
+
+        if self.scan_identifier("not") {
+            // for example, `screen and not (color)`
+            self.expect_whitespace()?;
+            let condition = format!("(not {})", self.media_in_parens()?);
+            return Ok(MediaQuery::media_type(
+                media_type,
+                modifier,
+                vec![condition],
+            ));
+        }
+
+        Ok(MediaQuery::media_type(
+            media_type,
+            modifier,
+            self.media_logic_sequence("and")?,
+        ))
+    }
+
+    /// Consume conditions in parentheses separated by `operator`
+    fn media_logic_sequence(
+        &mut self,
+        operator: &'static str,
+    ) -> Result<Vec<String>, &'static str> {
+        let mut conditions = Vec::new();
+
+        loop {
+            conditions.push(self.media_in_parens()?);
+            self.whitespace();
+
+            if !self.scan_identifier(operator) {
+                return Ok(conditions);
+            }
+
+            self.expect_whitespace()?;
+        }
+    }
+
+    /// Consume a condition in parentheses, including the parentheses
+    fn media_in_parens(&mut self) -> Result<String, &'static str> {
+        if !self.scan_char('(') {
+            return Err("expected media condition in parentheses.");
+        }
+
+        let mut buf = String::from("(");
+        let mut depth = 0_usize;
+        let mut quote = None;
+
+        while let Some(c) = self.next() {
+            match (c, quote) {
+                ('\\', _) => {
+                    buf.push(c);
+                    if let Some(escaped) = self.next() {
+                        buf.push(escaped);
+                    }
+                    continue;
+                }
+                (_, Some(q)) if c == q => quote = None,
+                (_, Some(..)) => {}
+                ('"', None) | ('\'', None) => quote = Some(c),
+                ('(', None) | ('[', None) | ('{', None) => depth += 1,
+                (')', None) if depth == 0 => {
+                    buf.push(')');
+                    return Ok(buf);
+                }
+                (')', None) | (']', None) | ('}', None) => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+
+            buf.push(c);
+        }
+
+        Err("expected \")\".")
+    }
+
+    fn identifier(&mut self) -> Result<String, &'static str> {
+        if !self.looking_at_identifier() {
+            return Err("Expected identifier.");
+        }
+
+        let mut buf = String::new();
+
+        while let Some(c) = self.peek() {
+            if c == '\\' {
+                buf.push(c);
+                self.cursor += 1;
+                if let Some(escaped) = self.next() {
+                    buf.push(escaped);
+                }
+            } else if is_name(c) {
+                buf.push(c);
+                self.cursor += 1;
+            } else {
+                break;
+            }
+        }
+
+        Ok(buf)
+    }
+
+    fn looking_at_identifier(&self) -> bool {
+        let is_start = |c: Option<&char>| matches!(c, Some(c) if c.is_alphabetic() || *c == '_' || *c == '\\' || !c.is_ascii());
+
+        match self.chars.get(self.cursor) {
+            Some('-') => {
+                let next = self.chars.get(self.cursor + 1);
+                is_start(next) || next == Some(&'-')
+            }
+            c => is_start(c),
+        }
+    }
+
+    /// Consume `ident` if it is the entire identifier at the current position,
+    /// ignoring case
+    fn scan_identifier(&mut self, ident: &str) -> bool {
+        let start = self.cursor;
+
+        match self.identifier() {
+            Ok(found) if found.eq_ignore_ascii_case(ident) => true,
+            _ => {
+                self.cursor = start;
+                false
+            }
+        }
+    }
+
+    fn whitespace(&mut self) -> bool {
+        let start = self.cursor;
+
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => self.cursor += 1,
+                Some('/') if self.chars.get(self.cursor + 1) == Some(&'*') => {
+                    self.cursor += 2;
+                    while let Some(c) = self.next() {
+                        if c == '*' && self.scan_char('/') {
+                            break;
+                        }
+                    }
+                }
+                _ => return self.cursor != start,
+            }
+        }
+    }
+
+    fn expect_whitespace(&mut self) -> Result<(), &'static str> {
+        if self.whitespace() {
+            Ok(())
+        } else {
+            Err("Expected whitespace.")
+        }
+    }
+
+    fn scan_char(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.cursor += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.cursor).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.cursor += 1;
+        Some(c)
+    }
+}
@@ -12,7 +12,8 @@ pub(crate) type BuiltinMixin = fn(CallArgs, &mut Parser) -> SassResult<Vec<Stmt>
 #[derive(Clone)]
 pub(crate) enum Mixin {
     UserDefined(UserDefinedMixin),
-    Builtin(BuiltinMixin),
+    /// A builtin mixin, along with whether it accepts a content block
+    Builtin(BuiltinMixin, bool),
 }
 
 impl fmt::Debug for Mixin {
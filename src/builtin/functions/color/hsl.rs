@@ -1,11 +1,14 @@
-use super::{Builtin, GlobalFunctionMap};
+use super::{
+    legacy_only, non_finite_channel, parse_channels, take_missing, with_missing, Builtin,
+    GlobalFunctionMap, ParsedChannels,
+};
 
 use codemap::Spanned;
 use num_traits::One;
 
 use crate::{
     args::CallArgs,
-    color::Color,
+    color::{Color, ColorSpace},
     common::{Brackets, ListSeparator, QuoteKind},
     error::SassResult,
     parse::Parser,
@@ -21,185 +24,152 @@ fn inner_hsl(name: &'static str, mut args: CallArgs, parser: &mut Parser) -> Sas
     let len = args.len();
 
     if len == 1 {
-        let mut channels = match args.get_err(0, "channels")? {
-            Value::List(v, ..) => v,
-            v if v.is_special_function() => vec![v],
-            _ => return Err(("Missing argument $channels.", args.span()).into()),
+        let mut channels = match parse_channels(
+            name,
+            ["hue", "saturation", "lightness"],
+            args.get_err(0, "channels")?,
+            args.span(),
+        )? {
+            ParsedChannels::String(s) => return Ok(s),
+            ParsedChannels::List(list) => list,
+        };
+
+        let missing = take_missing(ColorSpace::Hsl, &mut channels);
+
+        let alpha = if channels.len() == 4 {
+            channels.pop()
+        } else {
+            None
+        };
+        let lightness = channels.pop().unwrap();
+        let saturation = channels.pop().unwrap();
+        let hue = channels.pop().unwrap();
+
+        hsl_from_channels(name, hue, saturation, lightness, alpha, &args, parser)
+            .map(|color| with_missing(color, missing))
+    } else {
+        let hue = args.get_err(0, "hue")?;
+        let saturation = args.get_err(1, "saturation")?;
+        let lightness = args.get_err(2, "lightness")?;
+        let alpha = match args.get(3, "alpha") {
+            Some(v) => Some(v?.node),
+            None => None,
         };
 
-        if channels.len() > 3 {
+        hsl_from_channels(name, hue, saturation, lightness, alpha, &args, parser)
+    }
+}
+
+/// Create a color from its hue, saturation, lightness and (optionally) alpha
+/// channels, or a plain CSS function call if any of them are special functions
+fn hsl_from_channels(
+    name: &'static str,
+    hue: Value,
+    saturation: Value,
+    lightness: Value,
+    alpha: Option<Value>,
+    args: &CallArgs,
+    parser: &mut Parser,
+) -> SassResult<Value> {
+    if [
+        Some(&hue),
+        Some(&saturation),
+        Some(&lightness),
+        alpha.as_ref(),
+    ]
+    .iter()
+    .flatten()
+    .copied()
+    .any(Value::is_special_function)
+    {
+        let mut channels = vec![hue, saturation, lightness];
+        channels.extend(alpha);
+
+        return Ok(Value::String(
+            format!(
+                "{}({})",
+                name,
+                Value::List(channels, ListSeparator::Comma, Brackets::None)
+                    .to_css_string(args.span(), false)?
+            ),
+            QuoteKind::None,
+        ));
+    }
+
+    let alpha = alpha.unwrap_or(Value::Dimension(Number::one(), Unit::None, true));
+
+    let hue = match hue {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("hue", &n, args.span()))
+        }
+        Value::Dimension(n, ..) => n,
+        v => {
+            return Err((
+                format!("$hue: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    let saturation = match saturation {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("saturation", &n, args.span()))
+        }
+        Value::Dimension(n, ..) => n / Number::from(100),
+        v => {
             return Err((
                 format!(
-                    "Only 3 elements allowed, but {} were passed.",
-                    channels.len()
+                    "$saturation: {} is not a number.",
+                    v.to_css_string(args.span(), parser.options.is_compressed())?
                 ),
                 args.span(),
             )
-                .into());
+                .into())
         }
-
-        if channels.iter().any(Value::is_special_function) {
-            let channel_sep = if channels.len() < 3 {
-                ListSeparator::Space
-            } else {
-                ListSeparator::Comma
-            };
-
-            return Ok(Value::String(
+    };
+    let lightness = match lightness {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("lightness", &n, args.span()))
+        }
+        Value::Dimension(n, ..) => n / Number::from(100),
+        v => {
+            return Err((
                 format!(
-                    "{}({})",
-                    name,
-                    Value::List(channels, channel_sep, Brackets::None)
-                        .to_css_string(args.span(), false)?
+                    "$lightness: {} is not a number.",
+                    v.to_css_string(args.span(), parser.options.is_compressed())?
                 ),
-                QuoteKind::None,
-            ));
+                args.span(),
+            )
+                .into())
         }
-
-        let lightness = match channels.pop() {
-            Some(Value::Dimension(Some(n), ..)) => n / Number::from(100),
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) => {
-                return Err((
-                    format!("$lightness: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $lightness.", args.span()).into()),
-        };
-
-        let saturation = match channels.pop() {
-            Some(Value::Dimension(Some(n), ..)) => n / Number::from(100),
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) => {
-                return Err((
-                    format!("$saturation: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $saturation.", args.span()).into()),
-        };
-
-        let hue = match channels.pop() {
-            Some(Value::Dimension(Some(n), ..)) => n,
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) => {
-                return Err((
-                    format!("$hue: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $hue.", args.span()).into()),
-        };
-
-        Ok(Value::Color(Box::new(Color::from_hsla(
-            hue,
-            saturation,
-            lightness,
-            Number::one(),
-        ))))
-    } else {
-        let hue = args.get_err(0, "hue")?;
-        let saturation = args.get_err(1, "saturation")?;
-        let lightness = args.get_err(2, "lightness")?;
-        let alpha = args.default_arg(
-            3,
-            "alpha",
-            Value::Dimension(Some(Number::one()), Unit::None, true),
-        )?;
-
-        if [&hue, &saturation, &lightness, &alpha]
-            .iter()
-            .copied()
-            .any(Value::is_special_function)
-        {
-            return Ok(Value::String(
+    };
+    let alpha = match alpha {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("alpha", &n, args.span()))
+        }
+        Value::Dimension(n, Unit::None, _) => n,
+        Value::Dimension(n, Unit::Percent, _) => n / Number::from(100),
+        v @ Value::Dimension(..) => {
+            return Err((
                 format!(
-                    "{}({})",
-                    name,
-                    Value::List(
-                        if len == 4 {
-                            vec![hue, saturation, lightness, alpha]
-                        } else {
-                            vec![hue, saturation, lightness]
-                        },
-                        ListSeparator::Comma,
-                        Brackets::None
-                    )
-                    .to_css_string(args.span(), false)?
+                    "$alpha: Expected {} to have no units or \"%\".",
+                    v.to_css_string(args.span(), parser.options.is_compressed())?
                 ),
-                QuoteKind::None,
-            ));
+                args.span(),
+            )
+                .into())
         }
-
-        let hue = match hue {
-            Value::Dimension(Some(n), ..) => n,
-            Value::Dimension(None, ..) => todo!(),
-            v => {
-                return Err((
-                    format!("$hue: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let saturation = match saturation {
-            Value::Dimension(Some(n), ..) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
-            v => {
-                return Err((
-                    format!(
-                        "$saturation: {} is not a number.",
-                        v.to_css_string(args.span(), parser.options.is_compressed())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let lightness = match lightness {
-            Value::Dimension(Some(n), ..) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
-            v => {
-                return Err((
-                    format!(
-                        "$lightness: {} is not a number.",
-                        v.to_css_string(args.span(), parser.options.is_compressed())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let alpha = match alpha {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$alpha: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span(), parser.options.is_compressed())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v => {
-                return Err((
-                    format!("$alpha: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        Ok(Value::Color(Box::new(Color::from_hsla(
-            hue, saturation, lightness, alpha,
-        ))))
-    }
+        v => {
+            return Err((
+                format!("$alpha: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    Ok(Value::Color(Box::new(Color::from_hsla(
+        hue, saturation, lightness, alpha,
+    ))))
 }
 
 pub(crate) fn hsl(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
@@ -213,7 +183,7 @@ pub(crate) fn hsla(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
 pub(crate) fn hue(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.hue()), Unit::Deg, true)),
+        Value::Color(c) => Ok(Value::Dimension(c.hue(), Unit::Deg, true)),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -225,7 +195,7 @@ pub(crate) fn hue(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
 pub(crate) fn saturation(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.saturation()), Unit::Percent, true)),
+        Value::Color(c) => Ok(Value::Dimension(c.saturation(), Unit::Percent, true)),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -237,7 +207,7 @@ pub(crate) fn saturation(mut args: CallArgs, parser: &mut Parser) -> SassResult<
 pub(crate) fn lightness(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.lightness()), Unit::Percent, true)),
+        Value::Color(c) => Ok(Value::Dimension(c.lightness(), Unit::Percent, true)),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -259,8 +229,10 @@ pub(crate) fn adjust_hue(mut args: CallArgs, parser: &mut Parser) -> SassResult<
         }
     };
     let degrees = match args.get_err(1, "degrees")? {
-        Value::Dimension(Some(n), ..) => n,
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("degrees", &n, args.span()))
+        }
+        Value::Dimension(n, ..) => n,
         v => {
             return Err((
                 format!(
@@ -272,6 +244,11 @@ pub(crate) fn adjust_hue(mut args: CallArgs, parser: &mut Parser) -> SassResult<
                 .into())
         }
     };
+
+    if !color.is_legacy() {
+        return Err(legacy_only("adjust-hue", args.span()));
+    }
+
     Ok(Value::Color(Box::new(color.adjust_hue(degrees))))
 }
 
@@ -288,8 +265,10 @@ fn lighten(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
         }
     };
     let amount = match args.get_err(1, "amount")? {
-        Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("amount", &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
         v => {
             return Err((
                 format!(
@@ -301,6 +280,11 @@ fn lighten(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
                 .into())
         }
     };
+
+    if !color.is_legacy() {
+        return Err(legacy_only("lighten", args.span()));
+    }
+
     Ok(Value::Color(Box::new(color.lighten(amount))))
 }
 
@@ -317,8 +301,10 @@ fn darken(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
         }
     };
     let amount = match args.get_err(1, "amount")? {
-        Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("amount", &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
         v => {
             return Err((
                 format!(
@@ -330,6 +316,11 @@ fn darken(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
                 .into())
         }
     };
+
+    if !color.is_legacy() {
+        return Err(legacy_only("darken", args.span()));
+    }
+
     Ok(Value::Color(Box::new(color.darken(amount))))
 }
 
@@ -347,8 +338,10 @@ fn saturate(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     }
 
     let amount = match args.get_err(1, "amount")? {
-        Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("amount", &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
         v => {
             return Err((
                 format!(
@@ -362,9 +355,9 @@ fn saturate(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     };
     let color = match args.get_err(0, "color")? {
         Value::Color(c) => c,
-        Value::Dimension(Some(n), u, _) => {
+        v @ Value::Dimension(..) => {
             return Ok(Value::String(
-                format!("saturate({}{})", n.inspect(), u),
+                format!("saturate({})", v.to_css_string(args.span(), false)?),
                 QuoteKind::None,
             ))
         }
@@ -376,6 +369,11 @@ fn saturate(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
                 .into())
         }
     };
+
+    if !color.is_legacy() {
+        return Err(legacy_only("saturate", args.span()));
+    }
+
     Ok(Value::Color(Box::new(color.saturate(amount))))
 }
 
@@ -392,8 +390,10 @@ fn desaturate(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
         }
     };
     let amount = match args.get_err(1, "amount")? {
-        Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("amount", &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => bound!(args, "amount", n, u, 0, 100) / Number::from(100),
         v => {
             return Err((
                 format!(
@@ -405,6 +405,11 @@ fn desaturate(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
                 .into())
         }
     };
+
+    if !color.is_legacy() {
+        return Err(legacy_only("desaturate", args.span()));
+    }
+
     Ok(Value::Color(Box::new(color.desaturate(amount))))
 }
 
@@ -412,9 +417,9 @@ pub(crate) fn grayscale(mut args: CallArgs, parser: &mut Parser) -> SassResult<V
     args.max_args(1)?;
     let color = match args.get_err(0, "color")? {
         Value::Color(c) => c,
-        Value::Dimension(Some(n), u, _) => {
+        v @ Value::Dimension(..) => {
             return Ok(Value::String(
-                format!("grayscale({}{})", n.inspect(), u),
+                format!("grayscale({})", v.to_css_string(args.span(), false)?),
                 QuoteKind::None,
             ))
         }
@@ -449,13 +454,13 @@ pub(crate) fn invert(mut args: CallArgs, parser: &mut Parser) -> SassResult<Valu
     let weight = match args.get(1, "weight") {
         Some(Err(e)) => return Err(e),
         Some(Ok(Spanned {
-            node: Value::Dimension(Some(n), u, _),
+            node: Value::Dimension(n, ..),
             ..
-        })) => Some(bound!(args, "weight", n, u, 0, 100) / Number::from(100)),
+        })) if !n.is_finite() => return Err(non_finite_channel("weight", &n, args.span())),
         Some(Ok(Spanned {
-            node: Value::Dimension(None, ..),
+            node: Value::Dimension(n, u, _),
             ..
-        })) => todo!(),
+        })) => Some(bound!(args, "weight", n, u, 0, 100) / Number::from(100)),
         None => None,
         Some(Ok(v)) => {
             return Err((
@@ -472,7 +477,7 @@ pub(crate) fn invert(mut args: CallArgs, parser: &mut Parser) -> SassResult<Valu
         Value::Color(c) => Ok(Value::Color(Box::new(
             c.invert(weight.unwrap_or_else(Number::one)),
         ))),
-        Value::Dimension(Some(n), u, _) => {
+        Value::Dimension(n, u, _) => {
             if weight.is_some() {
                 return Err((
                     "Only one argument may be passed to the plain-CSS invert() function.",
@@ -481,13 +486,10 @@ pub(crate) fn invert(mut args: CallArgs, parser: &mut Parser) -> SassResult<Valu
                     .into());
             }
             Ok(Value::String(
-                format!("invert({}{})", n.inspect(), u),
+                format!("invert({})", n.to_css_string(&u, false)),
                 QuoteKind::None,
             ))
         }
-        Value::Dimension(None, u, _) => {
-            Ok(Value::String(format!("invert(NaN{})", u), QuoteKind::None))
-        }
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -1,8 +1,14 @@
 use num_traits::One;
 
+use super::{
+    non_finite_channel, parse_channels, take_missing, with_missing, Builtin, GlobalFunctionMap,
+    ParsedChannels,
+};
+
 use crate::{
     args::CallArgs,
-    color::Color,
+    color::{Color, ColorSpace},
+    common::{Brackets, ListSeparator, QuoteKind},
     error::SassResult,
     parse::Parser,
     unit::Unit,
@@ -26,7 +32,7 @@ pub(crate) fn blackness(mut args: CallArgs, parser: &mut Parser) -> SassResult<V
     let blackness =
         Number::from(1) - (color.red().max(color.green()).max(color.blue()) / Number::from(255));
 
-    Ok(Value::Dimension(Some(blackness * 100), Unit::Percent, true))
+    Ok(Value::Dimension(blackness * 100, Unit::Percent, true))
 }
 
 pub(crate) fn whiteness(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
@@ -45,7 +51,7 @@ pub(crate) fn whiteness(mut args: CallArgs, parser: &mut Parser) -> SassResult<V
 
     let whiteness = color.red().min(color.green()).min(color.blue()) / Number::from(255);
 
-    Ok(Value::Dimension(Some(whiteness * 100), Unit::Percent, true))
+    Ok(Value::Dimension(whiteness * 100, Unit::Percent, true))
 }
 
 pub(crate) fn hwb(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
@@ -55,78 +61,160 @@ pub(crate) fn hwb(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
         return Err(("Missing argument $channels.", args.span()).into());
     }
 
+    if args.len() == 1 {
+        let mut channels = match parse_channels(
+            "hwb",
+            ["hue", "whiteness", "blackness"],
+            args.get_err(0, "channels")?,
+            args.span(),
+        )? {
+            ParsedChannels::String(s) => return Ok(s),
+            ParsedChannels::List(list) => list,
+        };
+
+        let missing = take_missing(ColorSpace::Hwb, &mut channels);
+
+        let alpha = if channels.len() == 4 {
+            channels.pop()
+        } else {
+            None
+        };
+        let blackness = channels.pop().unwrap();
+        let whiteness = channels.pop().unwrap();
+        let hue = channels.pop().unwrap();
+
+        return hwb_from_channels(hue, whiteness, blackness, alpha, &args)
+            .map(|color| with_missing(color, missing));
+    }
+
     let hue = match args.get(0, "hue") {
-        Some(Ok(v)) => match v.node {
-            Value::Dimension(Some(n), ..) => n,
-            Value::Dimension(None, ..) => todo!(),
-            v => {
-                return Err((
-                    format!("$hue: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        },
-        Some(Err(e)) => return Err(e),
+        Some(v) => v?.node,
         None => return Err(("Missing element $hue.", args.span()).into()),
     };
 
     let whiteness = match args.get(1, "whiteness") {
-        Some(Ok(v)) => match v.node {
-            Value::Dimension(Some(n), Unit::Percent, ..) => n,
-            v @ Value::Dimension(Some(..), ..) => {
-                return Err((
-                    format!(
-                        "$whiteness: Expected {} to have unit \"%\".",
-                        v.inspect(args.span())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            Value::Dimension(None, ..) => todo!(),
-            v => {
-                return Err((
-                    format!("$whiteness: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        },
-        Some(Err(e)) => return Err(e),
+        Some(v) => v?.node,
         None => return Err(("Missing element $whiteness.", args.span()).into()),
     };
 
     let blackness = match args.get(2, "blackness") {
-        Some(Ok(v)) => match v.node {
-            Value::Dimension(Some(n), ..) => n,
-            Value::Dimension(None, ..) => todo!(),
-            v => {
-                return Err((
-                    format!("$blackness: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        },
-        Some(Err(e)) => return Err(e),
+        Some(v) => v?.node,
         None => return Err(("Missing element $blackness.", args.span()).into()),
     };
 
     let alpha = match args.get(3, "alpha") {
-        Some(Ok(v)) => match v.node {
-            Value::Dimension(Some(n), Unit::Percent, ..) => n / Number::from(100),
-            Value::Dimension(Some(n), ..) => n,
-            Value::Dimension(None, ..) => todo!(),
-            v => {
-                return Err((
-                    format!("$alpha: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        },
-        Some(Err(e)) => return Err(e),
+        Some(v) => Some(v?.node),
+        None => None,
+    };
+
+    hwb_from_channels(hue, whiteness, blackness, alpha, &args)
+}
+
+/// Create a color from its hue, whiteness, blackness and (optionally) alpha
+/// channels, or a plain CSS function call if any of them are special functions
+///
+/// Unlike `rgb()` and `hsl()`, the plain CSS call uses the space separated
+/// syntax, which is the only one CSS supports for `hwb()`
+fn hwb_from_channels(
+    hue: Value,
+    whiteness: Value,
+    blackness: Value,
+    alpha: Option<Value>,
+    args: &CallArgs,
+) -> SassResult<Value> {
+    if [
+        Some(&hue),
+        Some(&whiteness),
+        Some(&blackness),
+        alpha.as_ref(),
+    ]
+    .iter()
+    .flatten()
+    .copied()
+    .any(Value::is_special_function)
+    {
+        let channels = Value::List(
+            vec![hue, whiteness, blackness],
+            ListSeparator::Space,
+            Brackets::None,
+        );
+
+        let channels = match alpha {
+            Some(alpha) => Value::List(vec![channels, alpha], ListSeparator::Slash, Brackets::None),
+            None => channels,
+        };
+
+        return Ok(Value::String(
+            format!("hwb({})", channels.to_css_string(args.span(), false)?),
+            QuoteKind::None,
+        ));
+    }
+
+    let hue = match hue {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("hue", &n, args.span()))
+        }
+        Value::Dimension(n, ..) => n,
+        v => {
+            return Err((
+                format!("$hue: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let whiteness = match whiteness {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("whiteness", &n, args.span()))
+        }
+        Value::Dimension(n, Unit::Percent, ..) => n,
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "$whiteness: Expected {} to have unit \"%\".",
+                    v.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("$whiteness: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let blackness = match blackness {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("blackness", &n, args.span()))
+        }
+        Value::Dimension(n, ..) => n,
+        v => {
+            return Err((
+                format!("$blackness: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let alpha = match alpha {
+        Some(Value::Dimension(n, ..)) if !n.is_finite() => {
+            return Err(non_finite_channel("alpha", &n, args.span()))
+        }
+        Some(Value::Dimension(n, Unit::Percent, ..)) => n / Number::from(100),
+        Some(Value::Dimension(n, ..)) => n,
+        Some(v) => {
+            return Err((
+                format!("$alpha: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
         None => Number::one(),
     };
 
@@ -134,3 +222,7 @@ pub(crate) fn hwb(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
         hue, whiteness, blackness, alpha,
     ))))
 }
+
+pub(crate) fn declare(f: &mut GlobalFunctionMap) {
+    f.insert("hwb", Builtin::new(hwb));
+}
@@ -1,14 +1,220 @@
 use super::{Builtin, GlobalFunctionMap};
 
+use codemap::Span;
+
+use num_traits::Zero;
+
+use crate::{
+    color::ColorSpace,
+    common::{Brackets, ListSeparator, QuoteKind},
+    error::{SassError, SassResult},
+    unit::Unit,
+    value::{Number, Value},
+};
+
 pub mod hsl;
 pub mod hwb;
 pub mod opacity;
 pub mod other;
 pub mod rgb;
+pub mod space;
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     hsl::declare(f);
+    hwb::declare(f);
     opacity::declare(f);
     other::declare(f);
     rgb::declare(f);
+    space::declare(f);
+}
+
+/// The result of parsing the `$channels` argument of a color function
+pub(crate) enum ParsedChannels {
+    /// The function call, to be emitted as plain CSS because one of its
+    /// channels is a special function such as `var()`
+    String(Value),
+    /// The three color channels, followed by the alpha channel if one was
+    /// given after a slash
+    List(Vec<Value>),
+}
+
+/// The error emitted when `Infinity`, `-Infinity` or `NaN` is passed as the
+/// color channel `name`
+pub(crate) fn non_finite_channel(name: &str, number: &Number, span: Span) -> Box<SassError> {
+    (
+        format!("${}: {} is not a finite number.", name, number.inspect()),
+        span,
+    )
+        .into()
+}
+
+/// The error emitted when a function which only supports legacy colors, such
+/// as `lighten()`, is passed a color in another space
+pub(crate) fn legacy_only(name: &str, span: Span) -> Box<SassError> {
+    (
+        format!(
+            "{}() is only supported for legacy colors. Please use color.adjust() instead with an explicit $space argument.",
+            name
+        ),
+        span,
+    )
+        .into()
+}
+
+/// Whether `value` is the keyword `none`, which marks a color channel as missing
+pub(crate) fn is_none(value: &Value) -> bool {
+    matches!(value, Value::String(s, QuoteKind::None) if s.eq_ignore_ascii_case("none"))
+}
+
+/// Replace each channel of a color in `space` that is `none` with zero,
+/// returning which channels were missing
+///
+/// `channels` are the three color channels, optionally followed by alpha
+pub(crate) fn take_missing(space: ColorSpace, channels: &mut [Value]) -> [bool; 4] {
+    let mut missing = [false; 4];
+
+    for (idx, channel) in channels.iter_mut().enumerate() {
+        if is_none(channel) {
+            missing[idx] = true;
+            *channel = Value::Dimension(
+                Number::zero(),
+                if space.hue_index() == Some(idx) {
+                    Unit::None
+                } else {
+                    Unit::Percent
+                },
+                true,
+            );
+        }
+    }
+
+    missing
+}
+
+/// Mark the channels of the color in `value` that were `none` as missing
+pub(crate) fn with_missing(value: Value, missing: [bool; 4]) -> Value {
+    match value {
+        Value::Color(color) if missing.contains(&true) => {
+            Value::Color(Box::new(color.with_missing(missing)))
+        }
+        v => v,
+    }
+}
+
+fn function_string(name: &'static str, args: &Value, span: Span) -> SassResult<Value> {
+    Ok(Value::String(
+        format!("{}({})", name, args.to_css_string(span, false)?),
+        QuoteKind::None,
+    ))
+}
+
+/// Parse the single argument form of `rgb()`, `hsl()` and `hwb()`, such as
+/// `rgb(10 20 30)` or `hsl(120deg 50% 50% / 0.3)`
+///
+/// `arg_names` are the names of the three color channels, used in error messages
+pub(crate) fn parse_channels(
+    name: &'static str,
+    arg_names: [&'static str; 3],
+    channels: Value,
+    span: Span,
+) -> SassResult<ParsedChannels> {
+    if channels.is_special_function() {
+        return function_string(name, &channels, span).map(ParsedChannels::String);
+    }
+
+    let original_channels = channels.clone();
+
+    let (channels, alpha) = match channels {
+        Value::List(mut list, ListSeparator::Slash, _) => {
+            if list.len() != 2 {
+                return Err((
+                    format!(
+                        "Only 2 slash-separated elements allowed, but {} {} passed.",
+                        list.len(),
+                        if list.len() == 1 { "was" } else { "were" }
+                    ),
+                    span,
+                )
+                    .into());
+            }
+
+            let alpha = list.pop().unwrap();
+            let channels = list.pop().unwrap();
+
+            if !alpha.is_special_function()
+                && !is_none(&alpha)
+                && !matches!(alpha, Value::Dimension(..))
+            {
+                return Err((
+                    format!("$alpha: {} is not a number.", alpha.inspect(span)?),
+                    span,
+                )
+                    .into());
+            }
+
+            if channels.is_special_function() {
+                return function_string(name, &original_channels, span).map(ParsedChannels::String);
+            }
+
+            (channels, Some(alpha))
+        }
+        channels => (channels, None),
+    };
+
+    let is_comma_separated = matches!(channels, Value::List(_, ListSeparator::Comma, _));
+    let is_bracketed = matches!(channels, Value::List(_, _, Brackets::Bracketed));
+
+    if is_comma_separated || is_bracketed {
+        let mut message = String::from("$channels must be");
+
+        if is_bracketed {
+            message.push_str(" an unbracketed");
+        }
+
+        if is_comma_separated {
+            message.push_str(if is_bracketed { "," } else { " a" });
+            message.push_str(" space-separated");
+        }
+
+        message.push_str(" list.");
+
+        return Err((message, span).into());
+    }
+
+    let mut list = channels.as_list();
+
+    if list.len() > 3 {
+        return Err((
+            format!("Only 3 elements allowed, but {} were passed.", list.len()),
+            span,
+        )
+            .into());
+    }
+
+    if list.len() < 3 {
+        if list.iter().any(Value::is_special_function) {
+            return function_string(name, &original_channels, span).map(ParsedChannels::String);
+        }
+
+        return Err((format!("Missing element ${}.", arg_names[list.len()]), span).into());
+    }
+
+    if let Some(alpha) = alpha {
+        list.push(alpha);
+        return Ok(ParsedChannels::List(list));
+    }
+
+    // an alpha channel that couldn't be divided, such as `30/var(--alpha)`
+    if let Value::String(s, QuoteKind::None) = &list[2] {
+        if s.contains('/') {
+            return function_string(
+                name,
+                &Value::List(list, ListSeparator::Space, Brackets::None),
+                span,
+            )
+            .map(ParsedChannels::String);
+        }
+    }
+
+    Ok(ParsedChannels::List(list))
 }
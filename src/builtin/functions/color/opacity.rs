@@ -1,4 +1,4 @@
-use super::{Builtin, GlobalFunctionMap};
+use super::{non_finite_channel, Builtin, GlobalFunctionMap};
 
 use crate::{
     args::CallArgs, common::QuoteKind, error::SassResult, parse::Parser, unit::Unit, value::Number,
@@ -38,7 +38,7 @@ mod test {
 pub(crate) fn alpha(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     if args.len() <= 1 {
         match args.get_err(0, "color")? {
-            Value::Color(c) => Ok(Value::Dimension(Some(c.alpha()), Unit::None, true)),
+            Value::Color(c) => Ok(Value::Dimension(c.alpha(), Unit::None, true)),
             Value::String(s, QuoteKind::None) if is_ms_filter(&s) => {
                 Ok(Value::String(format!("alpha({})", s), QuoteKind::None))
             }
@@ -72,12 +72,11 @@ pub(crate) fn alpha(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value
 pub(crate) fn opacity(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.alpha()), Unit::None, true)),
-        Value::Dimension(Some(num), unit, _) => Ok(Value::String(
-            format!("opacity({}{})", num.inspect(), unit),
+        Value::Color(c) => Ok(Value::Dimension(c.alpha(), Unit::None, true)),
+        Value::Dimension(num, unit, _) => Ok(Value::String(
+            format!("opacity({})", num.to_css_string(&unit, false)),
             QuoteKind::None,
         )),
-        Value::Dimension(None, ..) => todo!(),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -100,8 +99,10 @@ fn opacify(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
         }
     };
     let amount = match args.get_err(1, "amount")? {
-        Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 1),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("amount", &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => bound!(args, "amount", n, u, 0, 1),
         v => {
             return Err((
                 format!("$amount: {} is not a number.", v.inspect(args.span())?),
@@ -126,8 +127,10 @@ fn fade_in(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
         }
     };
     let amount = match args.get_err(1, "amount")? {
-        Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 1),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("amount", &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => bound!(args, "amount", n, u, 0, 1),
         v => {
             return Err((
                 format!("$amount: {} is not a number.", v.inspect(args.span())?),
@@ -153,8 +156,10 @@ fn transparentize(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
         }
     };
     let amount = match args.get_err(1, "amount")? {
-        Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 1),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("amount", &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => bound!(args, "amount", n, u, 0, 1),
         v => {
             return Err((
                 format!("$amount: {} is not a number.", v.inspect(args.span())?),
@@ -179,8 +184,10 @@ fn fade_out(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
         }
     };
     let amount = match args.get_err(1, "amount")? {
-        Value::Dimension(Some(n), u, _) => bound!(args, "amount", n, u, 0, 1),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("amount", &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => bound!(args, "amount", n, u, 0, 1),
         v => {
             return Err((
                 format!("$amount: {} is not a number.", v.inspect(args.span())?),
@@ -1,10 +1,14 @@
-use super::{Builtin, GlobalFunctionMap};
+use super::{
+    non_finite_channel,
+    space::{clamp_channel, resolve_channel, space_arg},
+    Builtin, GlobalFunctionMap,
+};
 
 use num_traits::{One, Signed, Zero};
 
 use crate::{
     args::CallArgs,
-    color::Color,
+    color::{Color, ColorSpace},
     common::QuoteKind,
     error::SassResult,
     parse::Parser,
@@ -15,8 +19,10 @@ use crate::{
 macro_rules! opt_rgba {
     ($args:ident, $name:ident, $arg:literal, $low:literal, $high:literal) => {
         let $name = match $args.default_named_arg($arg, Value::Null)? {
-            Value::Dimension(Some(n), u, _) => Some(bound!($args, $arg, n, u, $low, $high)),
-            Value::Dimension(None, ..) => todo!(),
+            Value::Dimension(n, ..) if !n.is_finite() => {
+                return Err(non_finite_channel($arg, &n, $args.span()))
+            }
+            Value::Dimension(n, u, _) => Some(bound!($args, $arg, n, u, $low, $high)),
             Value::Null => None,
             v => {
                 return Err((
@@ -32,10 +38,12 @@ macro_rules! opt_rgba {
 macro_rules! opt_hsl {
     ($args:ident, $name:ident, $arg:literal, $low:literal, $high:literal) => {
         let $name = match $args.default_named_arg($arg, Value::Null)? {
-            Value::Dimension(Some(n), u, _) => {
+            Value::Dimension(n, ..) if !n.is_finite() => {
+                return Err(non_finite_channel($arg, &n, $args.span()))
+            }
+            Value::Dimension(n, u, _) => {
                 Some(bound!($args, $arg, n, u, $low, $high) / Number::from(100))
             }
-            Value::Dimension(None, ..) => todo!(),
             Value::Null => None,
             v => {
                 return Err((
@@ -48,6 +56,199 @@ macro_rules! opt_hsl {
     };
 }
 
+/// How `color.change()`, `color.adjust()` and `color.scale()` modify channels
+#[derive(Copy, Clone)]
+enum Modification {
+    Change,
+    Adjust,
+    Scale,
+}
+
+/// The channel names of the legacy and non-legacy color spaces, which may be
+/// passed to `color.change()` and friends
+const CHANNEL_NAMES: [&str; 14] = [
+    "red",
+    "green",
+    "blue",
+    "hue",
+    "saturation",
+    "lightness",
+    "whiteness",
+    "blackness",
+    "chroma",
+    "a",
+    "b",
+    "x",
+    "y",
+    "z",
+];
+
+/// The range that the channel at `idx` of a color in `space` is scaled within,
+/// or `None` for channels that can't be scaled, such as hue
+fn scale_range(space: ColorSpace, idx: usize) -> Option<(f64, f64)> {
+    Some(match (space, idx) {
+        (space, idx) if space.hue_index() == Some(idx) => return None,
+        (ColorSpace::Rgb, _) => (0.0, 255.0),
+        (ColorSpace::Hsl | ColorSpace::Hwb, _) => (0.0, 100.0),
+        (ColorSpace::Lab | ColorSpace::Lch, 0) => (0.0, 100.0),
+        (ColorSpace::Lab, _) => (-125.0, 125.0),
+        (ColorSpace::Lch, _) => (0.0, 150.0),
+        (ColorSpace::Oklab | ColorSpace::Oklch, 0) => (0.0, 1.0),
+        (ColorSpace::Oklab, _) => (-0.4, 0.4),
+        (ColorSpace::Oklch, _) => (0.0, 0.4),
+        _ => (0.0, 1.0),
+    })
+}
+
+/// Modify a color in `$space`, which defaults to the space of the color, and
+/// convert the result back to the space of the color
+///
+/// The channels are named after the channels of that space and keep their own
+/// units, so that the lightness of `oklch()` may be changed to `50%` or `0.5`.
+/// `alpha` has already been parsed by the caller
+fn modify_in_space(
+    color: &Color,
+    space: Value,
+    args: &mut CallArgs,
+    alpha: Option<Number>,
+    modification: Modification,
+) -> SassResult<Value> {
+    let space = space_arg(space, color, args)?;
+    let converted = color.to_space(space);
+    let names = space.channel_names();
+
+    for name in CHANNEL_NAMES {
+        if !names.contains(&name) && args.get_named(name).is_some() {
+            return Err((
+                format!(
+                    "${}: Color space {} doesn't have a channel with this name.",
+                    name, space
+                ),
+                args.span(),
+            )
+                .into());
+        }
+    }
+
+    let mut channels = converted.channels();
+    let mut missing = converted.missing_in(space);
+
+    for (idx, name) in names.iter().enumerate() {
+        let value = match args.default_named_arg(name, Value::Null)? {
+            Value::Null => continue,
+            v => v,
+        };
+
+        let channel = match modification {
+            Modification::Change => resolve_channel(space, idx, value, args)?,
+            Modification::Adjust => channels[idx] + resolve_channel(space, idx, value, args)?,
+            Modification::Scale => {
+                let by = match value {
+                    Value::Dimension(n, ..) if !n.is_finite() => {
+                        return Err(non_finite_channel(name, &n, args.span()))
+                    }
+                    Value::Dimension(n, Unit::Percent, _) => {
+                        if n > Number::from(100) || n < Number::from(-100) {
+                            return Err((
+                                format!(
+                                    "${}: Expected {}% to be within -100% and 100%.",
+                                    name,
+                                    n.inspect()
+                                ),
+                                args.span(),
+                            )
+                                .into());
+                        }
+
+                        n.as_f64() / 100.0
+                    }
+                    v @ Value::Dimension(..) => {
+                        return Err((
+                            format!(
+                                "${}: Expected {} to have unit \"%\".",
+                                name,
+                                v.inspect(args.span())?
+                            ),
+                            args.span(),
+                        )
+                            .into())
+                    }
+                    v => {
+                        return Err((
+                            format!("${}: {} is not a number.", name, v.inspect(args.span())?),
+                            args.span(),
+                        )
+                            .into())
+                    }
+                };
+
+                let (min, max) = match scale_range(space, idx) {
+                    Some(range) => range,
+                    None => {
+                        return Err(
+                            (format!("${}: Channel isn't scalable.", name), args.span()).into()
+                        )
+                    }
+                };
+
+                let channel = channels[idx];
+
+                if by > 0.0 {
+                    channel + (max - channel) * by
+                } else {
+                    channel + (channel - min) * by
+                }
+            }
+        };
+
+        channels[idx] = clamp_channel(space, idx, channel);
+        missing[idx] = false;
+    }
+
+    if alpha.is_some() {
+        missing[3] = false;
+    }
+
+    let alpha = match (modification, alpha) {
+        (_, None) => converted.alpha(),
+        (Modification::Change, Some(alpha)) => alpha,
+        (Modification::Adjust, Some(alpha)) => converted.alpha() + alpha,
+        (Modification::Scale, Some(by)) => {
+            let alpha = converted.alpha();
+
+            if by.is_positive() {
+                alpha.clone() + (Number::one() - alpha) * by
+            } else {
+                alpha.clone() + alpha * by
+            }
+        }
+    };
+
+    Ok(Value::Color(Box::new(
+        Color::for_space(space, channels, alpha)
+            .with_missing(missing)
+            .to_space(color.space()),
+    )))
+}
+
+/// Keep the channels of `color` that were missing in `result`, other than those
+/// that were passed by the caller
+///
+/// `set` is whether each channel of `result`, followed by alpha, was passed
+fn preserve_missing(color: &Color, result: Color, set: [bool; 4]) -> Value {
+    if !color.has_missing() {
+        return Value::Color(Box::new(result));
+    }
+
+    let mut missing = color.missing_in(result.space());
+
+    for (missing, set) in missing.iter_mut().zip(set) {
+        *missing &= !set;
+    }
+
+    Value::Color(Box::new(result.with_missing(missing)))
+}
+
 pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     if args.positional_arg(1).is_some() {
         return Err((
@@ -69,22 +270,42 @@ pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser) -> SassResul
     };
 
     opt_rgba!(args, alpha, "alpha", 0, 1);
+
+    let space = args.default_named_arg("space", Value::Null)?;
+
+    if !color.is_legacy() || space != Value::Null {
+        return modify_in_space(&color, space, &mut args, alpha, Modification::Change);
+    }
+
     opt_rgba!(args, red, "red", 0, 255);
     opt_rgba!(args, green, "green", 0, 255);
     opt_rgba!(args, blue, "blue", 0, 255);
 
     if red.is_some() || green.is_some() || blue.is_some() {
-        return Ok(Value::Color(Box::new(Color::from_rgba(
-            red.unwrap_or_else(|| color.red()),
-            green.unwrap_or_else(|| color.green()),
-            blue.unwrap_or_else(|| color.blue()),
-            alpha.unwrap_or_else(|| color.alpha()),
-        ))));
+        let set = [
+            red.is_some(),
+            green.is_some(),
+            blue.is_some(),
+            alpha.is_some(),
+        ];
+
+        return Ok(preserve_missing(
+            &color,
+            Color::from_rgba(
+                red.unwrap_or_else(|| color.red()),
+                green.unwrap_or_else(|| color.green()),
+                blue.unwrap_or_else(|| color.blue()),
+                alpha.unwrap_or_else(|| color.alpha()),
+            ),
+            set,
+        ));
     }
 
     let hue = match args.default_named_arg("hue", Value::Null)? {
-        Value::Dimension(Some(n), ..) => Some(n),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("hue", &n, args.span()))
+        }
+        Value::Dimension(n, ..) => Some(n),
         Value::Null => None,
         v => {
             return Err((
@@ -101,12 +322,23 @@ pub(crate) fn change_color(mut args: CallArgs, parser: &mut Parser) -> SassResul
     if hue.is_some() || saturation.is_some() || luminance.is_some() {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
-        return Ok(Value::Color(Box::new(Color::from_hsla(
-            hue.unwrap_or(this_hue),
-            saturation.unwrap_or(this_saturation),
-            luminance.unwrap_or(this_luminance),
-            alpha.unwrap_or(this_alpha),
-        ))));
+        let set = [
+            hue.is_some(),
+            saturation.is_some(),
+            luminance.is_some(),
+            alpha.is_some(),
+        ];
+
+        return Ok(preserve_missing(
+            &color,
+            Color::from_hsla(
+                hue.unwrap_or(this_hue),
+                saturation.unwrap_or(this_saturation),
+                luminance.unwrap_or(this_luminance),
+                alpha.unwrap_or(this_alpha),
+            ),
+            set,
+        ));
     }
 
     Ok(Value::Color(if let Some(a) = alpha {
@@ -129,22 +361,42 @@ pub(crate) fn adjust_color(mut args: CallArgs, parser: &mut Parser) -> SassResul
     };
 
     opt_rgba!(args, alpha, "alpha", -1, 1);
+
+    let space = args.default_named_arg("space", Value::Null)?;
+
+    if !color.is_legacy() || space != Value::Null {
+        return modify_in_space(&color, space, &mut args, alpha, Modification::Adjust);
+    }
+
     opt_rgba!(args, red, "red", -255, 255);
     opt_rgba!(args, green, "green", -255, 255);
     opt_rgba!(args, blue, "blue", -255, 255);
 
     if red.is_some() || green.is_some() || blue.is_some() {
-        return Ok(Value::Color(Box::new(Color::from_rgba(
-            color.red() + red.unwrap_or_else(Number::zero),
-            color.green() + green.unwrap_or_else(Number::zero),
-            color.blue() + blue.unwrap_or_else(Number::zero),
-            color.alpha() + alpha.unwrap_or_else(Number::zero),
-        ))));
+        let set = [
+            red.is_some(),
+            green.is_some(),
+            blue.is_some(),
+            alpha.is_some(),
+        ];
+
+        return Ok(preserve_missing(
+            &color,
+            Color::from_rgba(
+                color.red() + red.unwrap_or_else(Number::zero),
+                color.green() + green.unwrap_or_else(Number::zero),
+                color.blue() + blue.unwrap_or_else(Number::zero),
+                color.alpha() + alpha.unwrap_or_else(Number::zero),
+            ),
+            set,
+        ));
     }
 
     let hue = match args.default_named_arg("hue", Value::Null)? {
-        Value::Dimension(Some(n), ..) => Some(n),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("hue", &n, args.span()))
+        }
+        Value::Dimension(n, ..) => Some(n),
         Value::Null => None,
         v => {
             return Err((
@@ -161,12 +413,23 @@ pub(crate) fn adjust_color(mut args: CallArgs, parser: &mut Parser) -> SassResul
     if hue.is_some() || saturation.is_some() || luminance.is_some() {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
-        return Ok(Value::Color(Box::new(Color::from_hsla(
-            this_hue + hue.unwrap_or_else(Number::zero),
-            this_saturation + saturation.unwrap_or_else(Number::zero),
-            this_luminance + luminance.unwrap_or_else(Number::zero),
-            this_alpha + alpha.unwrap_or_else(Number::zero),
-        ))));
+        let set = [
+            hue.is_some(),
+            saturation.is_some(),
+            luminance.is_some(),
+            alpha.is_some(),
+        ];
+
+        return Ok(preserve_missing(
+            &color,
+            Color::from_hsla(
+                this_hue + hue.unwrap_or_else(Number::zero),
+                this_saturation + saturation.unwrap_or_else(Number::zero),
+                this_luminance + luminance.unwrap_or_else(Number::zero),
+                this_alpha + alpha.unwrap_or_else(Number::zero),
+            ),
+            set,
+        ));
     }
 
     Ok(Value::Color(if let Some(a) = alpha {
@@ -202,10 +465,12 @@ pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser) -> SassResult
     macro_rules! opt_scale_arg {
         ($args:ident, $name:ident, $arg:literal, $low:literal, $high:literal) => {
             let $name = match $args.default_named_arg($arg, Value::Null)? {
-                Value::Dimension(Some(n), Unit::Percent, _) => {
+                Value::Dimension(n, ..) if !n.is_finite() => {
+                    return Err(non_finite_channel($arg, &n, $args.span()))
+                }
+                Value::Dimension(n, Unit::Percent, _) => {
                     Some(bound!($args, $arg, n, Unit::Percent, $low, $high) / Number::from(100))
                 }
-                Value::Dimension(None, ..) => todo!(),
                 v @ Value::Dimension(..) => {
                     return Err((
                         format!(
@@ -230,33 +495,51 @@ pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser) -> SassResult
     }
 
     opt_scale_arg!(args, alpha, "alpha", -100, 100);
+
+    let space = args.default_named_arg("space", Value::Null)?;
+
+    if !color.is_legacy() || space != Value::Null {
+        return modify_in_space(&color, space, &mut args, alpha, Modification::Scale);
+    }
+
     opt_scale_arg!(args, red, "red", -100, 100);
     opt_scale_arg!(args, green, "green", -100, 100);
     opt_scale_arg!(args, blue, "blue", -100, 100);
 
     if red.is_some() || green.is_some() || blue.is_some() {
-        return Ok(Value::Color(Box::new(Color::from_rgba(
-            scale(
-                color.red(),
-                red.unwrap_or_else(Number::zero),
-                Number::from(255),
-            ),
-            scale(
-                color.green(),
-                green.unwrap_or_else(Number::zero),
-                Number::from(255),
-            ),
-            scale(
-                color.blue(),
-                blue.unwrap_or_else(Number::zero),
-                Number::from(255),
-            ),
-            scale(
-                color.alpha(),
-                alpha.unwrap_or_else(Number::zero),
-                Number::one(),
+        let set = [
+            red.is_some(),
+            green.is_some(),
+            blue.is_some(),
+            alpha.is_some(),
+        ];
+
+        return Ok(preserve_missing(
+            &color,
+            Color::from_rgba(
+                scale(
+                    color.red(),
+                    red.unwrap_or_else(Number::zero),
+                    Number::from(255),
+                ),
+                scale(
+                    color.green(),
+                    green.unwrap_or_else(Number::zero),
+                    Number::from(255),
+                ),
+                scale(
+                    color.blue(),
+                    blue.unwrap_or_else(Number::zero),
+                    Number::from(255),
+                ),
+                scale(
+                    color.alpha(),
+                    alpha.unwrap_or_else(Number::zero),
+                    Number::one(),
+                ),
             ),
-        ))));
+            set,
+        ));
     }
 
     opt_scale_arg!(args, saturation, "saturation", -100, 100);
@@ -265,24 +548,35 @@ pub(crate) fn scale_color(mut args: CallArgs, parser: &mut Parser) -> SassResult
     if saturation.is_some() || luminance.is_some() {
         // Color::as_hsla() returns more exact values than Color::hue(), etc.
         let (this_hue, this_saturation, this_luminance, this_alpha) = color.as_hsla();
-        return Ok(Value::Color(Box::new(Color::from_hsla(
-            scale(this_hue, Number::zero(), Number::from(360)),
-            scale(
-                this_saturation,
-                saturation.unwrap_or_else(Number::zero),
-                Number::one(),
-            ),
-            scale(
-                this_luminance,
-                luminance.unwrap_or_else(Number::zero),
-                Number::one(),
-            ),
-            scale(
-                this_alpha,
-                alpha.unwrap_or_else(Number::zero),
-                Number::one(),
+        let set = [
+            false,
+            saturation.is_some(),
+            luminance.is_some(),
+            alpha.is_some(),
+        ];
+
+        return Ok(preserve_missing(
+            &color,
+            Color::from_hsla(
+                scale(this_hue, Number::zero(), Number::from(360)),
+                scale(
+                    this_saturation,
+                    saturation.unwrap_or_else(Number::zero),
+                    Number::one(),
+                ),
+                scale(
+                    this_luminance,
+                    luminance.unwrap_or_else(Number::zero),
+                    Number::one(),
+                ),
+                scale(
+                    this_alpha,
+                    alpha.unwrap_or_else(Number::zero),
+                    Number::one(),
+                ),
             ),
-        ))));
+            set,
+        ));
     }
 
     Ok(Value::Color(if let Some(a) = alpha {
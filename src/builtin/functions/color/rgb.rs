@@ -1,10 +1,13 @@
-use super::{Builtin, GlobalFunctionMap};
+use super::{
+    non_finite_channel, parse_channels, space::interpolation_method, take_missing, with_missing,
+    Builtin, GlobalFunctionMap, ParsedChannels,
+};
 
 use num_traits::One;
 
 use crate::{
     args::CallArgs,
-    color::Color,
+    color::{Color, ColorSpace},
     common::{Brackets, ListSeparator, QuoteKind},
     error::SassResult,
     parse::Parser,
@@ -13,8 +16,6 @@ use crate::{
 };
 
 /// name: Either `rgb` or `rgba` depending on the caller
-// todo: refactor into smaller functions
-#[allow(clippy::cognitive_complexity)]
 fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     if args.is_empty() {
         return Err(("Missing argument $channels.", args.span()).into());
@@ -23,136 +24,29 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser) -> Sas
     let len = args.len();
 
     if len == 1 {
-        let mut channels = match args.get_err(0, "channels")? {
-            Value::List(v, ..) => v,
-            v if v.is_special_function() => vec![v],
-            _ => return Err(("Missing argument $channels.", args.span()).into()),
-        };
-
-        if channels.len() > 3 {
-            return Err((
-                format!(
-                    "Only 3 elements allowed, but {} were passed.",
-                    channels.len()
-                ),
-                args.span(),
-            )
-                .into());
-        }
-
-        if channels.iter().any(Value::is_special_function) {
-            let channel_sep = if channels.len() < 3 {
-                ListSeparator::Space
-            } else {
-                ListSeparator::Comma
-            };
-
-            return Ok(Value::String(
-                format!(
-                    "{}({})",
-                    name,
-                    Value::List(channels, channel_sep, Brackets::None)
-                        .to_css_string(args.span(), false)?
-                ),
-                QuoteKind::None,
-            ));
-        }
-
-        let blue = match channels.pop() {
-            Some(Value::Dimension(Some(n), Unit::None, _)) => n,
-            Some(Value::Dimension(Some(n), Unit::Percent, _)) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) if v.is_special_function() => {
-                let green = channels.pop().unwrap();
-                let red = channels.pop().unwrap();
-                return Ok(Value::String(
-                    format!(
-                        "{}({}, {}, {})",
-                        name,
-                        red.to_css_string(args.span(), parser.options.is_compressed())?,
-                        green.to_css_string(args.span(), parser.options.is_compressed())?,
-                        v.to_css_string(args.span(), parser.options.is_compressed())?
-                    ),
-                    QuoteKind::None,
-                ));
-            }
-            Some(v) => {
-                return Err((
-                    format!("$blue: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $blue.", args.span()).into()),
+        let mut channels = match parse_channels(
+            name,
+            ["red", "green", "blue"],
+            args.get_err(0, "channels")?,
+            args.span(),
+        )? {
+            ParsedChannels::String(s) => return Ok(s),
+            ParsedChannels::List(list) => list,
         };
 
-        let green = match channels.pop() {
-            Some(Value::Dimension(Some(n), Unit::None, _)) => n,
-            Some(Value::Dimension(Some(n), Unit::Percent, _)) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) if v.is_special_function() => {
-                let string = match channels.pop() {
-                    Some(red) => format!(
-                        "{}({}, {}, {})",
-                        name,
-                        red.to_css_string(args.span(), parser.options.is_compressed())?,
-                        v.to_css_string(args.span(), parser.options.is_compressed())?,
-                        blue.to_string(parser.options.is_compressed())
-                    ),
-                    None => format!(
-                        "{}({} {})",
-                        name,
-                        v.to_css_string(args.span(), parser.options.is_compressed())?,
-                        blue.to_string(parser.options.is_compressed())
-                    ),
-                };
-                return Ok(Value::String(string, QuoteKind::None));
-            }
-            Some(v) => {
-                return Err((
-                    format!("$green: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $green.", args.span()).into()),
-        };
+        let missing = take_missing(ColorSpace::Rgb, &mut channels);
 
-        let red = match channels.pop() {
-            Some(Value::Dimension(Some(n), Unit::None, _)) => n,
-            Some(Value::Dimension(Some(n), Unit::Percent, _)) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Some(Value::Dimension(None, ..)) => todo!(),
-            Some(v) if v.is_special_function() => {
-                return Ok(Value::String(
-                    format!(
-                        "{}({}, {}, {})",
-                        name,
-                        v.to_css_string(args.span(), parser.options.is_compressed())?,
-                        green.to_string(parser.options.is_compressed()),
-                        blue.to_string(parser.options.is_compressed())
-                    ),
-                    QuoteKind::None,
-                ));
-            }
-            Some(v) => {
-                return Err((
-                    format!("$red: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-            None => return Err(("Missing element $red.", args.span()).into()),
+        let alpha = if channels.len() == 4 {
+            channels.pop()
+        } else {
+            None
         };
+        let blue = channels.pop().unwrap();
+        let green = channels.pop().unwrap();
+        let red = channels.pop().unwrap();
 
-        let color = Color::from_rgba(red, green, blue, Number::one());
-
-        Ok(Value::Color(Box::new(color)))
+        rgb_from_channels(name, red, green, blue, alpha, &args, parser)
+            .map(|color| with_missing(color, missing))
     } else if len == 2 {
         let color = args.get_err(0, "color")?;
         let alpha = args.get_err(1, "alpha")?;
@@ -195,9 +89,11 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser) -> Sas
         }
 
         let alpha = match alpha {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
+            Value::Dimension(n, ..) if !n.is_finite() => {
+                return Err(non_finite_channel("alpha", &n, args.span()))
+            }
+            Value::Dimension(n, Unit::None, _) => n,
+            Value::Dimension(n, Unit::Percent, _) => n / Number::from(100),
             v @ Value::Dimension(..) => {
                 return Err((
                     format!(
@@ -221,134 +117,147 @@ fn inner_rgb(name: &'static str, mut args: CallArgs, parser: &mut Parser) -> Sas
         let red = args.get_err(0, "red")?;
         let green = args.get_err(1, "green")?;
         let blue = args.get_err(2, "blue")?;
-        let alpha = args.default_arg(
-            3,
-            "alpha",
-            Value::Dimension(Some(Number::one()), Unit::None, true),
-        )?;
+        let alpha = match args.get(3, "alpha") {
+            Some(v) => Some(v?.node),
+            None => None,
+        };
 
-        if [&red, &green, &blue, &alpha]
-            .iter()
-            .copied()
-            .any(Value::is_special_function)
-        {
-            return Ok(Value::String(
-                format!(
-                    "{}({})",
-                    name,
-                    Value::List(
-                        if len == 4 {
-                            vec![red, green, blue, alpha]
-                        } else {
-                            vec![red, green, blue]
-                        },
-                        ListSeparator::Comma,
-                        Brackets::None
-                    )
+        rgb_from_channels(name, red, green, blue, alpha, &args, parser)
+    }
+}
+
+/// Create a color from its red, green, blue and (optionally) alpha channels,
+/// or a plain CSS function call if any of them are special functions
+fn rgb_from_channels(
+    name: &'static str,
+    red: Value,
+    green: Value,
+    blue: Value,
+    alpha: Option<Value>,
+    args: &CallArgs,
+    parser: &mut Parser,
+) -> SassResult<Value> {
+    if [Some(&red), Some(&green), Some(&blue), alpha.as_ref()]
+        .iter()
+        .flatten()
+        .copied()
+        .any(Value::is_special_function)
+    {
+        let mut channels = vec![red, green, blue];
+        channels.extend(alpha);
+
+        return Ok(Value::String(
+            format!(
+                "{}({})",
+                name,
+                Value::List(channels, ListSeparator::Comma, Brackets::None)
                     .to_css_string(args.span(), false)?
+            ),
+            QuoteKind::None,
+        ));
+    }
+
+    let alpha = alpha.unwrap_or(Value::Dimension(Number::one(), Unit::None, true));
+
+    let red = match red {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("red", &n, args.span()))
+        }
+        Value::Dimension(n, Unit::None, _) => n,
+        Value::Dimension(n, Unit::Percent, _) => (n / Number::from(100)) * Number::from(255),
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "$red: Expected {} to have no units or \"%\".",
+                    v.to_css_string(args.span(), parser.options.is_compressed())?
                 ),
-                QuoteKind::None,
-            ));
+                args.span(),
+            )
+                .into())
         }
-
-        let red = match red {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$red: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span(), parser.options.is_compressed())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v => {
-                return Err((
-                    format!("$red: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let green = match green {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$green: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span(), parser.options.is_compressed())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v => {
-                return Err((
-                    format!("$green: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let blue = match blue {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => {
-                (n / Number::from(100)) * Number::from(255)
-            }
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$blue: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span(), parser.options.is_compressed())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v => {
-                return Err((
-                    format!("$blue: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        let alpha = match alpha {
-            Value::Dimension(Some(n), Unit::None, _) => n,
-            Value::Dimension(Some(n), Unit::Percent, _) => n / Number::from(100),
-            Value::Dimension(None, ..) => todo!(),
-            v @ Value::Dimension(..) => {
-                return Err((
-                    format!(
-                        "$alpha: Expected {} to have no units or \"%\".",
-                        v.to_css_string(args.span(), parser.options.is_compressed())?
-                    ),
-                    args.span(),
-                )
-                    .into())
-            }
-            v => {
-                return Err((
-                    format!("$alpha: {} is not a number.", v.inspect(args.span())?),
-                    args.span(),
-                )
-                    .into())
-            }
-        };
-        Ok(Value::Color(Box::new(Color::from_rgba(
-            red, green, blue, alpha,
-        ))))
-    }
+        v => {
+            return Err((
+                format!("$red: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    let green = match green {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("green", &n, args.span()))
+        }
+        Value::Dimension(n, Unit::None, _) => n,
+        Value::Dimension(n, Unit::Percent, _) => (n / Number::from(100)) * Number::from(255),
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "$green: Expected {} to have no units or \"%\".",
+                    v.to_css_string(args.span(), parser.options.is_compressed())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("$green: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    let blue = match blue {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("blue", &n, args.span()))
+        }
+        Value::Dimension(n, Unit::None, _) => n,
+        Value::Dimension(n, Unit::Percent, _) => (n / Number::from(100)) * Number::from(255),
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "$blue: Expected {} to have no units or \"%\".",
+                    v.to_css_string(args.span(), parser.options.is_compressed())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("$blue: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    let alpha = match alpha {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("alpha", &n, args.span()))
+        }
+        Value::Dimension(n, Unit::None, _) => n,
+        Value::Dimension(n, Unit::Percent, _) => n / Number::from(100),
+        v @ Value::Dimension(..) => {
+            return Err((
+                format!(
+                    "$alpha: Expected {} to have no units or \"%\".",
+                    v.to_css_string(args.span(), parser.options.is_compressed())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+        v => {
+            return Err((
+                format!("$alpha: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+    Ok(Value::Color(Box::new(Color::from_rgba(
+        red, green, blue, alpha,
+    ))))
 }
 
 pub(crate) fn rgb(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
@@ -362,7 +271,7 @@ pub(crate) fn rgba(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
 pub(crate) fn red(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.red()), Unit::None, true)),
+        Value::Color(c) => Ok(Value::Dimension(c.red(), Unit::None, true)),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -374,7 +283,7 @@ pub(crate) fn red(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
 pub(crate) fn green(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.green()), Unit::None, true)),
+        Value::Color(c) => Ok(Value::Dimension(c.green(), Unit::None, true)),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -386,7 +295,7 @@ pub(crate) fn green(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value
 pub(crate) fn blue(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "color")? {
-        Value::Color(c) => Ok(Value::Dimension(Some(c.blue()), Unit::None, true)),
+        Value::Color(c) => Ok(Value::Dimension(c.blue(), Unit::None, true)),
         v => Err((
             format!("$color: {} is not a color.", v.inspect(args.span())?),
             args.span(),
@@ -396,7 +305,7 @@ pub(crate) fn blue(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
 }
 
 pub(crate) fn mix(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
-    args.max_args(3)?;
+    args.max_args(4)?;
     let color1 = match args.get_err(0, "color1")? {
         Value::Color(c) => c,
         v => {
@@ -422,10 +331,12 @@ pub(crate) fn mix(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
     let weight = match args.default_arg(
         2,
         "weight",
-        Value::Dimension(Some(Number::from(50)), Unit::None, true),
+        Value::Dimension(Number::from(50), Unit::None, true),
     )? {
-        Value::Dimension(Some(n), u, _) => bound!(args, "weight", n, u, 0, 100) / Number::from(100),
-        Value::Dimension(None, ..) => todo!(),
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel("weight", &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => bound!(args, "weight", n, u, 0, 100) / Number::from(100),
         v => {
             return Err((
                 format!(
@@ -437,7 +348,24 @@ pub(crate) fn mix(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
                 .into())
         }
     };
-    Ok(Value::Color(Box::new(color1.mix(&color2, weight))))
+
+    match args.default_arg(3, "method", Value::Null)? {
+        Value::Null if !color1.is_legacy() || !color2.is_legacy() => Err((
+            "To use color.mix() with non-legacy colors, you must provide a $method.",
+            args.span(),
+        )
+            .into()),
+        Value::Null => Ok(Value::Color(Box::new(color1.mix(&color2, weight)))),
+        method => {
+            let method = interpolation_method(method, &args)?;
+
+            Ok(Value::Color(Box::new(color1.interpolate(
+                &color2,
+                method,
+                weight.as_f64(),
+            ))))
+        }
+    }
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
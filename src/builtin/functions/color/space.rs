@@ -0,0 +1,536 @@
+use super::{
+    function_string, non_finite_channel, parse_channels, take_missing, Builtin, GlobalFunctionMap,
+    ParsedChannels,
+};
+
+use num_traits::One;
+
+use crate::{
+    args::CallArgs,
+    color::{Color, ColorSpace, GamutMapMethod, HueInterpolation, InterpolationMethod},
+    common::{Brackets, ListSeparator, QuoteKind},
+    error::SassResult,
+    parse::Parser,
+    unit::Unit,
+    value::{Number, Value},
+};
+
+fn inner_space(name: &'static str, space: ColorSpace, mut args: CallArgs) -> SassResult<Value> {
+    args.max_args(1)?;
+
+    let channels = args.get_err(0, "channels")?;
+
+    let list = match parse_channels(name, space.channel_names(), channels.clone(), args.span())? {
+        ParsedChannels::String(s) => return Ok(s),
+        ParsedChannels::List(list) => list,
+    };
+
+    if list.iter().any(Value::is_special_function) {
+        return function_string(name, &channels, args.span());
+    }
+
+    color_from_channels(space, list, &args)
+}
+
+pub(crate) fn lab(args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    inner_space("lab", ColorSpace::Lab, args)
+}
+
+pub(crate) fn lch(args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    inner_space("lch", ColorSpace::Lch, args)
+}
+
+pub(crate) fn oklab(args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    inner_space("oklab", ColorSpace::Oklab, args)
+}
+
+pub(crate) fn oklch(args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    inner_space("oklch", ColorSpace::Oklch, args)
+}
+
+/// Remove the color space from the `$description` of `color()`, which is the
+/// first element of its channels
+fn split_space(description: Value) -> (Option<Value>, Value) {
+    match description {
+        Value::List(mut list, ListSeparator::Slash, brackets) if !list.is_empty() => {
+            let (space, channels) = split_space(list.remove(0));
+            list.insert(0, channels);
+            (space, Value::List(list, ListSeparator::Slash, brackets))
+        }
+        Value::List(mut list, ListSeparator::Space, Brackets::None) if !list.is_empty() => {
+            let space = list.remove(0);
+            (
+                Some(space),
+                Value::List(list, ListSeparator::Space, Brackets::None),
+            )
+        }
+        v @ Value::String(..) => (
+            Some(v),
+            Value::List(Vec::new(), ListSeparator::Space, Brackets::None),
+        ),
+        v => (None, v),
+    }
+}
+
+pub(crate) fn color(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    args.max_args(1)?;
+
+    let description = args.get_err(0, "description")?;
+
+    if description.is_special_function() {
+        return function_string("color", &description, args.span());
+    }
+
+    let (space, channels) = split_space(description.clone());
+
+    let space = match space {
+        Some(v) if v.is_special_function() => {
+            return function_string("color", &description, args.span())
+        }
+        Some(Value::String(s, QuoteKind::None)) => match ColorSpace::from_name(&s) {
+            Some(
+                space @ (ColorSpace::Srgb
+                | ColorSpace::SrgbLinear
+                | ColorSpace::DisplayP3
+                | ColorSpace::A98Rgb
+                | ColorSpace::ProphotoRgb
+                | ColorSpace::Rec2020
+                | ColorSpace::Xyz
+                | ColorSpace::XyzD50),
+            ) => space,
+            _ => {
+                return Err((
+                    format!("$description: Unknown color space \"{}\".", s),
+                    args.span(),
+                )
+                    .into())
+            }
+        },
+        _ => {
+            return Err((
+                format!(
+                    "$description: Expected {} to begin with a color space.",
+                    description.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let list = match parse_channels("color", space.channel_names(), channels, args.span())? {
+        ParsedChannels::String(..) => return function_string("color", &description, args.span()),
+        ParsedChannels::List(list) => list,
+    };
+
+    if list.iter().any(Value::is_special_function) {
+        return function_string("color", &description, args.span());
+    }
+
+    color_from_channels(space, list, &args)
+}
+
+/// Create a color from its three channels in `space`, optionally followed by
+/// its alpha channel
+fn color_from_channels(
+    space: ColorSpace,
+    mut channels: Vec<Value>,
+    args: &CallArgs,
+) -> SassResult<Value> {
+    let missing = take_missing(space, &mut channels);
+
+    let alpha = match channels.get(3).cloned() {
+        Some(Value::Dimension(n, ..)) if !n.is_finite() => {
+            return Err(non_finite_channel("alpha", &n, args.span()))
+        }
+        Some(Value::Dimension(n, Unit::Percent, ..)) => n / Number::from(100),
+        Some(Value::Dimension(n, ..)) => n,
+        Some(v) => {
+            return Err((
+                format!("$alpha: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+        None => Number::one(),
+    };
+
+    channels.truncate(3);
+
+    let mut resolved = [0.0; 3];
+
+    for (idx, (channel, value)) in resolved.iter_mut().zip(channels).enumerate() {
+        *channel = parse_channel(space, idx, value, args)?;
+    }
+
+    Ok(Value::Color(Box::new(
+        Color::for_space(space, resolved, alpha).with_missing(missing),
+    )))
+}
+
+/// Resolve and clamp the channel at `idx` of a color in `space`
+fn parse_channel(space: ColorSpace, idx: usize, value: Value, args: &CallArgs) -> SassResult<f64> {
+    Ok(clamp_channel(
+        space,
+        idx,
+        resolve_channel(space, idx, value, args)?,
+    ))
+}
+
+/// The value of `100%` for the channel at `idx` of a color in `space`
+fn percent_reference(space: ColorSpace, idx: usize) -> f64 {
+    match (space, idx) {
+        (ColorSpace::Rgb, _) => 255.0,
+        (ColorSpace::Hsl | ColorSpace::Hwb, _) => 100.0,
+        (ColorSpace::Lab | ColorSpace::Lch, 0) => 100.0,
+        (ColorSpace::Lab, _) => 125.0,
+        (ColorSpace::Lch, _) => 150.0,
+        (ColorSpace::Oklab | ColorSpace::Oklch, 0) => 1.0,
+        (ColorSpace::Oklab | ColorSpace::Oklch, _) => 0.4,
+        _ => 1.0,
+    }
+}
+
+/// Clamp the channel at `idx` of a color in `space` to the values CSS allows,
+/// which are only bounded for lightness and chroma
+pub(crate) fn clamp_channel(space: ColorSpace, idx: usize, channel: f64) -> f64 {
+    match (space, idx) {
+        (ColorSpace::Lab | ColorSpace::Lch | ColorSpace::Oklab | ColorSpace::Oklch, 0) => {
+            channel.clamp(0.0, percent_reference(space, idx))
+        }
+        (ColorSpace::Lch | ColorSpace::Oklch, 1) => channel.max(0.0),
+        _ => channel,
+    }
+}
+
+/// Resolve the channel at `idx` of a color in `space` to the scale used by
+/// `ColorSpace`, so that `50%` becomes `0.5` for the lightness of `oklab()`
+pub(crate) fn resolve_channel(
+    space: ColorSpace,
+    idx: usize,
+    value: Value,
+    args: &CallArgs,
+) -> SassResult<f64> {
+    let name = space.channel_names()[idx];
+
+    let (number, unit) = match value {
+        Value::Dimension(n, ..) if !n.is_finite() => {
+            return Err(non_finite_channel(name, &n, args.span()))
+        }
+        Value::Dimension(n, u, _) => (n, u),
+        v => {
+            return Err((
+                format!("${}: {} is not a number.", name, v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    if space.hue_index() == Some(idx) {
+        return match unit {
+            Unit::None => Ok(number.as_f64()),
+            Unit::Deg | Unit::Grad | Unit::Rad | Unit::Turn => {
+                Ok(number.convert(&unit, &Unit::Deg).as_f64())
+            }
+            unit => Err((
+                format!(
+                    "${}: Expected {} to have an angle unit (deg, grad, rad, turn).",
+                    name,
+                    Value::Dimension(number, unit, true).inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into()),
+        };
+    }
+
+    match unit {
+        Unit::None => Ok(number.as_f64()),
+        Unit::Percent => Ok(number.as_f64() / 100.0 * percent_reference(space, idx)),
+        unit => Err((
+            format!(
+                "${}: Expected {} to have unit \"%\" or no units.",
+                name,
+                Value::Dimension(number, unit, true).inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+fn color_arg(args: &mut CallArgs, position: usize, name: &'static str) -> SassResult<Box<Color>> {
+    match args.get_err(position, name)? {
+        Value::Color(c) => Ok(c),
+        v => Err((
+            format!("${}: {} is not a color.", name, v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+/// Parse the `$space` argument of the color space functions, which is the
+/// space of `color` if it is `null`
+pub(crate) fn space_arg(value: Value, color: &Color, args: &CallArgs) -> SassResult<ColorSpace> {
+    match value {
+        Value::Null => Ok(color.space()),
+        Value::String(s, ..) => ColorSpace::from_name(&s).ok_or_else(|| {
+            (
+                format!("$space: Unknown color space \"{}\".", s),
+                args.span(),
+            )
+                .into()
+        }),
+        v => Err((
+            format!("$space: {} is not a string.", v.inspect(args.span())?),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+pub(crate) fn to_space(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    args.max_args(2)?;
+
+    let color = color_arg(&mut args, 0, "color")?;
+    let space = match args.get_err(1, "space")? {
+        Value::Null => return Err(("Missing argument $space.", args.span()).into()),
+        v => space_arg(v, &color, &args)?,
+    };
+
+    Ok(Value::Color(Box::new(color.to_space(space))))
+}
+
+pub(crate) fn space(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    args.max_args(1)?;
+
+    let color = color_arg(&mut args, 0, "color")?;
+
+    Ok(Value::String(
+        color.space().name().to_owned(),
+        QuoteKind::None,
+    ))
+}
+
+pub(crate) fn channel(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    args.max_args(3)?;
+
+    let color = color_arg(&mut args, 0, "color")?;
+
+    let channel = match args.get_err(1, "channel")? {
+        Value::String(s, QuoteKind::Quoted) => s,
+        v => {
+            return Err((
+                format!(
+                    "$channel: Expected {} to be a quoted string.",
+                    v.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let space = space_arg(args.default_arg(2, "space", Value::Null)?, &color, &args)?;
+
+    if channel == "alpha" {
+        return Ok(Value::Dimension(color.alpha(), Unit::None, true));
+    }
+
+    let idx = match space
+        .channel_names()
+        .iter()
+        .position(|name| *name == channel)
+    {
+        Some(idx) => idx,
+        None => {
+            return Err((
+                format!(
+                    "$channel: Color {} has no channel named {}.",
+                    color.to_space(space),
+                    channel
+                ),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let value = color.to_space(space).channels()[idx];
+
+    let (value, unit) = match (space, idx) {
+        (space, idx) if space.hue_index() == Some(idx) => (value, Unit::Deg),
+        (ColorSpace::Hsl | ColorSpace::Hwb, _) | (ColorSpace::Lab | ColorSpace::Lch, 0) => {
+            (value, Unit::Percent)
+        }
+        (ColorSpace::Oklab | ColorSpace::Oklch, 0) => (value * 100.0, Unit::Percent),
+        _ => (value, Unit::None),
+    };
+
+    Ok(Value::Dimension(Number::from(value), unit, true))
+}
+
+pub(crate) fn is_missing(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    args.max_args(2)?;
+
+    let color = color_arg(&mut args, 0, "color")?;
+
+    let channel = match args.get_err(1, "channel")? {
+        Value::String(s, QuoteKind::Quoted) => s,
+        v => {
+            return Err((
+                format!(
+                    "$channel: Expected {} to be a quoted string.",
+                    v.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    if channel == "alpha" {
+        return Ok(Value::bool(color.is_missing(3)));
+    }
+
+    match color
+        .space()
+        .channel_names()
+        .iter()
+        .position(|name| *name == channel)
+    {
+        Some(idx) => Ok(Value::bool(color.is_missing(idx))),
+        None => Err((
+            format!(
+                "$channel: Color {} has no channel named {}.",
+                color, channel
+            ),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
+pub(crate) fn is_in_gamut(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    args.max_args(2)?;
+
+    let color = color_arg(&mut args, 0, "color")?;
+    let space = space_arg(args.default_arg(1, "space", Value::Null)?, &color, &args)?;
+
+    Ok(Value::bool(color.is_in_gamut(space)))
+}
+
+pub(crate) fn to_gamut(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    args.max_args(3)?;
+
+    let color = color_arg(&mut args, 0, "color")?;
+    let space = space_arg(args.default_arg(1, "space", Value::Null)?, &color, &args)?;
+
+    let method = match args.default_arg(2, "method", Value::Null)? {
+        Value::Null => {
+            return Err((
+                "$method: color.to-gamut() requires a $method argument, either \"clip\" or \"local-minde\".",
+                args.span(),
+            )
+                .into())
+        }
+        Value::String(s, ..) => match GamutMapMethod::from_name(&s) {
+            Some(method) => method,
+            None => {
+                return Err((
+                    format!("$method: Unknown gamut map method \"{}\".", s),
+                    args.span(),
+                )
+                    .into())
+            }
+        },
+        v => {
+            return Err((
+                format!("$method: {} is not a string.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    Ok(Value::Color(Box::new(color.to_gamut(space, method))))
+}
+
+/// Parse the `$method` argument of `color.mix()`, which is a color space
+/// optionally followed by a hue interpolation method, as in `oklch longer hue`
+pub(crate) fn interpolation_method(
+    method: Value,
+    args: &CallArgs,
+) -> SassResult<InterpolationMethod> {
+    let list = method.clone().as_list();
+
+    let words = list
+        .iter()
+        .map(|v| match v {
+            Value::String(s, QuoteKind::None) => Some(s.as_str()),
+            _ => None,
+        })
+        .collect::<Option<Vec<&str>>>();
+
+    let (space, hue) = match words.as_deref() {
+        Some([space]) => (*space, None),
+        Some([space, hue, "hue"]) => (*space, Some(*hue)),
+        _ => {
+            return Err((
+                format!(
+                    "$method: Expected {} to be a color interpolation method, such as \"oklch\" or \"hsl longer hue\".",
+                    method.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let space = match ColorSpace::from_name(space) {
+        Some(space) => space,
+        None => {
+            return Err((
+                format!("$method: Unknown color space \"{}\".", space),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let hue = match hue {
+        Some(hue) if space.hue_index().is_none() => {
+            return Err((
+                format!(
+                    "$method: Hue interpolation method \"{} hue\" may not be set for rectangular color space {}.",
+                    hue, space
+                ),
+                args.span(),
+            )
+                .into())
+        }
+        Some(hue) => match HueInterpolation::from_name(hue) {
+            Some(hue) => hue,
+            None => {
+                return Err((
+                    format!("$method: Unknown hue interpolation method {}.", hue),
+                    args.span(),
+                )
+                    .into())
+            }
+        },
+        None => HueInterpolation::Shorter,
+    };
+
+    Ok(InterpolationMethod { space, hue })
+}
+
+pub(crate) fn declare(f: &mut GlobalFunctionMap) {
+    f.insert("lab", Builtin::new(lab));
+    f.insert("lch", Builtin::new(lch));
+    f.insert("oklab", Builtin::new(oklab));
+    f.insert("oklch", Builtin::new(oklch));
+    f.insert("color", Builtin::new(color));
+}
@@ -14,7 +14,7 @@ use crate::{
 pub(crate) fn length(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     Ok(Value::Dimension(
-        Some(Number::from(args.get_err(0, "list")?.as_list().len())),
+        Number::from(args.get_err(0, "list")?.as_list().len()),
         Unit::None,
         true,
     ))
@@ -24,10 +24,10 @@ pub(crate) fn nth(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
     args.max_args(2)?;
     let mut list = args.get_err(0, "list")?.as_list();
     let (n, unit) = match args.get_err(1, "n")? {
-        Value::Dimension(Some(num), unit, ..) => (num, unit),
-        Value::Dimension(None, u, ..) => {
+        Value::Dimension(num, u, ..) if num.is_nan() => {
             return Err((format!("$n: NaN{} is not an int.", u), args.span()).into())
         }
+        Value::Dimension(num, unit, ..) => (num, unit),
         v => {
             return Err((
                 format!("$n: {} is not a number.", v.inspect(args.span())?),
@@ -83,7 +83,7 @@ pub(crate) fn set_nth(mut args: CallArgs, parser: &mut Parser) -> SassResult<Val
     let (mut list, sep, brackets) = match args.get_err(0, "list")? {
         Value::List(v, sep, b) => (v, sep, b),
         Value::ArgList(v) => (
-            v.into_iter().map(|val| val.node).collect(),
+            v.elems.into_iter().map(|val| val.node).collect(),
             ListSeparator::Comma,
             Brackets::None,
         ),
@@ -91,10 +91,10 @@ pub(crate) fn set_nth(mut args: CallArgs, parser: &mut Parser) -> SassResult<Val
         v => (vec![v], ListSeparator::Space, Brackets::None),
     };
     let (n, unit) = match args.get_err(1, "n")? {
-        Value::Dimension(Some(num), unit, ..) => (num, unit),
-        Value::Dimension(None, u, ..) => {
+        Value::Dimension(num, u, ..) if num.is_nan() => {
             return Err((format!("$n: NaN{} is not an int.", u), args.span()).into())
         }
+        Value::Dimension(num, unit, ..) => (num, unit),
         v => {
             return Err((
                 format!("$n: {} is not a number.", v.inspect(args.span())?),
@@ -154,9 +154,10 @@ pub(crate) fn append(mut args: CallArgs, parser: &mut Parser) -> SassResult<Valu
             "auto" => sep,
             "comma" => ListSeparator::Comma,
             "space" => ListSeparator::Space,
+            "slash" => ListSeparator::Slash,
             _ => {
                 return Err((
-                    "$separator: Must be \"space\", \"comma\", or \"auto\".",
+                    "$separator: Must be \"space\", \"comma\", \"slash\", or \"auto\".",
                     args.span(),
                 )
                     .into())
@@ -203,9 +204,10 @@ pub(crate) fn join(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
             }
             "comma" => ListSeparator::Comma,
             "space" => ListSeparator::Space,
+            "slash" => ListSeparator::Slash,
             _ => {
                 return Err((
-                    "$separator: Must be \"space\", \"comma\", or \"auto\".",
+                    "$separator: Must be \"space\", \"comma\", \"slash\", or \"auto\".",
                     args.span(),
                 )
                     .into())
@@ -262,7 +264,7 @@ pub(crate) fn index(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value
         Some(v) => Number::from(v + 1),
         None => return Ok(Value::Null),
     };
-    Ok(Value::Dimension(Some(index), Unit::None, true))
+    Ok(Value::Dimension(index, Unit::None, true))
 }
 
 pub(crate) fn zip(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
@@ -292,6 +294,22 @@ pub(crate) fn zip(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     Ok(Value::List(result, ListSeparator::Comma, Brackets::None))
 }
 
+pub(crate) fn slash(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
+    let span = args.span();
+
+    let elements = args
+        .get_variadic()?
+        .into_iter()
+        .map(|arg| arg.node)
+        .collect::<Vec<Value>>();
+
+    if elements.len() < 2 {
+        return Err(("At least two elements are required.", span).into());
+    }
+
+    Ok(Value::List(elements, ListSeparator::Slash, Brackets::None))
+}
+
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
     f.insert("length", Builtin::new(length));
     f.insert("nth", Builtin::new(nth));
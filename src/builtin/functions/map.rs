@@ -9,7 +9,6 @@ use crate::{
 };
 
 pub(crate) fn map_get(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
-    args.max_args(2)?;
     let key = args.get_err(1, "key")?;
     let map = match args.get_err(0, "map")? {
         Value::Map(m) => m,
@@ -23,11 +22,20 @@ pub(crate) fn map_get(mut args: CallArgs, parser: &mut Parser) -> SassResult<Val
                 .into())
         }
     };
-    Ok(map.get(&key).unwrap_or(Value::Null))
+
+    let mut value = map.get(&key);
+
+    for key in args.get_variadic()? {
+        value = match value {
+            Some(Value::Map(m)) => m.get(&key.node),
+            _ => return Ok(Value::Null),
+        };
+    }
+
+    Ok(value.unwrap_or(Value::Null))
 }
 
 pub(crate) fn map_has_key(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
-    args.max_args(2)?;
     let key = args.get_err(1, "key")?;
     let map = match args.get_err(0, "map")? {
         Value::Map(m) => m,
@@ -41,7 +49,22 @@ pub(crate) fn map_has_key(mut args: CallArgs, parser: &mut Parser) -> SassResult
                 .into())
         }
     };
-    Ok(Value::bool(map.get(&key).is_some()))
+
+    let mut keys = vec![key];
+    keys.extend(args.get_variadic()?.into_iter().map(|key| key.node));
+
+    // every key but the last must name a nested map
+    let last_key = keys.pop().unwrap();
+    let mut map = map;
+
+    for key in keys {
+        map = match map.get(&key) {
+            Some(Value::Map(m)) => m,
+            _ => return Ok(Value::False),
+        };
+    }
+
+    Ok(Value::bool(map.get(&last_key).is_some()))
 }
 
 pub(crate) fn map_keys(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
@@ -183,6 +206,94 @@ pub(crate) fn map_remove(mut args: CallArgs, parser: &mut Parser) -> SassResult<
     Ok(Value::Map(map))
 }
 
+pub(crate) fn map_deep_merge(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
+    args.max_args(2)?;
+
+    let map1 = match args.get_err(0, "map1")? {
+        Value::Map(m) => m,
+        Value::List(v, ..) if v.is_empty() => SassMap::new(),
+        Value::ArgList(v) if v.is_empty() => SassMap::new(),
+        v => {
+            return Err((
+                format!("$map1: {} is not a map.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let map2 = match args.get_err(1, "map2")? {
+        Value::Map(m) => m,
+        Value::List(v, ..) if v.is_empty() => SassMap::new(),
+        Value::ArgList(v) if v.is_empty() => SassMap::new(),
+        v => {
+            return Err((
+                format!("$map2: {} is not a map.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    Ok(Value::Map(deep_merge(map1, map2)))
+}
+
+/// Merge `map2` into `map1`, merging rather than replacing the values of keys
+/// that are maps in both
+fn deep_merge(mut map1: SassMap, map2: SassMap) -> SassMap {
+    for (key, value) in map2 {
+        match (map1.get_mut(&key), value) {
+            (Some(Value::Map(m1)), Value::Map(m2)) => {
+                *m1 = deep_merge(std::mem::take(m1), m2);
+            }
+            // an empty list is treated as an empty map, which leaves `m1` as-is
+            (Some(Value::Map(..)), Value::List(v, ..)) if v.is_empty() => {}
+            (.., value) => {
+                map1.insert(key, value);
+            }
+        }
+    }
+
+    map1
+}
+
+pub(crate) fn map_deep_remove(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
+    let mut map = match args.get_err(0, "map")? {
+        Value::Map(m) => m,
+        Value::List(v, ..) if v.is_empty() => SassMap::new(),
+        Value::ArgList(v) if v.is_empty() => SassMap::new(),
+        v => {
+            return Err((
+                format!("$map: {} is not a map.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let key = args.get_err(1, "key")?;
+
+    let mut keys = vec![key];
+    keys.extend(args.get_variadic()?.into_iter().map(|key| key.node));
+
+    let last_key = keys.pop().unwrap();
+
+    // if any key but the last doesn't name a nested map, there is nothing to
+    // remove and the map is returned unchanged
+    let mut current_map = &mut map;
+
+    for key in &keys {
+        current_map = match current_map.get_mut(key) {
+            Some(Value::Map(m)) => m,
+            _ => return Ok(Value::Map(map)),
+        };
+    }
+
+    current_map.remove(&last_key);
+
+    Ok(Value::Map(map))
+}
+
 pub(crate) fn map_set(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     let key_position = args.len().saturating_sub(2);
     let value_position = args.len().saturating_sub(1);
@@ -17,8 +17,7 @@ use crate::{
 pub(crate) fn percentage(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     let num = match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), Unit::None, _) => Some(n * Number::from(100)),
-        Value::Dimension(None, Unit::None, _) => None,
+        Value::Dimension(n, Unit::None, _) => n * Number::from(100),
         v @ Value::Dimension(..) => {
             return Err((
                 format!(
@@ -43,8 +42,7 @@ pub(crate) fn percentage(mut args: CallArgs, parser: &mut Parser) -> SassResult<
 pub(crate) fn round(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), u, _) => Ok(Value::Dimension(Some(n.round()), u, true)),
-        Value::Dimension(None, ..) => Err(("Infinity or NaN toInt", args.span()).into()),
+        Value::Dimension(n, u, _) => Ok(Value::Dimension(n.round(), u, true)),
         v => Err((
             format!("$number: {} is not a number.", v.inspect(args.span())?),
             args.span(),
@@ -56,8 +54,7 @@ pub(crate) fn round(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value
 pub(crate) fn ceil(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), u, _) => Ok(Value::Dimension(Some(n.ceil()), u, true)),
-        Value::Dimension(None, ..) => Err(("Infinity or NaN toInt", args.span()).into()),
+        Value::Dimension(n, u, _) => Ok(Value::Dimension(n.ceil(), u, true)),
         v => Err((
             format!("$number: {} is not a number.", v.inspect(args.span())?),
             args.span(),
@@ -69,8 +66,7 @@ pub(crate) fn ceil(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value>
 pub(crate) fn floor(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), u, _) => Ok(Value::Dimension(Some(n.floor()), u, true)),
-        Value::Dimension(None, ..) => Err(("Infinity or NaN toInt", args.span()).into()),
+        Value::Dimension(n, u, _) => Ok(Value::Dimension(n.floor(), u, true)),
         v => Err((
             format!("$number: {} is not a number.", v.inspect(args.span())?),
             args.span(),
@@ -82,8 +78,7 @@ pub(crate) fn floor(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value
 pub(crate) fn abs(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), u, _) => Ok(Value::Dimension(Some(n.abs()), u, true)),
-        Value::Dimension(None, u, ..) => Ok(Value::Dimension(None, u, true)),
+        Value::Dimension(n, u, _) => Ok(Value::Dimension(n.abs(), u, true)),
         v => Err((
             format!("$number: {} is not a number.", v.inspect(args.span())?),
             args.span(),
@@ -123,14 +118,14 @@ pub(crate) fn comparable(mut args: CallArgs, parser: &mut Parser) -> SassResult<
 pub(crate) fn random(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
     let limit = match args.default_arg(0, "limit", Value::Null)? {
-        Value::Dimension(Some(n), ..) => n,
-        Value::Dimension(None, u, ..) => {
+        Value::Dimension(n, u, ..) if n.is_nan() => {
             return Err((format!("$limit: NaN{} is not an int.", u), args.span()).into())
         }
+        Value::Dimension(n, ..) => n,
         Value::Null => {
             let mut rng = rand::thread_rng();
             return Ok(Value::Dimension(
-                Some(Number::from(rng.gen_range(0.0..1.0))),
+                Number::from(rng.gen_range(0.0..1.0)),
                 Unit::None,
                 true,
             ));
@@ -145,7 +140,7 @@ pub(crate) fn random(mut args: CallArgs, parser: &mut Parser) -> SassResult<Valu
     };
 
     if limit.is_one() {
-        return Ok(Value::Dimension(Some(Number::one()), Unit::None, true));
+        return Ok(Value::Dimension(Number::one(), Unit::None, true));
     }
 
     if limit.is_decimal() {
@@ -180,7 +175,7 @@ pub(crate) fn random(mut args: CallArgs, parser: &mut Parser) -> SassResult<Valu
 
     let mut rng = rand::thread_rng();
     Ok(Value::Dimension(
-        Some(Number::from(rng.gen_range(0..limit) + 1)),
+        Number::from(rng.gen_range(0..limit) + 1),
         Unit::None,
         true,
     ))
@@ -196,30 +191,22 @@ pub(crate) fn min(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
             Value::Dimension(number, unit, _) => Ok((number, unit)),
             v => Err((format!("{} is not a number.", v.inspect(span)?), span).into()),
         })
-        .collect::<SassResult<Vec<(Option<Number>, Unit)>>>()?
+        .collect::<SassResult<Vec<(Number, Unit)>>>()?
         .into_iter();
 
+    // `NaN` is neither less than nor greater than any number, so it's only
+    // returned if it's the first argument
     let mut min = match nums.next() {
-        Some((Some(n), u)) => (n, u),
-        Some((None, u)) => return Ok(Value::Dimension(None, u, true)),
+        Some((n, u)) => (n, u),
         None => unreachable!(),
     };
 
     for (num, unit) in nums {
-        let num = match num {
-            Some(n) => n,
-            None => continue,
-        };
-
         if ValueVisitor::new(parser, span)
             .less_than(
+                HigherIntermediateValue::Literal(Value::Dimension(num.clone(), unit.clone(), true)),
                 HigherIntermediateValue::Literal(Value::Dimension(
-                    Some(num.clone()),
-                    unit.clone(),
-                    true,
-                )),
-                HigherIntermediateValue::Literal(Value::Dimension(
-                    Some(min.0.clone()),
+                    min.0.clone(),
                     min.1.clone(),
                     true,
                 )),
@@ -229,7 +216,7 @@ pub(crate) fn min(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
             min = (num, unit);
         }
     }
-    Ok(Value::Dimension(Some(min.0), min.1, true))
+    Ok(Value::Dimension(min.0, min.1, true))
 }
 
 pub(crate) fn max(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
@@ -242,30 +229,22 @@ pub(crate) fn max(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
             Value::Dimension(number, unit, _) => Ok((number, unit)),
             v => Err((format!("{} is not a number.", v.inspect(span)?), span).into()),
         })
-        .collect::<SassResult<Vec<(Option<Number>, Unit)>>>()?
+        .collect::<SassResult<Vec<(Number, Unit)>>>()?
         .into_iter();
 
+    // `NaN` is neither less than nor greater than any number, so it's only
+    // returned if it's the first argument
     let mut max = match nums.next() {
-        Some((Some(n), u)) => (n, u),
-        Some((None, u)) => return Ok(Value::Dimension(None, u, true)),
+        Some((n, u)) => (n, u),
         None => unreachable!(),
     };
 
     for (num, unit) in nums {
-        let num = match num {
-            Some(n) => n,
-            None => continue,
-        };
-
         if ValueVisitor::new(parser, span)
             .greater_than(
+                HigherIntermediateValue::Literal(Value::Dimension(num.clone(), unit.clone(), true)),
                 HigherIntermediateValue::Literal(Value::Dimension(
-                    Some(num.clone()),
-                    unit.clone(),
-                    true,
-                )),
-                HigherIntermediateValue::Literal(Value::Dimension(
-                    Some(max.0.clone()),
+                    max.0.clone(),
                     max.1.clone(),
                     true,
                 )),
@@ -275,7 +254,7 @@ pub(crate) fn max(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
             max = (num, unit);
         }
     }
-    Ok(Value::Dimension(Some(max.0), max.1, true))
+    Ok(Value::Dimension(max.0, max.1, true))
 }
 
 pub(crate) fn divide(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
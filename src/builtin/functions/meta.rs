@@ -8,7 +8,7 @@ use crate::{
     error::SassResult,
     parse::Parser,
     unit::Unit,
-    value::{SassFunction, Value},
+    value::{SassFunction, SassMap, Value},
 };
 
 fn if_(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
@@ -212,6 +212,7 @@ pub(crate) fn function_exists(mut args: CallArgs, parser: &mut Parser) -> SassRe
             .fn_exists(name)
     } else {
         parser.scopes.fn_exists(name, parser.global_scope)
+            || parser.get_custom_function(name).is_some()
     }))
 }
 
@@ -260,9 +261,12 @@ pub(crate) fn get_function(mut args: CallArgs, parser: &mut Parser) -> SassResul
         parser.scopes.get_fn(name, parser.global_scope)
     } {
         Some(f) => f,
-        None => match GLOBAL_FUNCTIONS.get(name.as_str()) {
-            Some(f) => SassFunction::Builtin(f.clone(), name),
-            None => return Err((format!("Function not found: {}", name), args.span()).into()),
+        None => match parser.get_custom_function(name) {
+            Some(f) => f,
+            None => match GLOBAL_FUNCTIONS.get(name.as_str()) {
+                Some(f) => SassFunction::Builtin(f.clone(), name),
+                None => return Err((format!("Function not found: {}", name), args.span()).into()),
+            },
         },
     };
 
@@ -302,14 +306,31 @@ pub(crate) fn content_exists(args: CallArgs, parser: &mut Parser) -> SassResult<
 }
 
 #[allow(unused_variables, clippy::needless_pass_by_value)]
-pub(crate) fn keywords(args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
+pub(crate) fn keywords(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
 
-    Err((
-        "Builtin function `keywords` is not yet implemented",
-        args.span(),
-    )
-        .into())
+    match args.get_err(0, "args")? {
+        Value::ArgList(arg_list) => Ok(Value::Map(SassMap::new_with(
+            arg_list
+                .keywords()
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        Value::String(name.to_string(), QuoteKind::None),
+                        value.node.clone(),
+                    )
+                })
+                .collect(),
+        ))),
+        v => Err((
+            format!(
+                "$args: {} is not an argument list.",
+                v.inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+    }
 }
 
 pub(crate) fn declare(f: &mut GlobalFunctionMap) {
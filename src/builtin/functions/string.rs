@@ -1,14 +1,14 @@
 use super::{Builtin, GlobalFunctionMap};
 
 use num_bigint::BigInt;
-use num_traits::{Signed, ToPrimitive, Zero};
+use num_traits::{One, Signed, ToPrimitive, Zero};
 
 #[cfg(feature = "random")]
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 
 use crate::{
     args::CallArgs,
-    common::QuoteKind,
+    common::{Brackets, ListSeparator, QuoteKind},
     error::SassResult,
     parse::Parser,
     unit::Unit,
@@ -49,7 +49,7 @@ pub(crate) fn str_length(mut args: CallArgs, parser: &mut Parser) -> SassResult<
     args.max_args(1)?;
     match args.get_err(0, "string")? {
         Value::String(i, _) => Ok(Value::Dimension(
-            Some(Number::from(i.chars().count())),
+            Number::from(i.chars().count()),
             Unit::None,
             true,
         )),
@@ -99,20 +99,17 @@ pub(crate) fn str_slice(mut args: CallArgs, parser: &mut Parser) -> SassResult<V
     };
     let str_len = string.chars().count();
     let start = match args.get_err(1, "start-at")? {
-        Value::Dimension(Some(n), Unit::None, _) if n.is_decimal() => {
+        Value::Dimension(n, Unit::None, _) if n.is_decimal() => {
             return Err((format!("{} is not an int.", n.inspect()), args.span()).into())
         }
-        Value::Dimension(Some(n), Unit::None, _) if n.is_positive() => {
+        Value::Dimension(n, Unit::None, _) if n.is_positive() => {
             n.to_integer().to_usize().unwrap_or(str_len + 1)
         }
-        Value::Dimension(Some(n), Unit::None, _) if n.is_zero() => 1_usize,
-        Value::Dimension(Some(n), Unit::None, _) if n < -Number::from(str_len) => 1_usize,
-        Value::Dimension(Some(n), Unit::None, _) => (n.to_integer() + BigInt::from(str_len + 1))
+        Value::Dimension(n, Unit::None, _) if n.is_zero() => 1_usize,
+        Value::Dimension(n, Unit::None, _) if n < -Number::from(str_len) => 1_usize,
+        Value::Dimension(n, Unit::None, _) => (n.to_integer() + BigInt::from(str_len + 1))
             .to_usize()
             .unwrap(),
-        Value::Dimension(None, Unit::None, ..) => {
-            return Err(("NaN is not an int.", args.span()).into())
-        }
         v @ Value::Dimension(..) => {
             return Err((
                 format!(
@@ -132,20 +129,17 @@ pub(crate) fn str_slice(mut args: CallArgs, parser: &mut Parser) -> SassResult<V
         }
     };
     let mut end = match args.default_arg(2, "end-at", Value::Null)? {
-        Value::Dimension(Some(n), Unit::None, _) if n.is_decimal() => {
+        Value::Dimension(n, Unit::None, _) if n.is_decimal() => {
             return Err((format!("{} is not an int.", n.inspect()), args.span()).into())
         }
-        Value::Dimension(Some(n), Unit::None, _) if n.is_positive() => {
+        Value::Dimension(n, Unit::None, _) if n.is_positive() => {
             n.to_integer().to_usize().unwrap_or(str_len + 1)
         }
-        Value::Dimension(Some(n), Unit::None, _) if n.is_zero() => 0_usize,
-        Value::Dimension(Some(n), Unit::None, _) if n < -Number::from(str_len) => 0_usize,
-        Value::Dimension(Some(n), Unit::None, _) => (n.to_integer() + BigInt::from(str_len + 1))
+        Value::Dimension(n, Unit::None, _) if n.is_zero() => 0_usize,
+        Value::Dimension(n, Unit::None, _) if n < -Number::from(str_len) => 0_usize,
+        Value::Dimension(n, Unit::None, _) => (n.to_integer() + BigInt::from(str_len + 1))
             .to_usize()
             .unwrap_or(str_len + 1),
-        Value::Dimension(None, Unit::None, ..) => {
-            return Err(("NaN is not an int.", args.span()).into())
-        }
         v @ Value::Dimension(..) => {
             return Err((
                 format!(
@@ -209,7 +203,8 @@ pub(crate) fn str_index(mut args: CallArgs, parser: &mut Parser) -> SassResult<V
     };
 
     Ok(match s1.find(&substr) {
-        Some(v) => Value::Dimension(Some(Number::from(v + 1)), Unit::None, true),
+        // `find` returns a byte offset, but Sass indexes by codepoint
+        Some(v) => Value::Dimension(Number::from(s1[..v].chars().count() + 1), Unit::None, true),
         None => Value::Null,
     })
 }
@@ -239,17 +234,14 @@ pub(crate) fn str_insert(mut args: CallArgs, parser: &mut Parser) -> SassResult<
     };
 
     let index = match args.get_err(2, "index")? {
-        Value::Dimension(Some(n), Unit::None, _) if n.is_decimal() => {
+        Value::Dimension(n, Unit::None, _) if n.is_decimal() => {
             return Err((
                 format!("$index: {} is not an int.", n.inspect()),
                 args.span(),
             )
                 .into())
         }
-        Value::Dimension(Some(n), Unit::None, _) => n,
-        Value::Dimension(None, Unit::None, ..) => {
-            return Err(("$index: NaN is not an int.", args.span()).into())
-        }
+        Value::Dimension(n, Unit::None, _) => n,
         v @ Value::Dimension(..) => {
             return Err((
                 format!(
@@ -316,6 +308,87 @@ pub(crate) fn str_insert(mut args: CallArgs, parser: &mut Parser) -> SassResult<
     Ok(Value::String(string, quotes))
 }
 
+pub(crate) fn str_split(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
+    args.max_args(3)?;
+    let (string, quotes) = match args.get_err(0, "string")? {
+        Value::String(i, q) => (i, q),
+        v => {
+            return Err((
+                format!("$string: {} is not a string.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let separator = match args.get_err(1, "separator")? {
+        Value::String(i, _) => i,
+        v => {
+            return Err((
+                format!("$separator: {} is not a string.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let limit = match args.default_arg(2, "limit", Value::Null)? {
+        Value::Null => None,
+        Value::Dimension(n, ..) if n.is_decimal() => {
+            return Err((
+                format!("$limit: {} is not an int.", n.inspect()),
+                args.span(),
+            )
+                .into())
+        }
+        Value::Dimension(n, ..) if n < Number::one() => {
+            return Err((
+                format!("$limit: Must be 1 or greater, was {}.", n.inspect()),
+                args.span(),
+            )
+                .into())
+        }
+        Value::Dimension(n, ..) => Some(n.to_integer().to_usize().unwrap_or(usize::MAX)),
+        v => {
+            return Err((
+                format!("$limit: {} is not a number.", v.inspect(args.span())?),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    let chunks: Vec<Value> = if string.is_empty() {
+        Vec::new()
+    } else if separator.is_empty() {
+        // an empty separator splits the string into its codepoints, regardless
+        // of `$limit`
+        string
+            .chars()
+            .map(|c| Value::String(c.to_string(), quotes))
+            .collect()
+    } else {
+        // `$limit` is the number of splits, so there is one more chunk
+        let chunks = match limit {
+            Some(limit) => string
+                .splitn(limit.saturating_add(1), separator.as_str())
+                .collect::<Vec<&str>>(),
+            None => string.split(separator.as_str()).collect(),
+        };
+
+        chunks
+            .into_iter()
+            .map(|chunk| Value::String(chunk.to_owned(), quotes))
+            .collect()
+    };
+
+    Ok(Value::List(
+        chunks,
+        ListSeparator::Comma,
+        Brackets::Bracketed,
+    ))
+}
+
 #[cfg(feature = "random")]
 #[allow(clippy::needless_pass_by_value)]
 pub(crate) fn unique_id(args: CallArgs, _: &mut Parser) -> SassResult<Value> {
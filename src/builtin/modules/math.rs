@@ -87,15 +87,15 @@ fn clamp(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     }
 
     match min.cmp(&number, span, Op::LessThan)? {
-        Ordering::Greater => return Ok(min),
-        Ordering::Equal => return Ok(number),
-        Ordering::Less => {}
+        Some(Ordering::Greater) => return Ok(min),
+        Some(Ordering::Equal) => return Ok(number),
+        Some(Ordering::Less) | None => {}
     }
 
     match max.cmp(&number, span, Op::GreaterThan)? {
-        Ordering::Less => return Ok(max),
-        Ordering::Equal => return Ok(number),
-        Ordering::Greater => {}
+        Some(Ordering::Less) => return Ok(max),
+        Some(Ordering::Equal) => return Ok(number),
+        Some(Ordering::Greater) | None => {}
     }
 
     Ok(number)
@@ -114,17 +114,16 @@ fn hypot(args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     });
 
     let first: (Number, Unit) = match numbers.next().unwrap()? {
-        (Some(n), u) => (n.clone() * n, u),
-        (None, u) => return Ok(Value::Dimension(None, u, true)),
+        (n, u) => (n.clone() * n, u),
     };
 
     let rest = numbers
         .enumerate()
-        .map(|(idx, val)| -> SassResult<Option<Number>> {
+        .map(|(idx, val)| -> SassResult<Number> {
             let (number, unit) = val?;
             if first.1 == Unit::None {
                 if unit == Unit::None {
-                    Ok(number.map(|n| n.clone() * n))
+                    Ok(number.clone() * number)
                 } else {
                     Err((
                         format!(
@@ -149,9 +148,8 @@ fn hypot(args: CallArgs, _: &mut Parser) -> SassResult<Value> {
                 )
                     .into())
             } else if first.1.comparable(&unit) {
-                Ok(number
-                    .map(|n| n.convert(&unit, &first.1))
-                    .map(|n| n.clone() * n))
+                let number = number.convert(&unit, &first.1);
+                Ok(number.clone() * number)
             } else {
                 Err((
                     format!("Incompatible units {} and {}.", first.1, unit),
@@ -160,12 +158,7 @@ fn hypot(args: CallArgs, _: &mut Parser) -> SassResult<Value> {
                     .into())
             }
         })
-        .collect::<SassResult<Option<Vec<Number>>>>()?;
-
-    let rest = match rest {
-        Some(v) => v,
-        None => return Ok(Value::Dimension(None, first.1, true)),
-    };
+        .collect::<SassResult<Vec<Number>>>()?;
 
     let sum = first.0 + rest.into_iter().fold(Number::zero(), |a, b| a + b);
 
@@ -176,8 +169,8 @@ fn log(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     args.max_args(2)?;
 
     let number = match args.get_err(0, "number")? {
-        Value::Dimension(Some(n), Unit::None, ..) => n,
-        v @ Value::Dimension(Some(..), ..) => {
+        Value::Dimension(n, Unit::None, ..) => n,
+        v @ Value::Dimension(..) => {
             return Err((
                 format!(
                     "$number: Expected {} to be unitless.",
@@ -187,7 +180,6 @@ fn log(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
             )
                 .into())
         }
-        v @ Value::Dimension(None, ..) => return Ok(v),
         v => {
             return Err((
                 format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -199,8 +191,8 @@ fn log(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
 
     let base = match args.default_arg(1, "base", Value::Null)? {
         Value::Null => None,
-        Value::Dimension(Some(n), Unit::None, ..) => Some(n),
-        v @ Value::Dimension(Some(..), ..) => {
+        Value::Dimension(n, Unit::None, ..) => Some(n),
+        v @ Value::Dimension(..) => {
             return Err((
                 format!(
                     "$number: Expected {} to be unitless.",
@@ -210,7 +202,6 @@ fn log(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
             )
                 .into())
         }
-        v @ Value::Dimension(None, ..) => return Ok(v),
         v => {
             return Err((
                 format!("$base: {} is not a number.", v.inspect(args.span())?),
@@ -223,14 +214,10 @@ fn log(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     Ok(Value::Dimension(
         if let Some(base) = base {
             if base.is_zero() {
-                Some(Number::zero())
+                Number::zero()
             } else {
                 number.log(base)
             }
-        } else if number.is_negative() {
-            None
-        } else if number.is_zero() {
-            todo!()
         } else {
             number.ln()
         },
@@ -243,8 +230,8 @@ fn pow(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     args.max_args(2)?;
 
     let base = match args.get_err(0, "base")? {
-        Value::Dimension(Some(n), Unit::None, ..) => n,
-        v @ Value::Dimension(Some(..), ..) => {
+        Value::Dimension(n, Unit::None, ..) => n,
+        v @ Value::Dimension(..) => {
             return Err((
                 format!(
                     "$base: Expected {} to have no units.",
@@ -254,7 +241,6 @@ fn pow(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => return Ok(Value::Dimension(None, Unit::None, true)),
         v => {
             return Err((
                 format!("$base: {} is not a number.", v.inspect(args.span())?),
@@ -265,8 +251,8 @@ fn pow(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     };
 
     let exponent = match args.get_err(1, "exponent")? {
-        Value::Dimension(Some(n), Unit::None, ..) => n,
-        v @ Value::Dimension(Some(..), ..) => {
+        Value::Dimension(n, Unit::None, ..) => n,
+        v @ Value::Dimension(..) => {
             return Err((
                 format!(
                     "$exponent: Expected {} to have no units.",
@@ -276,7 +262,6 @@ fn pow(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => return Ok(Value::Dimension(None, Unit::None, true)),
         v => {
             return Err((
                 format!("$exponent: {} is not a number.", v.inspect(args.span())?),
@@ -294,8 +279,8 @@ fn sqrt(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     let number = args.get_err(0, "number")?;
 
     Ok(match number {
-        Value::Dimension(Some(n), Unit::None, ..) => Value::Dimension(n.sqrt(), Unit::None, true),
-        v @ Value::Dimension(Some(..), ..) => {
+        Value::Dimension(n, Unit::None, ..) => Value::Dimension(n.sqrt(), Unit::None, true),
+        v @ Value::Dimension(..) => {
             return Err((
                 format!(
                     "$number: Expected {} to have no units.",
@@ -305,7 +290,6 @@ fn sqrt(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => Value::Dimension(None, Unit::None, true),
         v => {
             return Err((
                 format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -323,14 +307,13 @@ macro_rules! trig_fn {
             let number = args.get_err(0, "number")?;
 
             Ok(match number {
-                Value::Dimension(Some(n), Unit::None, ..)
-                | Value::Dimension(Some(n), Unit::Rad, ..) => {
+                Value::Dimension(n, Unit::None, ..) | Value::Dimension(n, Unit::Rad, ..) => {
                     Value::Dimension(n.$name(), Unit::None, true)
                 }
-                Value::Dimension(Some(n), Unit::Deg, ..) => {
+                Value::Dimension(n, Unit::Deg, ..) => {
                     Value::Dimension(n.$name_deg(), Unit::None, true)
                 }
-                v @ Value::Dimension(Some(..), ..) => {
+                v @ Value::Dimension(..) => {
                     return Err((
                         format!(
                             "$number: Expected {} to be an angle.",
@@ -340,7 +323,6 @@ macro_rules! trig_fn {
                     )
                         .into())
                 }
-                Value::Dimension(None, ..) => Value::Dimension(None, Unit::None, true),
                 v => {
                     return Err((
                         format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -362,18 +344,18 @@ fn acos(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     let number = args.get_err(0, "number")?;
 
     Ok(match number {
-        Value::Dimension(Some(n), Unit::None, ..) => Value::Dimension(
+        Value::Dimension(n, Unit::None, ..) => Value::Dimension(
             if n > Number::from(1) || n < Number::from(-1) {
-                None
+                Number::NaN
             } else if n.is_one() {
-                Some(Number::zero())
+                Number::zero()
             } else {
                 n.acos()
             },
             Unit::Deg,
             true,
         ),
-        v @ Value::Dimension(Some(..), ..) => {
+        v @ Value::Dimension(..) => {
             return Err((
                 format!(
                     "$number: Expected {} to be unitless.",
@@ -383,7 +365,6 @@ fn acos(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => Value::Dimension(None, Unit::Deg, true),
         v => {
             return Err((
                 format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -399,16 +380,16 @@ fn asin(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     let number = args.get_err(0, "number")?;
 
     Ok(match number {
-        Value::Dimension(Some(n), Unit::None, ..) => {
+        Value::Dimension(n, Unit::None, ..) => {
             if n > Number::from(1) || n < Number::from(-1) {
-                return Ok(Value::Dimension(None, Unit::Deg, true));
+                return Ok(Value::Dimension(Number::NaN, Unit::Deg, true));
             } else if n.is_zero() {
-                return Ok(Value::Dimension(Some(Number::zero()), Unit::Deg, true));
+                return Ok(Value::Dimension(Number::zero(), Unit::Deg, true));
             }
 
             Value::Dimension(n.asin(), Unit::Deg, true)
         }
-        v @ Value::Dimension(Some(..), ..) => {
+        v @ Value::Dimension(..) => {
             return Err((
                 format!(
                     "$number: Expected {} to be unitless.",
@@ -418,7 +399,6 @@ fn asin(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => Value::Dimension(None, Unit::Deg, true),
         v => {
             return Err((
                 format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -434,14 +414,14 @@ fn atan(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     let number = args.get_err(0, "number")?;
 
     Ok(match number {
-        Value::Dimension(Some(n), Unit::None, ..) => {
+        Value::Dimension(n, Unit::None, ..) => {
             if n.is_zero() {
-                return Ok(Value::Dimension(Some(Number::zero()), Unit::Deg, true));
+                return Ok(Value::Dimension(Number::zero(), Unit::Deg, true));
             }
 
             Value::Dimension(n.atan(), Unit::Deg, true)
         }
-        v @ Value::Dimension(Some(..), ..) => {
+        v @ Value::Dimension(..) => {
             return Err((
                 format!(
                     "$number: Expected {} to be unitless.",
@@ -451,7 +431,6 @@ fn atan(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
             )
                 .into())
         }
-        Value::Dimension(None, ..) => Value::Dimension(None, Unit::Deg, true),
         v => {
             return Err((
                 format!("$number: {} is not a number.", v.inspect(args.span())?),
@@ -487,17 +466,7 @@ fn atan2(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
     };
 
     let (x_num, y_num) = if x_unit == Unit::None && y_unit == Unit::None {
-        let x = match x_num {
-            Some(n) => n,
-            None => return Ok(Value::Dimension(None, Unit::Deg, true)),
-        };
-
-        let y = match y_num {
-            Some(n) => n,
-            None => return Ok(Value::Dimension(None, Unit::Deg, true)),
-        };
-
-        (x, y)
+        (x_num, y_num)
     } else if y_unit == Unit::None {
         return Err((
             format!(
@@ -519,17 +488,7 @@ fn atan2(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
         )
             .into());
     } else if x_unit.comparable(&y_unit) {
-        let x = match x_num {
-            Some(n) => n,
-            None => return Ok(Value::Dimension(None, Unit::Deg, true)),
-        };
-
-        let y = match y_num {
-            Some(n) => n,
-            None => return Ok(Value::Dimension(None, Unit::Deg, true)),
-        };
-
-        (x, y.convert(&y_unit, &x_unit))
+        (x_num, y_num.convert(&y_unit, &x_unit))
     } else {
         return Err((
             format!("Incompatible units {} and {}.", y_unit, x_unit),
@@ -538,32 +497,38 @@ fn atan2(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
             .into());
     };
 
+    if !x_num.is_finite() || !y_num.is_finite() {
+        return Ok(Value::Dimension(
+            (y_num.atan2(x_num) * Number::from(180)) / Number::pi(),
+            Unit::Deg,
+            true,
+        ));
+    }
+
     Ok(
         match (
             NumberState::from_number(&x_num),
             NumberState::from_number(&y_num),
         ) {
             (NumberState::Zero, NumberState::FiniteNegative) => {
-                Value::Dimension(Some(Number::from(-90)), Unit::Deg, true)
+                Value::Dimension(Number::from(-90), Unit::Deg, true)
             }
             (NumberState::Zero, NumberState::Zero) | (NumberState::Finite, NumberState::Zero) => {
-                Value::Dimension(Some(Number::zero()), Unit::Deg, true)
+                Value::Dimension(Number::zero(), Unit::Deg, true)
             }
             (NumberState::Zero, NumberState::Finite) => {
-                Value::Dimension(Some(Number::from(90)), Unit::Deg, true)
+                Value::Dimension(Number::from(90), Unit::Deg, true)
             }
             (NumberState::Finite, NumberState::Finite)
             | (NumberState::FiniteNegative, NumberState::Finite)
             | (NumberState::Finite, NumberState::FiniteNegative)
             | (NumberState::FiniteNegative, NumberState::FiniteNegative) => Value::Dimension(
-                y_num
-                    .atan2(x_num)
-                    .map(|n| (n * Number::from(180)) / Number::pi()),
+                (y_num.atan2(x_num) * Number::from(180)) / Number::pi(),
                 Unit::Deg,
                 true,
             ),
             (NumberState::FiniteNegative, NumberState::Zero) => {
-                Value::Dimension(Some(Number::from(180)), Unit::Deg, true)
+                Value::Dimension(Number::from(180), Unit::Deg, true)
             }
         },
     )
@@ -614,10 +579,10 @@ pub(crate) fn declare(f: &mut Module) {
 
     f.insert_builtin_var(
         "e",
-        Value::Dimension(Some(Number::from(std::f64::consts::E)), Unit::None, true),
+        Value::Dimension(Number::from(std::f64::consts::E), Unit::None, true),
     );
     f.insert_builtin_var(
         "pi",
-        Value::Dimension(Some(Number::from(std::f64::consts::PI)), Unit::None, true),
+        Value::Dimension(Number::from(std::f64::consts::PI), Unit::None, true),
     );
 }
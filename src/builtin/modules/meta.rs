@@ -2,6 +2,7 @@ use codemap::Spanned;
 
 use crate::{
     args::CallArgs,
+    atrule::mixin::Content,
     builtin::{
         meta::{
             call, content_exists, feature_exists, function_exists, get_function,
@@ -9,9 +10,10 @@ use crate::{
         },
         modules::{Module, ModuleConfig},
     },
+    common::Identifier,
     error::SassResult,
     parse::{Parser, Stmt},
-    value::Value,
+    value::{SassMixin, Value},
 };
 
 fn load_css(mut args: CallArgs, parser: &mut Parser) -> SassResult<Vec<Stmt>> {
@@ -61,7 +63,19 @@ fn load_css(mut args: CallArgs, parser: &mut Parser) -> SassResult<Vec<Stmt>> {
             )?;
         }
 
-        let (_, stmts) = parser.load_module(&url, &mut config)?;
+        let (module, stmts) = parser.load_module(&url, &mut config)?;
+
+        if !config.is_empty() {
+            if module.is_builtin() {
+                return Err(("Built-in modules can't be configured.", span).into());
+            }
+
+            return Err((
+                "This variable was not declared with !default in the @used module.",
+                span,
+            )
+                .into());
+        }
 
         Ok(stmts)
     } else {
@@ -69,6 +83,107 @@ fn load_css(mut args: CallArgs, parser: &mut Parser) -> SassResult<Vec<Stmt>> {
     }
 }
 
+fn apply(mut args: CallArgs, parser: &mut Parser) -> SassResult<Vec<Stmt>> {
+    let mixin = match args.get_err(0, "mixin")? {
+        Value::MixinRef(m) => m,
+        v => {
+            return Err((
+                format!(
+                    "$mixin: {} is not a mixin reference.",
+                    v.inspect(args.span())?
+                ),
+                args.span(),
+            )
+                .into())
+        }
+    };
+
+    // the content block passed to `meta.apply()` is forwarded to the mixin
+    let (content, content_args) = match parser.content.last() {
+        Some(Content {
+            content,
+            content_args,
+            ..
+        }) => (content.clone(), content_args.clone()),
+        None => (None, None),
+    };
+
+    if content.is_some() && !mixin.accepts_content() {
+        return Err(("Mixin doesn't accept a content block.", args.span()).into());
+    }
+
+    parser.include_mixin(mixin, args.decrement(), content, content_args)
+}
+
+fn get_mixin(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
+    args.max_args(2)?;
+
+    let span = args.span();
+
+    let name: Identifier = match args.get_err(0, "name")? {
+        Value::String(s, ..) => s.into(),
+        v => {
+            return Err((
+                format!("$name: {} is not a string.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let module: Option<Identifier> = match args.default_arg(1, "module", Value::Null)? {
+        Value::String(s, ..) => Some(s.into()),
+        Value::Null => None,
+        v => {
+            return Err((
+                format!("$module: {} is not a string.", v.inspect(span)?),
+                span,
+            )
+                .into())
+        }
+    };
+
+    let mixin = if let Some(module) = module {
+        let module = parser.modules.get(module, span)?;
+
+        if !module.mixin_exists(name) {
+            return Err((format!("Mixin not found: {}", name), span).into());
+        }
+
+        module.get_mixin(Spanned { node: name, span })?
+    } else {
+        if !parser.scopes.mixin_exists(name, parser.global_scope) {
+            return Err((format!("Mixin not found: {}", name), span).into());
+        }
+
+        parser
+            .scopes
+            .get_mixin(Spanned { node: name, span }, parser.global_scope)?
+    };
+
+    Ok(Value::MixinRef(SassMixin::new(
+        mixin,
+        name,
+        module.map(|node| Spanned { node, span }),
+    )))
+}
+
+fn accepts_content(mut args: CallArgs, _: &mut Parser) -> SassResult<Value> {
+    args.max_args(1)?;
+
+    match args.get_err(0, "mixin")? {
+        Value::MixinRef(m) => Ok(Value::bool(m.accepts_content())),
+        v => Err((
+            format!(
+                "$mixin: {} is not a mixin reference.",
+                v.inspect(args.span())?
+            ),
+            args.span(),
+        )
+            .into()),
+    }
+}
+
 fn module_functions(mut args: CallArgs, parser: &mut Parser) -> SassResult<Value> {
     args.max_args(1)?;
 
@@ -121,6 +236,9 @@ pub(crate) fn declare(f: &mut Module) {
     f.insert_builtin("module-functions", module_functions);
     f.insert_builtin("get-function", get_function);
     f.insert_builtin("call", call);
+    f.insert_builtin("get-mixin", get_mixin);
+    f.insert_builtin("accepts-content", accepts_content);
 
-    f.insert_builtin_mixin("load-css", load_css);
+    f.insert_builtin_mixin("load-css", load_css, false);
+    f.insert_builtin_mixin("apply", apply, true);
 }
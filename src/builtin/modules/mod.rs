@@ -1,10 +1,13 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use codemap::{Span, Spanned};
 
 use crate::{
     args::CallArgs,
-    atrule::mixin::{BuiltinMixin, Mixin},
+    atrule::{
+        mixin::{BuiltinMixin, Mixin},
+        Function,
+    },
     builtin::Builtin,
     common::{Identifier, QuoteKind},
     error::SassResult,
@@ -34,11 +37,121 @@ pub(crate) struct Module {
 }
 
 #[derive(Debug, Default)]
-pub(crate) struct Modules(BTreeMap<Identifier, Module>);
+pub(crate) struct Modules {
+    namespaced: BTreeMap<Identifier, Module>,
+
+    /// Modules loaded through `@forward`
+    ///
+    /// Their members are part of the public API of the module
+    /// that forwards them, but are not visible inside of it
+    forwarded: Vec<ForwardedModule>,
+}
 
 #[derive(Debug, Default)]
 pub(crate) struct ModuleConfig(BTreeMap<Identifier, Value>);
 
+/// A module made available through `@forward`, along with
+/// the `as`, `show`, and `hide` clauses that were applied to it
+#[derive(Debug)]
+pub(crate) struct ForwardedModule {
+    module: Module,
+    filter: ForwardFilter,
+}
+
+/// The `as`, `show`, and `hide` clauses of an `@forward` rule
+#[derive(Debug, Default)]
+pub(crate) struct ForwardFilter {
+    /// Normalized prefix from `as <prefix>-*`
+    prefix: Option<String>,
+    visibility: ForwardVisibility,
+}
+
+#[derive(Debug)]
+pub(crate) enum ForwardVisibility {
+    All,
+    Show(MemberNames),
+    Hide(MemberNames),
+}
+
+impl Default for ForwardVisibility {
+    fn default() -> Self {
+        ForwardVisibility::All
+    }
+}
+
+/// A set of member names listed by a `show` or `hide` clause
+///
+/// Variables live in a separate namespace from mixins and functions
+#[derive(Debug, Default)]
+pub(crate) struct MemberNames {
+    pub variables: BTreeSet<Identifier>,
+    pub mixins_and_functions: BTreeSet<Identifier>,
+}
+
+impl MemberNames {
+    fn contains(&self, name: Identifier, is_variable: bool) -> bool {
+        if is_variable {
+            self.variables.contains(&name)
+        } else {
+            self.mixins_and_functions.contains(&name)
+        }
+    }
+}
+
+impl ForwardFilter {
+    pub fn new(prefix: Option<String>, visibility: ForwardVisibility) -> Self {
+        Self {
+            prefix: prefix.map(|prefix| prefix.replace('_', "-")),
+            visibility,
+        }
+    }
+
+    /// Given the name a member is accessed by from outside the forwarding
+    /// module, returns the name of that member inside the forwarded module
+    ///
+    /// Returns `None` if the member is not forwarded
+    fn inner_name(&self, name: Identifier, is_variable: bool) -> Option<Identifier> {
+        if name.as_str().starts_with('-') {
+            return None;
+        }
+
+        let is_visible = match &self.visibility {
+            ForwardVisibility::All => true,
+            ForwardVisibility::Show(names) => names.contains(name, is_variable),
+            ForwardVisibility::Hide(names) => !names.contains(name, is_variable),
+        };
+
+        if !is_visible {
+            return None;
+        }
+
+        match &self.prefix {
+            Some(prefix) => name.as_str().strip_prefix(prefix.as_str()).map(Into::into),
+            None => Some(name),
+        }
+    }
+
+    /// The inverse of `ForwardFilter::inner_name`
+    fn outer_name(&self, name: Identifier, is_variable: bool) -> Option<Identifier> {
+        if name.as_str().starts_with('-') {
+            return None;
+        }
+
+        let outer: Identifier = match &self.prefix {
+            Some(prefix) => format!("{}{}", prefix, name).into(),
+            None => name,
+        };
+
+        self.inner_name(outer, is_variable).map(|_| outer)
+    }
+}
+
+impl ForwardedModule {
+    pub const fn new(module: Module, filter: ForwardFilter) -> Self {
+        Self { module, filter }
+    }
+}
+
 impl ModuleConfig {
     /// Removes and returns element with name
     pub fn get(&mut self, name: Identifier) -> Option<Value> {
@@ -62,11 +175,74 @@ impl ModuleConfig {
             Ok(())
         }
     }
+
+    /// Removes every variable that passes through the given `@forward`
+    /// rule, returning them keyed by their name inside the forwarded module
+    pub fn take_forwarded(&mut self, filter: &ForwardFilter) -> Self {
+        let names: Vec<(Identifier, Identifier)> = self
+            .0
+            .keys()
+            .filter_map(|&name| Some((name, filter.inner_name(name, true)?)))
+            .collect();
+
+        let mut forwarded = ModuleConfig::default();
+
+        for (outer, inner) in names {
+            if let Some(value) = self.0.remove(&outer) {
+                forwarded.0.insert(inner, value);
+            }
+        }
+
+        forwarded
+    }
+
+    /// Applies the `with` clause of an `@forward` rule on top of the
+    /// configuration passed through from the module that loaded it
+    ///
+    /// Variables marked `!default` only take effect if they
+    /// were not already configured
+    pub fn configure_forward(&mut self, config: ModuleConfig, guarded: &BTreeSet<Identifier>) {
+        for (name, value) in config.0 {
+            if guarded.contains(&name) && self.0.contains_key(&name) {
+                continue;
+            }
+
+            self.0.insert(name, value);
+        }
+    }
+
+    /// Returns variables which were passed through an `@forward` rule but
+    /// not used by the forwarded module, so that the forwarding module may
+    /// use them itself
+    ///
+    /// Returns `false` if any unused variable was configured by the
+    /// `@forward` rule itself rather than passed through
+    pub fn restore_forwarded(
+        &mut self,
+        unused: ModuleConfig,
+        passed_through: &BTreeSet<Identifier>,
+        filter: &ForwardFilter,
+    ) -> bool {
+        for (name, value) in unused.0 {
+            match filter.outer_name(name, true) {
+                Some(outer) if passed_through.contains(&name) => {
+                    self.0.insert(outer, value);
+                }
+                Some(..) | None => return false,
+            }
+        }
+
+        true
+    }
+
+    pub fn names(&self) -> BTreeSet<Identifier> {
+        self.0.keys().copied().collect()
+    }
 }
 
 impl Modules {
     pub fn insert(&mut self, name: Identifier, module: Module, span: Span) -> SassResult<()> {
-        if self.0.contains_key(&name) {
+        if self.namespaced.contains_key(&name) {
             return Err((
                 format!("There's already a module with namespace \"{}\".", name),
                 span,
@@ -74,13 +250,17 @@ impl Modules {
                 .into());
         }
 
-        self.0.insert(name, module);
+        self.namespaced.insert(name, module);
 
         Ok(())
     }
 
+    pub fn forward(&mut self, module: ForwardedModule) {
+        self.forwarded.push(module);
+    }
+
     pub fn get(&self, name: Identifier, span: Span) -> SassResult<&Module> {
-        match self.0.get(&name) {
+        match self.namespaced.get(&name) {
             Some(v) => Ok(v),
             None => Err((
                 format!(
@@ -94,7 +274,7 @@ impl Modules {
     }
 
     pub fn get_mut(&mut self, name: Identifier, span: Span) -> SassResult<&mut Module> {
-        match self.0.get_mut(&name) {
+        match self.namespaced.get_mut(&name) {
             Some(v) => Ok(v),
             None => Err((
                 format!(
@@ -107,8 +287,12 @@ impl Modules {
         }
     }
 
+    /// Merge the namespaced modules of `other` into `self`
+    ///
+    /// Forwarded modules are not merged, as their members are
+    /// instead exposed through `Module::flattened_scope`
     pub fn merge(&mut self, other: Self) {
-        self.0.extend(other.0);
+        self.namespaced.extend(other.namespaced);
     }
 }
 
@@ -121,6 +305,53 @@ impl Module {
         }
     }
 
+    pub const fn is_builtin(&self) -> bool {
+        self.is_builtin
+    }
+
+    /// Find the first forwarded module which exposes a member with the
+    /// given name, returning that module and the member's name inside of it
+    fn forwarded_member(
+        &self,
+        name: Identifier,
+        is_variable: bool,
+    ) -> Option<(&Module, Identifier)> {
+        self.modules.forwarded.iter().find_map(|forwarded| {
+            let inner = forwarded.filter.inner_name(name, is_variable)?;
+            Some((&forwarded.module, inner))
+        })
+    }
+
+    fn lookup_var(&self, name: Identifier) -> Option<&Value> {
+        match self.scope.vars.get(&name) {
+            Some(v) => Some(v),
+            None => {
+                let (module, inner) = self.forwarded_member(name, true)?;
+                module.lookup_var(inner)
+            }
+        }
+    }
+
+    fn lookup_mixin(&self, name: Identifier) -> Option<&Mixin> {
+        match self.scope.mixins.get(&name) {
+            Some(v) => Some(v),
+            None => {
+                let (module, inner) = self.forwarded_member(name, false)?;
+                module.lookup_mixin(inner)
+            }
+        }
+    }
+
+    fn lookup_fn(&self, name: Identifier) -> Option<&SassFunction> {
+        match self.scope.functions.get(&name) {
+            Some(v) => Some(v),
+            None => {
+                let (module, inner) = self.forwarded_member(name, false)?;
+                module.lookup_fn(inner)
+            }
+        }
+    }
+
     pub fn get_var(&self, name: Spanned<Identifier>) -> SassResult<&Value> {
         if name.node.as_str().starts_with('-') {
             return Err((
@@ -130,7 +361,7 @@ impl Module {
                 .into());
         }
 
-        match self.scope.vars.get(&name.node) {
+        match self.lookup_var(name.node) {
             Some(v) => Ok(v),
             None => Err(("Undefined variable.", name.span).into()),
         }
@@ -149,11 +380,26 @@ impl Module {
                 .into());
         }
 
-        if self.scope.insert_var(name.node, value).is_some() {
-            Ok(())
-        } else {
-            Err(("Undefined variable.", name.span).into())
+        if self.scope.var_exists(name.node) {
+            self.scope.insert_var(name.node, value);
+            return Ok(());
+        }
+
+        for forwarded in &mut self.modules.forwarded {
+            if let Some(inner) = forwarded.filter.inner_name(name.node, true) {
+                if forwarded.module.var_exists(inner) {
+                    return forwarded.module.update_var(
+                        Spanned {
+                            node: inner,
+                            span: name.span,
+                        },
+                        value,
+                    );
+                }
+            }
         }
+
+        Err(("Undefined variable.", name.span).into())
     }
 
     pub fn get_mixin(&self, name: Spanned<Identifier>) -> SassResult<Mixin> {
@@ -165,14 +411,56 @@ impl Module {
                 .into());
         }
 
-        match self.scope.mixins.get(&name.node) {
+        match self.lookup_mixin(name.node) {
             Some(v) => Ok(v.clone()),
             None => Err(("Undefined mixin.", name.span).into()),
         }
     }
 
-    pub fn insert_builtin_mixin(&mut self, name: &'static str, mixin: BuiltinMixin) {
-        self.scope.mixins.insert(name.into(), Mixin::Builtin(mixin));
+    /// The scope of the module in which the mixin `name` was declared
+    ///
+    /// This differs from `self.scope` only for forwarded mixins
+    pub fn mixin_scope(&self, name: Identifier) -> &Scope {
+        if !self.scope.mixin_exists(name) {
+            if let Some((module, inner)) = self.forwarded_member(name, false) {
+                return module.mixin_scope(inner);
+            }
+        }
+
+        &self.scope
+    }
+
+    /// The scope of the module in which `function` was declared
+    ///
+    /// This differs from `self.scope` only for forwarded functions
+    pub fn fn_scope(&self, function: &Function) -> &Scope {
+        self.find_fn_scope(function).unwrap_or(&self.scope)
+    }
+
+    fn find_fn_scope(&self, function: &Function) -> Option<&Scope> {
+        let is_declared_here = self.scope.functions.values().any(
+            |f| matches!(f, SassFunction::UserDefined { function: f, .. } if **f == *function),
+        );
+
+        if is_declared_here {
+            return Some(&self.scope);
+        }
+
+        self.modules
+            .forwarded
+            .iter()
+            .find_map(|forwarded| forwarded.module.find_fn_scope(function))
+    }
+
+    pub fn insert_builtin_mixin(
+        &mut self,
+        name: &'static str,
+        mixin: BuiltinMixin,
+        accepts_content: bool,
+    ) {
+        self.scope
+            .mixins
+            .insert(name.into(), Mixin::Builtin(mixin, accepts_content));
     }
 
     pub fn insert_builtin_var(&mut self, name: &'static str, value: Value) {
@@ -188,19 +476,19 @@ impl Module {
                 .into());
         }
 
-        Ok(self.scope.functions.get(&name.node).cloned())
+        Ok(self.lookup_fn(name.node).cloned())
     }
 
     pub fn var_exists(&self, name: Identifier) -> bool {
-        !name.as_str().starts_with('-') && self.scope.var_exists(name)
+        !name.as_str().starts_with('-') && self.lookup_var(name).is_some()
     }
 
     pub fn mixin_exists(&self, name: Identifier) -> bool {
-        !name.as_str().starts_with('-') && self.scope.mixin_exists(name)
+        !name.as_str().starts_with('-') && self.lookup_mixin(name).is_some()
     }
 
     pub fn fn_exists(&self, name: Identifier) -> bool {
-        !name.as_str().starts_with('-') && self.scope.fn_exists(name)
+        !name.as_str().starts_with('-') && self.lookup_fn(name).is_some()
     }
 
     pub fn insert_builtin(
@@ -214,16 +502,49 @@ impl Module {
             .insert(ident, SassFunction::Builtin(Builtin::new(function), ident));
     }
 
+    /// All public members of this module, including those that are
+    /// forwarded, under the names they are accessed by from outside
+    pub fn flattened_scope(&self) -> Scope {
+        let mut scope = Scope::new();
+
+        for forwarded in self.modules.forwarded.iter().rev() {
+            let inner = forwarded.module.flattened_scope();
+            let filter = &forwarded.filter;
+
+            for (name, value) in inner.vars {
+                if let Some(name) = filter.outer_name(name, true) {
+                    scope.vars.insert(name, value);
+                }
+            }
+
+            for (name, mixin) in inner.mixins {
+                if let Some(name) = filter.outer_name(name, false) {
+                    scope.mixins.insert(name, mixin);
+                }
+            }
+
+            for (name, function) in inner.functions {
+                if let Some(name) = filter.outer_name(name, false) {
+                    scope.functions.insert(name, function);
+                }
+            }
+        }
+
+        scope.merge_module_scope(self.scope.clone());
+
+        scope
+    }
+
     pub fn functions(&self) -> SassMap {
         SassMap::new_with(
-            self.scope
+            self.flattened_scope()
                 .functions
-                .iter()
+                .into_iter()
                 .filter(|(key, _)| !key.as_str().starts_with('-'))
                 .map(|(key, value)| {
                     (
                         Value::String(key.to_string(), QuoteKind::Quoted),
-                        Value::FunctionRef(value.clone()),
+                        Value::FunctionRef(value),
                     )
                 })
                 .collect::<Vec<(Value, Value)>>(),
@@ -232,16 +553,11 @@ impl Module {
 
     pub fn variables(&self) -> SassMap {
         SassMap::new_with(
-            self.scope
+            self.flattened_scope()
                 .vars
-                .iter()
+                .into_iter()
                 .filter(|(key, _)| !key.as_str().starts_with('-'))
-                .map(|(key, value)| {
-                    (
-                        Value::String(key.to_string(), QuoteKind::Quoted),
-                        value.clone(),
-                    )
-                })
+                .map(|(key, value)| (Value::String(key.to_string(), QuoteKind::Quoted), value))
                 .collect::<Vec<(Value, Value)>>(),
         )
     }
@@ -14,6 +14,11 @@
 //!
 //! Named colors retain their original casing,
 //! so `rEd` should be emitted as `rEd`.
+//!
+//! Colors may also be in one of the color spaces from CSS Color Level 4, such as
+//! `lab(50% 20 30)` or `color(display-p3 1 0 0)`. These keep their channels in
+//! that space, along with the closest RGBA color, which is used by the functions
+//! that only support legacy colors.
 
 use std::{
     cmp::{max, min},
@@ -22,21 +27,47 @@ use std::{
 
 use crate::value::Number;
 pub(crate) use name::NAMED_COLORS;
+pub(crate) use space::{ColorSpace, GamutMapMethod, HueInterpolation, InterpolationMethod};
 
 use num_traits::{One, Signed, ToPrimitive, Zero};
 
 mod name;
+mod space;
 
 #[derive(Debug, Clone)]
 pub(crate) struct Color {
     rgba: Rgba,
     hsla: Option<Hsla>,
     repr: String,
+    space: ColorSpace,
+    /// The channels of a color in a non-legacy color space
+    ///
+    /// Legacy colors keep their channels in `rgba` and `hsla` instead
+    channels: Option<[f64; 3]>,
+    /// Which of the channels of this color in `space`, followed by alpha, were
+    /// given as `none`
+    ///
+    /// Missing channels have the value `0`, except when mixing colors, where
+    /// they take on the value of the other color
+    missing: [bool; 4],
 }
 
 impl PartialEq for Color {
     fn eq(&self, other: &Self) -> bool {
-        self.rgba == other.rgba
+        match (&self.channels, &other.channels) {
+            (None, None) => self.rgba == other.rgba && self.missing == other.missing,
+            (Some(channels1), Some(channels2)) => {
+                self.space == other.space
+                    && channels1
+                        .iter()
+                        .zip(channels2)
+                        .all(|(c1, c2)| (c1 - c2).abs() < 1e-11)
+                    && self.alpha() == other.alpha()
+                    && self.missing == other.missing
+            }
+            // legacy colors are never equal to colors in other spaces
+            _ => false,
+        }
     }
 }
 
@@ -54,6 +85,9 @@ impl Color {
             rgba: Rgba::new(red, green, blue, alpha),
             hsla: None,
             repr,
+            space: ColorSpace::Rgb,
+            channels: None,
+            missing: [false; 4],
         }
     }
 
@@ -69,6 +103,9 @@ impl Color {
             rgba: Rgba::new(red, green, blue, alpha),
             hsla: Some(hsla),
             repr,
+            space: ColorSpace::Hsl,
+            channels: None,
+            missing: [false; 4],
         }
     }
 }
@@ -166,6 +203,9 @@ impl Color {
             rgba: Rgba::new(red.into(), green.into(), blue.into(), alpha.into()),
             hsla: None,
             repr,
+            space: ColorSpace::Rgb,
+            channels: None,
+            missing: [false; 4],
         }
     }
 
@@ -483,6 +523,13 @@ impl Color {
 
     /// Change `alpha` to value given
     pub fn with_alpha(self, alpha: Number) -> Self {
+        if !self.is_legacy() || self.has_missing() {
+            let mut missing = self.missing;
+            missing[3] = false;
+
+            return Color::for_space(self.space, self.channels(), alpha).with_missing(missing);
+        }
+
         Color::from_rgba(self.red(), self.green(), self.blue(), alpha)
     }
 
@@ -490,14 +537,16 @@ impl Color {
     /// Takes a color and a number between 0 and 1,
     /// and returns a color with the opacity increased by that amount.
     pub fn fade_in(self, amount: Number) -> Self {
-        Color::from_rgba(self.red(), self.green(), self.blue(), self.alpha() + amount)
+        let alpha = self.alpha() + amount;
+        self.with_alpha(alpha)
     }
 
     /// Makes a color more transparent.
     /// Takes a color and a number between 0 and 1,
     /// and returns a color with the opacity decreased by that amount.
     pub fn fade_out(self, amount: Number) -> Self {
-        Color::from_rgba(self.red(), self.green(), self.blue(), self.alpha() - amount)
+        let alpha = self.alpha() - amount;
+        self.with_alpha(alpha)
     }
 }
 
@@ -569,10 +618,317 @@ impl Color {
 
         let repr = repr(&red, &green, &blue, &alpha);
 
-        Color::new_rgba(red, green, blue, alpha, repr)
+        Color {
+            space: ColorSpace::Hwb,
+            ..Color::new_rgba(red, green, blue, alpha, repr)
+        }
     }
 }
 
+/// Color space functions
+impl Color {
+    /// Create a color from its channels in `space`, on the scale described in
+    /// `color::space`
+    pub fn for_space(space: ColorSpace, mut channels: [f64; 3], alpha: Number) -> Self {
+        let [first, second, third] = channels.map(Number::from);
+
+        match space {
+            ColorSpace::Rgb => return Color::from_rgba(first, second, third, alpha),
+            ColorSpace::Hsl => {
+                return Color::from_hsla(
+                    first,
+                    second / Number::from(100),
+                    third / Number::from(100),
+                    alpha,
+                )
+            }
+            ColorSpace::Hwb => return Color::from_hwb(first, second, third, alpha),
+            _ => {}
+        }
+
+        if let Some(idx) = space.hue_index() {
+            channels[idx] = channels[idx].rem_euclid(360.0);
+        }
+
+        let [red, green, blue] = space.convert(ColorSpace::Rgb, channels).map(Number::from);
+        let rgba = Color::from_rgba(red, green, blue, alpha).rgba;
+
+        Color {
+            repr: space_repr(space, channels, &rgba.alpha, [false; 4]),
+            rgba,
+            hsla: None,
+            space,
+            channels: Some(channels),
+            missing: [false; 4],
+        }
+    }
+
+    pub const fn space(&self) -> ColorSpace {
+        self.space
+    }
+
+    pub const fn is_legacy(&self) -> bool {
+        self.channels.is_none()
+    }
+
+    /// The channels of this color in its own space
+    pub fn channels(&self) -> [f64; 3] {
+        if let Some(channels) = self.channels {
+            return channels;
+        }
+
+        let rgb = [
+            self.rgba.red.clone().as_f64(),
+            self.rgba.green.clone().as_f64(),
+            self.rgba.blue.clone().as_f64(),
+        ];
+
+        match self.space {
+            ColorSpace::Hsl => {
+                let (hue, saturation, lightness, _) = self.as_hsla();
+                [
+                    hue.as_f64(),
+                    saturation.as_f64() * 100.0,
+                    lightness.as_f64() * 100.0,
+                ]
+            }
+            space => ColorSpace::Rgb.convert(space, rgb),
+        }
+    }
+
+    pub fn to_space(&self, space: ColorSpace) -> Self {
+        if space == self.space {
+            return self.clone();
+        }
+
+        Color::for_space(
+            space,
+            self.space.convert(space, self.channels()),
+            self.alpha(),
+        )
+        .with_missing(self.missing_in(space))
+    }
+
+    pub fn has_missing(&self) -> bool {
+        self.missing.contains(&true)
+    }
+
+    /// Whether the channel at `idx` of this color, where `3` is alpha, is missing
+    pub const fn is_missing(&self, idx: usize) -> bool {
+        self.missing[idx]
+    }
+
+    /// Which channels of this color would be missing once converted to `space`
+    pub fn missing_in(&self, space: ColorSpace) -> [bool; 4] {
+        self.space.convert_missing(space, self.missing)
+    }
+
+    /// Mark channels of this color, where `3` is alpha, as missing
+    pub fn with_missing(mut self, missing: [bool; 4]) -> Self {
+        self.missing = missing;
+
+        self.repr = if self.channels.is_some() || self.has_missing() {
+            space_repr(self.space, self.channels(), &self.alpha(), missing)
+        } else {
+            repr(
+                &self.rgba.red,
+                &self.rgba.green,
+                &self.rgba.blue,
+                &self.rgba.alpha,
+            )
+        };
+
+        self
+    }
+
+    pub fn is_in_gamut(&self, space: ColorSpace) -> bool {
+        space.is_in_gamut(self.space.convert(space, self.channels()))
+    }
+
+    /// Map this color into the gamut of `space`, returning a color in the
+    /// same space as this one
+    pub fn to_gamut(&self, space: ColorSpace, method: GamutMapMethod) -> Self {
+        let channels = self.space.convert(space, self.channels());
+
+        if space.is_in_gamut(channels) {
+            return self.clone();
+        }
+
+        Color::for_space(space, method.map(space, channels), self.alpha()).to_space(self.space)
+    }
+
+    /// Mix this color with `other` in the color space of `method`, returning a
+    /// color in the same space as this one
+    ///
+    /// `weight` is the proportion of this color in the result, between `0`
+    /// and `1`
+    ///
+    /// <https://www.w3.org/TR/css-color-4/#interpolation>
+    pub fn interpolate(&self, other: &Color, method: InterpolationMethod, weight: f64) -> Self {
+        let space = method.space;
+
+        let mut channels1 = self.space.convert(space, self.channels());
+        let mut channels2 = other.space.convert(space, other.channels());
+
+        let missing1 = self.missing_in(space);
+        let missing2 = other.missing_in(space);
+
+        // a missing channel takes on the value of the other color, and is
+        // only missing in the result if it's missing in both
+        for idx in 0..3 {
+            match (missing1[idx], missing2[idx]) {
+                (true, false) => channels1[idx] = channels2[idx],
+                (false, true) => channels2[idx] = channels1[idx],
+                _ => {}
+            }
+        }
+
+        let missing = [0, 1, 2, 3].map(|idx| missing1[idx] && missing2[idx]);
+
+        let mut alpha1 = self.alpha().as_f64();
+        let mut alpha2 = other.alpha().as_f64();
+
+        match (missing1[3], missing2[3]) {
+            (true, false) => alpha1 = alpha2,
+            (false, true) => alpha2 = alpha1,
+            _ => {}
+        }
+
+        let alpha = alpha1 * weight + alpha2 * (1.0 - weight);
+
+        let hue_index = space.hue_index();
+
+        if let Some(idx) = hue_index {
+            // a powerless hue takes on the hue of the other color, so that
+            // mixing with a gray doesn't change the hue
+            match (
+                space.is_hue_powerless(channels1),
+                space.is_hue_powerless(channels2),
+            ) {
+                (true, false) => channels1[idx] = channels2[idx],
+                (false, true) => channels2[idx] = channels1[idx],
+                _ => {}
+            }
+
+            let (hue1, hue2) = method.hue.fixup(
+                channels1[idx].rem_euclid(360.0),
+                channels2[idx].rem_euclid(360.0),
+            );
+
+            channels1[idx] = hue1;
+            channels2[idx] = hue2;
+        }
+
+        let mut mixed = [0.0; 3];
+
+        for (idx, channel) in mixed.iter_mut().enumerate() {
+            *channel = if hue_index == Some(idx) {
+                channels1[idx] * weight + channels2[idx] * (1.0 - weight)
+            } else {
+                // channels other than hue are premultiplied by alpha
+                let premultiplied =
+                    channels1[idx] * alpha1 * weight + channels2[idx] * alpha2 * (1.0 - weight);
+
+                if alpha == 0.0 {
+                    premultiplied
+                } else {
+                    premultiplied / alpha
+                }
+            };
+        }
+
+        Color::for_space(space, mixed, Number::from(alpha))
+            .with_missing(missing)
+            .to_space(self.space)
+    }
+}
+
+/// The CSS representation of a color in a non-legacy color space, such as
+/// `lab(50% 20 30)` or `color(display-p3 1 0 0 / 0.5)`, or of a legacy color
+/// with missing channels, such as `hsl(none 0% 50%)`
+fn space_repr(space: ColorSpace, channels: [f64; 3], alpha: &Number, missing: [bool; 4]) -> String {
+    // the channel at `idx`, multiplied by `scale` and followed by `unit`
+    let channel = |idx: usize, scale: f64, unit: &str| {
+        if missing[idx] {
+            "none".to_owned()
+        } else {
+            format!("{}{}", Number::from(channels[idx] * scale).inspect(), unit)
+        }
+    };
+
+    let (prefix, [first, second, third]) = match space {
+        ColorSpace::Rgb => (
+            "rgb(".to_owned(),
+            [
+                channel(0, 1.0, ""),
+                channel(1, 1.0, ""),
+                channel(2, 1.0, ""),
+            ],
+        ),
+        ColorSpace::Hsl | ColorSpace::Hwb => (
+            format!("{}(", space),
+            [
+                channel(0, 1.0, "deg"),
+                channel(1, 1.0, "%"),
+                channel(2, 1.0, "%"),
+            ],
+        ),
+        ColorSpace::Lab => (
+            "lab(".to_owned(),
+            [
+                channel(0, 1.0, "%"),
+                channel(1, 1.0, ""),
+                channel(2, 1.0, ""),
+            ],
+        ),
+        ColorSpace::Lch => (
+            "lch(".to_owned(),
+            [
+                channel(0, 1.0, "%"),
+                channel(1, 1.0, ""),
+                channel(2, 1.0, "deg"),
+            ],
+        ),
+        ColorSpace::Oklab => (
+            "oklab(".to_owned(),
+            [
+                channel(0, 100.0, "%"),
+                channel(1, 1.0, ""),
+                channel(2, 1.0, ""),
+            ],
+        ),
+        ColorSpace::Oklch => (
+            "oklch(".to_owned(),
+            [
+                channel(0, 100.0, "%"),
+                channel(1, 1.0, ""),
+                channel(2, 1.0, "deg"),
+            ],
+        ),
+        _ => (
+            format!("color({} ", space),
+            [
+                channel(0, 1.0, ""),
+                channel(1, 1.0, ""),
+                channel(2, 1.0, ""),
+            ],
+        ),
+    };
+
+    let mut repr = format!("{}{} {} {}", prefix, first, second, third);
+
+    if missing[3] {
+        repr.push_str(" / none");
+    } else if alpha < &Number::one() {
+        repr.push_str(" / ");
+        repr.push_str(&alpha.inspect());
+    }
+
+    repr.push(')');
+
+    repr
+}
+
 /// Get the proper representation from RGBA values
 fn repr(red: &Number, green: &Number, blue: &Number, alpha: &Number) -> String {
     fn into_u8(channel: &Number) -> u8 {
@@ -0,0 +1,927 @@
+//! The color spaces from CSS Color Level 4, and conversions between them
+//!
+//! Conversions operate on `f64`s and go through CIE XYZ with a D65 white point,
+//! following the sample code in the specification:
+//! <https://www.w3.org/TR/css-color-4/#color-conversion-code>
+//!
+//! The channels of the legacy spaces `rgb`, `hsl` and `hwb` use the same scale
+//! as their CSS functions, so red is between `0` and `255` and saturation is
+//! between `0` and `100`. The channels of every other space use the scale of
+//! their CSS representation, except that percentages are resolved, so the
+//! lightness of `oklab` is between `0` and `1`.
+
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ColorSpace {
+    Rgb,
+    Hsl,
+    Hwb,
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    A98Rgb,
+    ProphotoRgb,
+    Rec2020,
+    /// CIE XYZ with a D65 white point, which may also be written `xyz-d65`
+    Xyz,
+    XyzD50,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+}
+
+impl ColorSpace {
+    /// Color space names are case-insensitive
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "rgb" => Self::Rgb,
+            "hsl" => Self::Hsl,
+            "hwb" => Self::Hwb,
+            "srgb" => Self::Srgb,
+            "srgb-linear" => Self::SrgbLinear,
+            "display-p3" => Self::DisplayP3,
+            "a98-rgb" => Self::A98Rgb,
+            "prophoto-rgb" => Self::ProphotoRgb,
+            "rec2020" => Self::Rec2020,
+            "xyz" | "xyz-d65" => Self::Xyz,
+            "xyz-d50" => Self::XyzD50,
+            "lab" => Self::Lab,
+            "lch" => Self::Lch,
+            "oklab" => Self::Oklab,
+            "oklch" => Self::Oklch,
+            _ => return None,
+        })
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Rgb => "rgb",
+            Self::Hsl => "hsl",
+            Self::Hwb => "hwb",
+            Self::Srgb => "srgb",
+            Self::SrgbLinear => "srgb-linear",
+            Self::DisplayP3 => "display-p3",
+            Self::A98Rgb => "a98-rgb",
+            Self::ProphotoRgb => "prophoto-rgb",
+            Self::Rec2020 => "rec2020",
+            Self::Xyz => "xyz",
+            Self::XyzD50 => "xyz-d50",
+            Self::Lab => "lab",
+            Self::Lch => "lch",
+            Self::Oklab => "oklab",
+            Self::Oklch => "oklch",
+        }
+    }
+
+    /// Whether this space has a hue channel, which is always the first channel
+    /// of `hsl` and `hwb` and the last of `lch` and `oklch`
+    pub const fn hue_index(self) -> Option<usize> {
+        match self {
+            Self::Hsl | Self::Hwb => Some(0),
+            Self::Lch | Self::Oklch => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Whether this space can only represent a limited range of colors
+    pub const fn is_bounded(self) -> bool {
+        !matches!(
+            self,
+            Self::Xyz | Self::XyzD50 | Self::Lab | Self::Lch | Self::Oklab | Self::Oklch
+        )
+    }
+
+    pub const fn channel_names(self) -> [&'static str; 3] {
+        match self {
+            Self::Rgb
+            | Self::Srgb
+            | Self::SrgbLinear
+            | Self::DisplayP3
+            | Self::A98Rgb
+            | Self::ProphotoRgb
+            | Self::Rec2020 => ["red", "green", "blue"],
+            Self::Hsl => ["hue", "saturation", "lightness"],
+            Self::Hwb => ["hue", "whiteness", "blackness"],
+            Self::Xyz | Self::XyzD50 => ["x", "y", "z"],
+            Self::Lab | Self::Oklab => ["lightness", "a", "b"],
+            Self::Lch | Self::Oklch => ["lightness", "chroma", "hue"],
+        }
+    }
+
+    /// Whether the hue of `channels` has no effect on the color they describe,
+    /// as is the case for grays
+    pub fn is_hue_powerless(self, channels: [f64; 3]) -> bool {
+        match self {
+            Self::Hsl => channels[1].abs() < EPSILON,
+            Self::Hwb => channels[1] + channels[2] >= 100.0 - EPSILON,
+            Self::Lch => channels[1].abs() < LCH_POWERLESS_CHROMA,
+            Self::Oklch => channels[1].abs() < OKLCH_POWERLESS_CHROMA,
+            _ => false,
+        }
+    }
+
+    /// Whether `channels` describe a color within the gamut of this space
+    pub fn is_in_gamut(self, channels: [f64; 3]) -> bool {
+        match self {
+            Self::Rgb => channels.iter().all(|c| in_range(*c, 0.0, 255.0)),
+            Self::Hsl | Self::Hwb => self
+                .convert(Self::Srgb, channels)
+                .iter()
+                .all(|c| in_range(*c, 0.0, 1.0)),
+            space if space.is_bounded() => channels.iter().all(|c| in_range(*c, 0.0, 1.0)),
+            _ => true,
+        }
+    }
+
+    /// Clamp `channels` to the gamut of this space
+    pub fn clip(self, channels: [f64; 3]) -> [f64; 3] {
+        match self {
+            Self::Rgb => channels.map(|c| c.clamp(0.0, 255.0)),
+            Self::Hsl | Self::Hwb => {
+                Self::Srgb.convert(self, Self::Srgb.clip(self.convert(Self::Srgb, channels)))
+            }
+            space if space.is_bounded() => channels.map(|c| c.clamp(0.0, 1.0)),
+            _ => channels,
+        }
+    }
+
+    /// Convert `channels` from this space to `to`
+    pub fn convert(self, to: ColorSpace, channels: [f64; 3]) -> [f64; 3] {
+        if self == to {
+            return channels;
+        }
+
+        match (self, to) {
+            (Self::Lab, Self::Lch) | (Self::Oklab, Self::Oklch) => return to_polar(channels, to),
+            (Self::Lch, Self::Lab) | (Self::Oklch, Self::Oklab) => return from_polar(channels),
+            (
+                Self::Rgb | Self::Hsl | Self::Hwb | Self::Srgb,
+                Self::Rgb | Self::Hsl | Self::Hwb | Self::Srgb,
+            ) => return from_srgb(to_srgb(self, channels), to),
+            _ => {}
+        }
+
+        from_xyz(to, to_xyz(self, channels))
+    }
+}
+
+impl ColorSpace {
+    /// The kind of each channel of this space, which determines the channels
+    /// of other spaces it is analogous to
+    ///
+    /// <https://www.w3.org/TR/css-color-4/#interpolation-missing>
+    const fn analogous_channels(self) -> [Option<&'static str>; 3] {
+        match self {
+            Self::Rgb
+            | Self::Srgb
+            | Self::SrgbLinear
+            | Self::DisplayP3
+            | Self::A98Rgb
+            | Self::ProphotoRgb
+            | Self::Rec2020 => [Some("red"), Some("green"), Some("blue")],
+            Self::Hsl => [Some("hue"), Some("colorfulness"), Some("lightness")],
+            Self::Hwb => [Some("hue"), None, None],
+            Self::Xyz | Self::XyzD50 => [None, None, None],
+            Self::Lab | Self::Oklab => [Some("lightness"), None, None],
+            Self::Lch | Self::Oklch => [Some("lightness"), Some("colorfulness"), Some("hue")],
+        }
+    }
+
+    /// Carry the missing channels of a color in this space, followed by alpha,
+    /// forward to the analogous channels of `to`
+    pub fn convert_missing(self, to: ColorSpace, missing: [bool; 4]) -> [bool; 4] {
+        if self == to {
+            return missing;
+        }
+
+        let from_kinds = self.analogous_channels();
+        let to_kinds = to.analogous_channels();
+
+        let mut converted = [false, false, false, missing[3]];
+
+        for (idx, kind) in to_kinds.iter().enumerate() {
+            if kind.is_some() {
+                converted[idx] = from_kinds
+                    .iter()
+                    .zip(missing)
+                    .any(|(from_kind, is_missing)| is_missing && from_kind == kind);
+            }
+        }
+
+        converted
+    }
+}
+
+impl ColorSpace {
+    /// Map `channels` into the gamut of this space using the CSS gamut mapping
+    /// algorithm, which reduces the chroma of the color in `oklch` until
+    /// clipping it is no longer noticeable
+    ///
+    /// <https://www.w3.org/TR/css-color-4/#binsearch>
+    fn local_minde(self, channels: [f64; 3]) -> [f64; 3] {
+        const JND: f64 = 0.02;
+        const MIN_CONVERGENCE: f64 = 0.0001;
+
+        let origin = self.convert(Self::Oklch, channels);
+
+        if origin[0] >= 1.0 {
+            return self.clip(Self::Oklab.convert(self, [1.0, 0.0, 0.0]));
+        }
+
+        if origin[0] <= 0.0 {
+            return self.clip(Self::Oklab.convert(self, [0.0, 0.0, 0.0]));
+        }
+
+        let delta_eok = |clipped: [f64; 3], current: [f64; 3]| {
+            let clipped = self.convert(Self::Oklab, clipped);
+            let current = Self::Oklch.convert(Self::Oklab, current);
+
+            clipped
+                .iter()
+                .zip(current)
+                .map(|(c1, c2)| (c1 - c2).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        };
+
+        let mut current = origin;
+        let mut clipped = self.clip(channels);
+
+        if delta_eok(clipped, current) < JND {
+            return clipped;
+        }
+
+        let mut min = 0.0;
+        let mut max = origin[1];
+        let mut min_in_gamut = true;
+
+        while max - min > MIN_CONVERGENCE {
+            let chroma = (min + max) / 2.0;
+            current[1] = chroma;
+
+            let converted = Self::Oklch.convert(self, current);
+
+            if min_in_gamut && self.is_in_gamut(converted) {
+                min = chroma;
+                continue;
+            }
+
+            clipped = self.clip(converted);
+
+            let error = delta_eok(clipped, current);
+
+            if error < JND {
+                if JND - error < MIN_CONVERGENCE {
+                    return clipped;
+                }
+
+                min_in_gamut = false;
+                min = chroma;
+            } else {
+                max = chroma;
+            }
+        }
+
+        clipped
+    }
+}
+
+/// The ways `color.to-gamut()` can map a color into a gamut
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum GamutMapMethod {
+    /// Clamp each channel to the gamut, which may change the hue of the color
+    Clip,
+    /// The algorithm from CSS Color Level 4, which preserves lightness and hue
+    LocalMinde,
+}
+
+impl GamutMapMethod {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "clip" => Some(Self::Clip),
+            "local-minde" => Some(Self::LocalMinde),
+            _ => None,
+        }
+    }
+
+    pub fn map(self, space: ColorSpace, channels: [f64; 3]) -> [f64; 3] {
+        match self {
+            Self::Clip => space.clip(channels),
+            Self::LocalMinde => space.local_minde(channels),
+        }
+    }
+}
+
+/// How to interpolate between the hues of two colors
+///
+/// <https://www.w3.org/TR/css-color-4/#hue-interpolation>
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum HueInterpolation {
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+impl HueInterpolation {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "shorter" => Self::Shorter,
+            "longer" => Self::Longer,
+            "increasing" => Self::Increasing,
+            "decreasing" => Self::Decreasing,
+            _ => return None,
+        })
+    }
+
+    /// Adjust two hues, in degrees between `0` and `360`, so that interpolating
+    /// linearly between them follows this method
+    pub fn fixup(self, mut hue1: f64, mut hue2: f64) -> (f64, f64) {
+        let difference = hue2 - hue1;
+
+        match self {
+            Self::Shorter if difference > 180.0 => hue1 += 360.0,
+            Self::Shorter if difference < -180.0 => hue2 += 360.0,
+            Self::Longer if difference > 0.0 && difference < 180.0 => hue1 += 360.0,
+            Self::Longer if difference > -180.0 && difference <= 0.0 => hue2 += 360.0,
+            Self::Increasing if difference < 0.0 => hue2 += 360.0,
+            Self::Decreasing if difference > 0.0 => hue1 += 360.0,
+            _ => {}
+        }
+
+        (hue1, hue2)
+    }
+}
+
+/// The `$method` argument of `color.mix()`, such as `oklch longer hue`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) struct InterpolationMethod {
+    pub space: ColorSpace,
+    pub hue: HueInterpolation,
+}
+
+impl fmt::Display for ColorSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+const EPSILON: f64 = 1e-11;
+
+/// The chroma below which the hue of an `lch()` color is considered powerless
+const LCH_POWERLESS_CHROMA: f64 = 0.0015;
+
+/// The chroma below which the hue of an `oklch()` color is considered powerless
+const OKLCH_POWERLESS_CHROMA: f64 = 0.000_004;
+
+fn in_range(channel: f64, min: f64, max: f64) -> bool {
+    channel >= min - EPSILON && channel <= max + EPSILON
+}
+
+type Matrix = [[f64; 3]; 3];
+
+fn multiply(matrix: &Matrix, channels: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * channels[0] + row[1] * channels[1] + row[2] * channels[2])
+}
+
+/// Convert the channels of a color in the `srgb` space or one of the legacy
+/// spaces to `srgb`
+fn to_srgb(space: ColorSpace, channels: [f64; 3]) -> [f64; 3] {
+    match space {
+        ColorSpace::Rgb => channels.map(|c| c / 255.0),
+        ColorSpace::Hsl => hsl_to_srgb(channels),
+        ColorSpace::Hwb => {
+            let [hue, white, black] = channels;
+            let white = white / 100.0;
+            let black = black / 100.0;
+
+            // whiteness and blackness that add up to more than 100% describe a gray
+            if white + black >= 1.0 {
+                let gray = white / (white + black);
+                return [gray, gray, gray];
+            }
+
+            hsl_to_srgb([hue, 100.0, 50.0]).map(|c| c * (1.0 - white - black) + white)
+        }
+        _ => channels,
+    }
+}
+
+fn from_srgb(srgb: [f64; 3], to: ColorSpace) -> [f64; 3] {
+    match to {
+        ColorSpace::Rgb => srgb.map(|c| c * 255.0),
+        ColorSpace::Hsl => {
+            let [hue, saturation, lightness] = srgb_to_hsl(srgb);
+            [hue, saturation * 100.0, lightness * 100.0]
+        }
+        ColorSpace::Hwb => {
+            let [hue, ..] = srgb_to_hsl(srgb);
+            let white = srgb[0].min(srgb[1]).min(srgb[2]);
+            let black = 1.0 - srgb[0].max(srgb[1]).max(srgb[2]);
+            [hue, white * 100.0, black * 100.0]
+        }
+        ColorSpace::Srgb => srgb,
+        _ => from_xyz(to, to_xyz(ColorSpace::Srgb, srgb)),
+    }
+}
+
+fn hsl_to_srgb([hue, saturation, lightness]: [f64; 3]) -> [f64; 3] {
+    let saturation = saturation / 100.0;
+    let lightness = lightness / 100.0;
+
+    let channel = |n: f64| {
+        let k = (n + hue / 30.0).rem_euclid(12.0);
+        let a = saturation * lightness.min(1.0 - lightness);
+        lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+
+    [channel(0.0), channel(8.0), channel(4.0)]
+}
+
+/// Returns the hue in degrees and the saturation and lightness between `0`
+/// and `1`
+fn srgb_to_hsl([red, green, blue]: [f64; 3]) -> [f64; 3] {
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let delta = max - min;
+    let lightness = (min + max) / 2.0;
+
+    let saturation = if lightness.abs() < EPSILON || (1.0 - lightness).abs() < EPSILON {
+        0.0
+    } else {
+        (max - lightness) / lightness.min(1.0 - lightness)
+    };
+
+    let hue = if delta.abs() < EPSILON {
+        0.0
+    } else if (max - red).abs() < EPSILON {
+        (green - blue) / delta + if green < blue { 6.0 } else { 0.0 }
+    } else if (max - green).abs() < EPSILON {
+        (blue - red) / delta + 2.0
+    } else {
+        (red - green) / delta + 4.0
+    } * 60.0;
+
+    [hue, saturation, lightness]
+}
+
+fn srgb_to_linear(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs <= 0.04045 {
+        channel / 12.92
+    } else {
+        channel.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs > 0.003_130_8 {
+        channel.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    } else {
+        channel * 12.92
+    }
+}
+
+fn a98_to_linear(channel: f64) -> f64 {
+    channel.signum() * channel.abs().powf(563.0 / 256.0)
+}
+
+fn linear_to_a98(channel: f64) -> f64 {
+    channel.signum() * channel.abs().powf(256.0 / 563.0)
+}
+
+fn prophoto_to_linear(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs <= 16.0 / 512.0 {
+        channel / 16.0
+    } else {
+        channel.signum() * abs.powf(1.8)
+    }
+}
+
+fn linear_to_prophoto(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs >= 1.0 / 512.0 {
+        channel.signum() * abs.powf(1.0 / 1.8)
+    } else {
+        channel * 16.0
+    }
+}
+
+const REC2020_ALPHA: f64 = 1.099_296_826_809_44;
+const REC2020_BETA: f64 = 0.018_053_968_510_807;
+
+fn rec2020_to_linear(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs < REC2020_BETA * 4.5 {
+        channel / 4.5
+    } else {
+        channel.signum() * ((abs + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+    }
+}
+
+fn linear_to_rec2020(channel: f64) -> f64 {
+    let abs = channel.abs();
+
+    if abs > REC2020_BETA {
+        channel.signum() * (REC2020_ALPHA * abs.powf(0.45) - (REC2020_ALPHA - 1.0))
+    } else {
+        channel * 4.5
+    }
+}
+
+const LINEAR_SRGB_TO_XYZ: Matrix = [
+    [
+        0.412_390_799_265_959_34,
+        0.357_584_339_383_878,
+        0.180_480_788_401_834_3,
+    ],
+    [
+        0.212_639_005_871_510_27,
+        0.715_168_678_767_756,
+        0.072_192_315_360_733_71,
+    ],
+    [
+        0.019_330_818_715_591_82,
+        0.119_194_779_794_625_98,
+        0.950_532_152_249_660_7,
+    ],
+];
+
+const XYZ_TO_LINEAR_SRGB: Matrix = [
+    [
+        3.240_969_941_904_522_6,
+        -1.537_383_177_570_094,
+        -0.498_610_760_293_003_4,
+    ],
+    [
+        -0.969_243_636_280_879_6,
+        1.875_967_501_507_720_2,
+        0.041_555_057_407_175_59,
+    ],
+    [
+        0.055_630_079_696_993_66,
+        -0.203_976_958_888_976_52,
+        1.056_971_514_242_878_6,
+    ],
+];
+
+const LINEAR_DISPLAY_P3_TO_XYZ: Matrix = [
+    [
+        0.486_570_948_648_216_2,
+        0.265_667_693_169_093_06,
+        0.198_217_285_234_362_5,
+    ],
+    [
+        0.228_974_564_069_748_8,
+        0.691_738_521_836_506_4,
+        0.079_286_914_093_745,
+    ],
+    [0.0, 0.045_113_381_858_902_64, 1.043_944_368_900_976],
+];
+
+const XYZ_TO_LINEAR_DISPLAY_P3: Matrix = [
+    [
+        2.493_496_911_941_425,
+        -0.931_383_617_919_123_9,
+        -0.402_710_784_450_716_84,
+    ],
+    [
+        -0.829_488_969_561_574_7,
+        1.762_664_060_318_346_3,
+        0.023_624_685_841_943_577,
+    ],
+    [
+        0.035_845_830_243_784_47,
+        -0.076_172_389_268_041_82,
+        0.956_884_524_007_687_2,
+    ],
+];
+
+const LINEAR_A98_TO_XYZ: Matrix = [
+    [
+        0.576_669_042_910_130_5,
+        0.185_558_237_906_546_3,
+        0.188_228_646_234_994_7,
+    ],
+    [
+        0.297_344_975_250_536_05,
+        0.627_363_566_255_466_1,
+        0.075_291_458_493_997_88,
+    ],
+    [
+        0.027_031_361_386_412_34,
+        0.070_688_852_535_827_23,
+        0.991_337_536_837_638_8,
+    ],
+];
+
+const XYZ_TO_LINEAR_A98: Matrix = [
+    [
+        2.041_587_903_810_746_5,
+        -0.565_006_974_278_859_6,
+        -0.344_731_350_778_329_56,
+    ],
+    [
+        -0.969_243_636_280_879_5,
+        1.875_967_501_507_720_2,
+        0.041_555_057_407_175_57,
+    ],
+    [
+        0.013_444_280_632_031_142,
+        -0.118_362_392_231_018_38,
+        1.015_174_994_391_205_4,
+    ],
+];
+
+const LINEAR_REC2020_TO_XYZ: Matrix = [
+    [
+        0.636_958_048_301_291_4,
+        0.144_616_903_586_208_32,
+        0.168_880_975_164_172_1,
+    ],
+    [
+        0.262_700_212_011_267_1,
+        0.677_998_071_518_870_8,
+        0.059_301_716_469_861_96,
+    ],
+    [0.0, 0.028_072_693_049_087_428, 1.060_985_057_710_791],
+];
+
+const XYZ_TO_LINEAR_REC2020: Matrix = [
+    [
+        1.716_651_187_971_267_4,
+        -0.355_670_783_776_392_33,
+        -0.253_366_281_373_659_74,
+    ],
+    [
+        -0.666_684_351_832_489_2,
+        1.616_481_236_634_939_5,
+        0.015_768_545_813_911_13,
+    ],
+    [
+        0.017_639_857_445_310_783,
+        -0.042_770_613_257_808_524,
+        0.942_103_121_235_473_8,
+    ],
+];
+
+/// ProPhoto RGB uses a D50 white point, so converts to `xyz-d50`
+const LINEAR_PROPHOTO_TO_XYZ_D50: Matrix = [
+    [
+        0.797_766_644_900_642_3,
+        0.135_181_297_400_533_08,
+        0.031_347_734_128_392_2,
+    ],
+    [
+        0.288_074_828_819_401_3,
+        0.711_835_234_241_873,
+        0.000_089_936_938_725_64,
+    ],
+    [0.0, 0.0, 0.825_104_602_510_460_2],
+];
+
+const XYZ_D50_TO_LINEAR_PROPHOTO: Matrix = [
+    [
+        1.345_786_881_647_158_3,
+        -0.255_572_087_379_794_64,
+        -0.051_101_864_975_545_26,
+    ],
+    [
+        -0.544_630_705_124_901_9,
+        1.508_247_742_845_146_8,
+        0.020_527_447_436_421_39,
+    ],
+    [0.0, 0.0, 1.211_967_545_638_945_2],
+];
+
+/// The Bradford chromatic adaptation from D65 to D50
+const XYZ_D65_TO_D50: Matrix = [
+    [
+        1.047_929_820_840_548_8,
+        0.022_946_793_341_019_088,
+        -0.050_192_229_543_135_57,
+    ],
+    [
+        0.029_627_815_688_159_344,
+        0.990_434_484_573_249,
+        -0.017_073_825_029_385_14,
+    ],
+    [
+        -0.009_243_058_152_591_178,
+        0.015_055_144_896_577_895,
+        0.751_874_289_958_000_8,
+    ],
+];
+
+const XYZ_D50_TO_D65: Matrix = [
+    [
+        0.955_473_452_704_218_2,
+        -0.023_098_536_874_261_423,
+        0.063_259_308_661_021_7,
+    ],
+    [
+        -0.028_369_706_963_208_136,
+        1.009_995_458_005_822_6,
+        0.021_041_398_966_943_008,
+    ],
+    [
+        0.012_314_001_688_319_899,
+        -0.020_507_696_433_477_912,
+        1.330_365_936_608_075_3,
+    ],
+];
+
+const XYZ_TO_LMS: Matrix = [
+    [
+        0.819_022_437_996_703,
+        0.361_906_260_052_890_4,
+        -0.128_873_781_520_987_9,
+    ],
+    [
+        0.032_983_653_932_388_5,
+        0.929_286_861_586_343_4,
+        0.036_144_666_350_642_4,
+    ],
+    [
+        0.048_177_189_359_624_2,
+        0.264_239_531_752_730_8,
+        0.633_547_828_469_430_9,
+    ],
+];
+
+const LMS_TO_XYZ: Matrix = [
+    [
+        1.226_879_875_845_924_3,
+        -0.557_814_994_460_217_1,
+        0.281_391_045_665_964_7,
+    ],
+    [
+        -0.040_575_745_214_800_8,
+        1.112_286_803_280_317,
+        -0.071_711_058_065_516_4,
+    ],
+    [
+        -0.076_372_936_674_660_1,
+        -0.421_493_332_402_243_2,
+        1.586_924_019_836_781_6,
+    ],
+];
+
+const LMS_TO_OKLAB: Matrix = [
+    [
+        0.210_454_268_309_314,
+        0.793_617_774_702_305_4,
+        -0.004_072_043_011_619_3,
+    ],
+    [
+        1.977_998_532_431_168_4,
+        -2.428_592_242_048_58,
+        0.450_593_709_617_411,
+    ],
+    [
+        0.025_904_042_465_547_8,
+        0.782_771_712_457_529_6,
+        -0.808_675_754_923_077_4,
+    ],
+];
+
+const OKLAB_TO_LMS: Matrix = [
+    [1.0, 0.396_337_777_376_174_9, 0.215_803_757_309_913_6],
+    [1.0, -0.105_561_345_815_658_6, -0.063_854_172_825_813_3],
+    [1.0, -0.089_484_177_529_811_9, -1.291_485_548_019_409_2],
+];
+
+/// The D50 white point, used by `lab` and `lch`
+const D50: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+
+/// Convert `channels` in `space` to `xyz`, with a D65 white point
+fn to_xyz(space: ColorSpace, channels: [f64; 3]) -> [f64; 3] {
+    match space {
+        ColorSpace::Rgb | ColorSpace::Hsl | ColorSpace::Hwb | ColorSpace::Srgb => multiply(
+            &LINEAR_SRGB_TO_XYZ,
+            to_srgb(space, channels).map(srgb_to_linear),
+        ),
+        ColorSpace::SrgbLinear => multiply(&LINEAR_SRGB_TO_XYZ, channels),
+        ColorSpace::DisplayP3 => multiply(&LINEAR_DISPLAY_P3_TO_XYZ, channels.map(srgb_to_linear)),
+        ColorSpace::A98Rgb => multiply(&LINEAR_A98_TO_XYZ, channels.map(a98_to_linear)),
+        ColorSpace::ProphotoRgb => multiply(
+            &XYZ_D50_TO_D65,
+            multiply(
+                &LINEAR_PROPHOTO_TO_XYZ_D50,
+                channels.map(prophoto_to_linear),
+            ),
+        ),
+        ColorSpace::Rec2020 => multiply(&LINEAR_REC2020_TO_XYZ, channels.map(rec2020_to_linear)),
+        ColorSpace::Xyz => channels,
+        ColorSpace::XyzD50 => multiply(&XYZ_D50_TO_D65, channels),
+        ColorSpace::Lab => multiply(&XYZ_D50_TO_D65, lab_to_xyz_d50(channels)),
+        ColorSpace::Lch => to_xyz(ColorSpace::Lab, from_polar(channels)),
+        ColorSpace::Oklab => {
+            let lms = multiply(&OKLAB_TO_LMS, channels).map(|c| c.powi(3));
+            multiply(&LMS_TO_XYZ, lms)
+        }
+        ColorSpace::Oklch => to_xyz(ColorSpace::Oklab, from_polar(channels)),
+    }
+}
+
+/// Convert `xyz`, with a D65 white point, to `space`
+fn from_xyz(space: ColorSpace, xyz: [f64; 3]) -> [f64; 3] {
+    match space {
+        ColorSpace::Rgb | ColorSpace::Hsl | ColorSpace::Hwb | ColorSpace::Srgb => from_srgb(
+            multiply(&XYZ_TO_LINEAR_SRGB, xyz).map(linear_to_srgb),
+            space,
+        ),
+        ColorSpace::SrgbLinear => multiply(&XYZ_TO_LINEAR_SRGB, xyz),
+        ColorSpace::DisplayP3 => multiply(&XYZ_TO_LINEAR_DISPLAY_P3, xyz).map(linear_to_srgb),
+        ColorSpace::A98Rgb => multiply(&XYZ_TO_LINEAR_A98, xyz).map(linear_to_a98),
+        ColorSpace::ProphotoRgb => {
+            multiply(&XYZ_D50_TO_LINEAR_PROPHOTO, multiply(&XYZ_D65_TO_D50, xyz))
+                .map(linear_to_prophoto)
+        }
+        ColorSpace::Rec2020 => multiply(&XYZ_TO_LINEAR_REC2020, xyz).map(linear_to_rec2020),
+        ColorSpace::Xyz => xyz,
+        ColorSpace::XyzD50 => multiply(&XYZ_D65_TO_D50, xyz),
+        ColorSpace::Lab => xyz_d50_to_lab(multiply(&XYZ_D65_TO_D50, xyz)),
+        ColorSpace::Lch => to_polar(from_xyz(ColorSpace::Lab, xyz), ColorSpace::Lch),
+        ColorSpace::Oklab => {
+            let lms = multiply(&XYZ_TO_LMS, xyz).map(f64::cbrt);
+            multiply(&LMS_TO_OKLAB, lms)
+        }
+        ColorSpace::Oklch => to_polar(from_xyz(ColorSpace::Oklab, xyz), ColorSpace::Oklch),
+    }
+}
+
+fn xyz_d50_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let f = |channel: f64, white: f64| {
+        let channel = channel / white;
+
+        if channel > LAB_EPSILON {
+            channel.cbrt()
+        } else {
+            (LAB_KAPPA * channel + 16.0) / 116.0
+        }
+    };
+
+    let fx = f(xyz[0], D50[0]);
+    let fy = f(xyz[1], D50[1]);
+    let fz = f(xyz[2], D50[2]);
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+fn lab_to_xyz_d50([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    let fy = (lightness + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+
+    let x = if fx.powi(3) > LAB_EPSILON {
+        fx.powi(3)
+    } else {
+        (116.0 * fx - 16.0) / LAB_KAPPA
+    };
+
+    let y = if lightness > LAB_KAPPA * LAB_EPSILON {
+        fy.powi(3)
+    } else {
+        lightness / LAB_KAPPA
+    };
+
+    let z = if fz.powi(3) > LAB_EPSILON {
+        fz.powi(3)
+    } else {
+        (116.0 * fz - 16.0) / LAB_KAPPA
+    };
+
+    [x * D50[0], y * D50[1], z * D50[2]]
+}
+
+/// Convert `lab` or `oklab` channels to `lch` or `oklch`
+fn to_polar([lightness, a, b]: [f64; 3], space: ColorSpace) -> [f64; 3] {
+    let chroma = (a * a + b * b).sqrt();
+
+    let hue = if space.is_hue_powerless([lightness, chroma, 0.0]) {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    };
+
+    [lightness, chroma, hue]
+}
+
+/// Convert `lch` or `oklch` channels to `lab` or `oklab`
+fn from_polar([lightness, chroma, hue]: [f64; 3]) -> [f64; 3] {
+    let hue = hue.to_radians();
+    [lightness, chroma * hue.cos(), chroma * hue.sin()]
+}
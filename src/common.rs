@@ -89,6 +89,7 @@ pub(crate) enum Brackets {
 pub(crate) enum ListSeparator {
     Space,
     Comma,
+    Slash,
 }
 
 impl ListSeparator {
@@ -96,6 +97,7 @@ impl ListSeparator {
         match self {
             Self::Space => " ",
             Self::Comma => ", ",
+            Self::Slash => " / ",
         }
     }
 
@@ -103,6 +105,7 @@ impl ListSeparator {
         match self {
             Self::Space => " ",
             Self::Comma => ",",
+            Self::Slash => "/",
         }
     }
 
@@ -110,6 +113,7 @@ impl ListSeparator {
         match self {
             Self::Space => "space",
             Self::Comma => "comma",
+            Self::Slash => "slash",
         }
     }
 }
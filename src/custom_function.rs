@@ -0,0 +1,94 @@
+//! Functions implemented by the embedding application
+//!
+//! Custom functions are registered on [`Options`] along with a signature
+//! written in Sass, such as `asset-url($path, $hash: true)`. Signatures are
+//! parsed once per compilation, so that default arguments can be evaluated
+//! like those of any other function.
+
+use std::{fmt, path::Path};
+
+use codemap::CodeMap;
+
+use crate::{
+    args::FuncArgs,
+    builtin::modules::{ModuleConfig, Modules},
+    common::Identifier,
+    error::SassResult,
+    lexer::Lexer,
+    parse::{
+        common::{ContextFlags, NeverEmptyVec},
+        Parser,
+    },
+    scope::{Scope, Scopes},
+    selector::{ExtendedSelector, Extender, SelectorList},
+    value::SassValue,
+    Options,
+};
+
+pub(crate) type CustomFunctionCallback<'a> = &'a dyn Fn(&[SassValue]) -> Result<SassValue, String>;
+
+/// A function registered with [`Options::add_function`]
+pub(crate) struct CustomFunction<'a> {
+    pub signature: &'a str,
+    pub callback: CustomFunctionCallback<'a>,
+}
+
+impl fmt::Debug for CustomFunction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomFunction")
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+/// The parsed signature of a custom function
+///
+/// Signatures are stored in the same order as the functions in [`Options`]
+#[derive(Debug, Clone)]
+pub(crate) struct CustomFunctionSignature {
+    pub name: Identifier,
+    pub args: FuncArgs,
+}
+
+/// Parse the signature of every custom function in `options`, adding each
+/// signature to `map` so that errors may point into it
+pub(crate) fn parse_signatures(
+    map: &mut CodeMap,
+    options: &Options,
+) -> SassResult<Vec<CustomFunctionSignature>> {
+    options
+        .functions
+        .iter()
+        .map(|function| {
+            let file = map.add_file(function.signature.to_owned(), function.signature.to_owned());
+            let empty_span = file.span.subspan(0, 0);
+
+            Parser {
+                toks: &mut Lexer::new_from_file(&file),
+                map,
+                path: Path::new(""),
+                scopes: &mut Scopes::new(),
+                global_scope: &mut Scope::new(),
+                super_selectors: &mut NeverEmptyVec::new(ExtendedSelector::new(SelectorList::new(
+                    empty_span,
+                ))),
+                span_before: empty_span,
+                content: &mut Vec::new(),
+                flags: ContextFlags::empty(),
+                at_root: true,
+                at_root_has_selector: false,
+                extender: &mut Extender::new(empty_span),
+                media_queries: &mut None,
+                content_scopes: &mut Scopes::new(),
+                options,
+                custom_functions: &[],
+                loaded_files: &mut Vec::new(),
+                call_stack: &mut Vec::new(),
+                importer: None,
+                modules: &mut Modules::default(),
+                module_config: &mut ModuleConfig::default(),
+            }
+            .parse_custom_function_signature()
+        })
+        .collect()
+}
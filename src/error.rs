@@ -37,10 +37,12 @@ pub struct SassError {
 }
 
 impl SassError {
-    pub(crate) fn raw(self) -> (String, Span) {
+    /// Errors that do not originate from the input, such as io errors, have
+    /// no raw form and are returned unchanged
+    pub(crate) fn raw(self) -> Result<(String, Span), Self> {
         match self.kind {
-            SassErrorKind::Raw(string, span) => (string, span),
-            e => todo!("unable to get raw of {:?}", e),
+            SassErrorKind::Raw(string, span) => Ok((string, span)),
+            _ => Err(self),
         }
     }
 
@@ -86,7 +88,9 @@ impl Display for SassError {
             } => (message, loc, *unicode),
             SassErrorKind::FromUtf8Error(s) => return writeln!(f, "Error: {}", s),
             SassErrorKind::IoError(s) => return writeln!(f, "Error: {}", s),
-            SassErrorKind::Raw(..) => todo!(),
+            // values detached from a stylesheet, such as a `SassValue`, have no
+            // source to point to
+            SassErrorKind::Raw(message, ..) => return writeln!(f, "Error: {}", message),
         };
 
         let first_bar = if unicode { '╷' } else { '|' };
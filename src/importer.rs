@@ -0,0 +1,91 @@
+/// The syntax a stylesheet is written in
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Syntax {
+    /// The SCSS syntax, usually found in `.scss` files
+    Scss,
+    /// The indented syntax, usually found in `.sass` files
+    Indented,
+}
+
+/// A trait to allow loading stylesheets from places other than the file system.
+///
+/// Importers are consulted in the order they were added to [`Options`](crate::Options),
+/// after looking for the stylesheet relative to the importing file and before
+/// searching the load paths. The first importer to return a stylesheet wins.
+///
+/// Within a stylesheet loaded by an importer, URLs are passed to that same
+/// importer first, instead of being looked for on the file system.
+///
+/// ```
+/// use grass::{Importer, ImporterResult, Syntax};
+///
+/// #[derive(Debug)]
+/// struct ThemeImporter;
+///
+/// impl Importer for ThemeImporter {
+///     fn import(
+///         &self,
+///         url: &str,
+///         _containing_url: Option<&str>,
+///     ) -> std::io::Result<Option<ImporterResult>> {
+///         Ok(if url == "theme:colors" {
+///             Some(ImporterResult::new(
+///                 url.to_owned(),
+///                 "$primary: red;".to_owned(),
+///                 Syntax::Scss,
+///             ))
+///         } else {
+///             None
+///         })
+///     }
+/// }
+///
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let css = grass::from_string(
+///         "@use 'theme:colors';\na { color: colors.$primary; }".to_string(),
+///         &grass::Options::default().importer(&ThemeImporter),
+///     )?;
+///     assert_eq!(css, "a {\n  color: red;\n}\n");
+///     Ok(())
+/// }
+/// ```
+pub trait Importer: std::fmt::Debug {
+    /// Load the stylesheet at `url`, as written in an `@use`, `@forward` or
+    /// `@import` rule or passed to `meta.load-css()`.
+    ///
+    /// `containing_url` is the canonical URL of the stylesheet containing the
+    /// rule, which may be used to resolve relative URLs. It is the path of the
+    /// file for stylesheets loaded from the file system, or the canonical URL
+    /// returned by an importer.
+    ///
+    /// Returns `Ok(None)` if this importer does not recognize the URL, in which
+    /// case the next importer is consulted.
+    fn import(
+        &self,
+        url: &str,
+        containing_url: Option<&str>,
+    ) -> std::io::Result<Option<ImporterResult>>;
+}
+
+/// A stylesheet loaded by an [`Importer`]
+#[derive(Clone, Debug)]
+pub struct ImporterResult {
+    pub(crate) canonical_url: String,
+    pub(crate) contents: String,
+    pub(crate) syntax: Syntax,
+}
+
+impl ImporterResult {
+    /// `canonical_url` uniquely identifies the stylesheet. It is used in error
+    /// messages and source maps, and is passed back to importers as the
+    /// containing URL of any rules within this stylesheet.
+    #[inline]
+    pub fn new(canonical_url: String, contents: String, syntax: Syntax) -> Self {
+        Self {
+            canonical_url,
+            contents,
+            syntax,
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! # The indented syntax
+//!
+//! Stylesheets written in the indented syntax (`.sass` files) are translated
+//! into the token stream of the equivalent SCSS, which is then consumed by the
+//! regular [`Parser`](crate::parse::Parser). Blocks are inferred from
+//! indentation and statements are terminated by newlines.
+//!
+//! Every token that came from the source keeps its original span, so errors
+//! and source maps point into the `.sass` file. Synthetic tokens, such as the
+//! `{`, `}` and `;` that delimit blocks and statements, borrow the span of a
+//! nearby token.
+use std::{path::Path, sync::Arc};
+
+use codemap::{File, Span};
+
+use crate::{error::SassResult, lexer::Lexer, Token};
+
+/// Whether the file at `path` should be parsed using the indented syntax
+pub(crate) fn is_indented_path(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("sass"))
+}
+
+/// Lex `file`, which is written in the indented syntax, into SCSS tokens
+pub(crate) fn lex_indented_file(file: &Arc<File>) -> SassResult<Lexer<'static>> {
+    let toks: Vec<Token> = Lexer::new_from_file(file).collect();
+    let eof = file.span.subspan(file.span.len(), file.span.len());
+
+    let lines = split_lines(&toks)?;
+    let statements = group_statements(&lines);
+
+    Ok(Lexer::new(emit(&statements, eof)?))
+}
+
+struct Line<'a> {
+    /// The width of the leading whitespace
+    indent: usize,
+    /// The full line, including indentation
+    raw: &'a [Token],
+    /// The line with leading and trailing whitespace removed
+    content: &'a [Token],
+    newline: Option<Token>,
+}
+
+fn split_lines(toks: &[Token]) -> SassResult<Vec<Line<'_>>> {
+    let mut lines = Vec::new();
+    // whether the file is indented with tabs, which is decided by the first
+    // indented line
+    let mut uses_tabs = None;
+
+    for raw in toks.split_inclusive(|tok| tok.kind == '\n') {
+        let (raw, newline) = match raw.split_last() {
+            Some((last, rest)) if last.kind == '\n' => (rest, Some(*last)),
+            _ => (raw, None),
+        };
+
+        let indent = raw
+            .iter()
+            .take_while(|tok| tok.kind == ' ' || tok.kind == '\t')
+            .count();
+
+        let leading = &raw[..indent];
+        if leading.iter().any(|tok| tok.kind == ' ') && leading.iter().any(|tok| tok.kind == '\t') {
+            return Err(("Tabs and spaces may not be mixed.", leading[0].pos).into());
+        }
+
+        let content = trim_end(&raw[indent..]);
+
+        if let (Some(first), false) = (leading.first(), content.is_empty()) {
+            let is_tab = first.kind == '\t';
+            match uses_tabs {
+                None => uses_tabs = Some(is_tab),
+                Some(true) if !is_tab => {
+                    return Err(("Expected tabs, was spaces.", first.pos).into())
+                }
+                Some(false) if is_tab => {
+                    return Err(("Expected spaces, was tabs.", first.pos).into())
+                }
+                Some(..) => {}
+            }
+        }
+
+        lines.push(Line {
+            indent,
+            raw,
+            content,
+            newline,
+        });
+    }
+
+    Ok(lines)
+}
+
+fn trim_end(toks: &[Token]) -> &[Token] {
+    let len = toks.len()
+        - toks
+            .iter()
+            .rev()
+            .take_while(|tok| tok.kind.is_whitespace())
+            .count();
+
+    &toks[..len]
+}
+
+fn starts_with(toks: &[Token], s: &str) -> bool {
+    toks.len() >= s.len()
+        && toks
+            .iter()
+            .zip(s.chars())
+            .all(|(tok, c)| tok.kind.eq_ignore_ascii_case(&c))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatementKind {
+    Normal,
+    LoudComment,
+}
+
+struct Statement {
+    kind: StatementKind,
+    indent: usize,
+    toks: Vec<Token>,
+}
+
+/// Remove a trailing silent comment from `toks`, additionally returning the
+/// nesting depth of parentheses, brackets and braces at the end of the line
+fn strip_silent_comment(toks: &[Token], mut depth: isize) -> (&[Token], isize) {
+    let mut quote = None;
+    let mut iter = toks.iter().enumerate().peekable();
+
+    while let Some((idx, tok)) = iter.next() {
+        match (quote, tok.kind) {
+            (Some(..), '\\') => {
+                iter.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(..), _) => {}
+            (None, '"' | '\'') => quote = Some(tok.kind),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')' | ']' | '}') => depth -= 1,
+            (None, '/') if depth <= 0 => {
+                if let Some((_, Token { kind: '/', .. })) = iter.peek() {
+                    return (trim_end(&toks[..idx]), depth);
+                }
+            }
+            (None, _) => {}
+        }
+    }
+
+    (toks, depth)
+}
+
+/// Group lines into statements, joining lines that continue the previous one
+/// and dropping silent comments
+fn group_statements(lines: &[Line]) -> Vec<Statement> {
+    let mut statements = Vec::new();
+    let mut idx = 0;
+
+    while idx < lines.len() {
+        let line = &lines[idx];
+        idx += 1;
+
+        if line.content.is_empty() {
+            continue;
+        }
+
+        // a comment consumes every line more indented than itself
+        let is_nested = |other: &Line| other.content.is_empty() || other.indent > line.indent;
+
+        if starts_with(line.content, "//") {
+            while idx < lines.len() && is_nested(&lines[idx]) {
+                idx += 1;
+            }
+            continue;
+        }
+
+        if starts_with(line.content, "/*") {
+            let mut toks = line.content.to_vec();
+
+            while idx < lines.len() && is_nested(&lines[idx]) {
+                toks.extend(lines[idx - 1].newline);
+                toks.extend_from_slice(trim_end(lines[idx].raw));
+                idx += 1;
+            }
+
+            toks.truncate(trim_end(&toks).len());
+
+            if !ends_with(&toks, "*/") {
+                let pos = toks.last().unwrap().pos;
+                toks.push(Token::new(pos, ' '));
+                toks.push(Token::new(pos, '*'));
+                toks.push(Token::new(pos, '/'));
+            }
+
+            statements.push(Statement {
+                kind: StatementKind::LoudComment,
+                indent: line.indent,
+                toks,
+            });
+            continue;
+        }
+
+        let (content, mut depth) = strip_silent_comment(line.content, 0);
+        let mut toks = content.to_vec();
+
+        // statements continue onto the next line inside of brackets or after a comma
+        while idx < lines.len() && (depth > 0 || toks.last().map_or(false, |tok| tok.kind == ',')) {
+            let next = &lines[idx];
+            idx += 1;
+
+            if next.content.is_empty() {
+                continue;
+            }
+
+            let (content, new_depth) = strip_silent_comment(next.content, depth);
+            depth = new_depth;
+
+            toks.push(Token::new(next.content[0].pos, '\n'));
+            toks.extend_from_slice(content);
+        }
+
+        if toks.is_empty() {
+            continue;
+        }
+
+        statements.push(Statement {
+            kind: StatementKind::Normal,
+            indent: line.indent,
+            toks,
+        });
+    }
+
+    statements
+}
+
+fn ends_with(toks: &[Token], s: &str) -> bool {
+    toks.len() >= s.len()
+        && toks[toks.len() - s.len()..]
+            .iter()
+            .zip(s.chars())
+            .all(|(tok, c)| tok.kind == c)
+}
+
+fn synthetic(s: &str, pos: Span) -> impl Iterator<Item = Token> + '_ {
+    s.chars().map(move |c| Token::new(pos, c))
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '-' || c == '\\' || !c.is_ascii()
+}
+
+/// Rewrite the syntax that is unique to the indented syntax into its SCSS
+/// equivalent
+fn rewrite_statement(toks: Vec<Token>, has_children: bool) -> Vec<Token> {
+    let first = toks[0];
+    let second = toks.get(1).map(|tok| tok.kind);
+
+    match first.kind {
+        // `=foo` declares the mixin `foo`
+        '=' => {
+            let rest = toks[1..].iter().skip_while(|tok| tok.kind.is_whitespace());
+            synthetic("@mixin ", first.pos)
+                .chain(rest.copied())
+                .collect()
+        }
+        // `+foo` includes the mixin `foo`
+        '+' if second.map_or(false, is_name_start) => synthetic("@include ", first.pos)
+            .chain(toks[1..].iter().copied())
+            .collect(),
+        // the old-style property syntax, `:name value`
+        ':' if !has_children && second.map_or(false, is_name_start) => {
+            let name_len = toks[1..]
+                .iter()
+                .take_while(|tok| !tok.kind.is_whitespace())
+                .count();
+
+            let name = &toks[1..=name_len];
+            let value = &toks[name_len + 1..];
+
+            if value.is_empty() || name.iter().any(|tok| tok.kind == ':') {
+                return toks;
+            }
+
+            name.iter()
+                .copied()
+                .chain(std::iter::once(Token::new(first.pos, ':')))
+                .chain(value.iter().copied())
+                .collect()
+        }
+        '@' if starts_with(&toks, "@import") => rewrite_import(toks),
+        _ => toks,
+    }
+}
+
+/// The indented syntax allows import urls to be unquoted
+fn rewrite_import(toks: Vec<Token>) -> Vec<Token> {
+    let keyword_len = "@import".len();
+    let mut out = toks[..keyword_len].to_vec();
+
+    let args = &toks[keyword_len..];
+
+    for (idx, arg) in args.split(|tok| tok.kind == ',').enumerate() {
+        if idx != 0 {
+            out.push(Token::new(arg.first().unwrap_or(&toks[0]).pos, ','));
+        }
+
+        let leading = arg
+            .iter()
+            .take_while(|tok| tok.kind.is_whitespace())
+            .count();
+        let (whitespace, url) = arg.split_at(leading);
+        let url = trim_end(url);
+
+        out.extend_from_slice(whitespace);
+
+        let is_quoted = matches!(
+            url.first(),
+            Some(Token {
+                kind: '"' | '\'',
+                ..
+            })
+        );
+
+        if url.is_empty() || is_quoted || starts_with(url, "url(") {
+            out.extend_from_slice(&arg[leading..]);
+            continue;
+        }
+
+        out.push(Token::new(url[0].pos, '"'));
+        for tok in url {
+            if tok.kind == '"' {
+                out.push(Token::new(tok.pos, '\\'));
+            }
+            out.push(*tok);
+        }
+        out.push(Token::new(url[url.len() - 1].pos, '"'));
+    }
+
+    out
+}
+
+/// Produce SCSS tokens, inferring blocks from indentation
+fn emit(statements: &[Statement], eof: Span) -> SassResult<Vec<Token>> {
+    let mut out = Vec::new();
+    let mut indentation = vec![0];
+
+    if let Some(first) = statements.first() {
+        if first.indent != 0 {
+            return Err((
+                "Indenting at the beginning of the document is illegal.",
+                first.toks[0].pos,
+            )
+                .into());
+        }
+    }
+
+    for (idx, statement) in statements.iter().enumerate() {
+        let pos = statement.toks[0].pos;
+
+        while statement.indent < *indentation.last().unwrap() {
+            indentation.pop();
+            out.push(Token::new(pos, '}'));
+            out.push(Token::new(pos, '\n'));
+        }
+
+        if statement.indent != *indentation.last().unwrap() {
+            return Err(("Inconsistent indentation.", pos).into());
+        }
+
+        let next_indent = statements.get(idx + 1).map(|next| next.indent);
+        let has_children = next_indent.map_or(false, |indent| indent > statement.indent);
+
+        match statement.kind {
+            StatementKind::LoudComment => {
+                if has_children {
+                    return Err(("Nothing may be indented beneath a comment.", pos).into());
+                }
+
+                out.extend_from_slice(&statement.toks);
+            }
+            StatementKind::Normal => {
+                let toks = rewrite_statement(statement.toks.clone(), has_children);
+                let last = toks.last().unwrap().pos;
+
+                out.extend(toks);
+
+                if has_children {
+                    out.push(Token::new(last, '{'));
+                    indentation.push(next_indent.unwrap());
+                } else {
+                    out.push(Token::new(last, ';'));
+                }
+            }
+        }
+
+        out.push(Token::new(pos, '\n'));
+    }
+
+    for _ in 1..indentation.len() {
+        out.push(Token::new(eof, '}'));
+    }
+
+    Ok(out)
+}
@@ -2,7 +2,7 @@ use std::{borrow::Cow, iter::Peekable, str::Chars, sync::Arc};
 
 use codemap::File;
 
-use crate::Token;
+use crate::{error::SassResult, indented::lex_indented_file, Token};
 
 const FORM_FEED: char = '\x0C';
 
@@ -118,6 +118,15 @@ impl<'a> Lexer<'a> {
         Self::new(buf)
     }
 
+    /// Lex `file`, translating it from the indented syntax if `indented` is set
+    pub fn new_from_file_with_syntax(file: &Arc<File>, indented: bool) -> SassResult<Self> {
+        if indented {
+            lex_indented_file(file)
+        } else {
+            Ok(Self::new_from_file(file))
+        }
+    }
+
     pub fn new(buf: Vec<Token>) -> Self {
         Lexer {
             buf: Cow::Owned(buf),
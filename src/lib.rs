@@ -97,7 +97,7 @@ grass input.scss
 #![cfg_attr(feature = "nightly", feature(track_caller))]
 #![cfg_attr(feature = "profiling", inline(never))]
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[cfg(feature = "wasm-exports")]
 use wasm_bindgen::prelude::*;
@@ -108,9 +108,18 @@ use codemap::CodeMap;
 
 pub use crate::error::{SassError as Error, SassResult as Result};
 pub use crate::fs::{Fs, NullFs, StdFs};
+pub use crate::importer::{Importer, ImporterResult, Syntax};
+pub use crate::logger::{Logger, NullLogger, SourceLocation, StackFrame, StdLogger};
+pub use crate::repl::Repl;
+pub use crate::source_map::SourceMapUrls;
 pub(crate) use crate::token::Token;
+pub use crate::value::sass_value::{
+    ListSeparator, SassCalculation, SassColor, SassFunctionRef, SassMixinRef, SassNumber, SassValue,
+};
 use crate::{
     builtin::modules::{ModuleConfig, Modules},
+    custom_function::CustomFunction,
+    indented::is_indented_path,
     lexer::Lexer,
     output::{AtRuleContext, Css},
     parse::{
@@ -126,14 +135,20 @@ mod atrule;
 mod builtin;
 mod color;
 mod common;
+mod custom_function;
 mod error;
 mod fs;
+mod importer;
+mod indented;
 mod interner;
 mod lexer;
+mod logger;
 mod output;
 mod parse;
+mod repl;
 mod scope;
 mod selector;
+mod source_map;
 mod style;
 mod token;
 mod unit;
@@ -160,11 +175,19 @@ pub enum OutputStyle {
 #[derive(Debug)]
 pub struct Options<'a> {
     fs: &'a dyn Fs,
+    logger: &'a dyn Logger,
     style: OutputStyle,
     load_paths: Vec<&'a Path>,
+    importers: Vec<&'a dyn Importer>,
+    functions: Vec<CustomFunction<'a>>,
     allows_charset: bool,
     unicode_error_messages: bool,
     quiet: bool,
+    source_map: bool,
+    source_map_urls: SourceMapUrls,
+    source_map_path: Option<&'a Path>,
+    embed_sources: bool,
+    indented_syntax: bool,
 }
 
 impl Default for Options<'_> {
@@ -172,11 +195,19 @@ impl Default for Options<'_> {
     fn default() -> Self {
         Self {
             fs: &StdFs,
+            logger: &StdLogger,
             style: OutputStyle::Expanded,
             load_paths: Vec::new(),
+            importers: Vec::new(),
+            functions: Vec::new(),
             allows_charset: true,
             unicode_error_messages: true,
             quiet: false,
+            source_map: false,
+            source_map_urls: SourceMapUrls::Relative,
+            source_map_path: None,
+            embed_sources: false,
+            indented_syntax: false,
         }
     }
 }
@@ -193,6 +224,17 @@ impl<'a> Options<'a> {
         self
     }
 
+    /// This option allows you to capture the messages emitted by `@warn`
+    /// and `@debug` rules, as well as deprecation warnings.
+    ///
+    /// By default, it uses [`StdLogger`], which writes messages to stderr.
+    #[must_use]
+    #[inline]
+    pub fn logger(mut self, logger: &'a dyn Logger) -> Self {
+        self.logger = logger;
+        self
+    }
+
     /// `grass` currently offers 2 different output styles
     ///
     ///  - `OutputStyle::Expanded` writes each selector and declaration on its own line.
@@ -253,6 +295,63 @@ impl<'a> Options<'a> {
         self
     }
 
+    /// Add a custom [`Importer`] used to load stylesheets referenced by
+    /// `@use`, `@forward`, `@import` and `meta.load-css()`
+    ///
+    /// Importers are consulted in the order they are added, after looking
+    /// for a file relative to the importing stylesheet and before searching
+    /// the load paths.
+    #[must_use]
+    #[inline]
+    pub fn importer(mut self, importer: &'a dyn Importer) -> Self {
+        self.importers.push(importer);
+        self
+    }
+
+    /// Register a function implemented in Rust that may be called from Sass
+    ///
+    /// `signature` declares the name and arguments of the function using
+    /// the same syntax as `@function`, for example `asset-url($path, $hash: true)`.
+    /// The callback receives one [`SassValue`] for each argument in the
+    /// signature, in order, with defaults already applied. A variadic argument
+    /// is passed as a comma-separated list.
+    ///
+    /// Returning `Err` aborts compilation, using the string as the error message.
+    ///
+    /// Custom functions take precedence over global builtin functions of the
+    /// same name, but not over functions defined in Sass using `@function`.
+    ///
+    /// ```
+    /// use grass::{SassNumber, SassValue};
+    ///
+    /// fn main() -> Result<(), Box<grass::Error>> {
+    ///     let double = |args: &[SassValue]| match &args[0] {
+    ///         SassValue::Number(n) => Ok(SassValue::Number(SassNumber::with_unit(
+    ///             n.value() * 2.0,
+    ///             &n.unit(),
+    ///         ))),
+    ///         _ => Err("$n: expected a number.".to_owned()),
+    ///     };
+    ///     let options = grass::Options::default().add_function("double($n)", &double);
+    ///     let css = grass::from_string("a { width: double(4px); }".to_string(), &options)?;
+    ///     assert_eq!(css, "a {\n  width: 8px;\n}\n");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn add_function(
+        mut self,
+        signature: &'a str,
+        callback: &'a dyn Fn(&[SassValue]) -> std::result::Result<SassValue, String>,
+    ) -> Self {
+        self.functions.push(CustomFunction {
+            signature,
+            callback,
+        });
+        self
+    }
+
     /// This flag tells Sass whether to emit a `@charset`
     /// declaration or a UTF-8 byte-order mark.
     ///
@@ -281,25 +380,160 @@ impl<'a> Options<'a> {
         self
     }
 
+    /// This flag tells Sass whether to generate a [source map](https://sourcemaps.info/spec.html)
+    /// alongside the compiled CSS. Source maps are only returned from
+    /// [`compile_path`] and [`compile_string`].
+    ///
+    /// By default, no source map is generated.
+    #[must_use]
+    #[inline]
+    pub const fn source_map(mut self, source_map: bool) -> Self {
+        self.source_map = source_map;
+        self
+    }
+
+    /// Controls how the generated source map links to source files
+    ///
+    ///  - `SourceMapUrls::Relative` uses paths relative to the location of the source map
+    ///  - `SourceMapUrls::Absolute` uses absolute `file:` URLs
+    ///
+    /// By default, relative URLs are used.
+    #[must_use]
+    #[inline]
+    pub const fn source_map_urls(mut self, source_map_urls: SourceMapUrls) -> Self {
+        self.source_map_urls = source_map_urls;
+        self
+    }
+
+    /// The path the source map will be written to. Relative URLs in the
+    /// source map are resolved against the directory containing this path.
+    ///
+    /// By default, URLs are made relative to the current working directory.
+    #[must_use]
+    #[inline]
+    pub const fn source_map_path(mut self, path: &'a Path) -> Self {
+        self.source_map_path = Some(path);
+        self
+    }
+
+    /// This flag tells Sass to embed the full contents of every
+    /// source file in the generated source map.
+    ///
+    /// By default, this value is `false` and sources are only linked.
+    #[must_use]
+    #[inline]
+    pub const fn embed_sources(mut self, embed_sources: bool) -> Self {
+        self.embed_sources = embed_sources;
+        self
+    }
+
+    /// This flag tells Sass to parse the input to [`from_string`] and
+    /// [`compile_string`] using the indented syntax, rather than SCSS.
+    ///
+    /// Files loaded from disk, such as the input to [`from_path`] or imported
+    /// files, always use the indented syntax if their extension is `.sass`.
+    ///
+    /// By default, this value is `false` and input is parsed as SCSS.
+    #[must_use]
+    #[inline]
+    pub const fn indented_syntax(mut self, indented_syntax: bool) -> Self {
+        self.indented_syntax = indented_syntax;
+        self
+    }
+
     pub(crate) fn is_compressed(&self) -> bool {
         matches!(self.style, OutputStyle::Compressed)
     }
 }
 
 fn raw_to_parse_error(map: &CodeMap, err: Error, unicode: bool) -> Box<Error> {
-    let (message, span) = err.raw();
-    Box::new(Error::from_loc(message, map.look_up_span(span), unicode))
+    match err.raw() {
+        Ok((message, span)) => Box::new(Error::from_loc(message, map.look_up_span(span), unicode)),
+        Err(err) => Box::new(err),
+    }
+}
+
+/// The output of [`compile_path`] or [`compile_string`]
+#[derive(Debug, Clone)]
+pub struct CompileResult {
+    css: String,
+    source_map: Option<String>,
+    loaded_files: Vec<PathBuf>,
 }
 
-fn from_string_with_file_name(input: String, file_name: &str, options: &Options) -> Result<String> {
+impl CompileResult {
+    /// The compiled CSS
+    #[must_use]
+    #[inline]
+    pub fn css(&self) -> &str {
+        &self.css
+    }
+
+    /// A [source map](https://sourcemaps.info/spec.html) in JSON form, if
+    /// one was requested using [`Options::source_map`]
+    #[must_use]
+    #[inline]
+    pub fn source_map(&self) -> Option<&str> {
+        self.source_map.as_deref()
+    }
+
+    /// Every stylesheet that was loaded during compilation, in the order they
+    /// were first loaded, including the input file passed to [`compile_path`].
+    ///
+    /// Files read from disk are listed by their resolved path, including those
+    /// loaded by `@use`, `@forward`, `@import` and `meta.load-css()`.
+    /// Stylesheets loaded by an [`Importer`] are listed by their canonical URL.
+    /// Plain CSS imports, such as `@import "foo.css"`, are not loaded and so
+    /// are not listed.
+    #[must_use]
+    #[inline]
+    pub fn loaded_files(&self) -> &[PathBuf] {
+        &self.loaded_files
+    }
+
+    /// Consume this result, returning the compiled CSS
+    #[must_use]
+    #[inline]
+    pub fn into_css(self) -> String {
+        self.css
+    }
+}
+
+fn compile_with_file_name(
+    input: String,
+    file_name: &str,
+    input_is_file: bool,
+    indented: bool,
+    options: &Options,
+) -> Result<CompileResult> {
     let mut map = CodeMap::new();
     let file = map.add_file(file_name.to_owned(), input);
     let empty_span = file.span.subspan(0, 0);
 
+    let mut toks = Lexer::new_from_file_with_syntax(&file, indented)
+        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+
+    let custom_functions = custom_function::parse_signatures(&mut map, options)
+        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+
+    // the entry point is a dependency too, unless it was passed as a string
+    let mut loaded_files = if input_is_file {
+        vec![PathBuf::from(file_name)]
+    } else {
+        Vec::new()
+    };
+
+    let mut extender = Extender::new(empty_span);
+
     let stmts = Parser {
-        toks: &mut Lexer::new_from_file(&file),
+        toks: &mut toks,
         map: &mut map,
-        path: file_name.as_ref(),
+        // input that was not read from disk has no path to resolve imports against
+        path: if input_is_file {
+            file_name.as_ref()
+        } else {
+            Path::new("")
+        },
         scopes: &mut Scopes::new(),
         global_scope: &mut Scope::new(),
         super_selectors: &mut NeverEmptyVec::new(ExtendedSelector::new(SelectorList::new(
@@ -310,19 +544,54 @@ fn from_string_with_file_name(input: String, file_name: &str, options: &Options)
         flags: ContextFlags::empty(),
         at_root: true,
         at_root_has_selector: false,
-        extender: &mut Extender::new(empty_span),
+        extender: &mut extender,
+        media_queries: &mut None,
         content_scopes: &mut Scopes::new(),
         options,
+        custom_functions: &custom_functions,
+        loaded_files: &mut loaded_files,
+        call_stack: &mut Vec::new(),
+        importer: None,
         modules: &mut Modules::default(),
         module_config: &mut ModuleConfig::default(),
     }
     .parse()
     .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
 
-    Css::from_stmts(stmts, AtRuleContext::None, options.allows_charset)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?
-        .pretty_print(&map, options.style)
-        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))
+    extender
+        .check_unsatisfied_extensions()
+        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+
+    let css = Css::from_stmts(stmts, AtRuleContext::None, options.allows_charset)
+        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+
+    if !options.source_map {
+        return Ok(CompileResult {
+            css: css
+                .pretty_print(&map, options.style)
+                .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?,
+            source_map: None,
+            loaded_files,
+        });
+    }
+
+    let (css, mappings) = css
+        .pretty_print_with_mappings(&map, options.style)
+        .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+
+    let source_map = source_map::build_source_map(
+        &css,
+        &mappings,
+        &map,
+        if input_is_file { None } else { Some(file_name) },
+        options,
+    );
+
+    Ok(CompileResult {
+        css,
+        source_map: Some(source_map),
+        loaded_files,
+    })
 }
 
 /// Compile CSS from a path
@@ -338,11 +607,7 @@ fn from_string_with_file_name(input: String, file_name: &str, options: &Options)
 #[cfg_attr(feature = "profiling", inline(never))]
 #[cfg_attr(not(feature = "profiling"), inline)]
 pub fn from_path(p: &str, options: &Options) -> Result<String> {
-    from_string_with_file_name(
-        String::from_utf8(options.fs.read(Path::new(p))?)?,
-        p,
-        options,
-    )
+    compile_path(p, options).map(CompileResult::into_css)
 }
 
 /// Compile CSS from a string
@@ -357,7 +622,48 @@ pub fn from_path(p: &str, options: &Options) -> Result<String> {
 #[cfg_attr(feature = "profiling", inline(never))]
 #[cfg_attr(not(feature = "profiling"), inline)]
 pub fn from_string(input: String, options: &Options) -> Result<String> {
-    from_string_with_file_name(input, "stdin", options)
+    compile_string(input, options).map(CompileResult::into_css)
+}
+
+/// Compile CSS from a path, returning a [`CompileResult`] that may
+/// additionally contain a source map
+///
+/// ```
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let options = grass::Options::default().source_map(true);
+///     let result = grass::compile_path("input.scss", &options)?;
+///     let source_map = result.source_map();
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+pub fn compile_path(p: &str, options: &Options) -> Result<CompileResult> {
+    compile_with_file_name(
+        String::from_utf8(options.fs.read(Path::new(p))?)?,
+        p,
+        true,
+        is_indented_path(Path::new(p)),
+        options,
+    )
+}
+
+/// Compile CSS from a string, returning a [`CompileResult`] that may
+/// additionally contain a source map
+///
+/// ```
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let options = grass::Options::default().source_map(true);
+///     let result = grass::compile_string("a { color: red; }".to_string(), &options)?;
+///     assert_eq!(result.css(), "a {\n  color: red;\n}\n");
+///     assert!(result.source_map().is_some());
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(feature = "profiling", inline(never))]
+#[cfg_attr(not(feature = "profiling"), inline)]
+pub fn compile_string(input: String, options: &Options) -> Result<CompileResult> {
+    compile_with_file_name(input, "stdin", false, options.indented_syntax, options)
 }
 
 #[cfg(feature = "wasm-exports")]
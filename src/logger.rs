@@ -0,0 +1,189 @@
+use codemap::SpanLoc;
+
+/// The location in a stylesheet that a message refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    file: String,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    stack_trace: Vec<StackFrame>,
+}
+
+impl SourceLocation {
+    pub(crate) fn from_span_loc(loc: &SpanLoc, stack_trace: Vec<StackFrame>) -> Self {
+        Self {
+            file: loc.file.name().to_owned(),
+            line: loc.begin.line + 1,
+            column: loc.begin.column + 1,
+            end_line: loc.end.line + 1,
+            end_column: loc.end.column + 1,
+            stack_trace,
+        }
+    }
+
+    /// The name of the file containing this location. This is the path of
+    /// files loaded from disk, the canonical URL of stylesheets loaded by an
+    /// [`Importer`](crate::Importer), or `stdin` for input passed as a string.
+    #[must_use]
+    #[inline]
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// The line on which this location begins, starting at `1`
+    #[must_use]
+    #[inline]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column at which this location begins, in characters and starting at `1`
+    #[must_use]
+    #[inline]
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The line on which this location ends, starting at `1`
+    #[must_use]
+    #[inline]
+    pub const fn end_line(&self) -> usize {
+        self.end_line
+    }
+
+    /// The column at which this location ends, in characters and starting at `1`
+    #[must_use]
+    #[inline]
+    pub const fn end_column(&self) -> usize {
+        self.end_column
+    }
+
+    /// The functions and mixins being evaluated when the message was
+    /// emitted, starting with the innermost, whose location is this location,
+    /// and ending with the root stylesheet
+    #[must_use]
+    #[inline]
+    pub fn stack_trace(&self) -> &[StackFrame] {
+        &self.stack_trace
+    }
+}
+
+/// A single frame of the stack trace of a [`SourceLocation`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackFrame {
+    file: String,
+    line: usize,
+    column: usize,
+    member: String,
+}
+
+impl StackFrame {
+    pub(crate) fn from_span_loc(loc: &SpanLoc, member: String) -> Self {
+        Self {
+            file: loc.file.name().to_owned(),
+            line: loc.begin.line + 1,
+            column: loc.begin.column + 1,
+            member,
+        }
+    }
+
+    /// The name of the file being evaluated in this frame
+    #[must_use]
+    #[inline]
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// The line being evaluated in this frame, starting at `1`
+    #[must_use]
+    #[inline]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column being evaluated in this frame, in characters and starting at `1`
+    #[must_use]
+    #[inline]
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The function or mixin being evaluated, such as `foo()`, or
+    /// `root stylesheet` outside of any function or mixin
+    #[must_use]
+    #[inline]
+    pub fn member(&self) -> &str {
+        &self.member
+    }
+}
+
+/// A trait to allow capturing the messages emitted during compilation.
+///
+/// Messages are not passed to the logger at all if [`Options::quiet`](crate::Options::quiet)
+/// is set.
+pub trait Logger: std::fmt::Debug {
+    /// Called for each `@warn` rule encountered, as well as for uses of
+    /// deprecated features, in which case `deprecation` is `true`
+    fn warn(&self, message: &str, location: &SourceLocation, deprecation: bool);
+    /// Called for each `@debug` rule encountered
+    fn debug(&self, message: &str, location: &SourceLocation);
+}
+
+/// Write messages to stderr.
+///
+/// This is the default logger implementation.
+#[derive(Debug)]
+pub struct StdLogger;
+
+impl Logger for StdLogger {
+    #[inline]
+    fn warn(&self, message: &str, location: &SourceLocation, deprecation: bool) {
+        let frames: Vec<(String, &str)> = location
+            .stack_trace()
+            .iter()
+            .map(|frame| {
+                (
+                    format!("{} {}:{}", frame.file(), frame.line(), frame.column()),
+                    frame.member(),
+                )
+            })
+            .collect();
+
+        let width = frames.iter().map(|(loc, _)| loc.len()).max().unwrap_or(0);
+
+        let mut trace = String::new();
+        for (loc, member) in frames {
+            trace.push_str(&format!("\n    {:width$}  {}", loc, member, width = width));
+        }
+
+        eprintln!(
+            "{}: {}{}",
+            if deprecation {
+                "DEPRECATION WARNING"
+            } else {
+                "Warning"
+            },
+            message,
+            trace
+        );
+    }
+
+    #[inline]
+    fn debug(&self, message: &str, location: &SourceLocation) {
+        eprintln!("{}:{} DEBUG: {}", location.file(), location.line(), message);
+    }
+}
+
+/// A logger that discards every message.
+#[derive(Debug)]
+pub struct NullLogger;
+
+impl Logger for NullLogger {
+    #[inline]
+    fn warn(&self, _message: &str, _location: &SourceLocation, _deprecation: bool) {}
+
+    #[inline]
+    fn debug(&self, _message: &str, _location: &SourceLocation) {}
+}
@@ -1,12 +1,20 @@
 use std::{
+    cell::RefCell,
+    collections::HashMap,
+    ffi::OsStr,
     fs::OpenOptions,
     io::{stdin, stdout, BufWriter, Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
 };
 
 use clap::{arg_enum, App, AppSettings, Arg};
 
-use grass::{from_path, from_string, Options, OutputStyle};
+use grass::{compile_path, compile_string, CompileResult, Fs, Options, OutputStyle, Repl, StdFs};
+
+/// How often watched files are checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
 
 // TODO remove this
 arg_enum! {
@@ -40,7 +48,6 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("INDENTED")
                 .long("indented")
-                .hidden(true)
                 .help("Use the indented syntax for input from stdin"),
         )
         .arg(
@@ -72,8 +79,8 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("UPDATE")
                 .long("update")
-                .hidden(true)
-                .help("Only compile out-of-date stylesheets."),
+                .help("Only compile out-of-date stylesheets.")
+                .conflicts_with("STDIN"),
         )
         .arg(
             Arg::with_name("NO_ERROR_CSS")
@@ -85,13 +92,11 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("NO_SOURCE_MAP")
                 .long("no-source-map")
-                .hidden(true)
                 .help("Whether to generate source maps."),
         )
         .arg(
             Arg::with_name("SOURCE_MAP_URLS")
                 .long("source-map-urls")
-                .hidden(true)
                 .help("How to link from source maps to source files.")
                 .default_value("relative")
                 .case_insensitive(true)
@@ -101,41 +106,38 @@ fn main() -> std::io::Result<()> {
         .arg(
             Arg::with_name("EMBED_SOURCES")
                 .long("embed-sources")
-                .hidden(true)
                 .help("Embed source file contents in source maps."),
         )
         .arg(
             Arg::with_name("EMBED_SOURCE_MAP")
                 .long("embed-source-map")
-                .hidden(true)
                 .help("Embed source map contents in CSS."),
         )
         // Other
         .arg(
             Arg::with_name("WATCH")
+                .short("w")
                 .long("watch")
-                .hidden(true)
-                .help("Watch stylesheets and recompile when they change."),
+                .help("Watch stylesheets and recompile when they change.")
+                .conflicts_with("STDIN"),
         )
         .arg(
             Arg::with_name("POLL")
                 .long("poll")
-                .hidden(true)
-                .help("Manually check for changes rather than using a native watcher. Only valid with --watch.")
+                .help("Compare the contents of files to detect changes rather than their modification times. Only valid with --watch.")
                 .requires("WATCH"),
         )
         .arg(
             Arg::with_name("NO_STOP_ON_ERROR")
                 .long("no-stop-on-error")
-                .hidden(true)
                 .help("Continue to compile more files after error is encountered.")
         )
         .arg(
             Arg::with_name("INTERACTIVE")
                 .short("i")
                 .long("interactive")
-                .hidden(true)
                 .help("Run an interactive SassScript shell.")
+                .conflicts_with_all(&["STDIN", "WATCH", "UPDATE"])
         )
         .arg(
             Arg::with_name("NO_COLOR")
@@ -157,12 +159,10 @@ fn main() -> std::io::Result<()> {
         )
         .arg(
             Arg::with_name("INPUT")
-                .required_unless("STDIN")
-                .help("SCSS files"),
-        )
-        .arg(
-            Arg::with_name("OUTPUT")
-                .help("Output SCSS file")
+                .required_unless_one(&["STDIN", "INTERACTIVE"])
+                .conflicts_with("INTERACTIVE")
+                .multiple(true)
+                .help("An input file and optional output file, or any number of input:output pairs of files or directories"),
         )
 
         // Hidden, legacy arguments
@@ -184,15 +184,563 @@ fn main() -> std::io::Result<()> {
         _ => unreachable!(),
     };
 
-    let options = &Options::default()
-        .load_paths(&load_paths)
-        .style(style)
-        .quiet(matches.is_present("QUIET"))
-        .unicode_error_messages(!matches.is_present("NO_UNICODE"))
-        .allows_charset(!matches.is_present("NO_CHARSET"));
+    let source_map_urls = match &matches.value_of("SOURCE_MAP_URLS").unwrap().to_lowercase() as &str
+    {
+        "relative" => grass::SourceMapUrls::Relative,
+        "absolute" => grass::SourceMapUrls::Absolute,
+        _ => unreachable!(),
+    };
+
+    if matches.is_present("INTERACTIVE") {
+        let options = Options::default()
+            .load_paths(&load_paths)
+            .style(style)
+            .quiet(matches.is_present("QUIET"))
+            .unicode_error_messages(!matches.is_present("NO_UNICODE"));
+        return interactive(&options);
+    }
+
+    let inputs: Vec<&str> = matches
+        .values_of("INPUT")
+        .map_or_else(Vec::new, Iterator::collect);
+
+    let targets = parse_targets(&inputs, matches.is_present("STDIN")).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1)
+    });
+
+    let stdin_input = if matches.is_present("STDIN") {
+        let mut buffer = String::new();
+        stdin().read_to_string(&mut buffer)?;
+        Some(buffer)
+    } else {
+        None
+    };
+
+    let embed_source_map = matches.is_present("EMBED_SOURCE_MAP");
+    let update = matches.is_present("UPDATE");
+    let watching = matches.is_present("WATCH");
+
+    // progress messages would be noise when compiling a single stylesheet once
+    let report_progress = watching || update || targets.len() > 1;
+
+    let compile_target = |target: &Target| -> std::io::Result<Compiled> {
+        let fs = RecordingFs::default();
+
+        // when writing to stdout, there is nowhere to put a separate source map file
+        let source_map =
+            !matches.is_present("NO_SOURCE_MAP") && (target.output.is_some() || embed_source_map);
+
+        let mut options = Options::default()
+            .fs(&fs)
+            .load_paths(&load_paths)
+            .style(style)
+            .quiet(matches.is_present("QUIET"))
+            .unicode_error_messages(!matches.is_present("NO_UNICODE"))
+            .allows_charset(!matches.is_present("NO_CHARSET"))
+            .source_map(source_map)
+            .source_map_urls(source_map_urls)
+            .embed_sources(matches.is_present("EMBED_SOURCES"))
+            .indented_syntax(matches.is_present("INDENTED"));
+
+        if let Some(path) = &target.source_map_path {
+            options = options.source_map_path(path);
+        }
+
+        let result = match (&target.input, &stdin_input) {
+            (Some(path), _) => compile_path(&path.to_string_lossy(), &options),
+            (None, Some(input)) => compile_string(input.clone(), &options),
+            (None, None) => unreachable!(),
+        };
+
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("{}", e);
+                return Ok(Compiled {
+                    succeeded: false,
+                    loaded_files: fs.read.into_inner(),
+                });
+            }
+        };
+
+        let compiled = Compiled {
+            succeeded: true,
+            loaded_files: result.loaded_files().to_vec(),
+        };
+
+        write_output(&result, target, embed_source_map)?;
+
+        if report_progress {
+            match &target.output {
+                Some(output) => println!(
+                    "Compiled {} to {}.",
+                    target.name(),
+                    output.to_string_lossy()
+                ),
+                None => eprintln!("Compiled {}.", target.name()),
+            }
+        }
+
+        Ok(compiled)
+    };
+
+    let compile = |target: &Target| {
+        if update {
+            compile_out_of_date(target, &load_paths, &compile_target)
+        } else {
+            compile_target(target)
+        }
+    };
+
+    if watching {
+        let detect: &dyn Fn(&Path) -> Option<FileState> = if matches.is_present("POLL") {
+            &|path| StdFs.read(path).ok().map(FileState::Contents)
+        } else {
+            &|path| {
+                std::fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .map(FileState::Modified)
+            }
+        };
+        // directories are expanded again on every check, so that new
+        // stylesheets are picked up
+        let find_targets = || parse_targets(&inputs, false);
+        watch(targets, &find_targets, &compile, detect);
+    }
+
+    let mut failed = false;
+
+    for target in &targets {
+        if !compile(target)?.succeeded {
+            failed = true;
+
+            if !matches.is_present("NO_STOP_ON_ERROR") {
+                break;
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Read SassScript from stdin line by line, printing the value of each
+/// expression until the input ends
+fn interactive(options: &Options) -> std::io::Result<()> {
+    let mut repl = Repl::new(options).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1)
+    });
+
+    let mut line = String::new();
+
+    loop {
+        print!(">> ");
+        stdout().flush()?;
+
+        line.clear();
+        if stdin().read_line(&mut line)? == 0 {
+            println!();
+            return Ok(());
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match repl.evaluate(line.trim_end()) {
+            Ok(Some(value)) => println!("{}", value),
+            Ok(None) => {}
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+/// A stylesheet to compile, along with where to write the result
+struct Target {
+    /// The stylesheet to compile, or `None` if it is read from stdin
+    input: Option<PathBuf>,
+    /// Where to write the CSS, or `None` to write it to stdout
+    output: Option<PathBuf>,
+    source_map_path: Option<PathBuf>,
+}
+
+impl Target {
+    fn new(input: Option<PathBuf>, output: Option<PathBuf>) -> Self {
+        let source_map_path = output.as_ref().map(|path| {
+            let mut path = path.clone().into_os_string();
+            path.push(".map");
+            PathBuf::from(path)
+        });
+
+        Self {
+            input,
+            output,
+            source_map_path,
+        }
+    }
+
+    fn name(&self) -> String {
+        self.input.as_ref().map_or_else(
+            || "stdin".to_owned(),
+            |path| path.to_string_lossy().into_owned(),
+        )
+    }
+
+    /// The stylesheets this target depends on, if its output file was written
+    /// after every one of them was last modified
+    ///
+    /// Returns `None` if the target is out of date, or if its dependencies
+    /// can't be determined without compiling it.
+    fn up_to_date_dependencies(&self, load_paths: &[&Path]) -> Option<Vec<PathBuf>> {
+        let dependencies = static_dependencies(self.input.as_deref()?, load_paths)?;
+
+        if self.is_newer_than(&dependencies) {
+            Some(dependencies)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the output file was written after every one of `files` was
+    /// last modified
+    fn is_newer_than(&self, loaded_files: &[PathBuf]) -> bool {
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        let output_modified = match self.output.as_deref().and_then(modified) {
+            Some(time) => time,
+            None => return false,
+        };
+
+        loaded_files
+            .iter()
+            .all(|path| modified(path).map_or(false, |time| time <= output_modified))
+    }
+}
+
+/// Determine the stylesheets to compile from the positional arguments, which
+/// are either an input and optional output, or any number of `input:output`
+/// pairs
+fn parse_targets(args: &[&str], from_stdin: bool) -> Result<Vec<Target>, String> {
+    if from_stdin {
+        return match args {
+            [] => Ok(vec![Target::new(None, None)]),
+            [output] => Ok(vec![Target::new(None, Some(PathBuf::from(output)))]),
+            _ => Err("Only one argument may be passed with --stdin.".to_owned()),
+        };
+    }
+
+    if !args.iter().any(|arg| split_pair(arg).is_some()) {
+        return match args {
+            [input] => Ok(vec![Target::new(Some(PathBuf::from(input)), None)]),
+            [input, output] => Ok(vec![Target::new(
+                Some(PathBuf::from(input)),
+                Some(PathBuf::from(output)),
+            )]),
+            _ => Err("Only two positional arguments may be passed.".to_owned()),
+        };
+    }
+
+    let mut targets = Vec::new();
+
+    for arg in args {
+        let (input, output) = match split_pair(arg) {
+            Some(pair) => pair,
+            None => {
+                return Err(format!(
+                    "Positional and \":\" arguments may not both be used, but got \"{}\".",
+                    arg
+                ))
+            }
+        };
+
+        let (input, output) = (Path::new(input), Path::new(output));
+
+        if input.is_dir() {
+            directory_targets(input, output, &mut targets)
+                .map_err(|e| format!("{}: {}", input.to_string_lossy(), e))?;
+        } else {
+            targets.push(Target::new(
+                Some(input.to_path_buf()),
+                Some(output.to_path_buf()),
+            ));
+        }
+    }
+
+    Ok(targets)
+}
+
+/// Split an `input:output` argument, ignoring the colon of a Windows drive
+/// letter at the start of the input
+fn split_pair(arg: &str) -> Option<(&str, &str)> {
+    let bytes = arg.as_bytes();
+    let start = match bytes {
+        [letter, b':', b'/', ..] | [letter, b':', b'\\', ..] if letter.is_ascii_alphabetic() => 2,
+        _ => 0,
+    };
+
+    let idx = start + arg[start..].find(':')?;
+
+    Some((&arg[..idx], &arg[idx + 1..]))
+}
+
+/// Add a target for every stylesheet in `input` and its subdirectories,
+/// skipping partials, to be written to the same relative path in `output`
+fn directory_targets(
+    input: &Path,
+    output: &Path,
+    targets: &mut Vec<Target>,
+) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(input)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<PathBuf>>>()?;
+
+    entries.sort();
+
+    for path in entries {
+        let name = match path.file_name() {
+            Some(name) => name,
+            None => continue,
+        };
+
+        if path.is_dir() {
+            directory_targets(&path, &output.join(name), targets)?;
+            continue;
+        }
+
+        let is_stylesheet = matches!(
+            path.extension().and_then(OsStr::to_str),
+            Some("scss") | Some("sass")
+        );
+
+        if is_stylesheet && !name.to_string_lossy().starts_with('_') {
+            let output = output.join(name).with_extension("css");
+            targets.push(Target::new(Some(path), Some(output)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Compile `target` unless its output is newer than every stylesheet it
+/// depends on, as for `--update`
+fn compile_out_of_date(
+    target: &Target,
+    load_paths: &[&Path],
+    compile: &dyn Fn(&Target) -> std::io::Result<Compiled>,
+) -> std::io::Result<Compiled> {
+    match target.up_to_date_dependencies(load_paths) {
+        Some(loaded_files) => Ok(Compiled {
+            succeeded: true,
+            loaded_files,
+        }),
+        None => compile(target),
+    }
+}
+
+/// Find `path` and every stylesheet it loads through `@use`, `@forward` and
+/// `@import` rules, without compiling it
+///
+/// Returns `None` if any of the urls can't be determined statically or can't
+/// be found on disk, in which case the stylesheet must be compiled to know
+/// what it depends on.
+fn static_dependencies(path: &Path, load_paths: &[&Path]) -> Option<Vec<PathBuf>> {
+    let mut found = vec![path.to_path_buf()];
+    let mut idx = 0;
+
+    while idx < found.len() {
+        let contents = std::fs::read_to_string(&found[idx]).ok()?;
+
+        for url in import_urls(&contents)? {
+            if url.starts_with("sass:") || is_plain_css_url(&url) {
+                continue;
+            }
+
+            let dependency = resolve_import(&found[idx], &url, load_paths)?;
+
+            if !found.contains(&dependency) {
+                found.push(dependency);
+            }
+        }
+
+        idx += 1;
+    }
+
+    Some(found)
+}
+
+fn is_plain_css_url(url: &str) -> bool {
+    let lower = url.to_ascii_lowercase();
+
+    lower.ends_with(".css")
+        || lower.starts_with("http://")
+        || lower.starts_with("https://")
+        || lower.starts_with("//")
+}
+
+/// The urls of the `@use`, `@forward` and `@import` rules in `contents`,
+/// skipping comments and strings
+///
+/// Returns `None` if a url isn't a plain quoted string, such as one containing
+/// interpolation or an unquoted `@import` in the indented syntax.
+fn import_urls(contents: &str) -> Option<Vec<String>> {
+    let bytes = contents.as_bytes();
+    let mut urls = Vec::new();
+    let mut idx = 0;
+
+    // the end of the quoted string starting at `start`
+    let string_end = |start: usize| -> usize {
+        let quote = bytes[start];
+        let mut end = start + 1;
+        while end < bytes.len() && bytes[end] != quote {
+            end += if bytes[end] == b'\\' { 2 } else { 1 };
+        }
+        end.min(bytes.len())
+    };
+
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'/' if bytes.get(idx + 1) == Some(&b'/') => {
+                idx = contents[idx..]
+                    .find('\n')
+                    .map_or(bytes.len(), |end| idx + end);
+            }
+            b'/' if bytes.get(idx + 1) == Some(&b'*') => {
+                idx = contents[idx + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| idx + end + 4);
+            }
+            b'"' | b'\'' => idx = string_end(idx) + 1,
+            b'@' => {
+                let name_end = contents[idx + 1..]
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+                    .map_or(bytes.len(), |end| idx + 1 + end);
+                let rule = &contents[idx + 1..name_end];
+                idx = name_end;
+
+                if !matches!(rule, "use" | "forward" | "import") {
+                    continue;
+                }
+
+                loop {
+                    idx += contents[idx..]
+                        .find(|c: char| !c.is_whitespace())
+                        .unwrap_or(bytes.len() - idx);
+
+                    match bytes.get(idx) {
+                        Some(b'"') | Some(b'\'') => {
+                            let end = string_end(idx);
+                            let url = contents.get(idx + 1..end)?;
+
+                            if url.contains('\\') || url.contains("#{") {
+                                return None;
+                            }
+
+                            urls.push(url.to_owned());
+                            idx = end + 1;
+                        }
+                        // a plain css import
+                        _ if rule == "import" && contents[idx..].starts_with("url(") => {}
+                        _ => return None,
+                    }
+
+                    if rule != "import" {
+                        break;
+                    }
+
+                    idx += contents[idx..]
+                        .find(|c: char| !c.is_whitespace() || c == '\n')
+                        .unwrap_or(bytes.len() - idx);
+
+                    if bytes.get(idx) != Some(&b',') {
+                        break;
+                    }
+
+                    idx += 1;
+                }
+            }
+            _ => idx += 1,
+        }
+    }
+
+    Some(urls)
+}
+
+/// Find the stylesheet `url` refers to, relative to the stylesheet at `from`
+/// or within one of the load paths
+fn resolve_import(from: &Path, url: &str, load_paths: &[&Path]) -> Option<PathBuf> {
+    let dir = from.parent().unwrap_or_else(|| Path::new(""));
+
+    std::iter::once(dir)
+        .chain(load_paths.iter().copied())
+        .find_map(|dir| find_stylesheet(&dir.join(url)))
+}
+
+/// Try each of the paths Sass looks for a stylesheet at: with each extension,
+/// as a partial and as an index file
+fn find_stylesheet(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+
+    let mut candidates = Vec::new();
+
+    for ext in ["scss", "sass"] {
+        candidates.push(path.with_file_name(format!("{}.{}", name, ext)));
+        candidates.push(path.with_file_name(format!("_{}.{}", name, ext)));
+    }
+
+    candidates.push(path.to_path_buf());
+    candidates.push(path.with_file_name(format!("_{}", name)));
+
+    for ext in ["scss", "sass"] {
+        candidates.push(path.join(format!("index.{}", ext)));
+        candidates.push(path.join(format!("_index.{}", ext)));
+    }
+
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+/// Write the compiled CSS to the target's output file, or to stdout if it has
+/// none, along with its source map
+fn write_output(
+    result: &CompileResult,
+    target: &Target,
+    embed_source_map: bool,
+) -> std::io::Result<()> {
+    let mut css = result.css().to_owned();
+
+    if let Some(path) = target.output.as_deref().and_then(Path::parent) {
+        if !path.as_os_str().is_empty() {
+            std::fs::create_dir_all(path)?;
+        }
+    }
+
+    if let Some(map) = result.source_map() {
+        let url = if embed_source_map {
+            format!(
+                "data:application/json;charset=utf-8;base64,{}",
+                base64_encode(map.as_bytes())
+            )
+        } else {
+            let map_path = target.source_map_path.as_deref().unwrap();
+            std::fs::write(map_path, map)?;
+
+            map_path.file_name().unwrap().to_string_lossy().into_owned()
+        };
+
+        if !css.is_empty() && !css.ends_with('\n') {
+            css.push('\n');
+        }
+
+        css.push_str(&format!("\n/*# sourceMappingURL={} */\n", url));
+    }
 
     let (mut stdout_write, mut file_write);
-    let buf_out: &mut dyn Write = if let Some(path) = matches.value_of("OUTPUT") {
+    let buf_out: &mut dyn Write = if let Some(path) = &target.output {
         file_write = BufWriter::new(
             OpenOptions::new()
                 .create(true)
@@ -206,26 +754,636 @@ fn main() -> std::io::Result<()> {
         &mut stdout_write
     };
 
-    buf_out.write_all(
-        if let Some(name) = matches.value_of("INPUT") {
-            from_path(name, options)
-        } else if matches.is_present("STDIN") {
-            from_string(
-                {
-                    let mut buffer = String::new();
-                    stdin().read_to_string(&mut buffer)?;
-                    buffer
-                },
-                options,
-            )
+    buf_out.write_all(css.as_bytes())?;
+    buf_out.flush()
+}
+
+/// The outcome of compiling a target
+struct Compiled {
+    succeeded: bool,
+    /// The files loaded by the compilation, including those read before an
+    /// error was encountered
+    loaded_files: Vec<PathBuf>,
+}
+
+/// A file system which records every file read from disk, so that the
+/// stylesheets loaded before a compilation error can be watched
+#[derive(Debug, Default)]
+struct RecordingFs {
+    read: RefCell<Vec<PathBuf>>,
+}
+
+impl Fs for RecordingFs {
+    fn is_dir(&self, path: &Path) -> bool {
+        StdFs.is_dir(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        StdFs.is_file(path)
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<Vec<u8>> {
+        self.read.borrow_mut().push(path.to_path_buf());
+        StdFs.read(path)
+    }
+}
+
+/// The state of a watched file, used to detect changes
+#[derive(Debug, PartialEq)]
+enum FileState {
+    Modified(SystemTime),
+    Contents(Vec<u8>),
+}
+
+/// A target being watched, along with the files it depends on and their state
+/// when it was last compiled
+struct Watched {
+    target: Target,
+    files: Vec<PathBuf>,
+    states: Vec<Option<FileState>>,
+}
+
+/// Compile each target, and then recompile it whenever it or any of the
+/// stylesheets it loads change.
+///
+/// After an error, the files loaded before the error are watched in addition
+/// to the files from the last successful compilation. `find_targets` is called
+/// before each check, so that stylesheets added to a watched directory are
+/// compiled.
+fn watch(
+    targets: Vec<Target>,
+    find_targets: &dyn Fn() -> Result<Vec<Target>, String>,
+    compile: &dyn Fn(&Target) -> std::io::Result<Compiled>,
+    detect: &dyn Fn(&Path) -> Option<FileState>,
+) -> ! {
+    let mut watched = Vec::new();
+    update_targets(&mut watched, targets, compile, detect);
+
+    loop {
+        thread::sleep(WATCH_INTERVAL);
+
+        match find_targets() {
+            Ok(targets) => update_targets(&mut watched, targets, compile, detect),
+            Err(e) => eprintln!("error: {}", e),
+        }
+
+        check_for_changes(&mut watched, compile, detect);
+    }
+}
+
+/// Stop watching targets which are no longer in `targets`, and compile those
+/// which are new for the first time
+fn update_targets(
+    watched: &mut Vec<Watched>,
+    targets: Vec<Target>,
+    compile: &dyn Fn(&Target) -> std::io::Result<Compiled>,
+    detect: &dyn Fn(&Path) -> Option<FileState>,
+) {
+    watched.retain(|entry| {
+        targets
+            .iter()
+            .any(|target| target.input == entry.target.input)
+    });
+
+    for target in targets {
+        if watched
+            .iter()
+            .any(|entry| entry.target.input == target.input)
+        {
+            continue;
+        }
+
+        let mut files: Vec<PathBuf> = target.input.iter().cloned().collect();
+        let states = recompile(&target, &mut files, compile, detect);
+
+        watched.push(Watched {
+            target,
+            files,
+            states,
+        });
+    }
+}
+
+/// Recompile every target with a watched file that has changed since it was
+/// last compiled
+fn check_for_changes(
+    watched: &mut [Watched],
+    compile: &dyn Fn(&Target) -> std::io::Result<Compiled>,
+    detect: &dyn Fn(&Path) -> Option<FileState>,
+) {
+    for entry in watched {
+        let current: Vec<Option<FileState>> = entry.files.iter().map(|path| detect(path)).collect();
+
+        entry.states = if current == entry.states {
+            current
         } else {
-            unreachable!()
-        }
-        .unwrap_or_else(|e| {
-            eprintln!("{}", e);
-            std::process::exit(1)
-        })
-        .as_bytes(),
-    )?;
-    Ok(())
+            recompile(&entry.target, &mut entry.files, compile, detect)
+        };
+    }
+}
+
+/// Compile `target`, updating the files it depends on, and return the state
+/// of those files
+///
+/// IO errors, such as failing to write the output, are reported without
+/// stopping the watcher. The files are left as they were, so that the target is
+/// compiled again once one of them changes.
+fn recompile(
+    target: &Target,
+    files: &mut Vec<PathBuf>,
+    compile: &dyn Fn(&Target) -> std::io::Result<Compiled>,
+    detect: &dyn Fn(&Path) -> Option<FileState>,
+) -> Vec<Option<FileState>> {
+    // take the state of each file before compiling, so that changes made
+    // during compilation trigger another compilation
+    let mut before: HashMap<PathBuf, Option<FileState>> = files
+        .iter()
+        .map(|path| (path.clone(), detect(path)))
+        .collect();
+
+    let compiled = match compile(target) {
+        Ok(compiled) => compiled,
+        Err(e) => {
+            eprintln!("Error: {}: {}", target.name(), e);
+            Compiled {
+                succeeded: false,
+                loaded_files: Vec::new(),
+            }
+        }
+    };
+
+    if compiled.succeeded {
+        *files = compiled.loaded_files;
+    } else {
+        // the error may be in a stylesheet that has never compiled successfully
+        for path in compiled.loaded_files {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    files
+        .iter()
+        .map(|path| before.remove(path).unwrap_or_else(|| detect(path)))
+        .collect()
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        out.push(CHARS[(n >> 18) as usize & 63] as char);
+        out.push(CHARS[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 {
+            CHARS[(n >> 6) as usize & 63] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            CHARS[n as usize & 63] as char
+        } else {
+            '='
+        });
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        path::{Path, PathBuf},
+        time::{Duration, SystemTime},
+    };
+
+    use super::{
+        check_for_changes, compile_out_of_date, directory_targets, import_urls, parse_targets,
+        recompile, split_pair, static_dependencies, update_targets, Compiled, FileState, Target,
+    };
+
+    /// A fake file system, where each file has a version which is bumped to
+    /// simulate a change
+    #[derive(Default)]
+    struct Files(RefCell<HashMap<PathBuf, u64>>);
+
+    impl Files {
+        fn touch(&self, path: &str) {
+            *self.0.borrow_mut().entry(PathBuf::from(path)).or_default() += 1;
+        }
+
+        fn detect(&self, path: &Path) -> Option<FileState> {
+            self.0.borrow().get(path).map(|version| {
+                FileState::Modified(SystemTime::UNIX_EPOCH + Duration::from_secs(*version))
+            })
+        }
+    }
+
+    fn compiled(succeeded: bool, loaded_files: &[&str]) -> Compiled {
+        Compiled {
+            succeeded,
+            loaded_files: loaded_files.iter().map(PathBuf::from).collect(),
+        }
+    }
+
+    fn target() -> Target {
+        Target::new(Some(PathBuf::from("a.scss")), Some(PathBuf::from("a.css")))
+    }
+
+    #[test]
+    fn recompile_replaces_files_after_success() {
+        let files = Files::default();
+        files.touch("a.scss");
+        files.touch("_p.scss");
+
+        let mut watched = vec![PathBuf::from("a.scss"), PathBuf::from("_old.scss")];
+
+        let states = recompile(
+            &target(),
+            &mut watched,
+            &|_| Ok(compiled(true, &["a.scss", "_p.scss"])),
+            &|path| files.detect(path),
+        );
+
+        assert_eq!(
+            watched,
+            vec![PathBuf::from("a.scss"), PathBuf::from("_p.scss")]
+        );
+        assert_eq!(states.len(), 2);
+        assert!(states.iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn recompile_keeps_files_after_error() {
+        let files = Files::default();
+
+        let mut watched = vec![PathBuf::from("a.scss"), PathBuf::from("_old.scss")];
+
+        recompile(
+            &target(),
+            &mut watched,
+            &|_| Ok(compiled(false, &["a.scss", "_p.scss"])),
+            &|path| files.detect(path),
+        );
+
+        assert_eq!(
+            watched,
+            vec![
+                PathBuf::from("a.scss"),
+                PathBuf::from("_old.scss"),
+                PathBuf::from("_p.scss")
+            ]
+        );
+    }
+
+    #[test]
+    fn recompile_keeps_files_after_io_error() {
+        let files = Files::default();
+        files.touch("a.scss");
+        files.touch("_p.scss");
+
+        let mut watched = vec![PathBuf::from("a.scss"), PathBuf::from("_p.scss")];
+
+        let states = recompile(
+            &target(),
+            &mut watched,
+            &|_| {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "read-only output",
+                ))
+            },
+            &|path| files.detect(path),
+        );
+
+        assert_eq!(
+            watched,
+            vec![PathBuf::from("a.scss"), PathBuf::from("_p.scss")]
+        );
+        assert_eq!(
+            states,
+            vec![
+                files.detect(Path::new("a.scss")),
+                files.detect(Path::new("_p.scss"))
+            ]
+        );
+    }
+
+    #[test]
+    fn watch_continues_after_io_error() {
+        let files = Files::default();
+        files.touch("a.scss");
+
+        let calls = Cell::new(0);
+        let compile = |_: &Target| {
+            calls.set(calls.get() + 1);
+            if calls.get() == 1 {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "output directory removed",
+                ))
+            } else {
+                Ok(compiled(true, &["a.scss"]))
+            }
+        };
+        let detect = |path: &Path| files.detect(path);
+
+        let mut watched = Vec::new();
+        update_targets(&mut watched, vec![target()], &compile, &detect);
+        assert_eq!(calls.get(), 1);
+
+        check_for_changes(&mut watched, &compile, &detect);
+        assert_eq!(calls.get(), 1);
+
+        files.touch("a.scss");
+        check_for_changes(&mut watched, &compile, &detect);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn watch_recompiles_when_partial_fixed_after_initial_error() {
+        let files = Files::default();
+        files.touch("a.scss");
+        files.touch("_p.scss");
+
+        let calls = Cell::new(0);
+        let compile = |_: &Target| {
+            calls.set(calls.get() + 1);
+            // the partial is broken until it is changed
+            Ok(compiled(calls.get() > 1, &["a.scss", "_p.scss"]))
+        };
+        let detect = |path: &Path| files.detect(path);
+
+        let mut watched = Vec::new();
+        update_targets(&mut watched, vec![target()], &compile, &detect);
+        assert_eq!(calls.get(), 1);
+
+        check_for_changes(&mut watched, &compile, &detect);
+        assert_eq!(calls.get(), 1);
+
+        files.touch("_p.scss");
+        check_for_changes(&mut watched, &compile, &detect);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn watch_ignores_files_no_longer_loaded() {
+        let files = Files::default();
+        files.touch("a.scss");
+        files.touch("_p.scss");
+
+        let calls = Cell::new(0);
+        let compile = |_: &Target| {
+            calls.set(calls.get() + 1);
+            Ok(compiled(true, &["a.scss"]))
+        };
+        let detect = |path: &Path| files.detect(path);
+
+        let mut watched = Vec::new();
+        update_targets(&mut watched, vec![target()], &compile, &detect);
+
+        files.touch("_p.scss");
+        check_for_changes(&mut watched, &compile, &detect);
+        assert_eq!(calls.get(), 1);
+
+        files.touch("a.scss");
+        check_for_changes(&mut watched, &compile, &detect);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn watch_compiles_new_targets_and_drops_removed_ones() {
+        let files = Files::default();
+
+        let compiled_targets = RefCell::new(Vec::new());
+        let compile = |target: &Target| {
+            compiled_targets.borrow_mut().push(target.name());
+            Ok(compiled(true, &[]))
+        };
+        let detect = |path: &Path| files.detect(path);
+
+        let b = || Target::new(Some(PathBuf::from("b.scss")), Some(PathBuf::from("b.css")));
+
+        let mut watched = Vec::new();
+        update_targets(&mut watched, vec![target()], &compile, &detect);
+        update_targets(&mut watched, vec![target(), b()], &compile, &detect);
+        assert_eq!(*compiled_targets.borrow(), vec!["a.scss", "b.scss"]);
+
+        update_targets(&mut watched, vec![b()], &compile, &detect);
+        assert_eq!(watched.len(), 1);
+        assert_eq!(watched[0].target.name(), "b.scss");
+        assert_eq!(compiled_targets.borrow().len(), 2);
+    }
+
+    #[test]
+    fn split_pair_splits_on_first_colon() {
+        assert_eq!(split_pair("a.scss:a.css"), Some(("a.scss", "a.css")));
+        assert_eq!(split_pair("in:out:put"), Some(("in", "out:put")));
+        assert_eq!(split_pair("a.scss"), None);
+    }
+
+    #[test]
+    fn split_pair_ignores_drive_letter() {
+        assert_eq!(
+            split_pair("C:\\in\\a.scss:D:/out/a.css"),
+            Some(("C:\\in\\a.scss", "D:/out/a.css"))
+        );
+        assert_eq!(split_pair("C:/a.scss"), None);
+    }
+
+    fn paths(targets: &[Target]) -> Vec<(Option<PathBuf>, Option<PathBuf>)> {
+        targets
+            .iter()
+            .map(|target| (target.input.clone(), target.output.clone()))
+            .collect()
+    }
+
+    fn pair(input: &str, output: &str) -> (Option<PathBuf>, Option<PathBuf>) {
+        (Some(PathBuf::from(input)), Some(PathBuf::from(output)))
+    }
+
+    #[test]
+    fn parse_targets_positional() {
+        assert_eq!(
+            paths(&parse_targets(&["a.scss"], false).unwrap()),
+            vec![(Some(PathBuf::from("a.scss")), None)]
+        );
+        assert_eq!(
+            paths(&parse_targets(&["a.scss", "a.css"], false).unwrap()),
+            vec![pair("a.scss", "a.css")]
+        );
+        assert!(parse_targets(&["a.scss", "b.scss", "c.scss"], false).is_err());
+    }
+
+    #[test]
+    fn parse_targets_stdin() {
+        assert_eq!(
+            paths(&parse_targets(&[], true).unwrap()),
+            vec![(None, None)]
+        );
+        assert_eq!(
+            paths(&parse_targets(&["a.css"], true).unwrap()),
+            vec![(None, Some(PathBuf::from("a.css")))]
+        );
+        assert!(parse_targets(&["a.scss", "a.css"], true).is_err());
+    }
+
+    #[test]
+    fn parse_targets_pairs() {
+        assert_eq!(
+            paths(&parse_targets(&["a.scss:a.css", "b.scss:b.css"], false).unwrap()),
+            vec![pair("a.scss", "a.css"), pair("b.scss", "b.css")]
+        );
+        assert_eq!(
+            parse_targets(&["a.scss:a.css", "b.scss"], false).err(),
+            Some(
+                "Positional and \":\" arguments may not both be used, but got \"b.scss\"."
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn directory_targets_skips_partials_and_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("in");
+        let output = dir.path().join("out");
+
+        std::fs::create_dir_all(input.join("nested")).unwrap();
+        for name in [
+            "a.scss",
+            "b.sass",
+            "_partial.scss",
+            "notes.txt",
+            "nested/c.scss",
+        ] {
+            std::fs::write(input.join(name), "").unwrap();
+        }
+
+        let mut targets = Vec::new();
+        directory_targets(&input, &output, &mut targets).unwrap();
+
+        assert_eq!(
+            paths(&targets),
+            vec![
+                (Some(input.join("a.scss")), Some(output.join("a.css"))),
+                (Some(input.join("b.sass")), Some(output.join("b.css"))),
+                (
+                    Some(input.join("nested").join("c.scss")),
+                    Some(output.join("nested").join("c.css"))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_targets_expands_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("in");
+        std::fs::create_dir(&input).unwrap();
+        std::fs::write(input.join("a.scss"), "").unwrap();
+
+        let arg = format!("{}:out", input.to_string_lossy());
+        let targets = parse_targets(&[&arg], false).unwrap();
+        assert_eq!(
+            paths(&targets),
+            vec![(Some(input.join("a.scss")), Some(PathBuf::from("out/a.css")))]
+        );
+
+        // a stylesheet added later is found when the directory is expanded again
+        std::fs::write(input.join("b.scss"), "").unwrap();
+        assert_eq!(parse_targets(&[&arg], false).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn import_urls_skips_comments_and_strings() {
+        assert_eq!(
+            import_urls(
+                "// @use \"a\";\n/* @import \"b\"; */\n$c: \"@use 'c'\";\n@use \"d\" as e;\n@forward 'f';\n@import \"g\", \"h\", url(i);\n"
+            ),
+            Some(vec![
+                "d".to_owned(),
+                "f".to_owned(),
+                "g".to_owned(),
+                "h".to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn import_urls_with_interpolation_are_unknown() {
+        assert_eq!(import_urls("@import \"#{$a}\";"), None);
+        assert_eq!(import_urls("@import a"), None);
+    }
+
+    /// Write `contents` to `path`, last modified `secs` after the epoch
+    fn write_at(path: &Path, contents: &str, secs: u64) {
+        std::fs::write(path, contents).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn static_dependencies_follow_partials() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("a.scss");
+        std::fs::write(&input, "@use \"sass:math\";\n@use \"b\";").unwrap();
+        std::fs::write(dir.path().join("_b.scss"), "@import \"c\", \"d.css\";").unwrap();
+        std::fs::create_dir(dir.path().join("c")).unwrap();
+        std::fs::write(dir.path().join("c").join("_index.scss"), "").unwrap();
+
+        assert_eq!(
+            static_dependencies(&input, &[]),
+            Some(vec![
+                input.clone(),
+                dir.path().join("_b.scss"),
+                dir.path().join("c").join("_index.scss")
+            ])
+        );
+
+        std::fs::write(&input, "@use \"missing\";").unwrap();
+        assert_eq!(static_dependencies(&input, &[]), None);
+    }
+
+    #[test]
+    fn update_does_not_compile_up_to_date_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("a.scss");
+        let output = dir.path().join("a.css");
+        write_at(&input, "@use \"b\";", 1);
+        write_at(&dir.path().join("_b.scss"), "", 2);
+        write_at(&output, "", 3);
+
+        let target = Target::new(Some(input), Some(output));
+        let calls = Cell::new(0);
+        let compile = |_: &Target| {
+            calls.set(calls.get() + 1);
+            Ok(compiled(true, &[]))
+        };
+
+        let result = compile_out_of_date(&target, &[], &compile).unwrap();
+        assert_eq!(calls.get(), 0);
+        assert!(result.succeeded);
+        assert_eq!(result.loaded_files.len(), 2);
+
+        // a dependency changing makes the target out of date
+        write_at(&dir.path().join("_b.scss"), "", 4);
+        compile_out_of_date(&target, &[], &compile).unwrap();
+        assert_eq!(calls.get(), 1);
+    }
 }
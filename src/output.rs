@@ -1,13 +1,13 @@
 //! # Convert from SCSS AST to CSS
 use std::{io::Write, mem};
 
-use codemap::CodeMap;
+use codemap::{CodeMap, Span};
 
 use crate::{
     atrule::{
         keyframes::{Keyframes, KeyframesRuleSet, KeyframesSelector},
-        media::MediaRule,
-        SupportsRule, UnknownAtRule,
+        media::{MediaQuery, MediaRule},
+        AtRootQuery, SupportsRule, UnknownAtRule,
     },
     error::SassResult,
     parse::Stmt,
@@ -44,7 +44,7 @@ enum Toplevel {
     Keyframes(Box<Keyframes>),
     KeyframesRuleSet(Vec<KeyframesSelector>, Vec<BlockEntry>),
     Media {
-        query: String,
+        queries: Vec<MediaQuery>,
         body: Vec<Stmt>,
         inside_rule: bool,
         is_group_end: bool,
@@ -65,7 +65,10 @@ impl Toplevel {
     pub fn is_invisible(&self) -> bool {
         match self {
             Toplevel::RuleSet { selector, body, .. } => selector.is_empty() || body.is_empty(),
-            Toplevel::Media { body, .. } => body.is_empty(),
+            Toplevel::Media { body, .. } => body.iter().all(is_invisible_stmt),
+            Toplevel::Supports { body, .. } => {
+                !body.is_empty() && body.iter().all(is_invisible_stmt)
+            }
             Toplevel::Empty => true,
             _ => false,
         }
@@ -90,6 +93,151 @@ impl Toplevel {
     }
 }
 
+/// Whether `stmt` is known to produce no output, such as a style rule
+/// without any styles
+fn is_invisible_stmt(stmt: &Stmt) -> bool {
+    match stmt {
+        Stmt::RuleSet { body, .. } | Stmt::AtRoot { body, .. } => {
+            body.iter().all(is_invisible_stmt)
+        }
+        Stmt::Media(media) => media.body.iter().all(is_invisible_stmt),
+        Stmt::Supports(supports) => {
+            !supports.body.is_empty() && supports.body.iter().all(is_invisible_stmt)
+        }
+        Stmt::Style(style) => style.value.is_null(),
+        _ => false,
+    }
+}
+
+/// Split the body of a `@media` rule around the `@media` rules nested inside
+/// it, as CSS doesn't allow them to be nested.
+///
+/// The queries of each nested rule are merged with `queries`, and the rule is
+/// emitted between the parts of the body before and after it. Nested rules
+/// that can never match are removed, and those whose queries can't be merged
+/// are left in place.
+fn merge_nested_media(
+    queries: &[MediaQuery],
+    body: Vec<Stmt>,
+) -> Vec<(Vec<MediaQuery>, Vec<Stmt>)> {
+    let mut parts = vec![(queries.to_vec(), Vec::new())];
+
+    for stmt in body {
+        let (stmt, nested) = match stmt {
+            Stmt::Media(media) => (None, vec![*media]),
+            Stmt::RuleSet { selector, mut body } => {
+                let nested = take_nested_media(&mut body);
+                (Some(Stmt::RuleSet { selector, body }), nested)
+            }
+            Stmt::AtRoot { query, mut body } => {
+                let nested = take_nested_media(&mut body);
+                (Some(Stmt::AtRoot { query, body }), nested)
+            }
+            Stmt::Supports(mut supports) => {
+                merge_media_in_place(queries, &mut supports.body);
+                (Some(Stmt::Supports(supports)), Vec::new())
+            }
+            Stmt::UnknownAtRule(mut unknown) => {
+                merge_media_in_place(queries, &mut unknown.body);
+                (Some(Stmt::UnknownAtRule(unknown)), Vec::new())
+            }
+            stmt => (Some(stmt), Vec::new()),
+        };
+
+        if let Some(stmt) = stmt {
+            parts.last_mut().unwrap().1.push(stmt);
+        }
+
+        for media in nested {
+            match MediaQuery::merge_lists(queries, &media.queries) {
+                Some(merged) if merged.is_empty() => {}
+                Some(merged) => {
+                    parts.push((merged, media.body));
+                    parts.push((queries.to_vec(), Vec::new()));
+                }
+                None => parts
+                    .last_mut()
+                    .unwrap()
+                    .1
+                    .push(Stmt::Media(Box::new(media))),
+            }
+        }
+    }
+
+    parts.retain(|(_, body)| !body.is_empty());
+
+    parts
+}
+
+/// Remove the `@media` rules nested in `body` through style rules, which are
+/// emitted after the style rule containing them
+fn take_nested_media(body: &mut Vec<Stmt>) -> Vec<MediaRule> {
+    let mut nested = Vec::new();
+
+    for stmt in mem::take(body) {
+        match stmt {
+            Stmt::Media(media) => nested.push(*media),
+            Stmt::RuleSet {
+                selector,
+                body: mut inner,
+            } => {
+                nested.append(&mut take_nested_media(&mut inner));
+                body.push(Stmt::RuleSet {
+                    selector,
+                    body: inner,
+                });
+            }
+            Stmt::AtRoot {
+                query,
+                body: mut inner,
+            } => {
+                nested.append(&mut take_nested_media(&mut inner));
+                body.push(Stmt::AtRoot { query, body: inner });
+            }
+            stmt => body.push(stmt),
+        }
+    }
+
+    nested
+}
+
+/// Merge `queries` into the `@media` rules nested in other at-rules within a
+/// `@media` rule. These stay where they are, since the at-rule between them
+/// keeps them inside the outer rule.
+fn merge_media_in_place(queries: &[MediaQuery], body: &mut Vec<Stmt>) {
+    body.retain_mut(|stmt| match stmt {
+        Stmt::Media(media) => match MediaQuery::merge_lists(queries, &media.queries) {
+            Some(merged) if merged.is_empty() => false,
+            Some(merged) => {
+                media.queries = merged;
+                true
+            }
+            None => true,
+        },
+        Stmt::RuleSet { body, .. } | Stmt::AtRoot { body, .. } => {
+            merge_media_in_place(queries, body);
+            true
+        }
+        Stmt::Supports(supports) => {
+            merge_media_in_place(queries, &mut supports.body);
+            true
+        }
+        Stmt::UnknownAtRule(unknown) => {
+            merge_media_in_place(queries, &mut unknown.body);
+            true
+        }
+        _ => true,
+    });
+}
+
+fn join_media_queries(queries: &[MediaQuery], separator: &str) -> String {
+    queries
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
 fn set_group_end(group: &mut [Toplevel]) {
     match group.last_mut() {
         Some(Toplevel::RuleSet { is_group_end, .. })
@@ -102,6 +250,84 @@ fn set_group_end(group: &mut [Toplevel]) {
     }
 }
 
+/// Remove the `@at-root` rules in `body` whose query `excludes` the at-rule
+/// containing `body`, so that they can be emitted after it instead.
+///
+/// At-rules nested between the `@at-root` rule and `body` that its query does
+/// not exclude are copied around the removed rule.
+fn extract_at_root(body: &mut Vec<Stmt>, excludes: &dyn Fn(&AtRootQuery) -> bool) -> Vec<Stmt> {
+    extract_at_root_with_query(body, excludes)
+        .into_iter()
+        .map(|(_, stmt)| stmt)
+        .collect()
+}
+
+/// Like `extract_at_root`, but also returns the query of each removed rule
+fn extract_at_root_with_query(
+    body: &mut Vec<Stmt>,
+    excludes: &dyn Fn(&AtRootQuery) -> bool,
+) -> Vec<(AtRootQuery, Stmt)> {
+    let mut hoisted = Vec::new();
+
+    for mut stmt in mem::take(body) {
+        let (name, inner) = match &mut stmt {
+            Stmt::AtRoot { query, .. } if excludes(query) => {
+                hoisted.push((query.clone(), stmt));
+                continue;
+            }
+            Stmt::AtRoot { body: inner, .. } | Stmt::RuleSet { body: inner, .. } => {
+                hoisted.append(&mut extract_at_root_with_query(inner, excludes));
+                body.push(stmt);
+                continue;
+            }
+            Stmt::Media(media) => ("media".to_owned(), &mut media.body),
+            Stmt::Supports(supports) => ("supports".to_owned(), &mut supports.body),
+            Stmt::UnknownAtRule(unknown) => (unknown.name.to_ascii_lowercase(), &mut unknown.body),
+            _ => {
+                body.push(stmt);
+                continue;
+            }
+        };
+
+        for (query, inner) in extract_at_root_with_query(inner, excludes) {
+            let inner = if query.excludes_name(&name) {
+                inner
+            } else {
+                copy_at_rule(&stmt, vec![inner])
+            };
+
+            hoisted.push((query, inner));
+        }
+
+        body.push(stmt);
+    }
+
+    hoisted
+}
+
+/// Copy the at-rule `stmt`, replacing its body
+fn copy_at_rule(stmt: &Stmt, body: Vec<Stmt>) -> Stmt {
+    match stmt {
+        Stmt::Media(media) => Stmt::Media(Box::new(MediaRule {
+            super_selector: media.super_selector.clone(),
+            queries: media.queries.clone(),
+            body,
+        })),
+        Stmt::Supports(supports) => Stmt::Supports(Box::new(SupportsRule {
+            params: supports.params.clone(),
+            body,
+        })),
+        Stmt::UnknownAtRule(unknown) => Stmt::UnknownAtRule(Box::new(UnknownAtRule {
+            name: unknown.name.clone(),
+            super_selector: unknown.super_selector.clone(),
+            params: unknown.params.clone(),
+            body,
+            has_body: true,
+        })),
+        _ => unreachable!("only at-rules may be copied"),
+    }
+}
+
 #[derive(Debug, Clone)]
 enum BlockEntry {
     Style(Style),
@@ -196,6 +422,18 @@ impl Css {
         Css::new(at_rule_context, allows_charset).parse_stylesheet(s)
     }
 
+    /// Parse statements that were hoisted out of an at-rule by `@at-root`,
+    /// which are emitted after it
+    fn parse_hoisted(&mut self, hoisted: Vec<Stmt>) -> SassResult<Vec<Toplevel>> {
+        let mut vals = Vec::new();
+
+        for stmt in hoisted {
+            vals.extend(self.parse_stmt(stmt)?);
+        }
+
+        Ok(vals)
+    }
+
     fn parse_stmt(&mut self, stmt: Stmt) -> SassResult<Vec<Toplevel>> {
         Ok(match stmt {
             Stmt::RuleSet { selector, body } => {
@@ -217,32 +455,45 @@ impl Css {
                         Stmt::Style(s) => vals.first_mut().unwrap().push_style(s),
                         Stmt::Comment(s) => vals.first_mut().unwrap().push_comment(s),
                         Stmt::Media(m) => {
-                            let MediaRule { query, body, .. } = *m;
-                            vals.push(Toplevel::Media {
-                                query,
-                                body,
-                                inside_rule: true,
-                                is_group_end: false,
-                            });
+                            let MediaRule {
+                                queries, mut body, ..
+                            } = *m;
+                            let hoisted = extract_at_root(&mut body, &|q| q.excludes_name("media"));
+                            for (queries, body) in merge_nested_media(&queries, body) {
+                                vals.push(Toplevel::Media {
+                                    queries,
+                                    body,
+                                    inside_rule: true,
+                                    is_group_end: false,
+                                });
+                            }
+                            vals.extend(self.parse_hoisted(hoisted)?);
                         }
                         Stmt::Supports(s) => {
-                            let SupportsRule { params, body } = *s;
+                            let SupportsRule { params, mut body } = *s;
+                            let hoisted =
+                                extract_at_root(&mut body, &|q| q.excludes_name("supports"));
                             vals.push(Toplevel::Supports {
                                 params,
                                 body,
                                 inside_rule: true,
                                 is_group_end: false,
                             });
+                            vals.extend(self.parse_hoisted(hoisted)?);
                         }
                         Stmt::UnknownAtRule(u) => {
                             let UnknownAtRule {
                                 params,
-                                body,
+                                mut body,
                                 name,
                                 has_body,
                                 ..
                             } = *u;
 
+                            let lowercase_name = name.to_ascii_lowercase();
+                            let hoisted =
+                                extract_at_root(&mut body, &|q| q.excludes_name(&lowercase_name));
+
                             let at_rule = ToplevelUnknownAtRule {
                                 name,
                                 params,
@@ -257,9 +508,11 @@ impl Css {
                             } else {
                                 vals.first_mut().unwrap().push_unknown_at_rule(at_rule);
                             }
+
+                            vals.extend(self.parse_hoisted(hoisted)?);
                         }
                         Stmt::Return(..) => unreachable!(),
-                        Stmt::AtRoot { body } => {
+                        Stmt::AtRoot { body, .. } => {
                             body.into_iter().try_for_each(|r| -> SassResult<()> {
                                 let mut stmts = self.parse_stmt(r)?;
 
@@ -292,42 +545,57 @@ impl Css {
             }
             Stmt::Style(s) => vec![Toplevel::Style(s)],
             Stmt::Media(m) => {
-                let MediaRule { query, body, .. } = *m;
-                vec![Toplevel::Media {
-                    query,
-                    body,
-                    inside_rule: false,
-                    is_group_end: false,
-                }]
+                let MediaRule {
+                    queries, mut body, ..
+                } = *m;
+                let hoisted = extract_at_root(&mut body, &|q| q.excludes_name("media"));
+                let mut vals: Vec<Toplevel> = merge_nested_media(&queries, body)
+                    .into_iter()
+                    .map(|(queries, body)| Toplevel::Media {
+                        queries,
+                        body,
+                        inside_rule: false,
+                        is_group_end: false,
+                    })
+                    .collect();
+                vals.extend(self.parse_hoisted(hoisted)?);
+                vals
             }
             Stmt::Supports(s) => {
-                let SupportsRule { params, body } = *s;
-                vec![Toplevel::Supports {
+                let SupportsRule { params, mut body } = *s;
+                let hoisted = extract_at_root(&mut body, &|q| q.excludes_name("supports"));
+                let mut vals = vec![Toplevel::Supports {
                     params,
                     body,
                     inside_rule: false,
                     is_group_end: false,
-                }]
+                }];
+                vals.extend(self.parse_hoisted(hoisted)?);
+                vals
             }
             Stmt::UnknownAtRule(u) => {
                 let UnknownAtRule {
                     params,
-                    body,
+                    mut body,
                     name,
                     has_body,
                     ..
                 } = *u;
-                vec![Toplevel::UnknownAtRule(Box::new(ToplevelUnknownAtRule {
+                let lowercase_name = name.to_ascii_lowercase();
+                let hoisted = extract_at_root(&mut body, &|q| q.excludes_name(&lowercase_name));
+                let mut vals = vec![Toplevel::UnknownAtRule(Box::new(ToplevelUnknownAtRule {
                     name,
                     params,
                     body,
                     has_body,
                     inside_rule: false,
                     is_group_end: false,
-                }))]
+                }))];
+                vals.extend(self.parse_hoisted(hoisted)?);
+                vals
             }
             Stmt::Return(..) => unreachable!("@return: {:?}", stmt),
-            Stmt::AtRoot { body } => body
+            Stmt::AtRoot { body, .. } => body
                 .into_iter()
                 .map(|r| self.parse_stmt(r))
                 .collect::<SassResult<Vec<Vec<Toplevel>>>>()?
@@ -370,32 +638,61 @@ impl Css {
     }
 
     pub fn pretty_print(self, map: &CodeMap, style: OutputStyle) -> SassResult<String> {
+        Ok(self.pretty_print_with_mappings(map, style)?.0)
+    }
+
+    /// Serialize this stylesheet, additionally returning the byte offsets into
+    /// the output at which each selector and declaration begins, alongside the
+    /// span that produced it
+    pub fn pretty_print_with_mappings(
+        self,
+        map: &CodeMap,
+        style: OutputStyle,
+    ) -> SassResult<(String, Vec<(usize, Span)>)> {
         let mut buf = Vec::new();
         let allows_charset = self.allows_charset;
-        match style {
+        let mut mappings = match style {
             OutputStyle::Compressed => {
-                CompressedFormatter::default().write_css(&mut buf, self, map)?;
+                let mut formatter = CompressedFormatter::default();
+                formatter.write_css(&mut buf, self, map)?;
+                formatter.mappings
             }
             OutputStyle::Expanded => {
-                ExpandedFormatter::default().write_css(&mut buf, self, map)?;
+                let mut formatter = ExpandedFormatter::default();
+                formatter.write_css(&mut buf, self, map)?;
 
                 if !buf.is_empty() {
                     writeln!(buf)?;
                 }
+
+                formatter.mappings
             }
-        }
+        };
 
         // TODO: check for this before writing
         let show_charset = allows_charset && buf.iter().any(|s| !s.is_ascii());
         let out = unsafe { String::from_utf8_unchecked(buf) };
-        Ok(if show_charset {
+        let out = if show_charset {
             match style {
                 OutputStyle::Compressed => format!("\u{FEFF}{}", out),
                 OutputStyle::Expanded => format!("@charset \"UTF-8\";\n{}", out),
             }
         } else {
             out
-        })
+        };
+
+        if show_charset {
+            let prefix_len = match style {
+                OutputStyle::Compressed => '\u{FEFF}'.len_utf8(),
+                OutputStyle::Expanded => "@charset \"UTF-8\";\n".len(),
+            };
+
+            for (offset, ..) in &mut mappings {
+                *offset += prefix_len;
+            }
+        }
+
+        Ok((out, mappings))
     }
 }
 
@@ -404,7 +701,9 @@ trait Formatter {
 }
 
 #[derive(Debug, Default)]
-struct CompressedFormatter {}
+struct CompressedFormatter {
+    mappings: Vec<(usize, Span)>,
+}
 
 impl Formatter for CompressedFormatter {
     fn write_css(&mut self, buf: &mut Vec<u8>, css: Css, map: &CodeMap) -> SassResult<()> {
@@ -415,6 +714,8 @@ impl Formatter for CompressedFormatter {
                         continue;
                     }
 
+                    self.mappings.push((buf.len(), selector.0.span));
+
                     let mut complexes = selector.0.components.iter().filter(|c| !c.is_invisible());
                     if let Some(complex) = complexes.next() {
                         self.write_complex(buf, complex)?;
@@ -490,6 +791,10 @@ impl Formatter for CompressedFormatter {
                     write!(buf, "}}")?;
                 }
                 Toplevel::Supports { params, body, .. } => {
+                    if !body.is_empty() && body.iter().all(is_invisible_stmt) {
+                        continue;
+                    }
+
                     if params.is_empty() {
                         write!(buf, "@supports")?;
                     } else {
@@ -506,19 +811,20 @@ impl Formatter for CompressedFormatter {
                     self.write_css(buf, css, map)?;
                     write!(buf, "}}")?;
                 }
-                Toplevel::Media { query, body, .. } => {
-                    if body.is_empty() {
+                Toplevel::Media { queries, body, .. } => {
+                    if body.iter().all(is_invisible_stmt) {
                         continue;
                     }
 
-                    write!(buf, "@media {}{{", query)?;
+                    write!(buf, "@media {}{{", join_media_queries(&queries, ","))?;
                     let css = Css::from_stmts(body, AtRuleContext::Media, css.allows_charset)?;
                     self.write_css(buf, css, map)?;
                     write!(buf, "}}")?;
                 }
                 Toplevel::Style(style) => {
                     let value = style.value.node.to_css_string(style.value.span, true)?;
-                    write!(buf, "{}:{};", style.property, value)?;
+                    self.mappings.push((buf.len(), style.property.span));
+                    write!(buf, "{}:{};", style.property.node, value)?;
                 }
             }
         }
@@ -542,14 +848,15 @@ impl CompressedFormatter {
         Ok(())
     }
 
-    fn write_block_entry(&self, buf: &mut Vec<u8>, styles: &[BlockEntry]) -> SassResult<()> {
+    fn write_block_entry(&mut self, buf: &mut Vec<u8>, styles: &[BlockEntry]) -> SassResult<()> {
         let mut styles = styles.iter();
 
         for style in &mut styles {
             match style {
                 BlockEntry::Style(s) => {
                     let value = s.value.node.to_css_string(s.value.span, true)?;
-                    write!(buf, "{}:{}", s.property, value)?;
+                    self.mappings.push((buf.len(), s.property.span));
+                    write!(buf, "{}:{}", s.property.node, value)?;
                     break;
                 }
                 BlockEntry::MultilineComment(..) => continue,
@@ -562,7 +869,9 @@ impl CompressedFormatter {
                 BlockEntry::Style(s) => {
                     let value = s.value.node.to_css_string(s.value.span, true)?;
 
-                    write!(buf, ";{}:{}", s.property, value)?;
+                    write!(buf, ";")?;
+                    self.mappings.push((buf.len(), s.property.span));
+                    write!(buf, "{}:{}", s.property.node, value)?;
                 }
                 BlockEntry::MultilineComment(..) => continue,
                 b @ BlockEntry::UnknownAtRule(_) => write!(buf, "{}", b.to_string()?)?,
@@ -575,6 +884,7 @@ impl CompressedFormatter {
 #[derive(Debug, Default)]
 struct ExpandedFormatter {
     nesting: usize,
+    mappings: Vec<(usize, Span)>,
 }
 
 #[derive(Clone, Copy)]
@@ -619,10 +929,12 @@ impl Formatter for ExpandedFormatter {
             match block {
                 Toplevel::Empty => continue,
                 Toplevel::RuleSet { selector, body, .. } => {
+                    self.mappings
+                        .push((buf.len() + padding.len(), selector.0.span));
                     writeln!(buf, "{}{} {{", padding, selector)?;
 
                     for style in body {
-                        writeln!(buf, "{}  {}", padding, style.to_string()?)?;
+                        self.write_block_entry(buf, &padding, &style)?;
                     }
 
                     write!(buf, "{}}}", padding)?;
@@ -643,7 +955,7 @@ impl Formatter for ExpandedFormatter {
                             .join(", ")
                     )?;
                     for style in body {
-                        writeln!(buf, "{}  {}", padding, style.to_string()?)?;
+                        self.write_block_entry(buf, &padding, &style)?;
                     }
                     write!(buf, "{}}}", padding)?;
                 }
@@ -747,12 +1059,17 @@ impl Formatter for ExpandedFormatter {
                     write!(buf, "\n{}}}", padding)?;
                 }
                 Toplevel::Media {
-                    query,
+                    queries,
                     body,
                     inside_rule,
                     ..
                 } => {
-                    writeln!(buf, "{}@media {} {{", padding, query)?;
+                    writeln!(
+                        buf,
+                        "{}@media {} {{",
+                        padding,
+                        join_media_queries(&queries, ", ")
+                    )?;
                     let css = Css::from_stmts(
                         body,
                         if inside_rule {
@@ -766,7 +1083,10 @@ impl Formatter for ExpandedFormatter {
                     write!(buf, "\n{}}}", padding)?;
                 }
                 Toplevel::Style(s) => {
-                    write!(buf, "{}{}", padding, s.to_string()?)?;
+                    let style = s.to_string()?;
+                    self.mappings
+                        .push((buf.len() + padding.len(), s.property.span));
+                    write!(buf, "{}{}", padding, style)?;
                 }
             }
 
@@ -778,3 +1098,23 @@ impl Formatter for ExpandedFormatter {
         Ok(())
     }
 }
+
+impl ExpandedFormatter {
+    fn write_block_entry(
+        &mut self,
+        buf: &mut Vec<u8>,
+        padding: &str,
+        entry: &BlockEntry,
+    ) -> SassResult<()> {
+        let entry_str = entry.to_string()?;
+
+        if let BlockEntry::Style(s) = entry {
+            self.mappings
+                .push((buf.len() + padding.len() + 2, s.property.span));
+        }
+
+        writeln!(buf, "{}  {}", padding, entry_str)?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
-use std::{collections::HashMap, mem};
+use std::mem;
 
 use codemap::Span;
+use indexmap::IndexMap;
 
 use crate::{
     args::{CallArg, CallArgs, FuncArg, FuncArgs},
@@ -8,7 +9,7 @@ use crate::{
     error::SassResult,
     scope::Scope,
     utils::{read_until_closing_paren, read_until_closing_quote, read_until_newline},
-    value::Value,
+    value::{ArgList, Value},
     Token,
 };
 
@@ -16,6 +17,20 @@ use super::Parser;
 
 impl<'a, 'b> Parser<'a, 'b> {
     pub(super) fn parse_func_args(&mut self) -> SassResult<FuncArgs> {
+        let (args, close_paren_span) = self.parse_func_arg_list()?;
+        self.whitespace_or_comment();
+        // TODO: this should NOT eat the opening curly brace
+        // todo: self.expect_char('{')?;
+        match self.toks.next() {
+            Some(v) if v.kind == '{' => {}
+            Some(..) | None => return Err(("expected \"{\".", close_paren_span).into()),
+        };
+        Ok(args)
+    }
+
+    /// Parse the arguments of a function or mixin declaration up to and
+    /// including the closing parenthesis, returning the span of that parenthesis
+    pub(super) fn parse_func_arg_list(&mut self) -> SassResult<(FuncArgs, Span)> {
         let mut args: Vec<FuncArg> = Vec::new();
         let mut close_paren_span: Span = match self.toks.peek() {
             Some(Token { pos, .. }) => pos,
@@ -131,18 +146,21 @@ impl<'a, 'b> Parser<'a, 'b> {
             }
             self.whitespace_or_comment();
         }
-        self.whitespace_or_comment();
-        // TODO: this should NOT eat the opening curly brace
-        // todo: self.expect_char('{')?;
-        match self.toks.next() {
-            Some(v) if v.kind == '{' => {}
-            Some(..) | None => return Err(("expected \"{\".", close_paren_span).into()),
-        };
-        Ok(FuncArgs(args))
+        Ok((FuncArgs(args), close_paren_span))
     }
 
     pub(super) fn parse_call_args(&mut self) -> SassResult<CallArgs> {
-        let mut args = HashMap::new();
+        self.parse_call_args_inner(false)
+    }
+
+    /// Parse the arguments to a color function such as `rgb()`, whose first
+    /// argument may contain the alpha channel, as in `rgb(10 20 30 / 50%)`
+    pub(super) fn parse_color_call_args(&mut self) -> SassResult<CallArgs> {
+        self.parse_call_args_inner(true)
+    }
+
+    fn parse_call_args_inner(&mut self, is_color_fn: bool) -> SassResult<CallArgs> {
+        let mut args = IndexMap::new();
         self.whitespace_or_comment();
         let mut name = String::new();
 
@@ -191,7 +209,7 @@ impl<'a, 'b> Parser<'a, 'b> {
 
             self.whitespace_or_comment();
 
-            let value = self.parse_value(true, &|parser| match parser.toks.peek() {
+            let predicate = &|parser: &mut Parser<'_, '_>| match parser.toks.peek() {
                 Some(Token { kind: ')', .. }) | Some(Token { kind: ',', .. }) => true,
                 Some(Token { kind: '.', .. }) => {
                     let next_is_dot =
@@ -205,7 +223,13 @@ impl<'a, 'b> Parser<'a, 'b> {
                     !next_is_eq
                 }
                 Some(..) | None => false,
-            });
+            };
+
+            let value = if is_color_fn && args.is_empty() {
+                self.parse_color_channels(predicate)
+            } else {
+                self.parse_value(true, predicate)
+            };
 
             match self.toks.peek() {
                 Some(Token { kind: ')', .. }) => {
@@ -252,9 +276,12 @@ impl<'a, 'b> Parser<'a, 'b> {
                     let val = value?;
                     match val.node {
                         Value::ArgList(v) => {
-                            for arg in v {
+                            for arg in v.elems.iter().cloned() {
                                 args.insert(CallArg::Positional(args.len()), Ok(arg));
                             }
+                            for (name, arg) in v.keywords() {
+                                args.insert(CallArg::Named(*name), Ok(arg.clone()));
+                            }
                         }
                         Value::List(v, ..) => {
                             for arg in v {
@@ -366,26 +393,34 @@ impl<'a, 'b> Parser<'a, 'b> {
 }
 
 impl<'a, 'b> Parser<'a, 'b> {
+    /// Bind `args` to the parameters `fn_args`, returning the new scope along
+    /// with the value of the rest parameter, if there is one
+    ///
+    /// Once the body has been evaluated, the caller should check that the
+    /// keywords of the rest argument were accessed.
     pub(super) fn eval_args(
         &mut self,
         fn_args: &FuncArgs,
         mut args: CallArgs,
-    ) -> SassResult<Scope> {
+    ) -> SassResult<(Scope, Option<ArgList>)> {
         let mut scope = Scope::new();
         if fn_args.0.is_empty() {
             args.max_args(0)?;
-            return Ok(scope);
+            return Ok((scope, None));
         }
 
         if !fn_args.0.iter().any(|arg| arg.is_variadic) {
             args.max_args(fn_args.len())?;
         }
 
+        let mut rest = None;
+
         self.scopes.enter_new_scope();
         for (idx, arg) in fn_args.0.iter().enumerate() {
             if arg.is_variadic {
-                let arg_list = Value::ArgList(args.get_variadic()?);
-                scope.insert_var(arg.name, arg_list);
+                let arg_list = args.into_arg_list()?;
+                scope.insert_var(arg.name, Value::ArgList(arg_list.clone()));
+                rest = Some(arg_list);
                 break;
             }
 
@@ -405,6 +440,6 @@ impl<'a, 'b> Parser<'a, 'b> {
             scope.insert_var(arg.name, val);
         }
         self.scopes.exit_scope();
-        Ok(scope)
+        Ok((scope, rest))
     }
 }
@@ -27,8 +27,13 @@ impl<'a, 'b> Parser<'a, 'b> {
             at_root: self.at_root,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
+            custom_functions: self.custom_functions,
+            loaded_files: self.loaded_files,
+            call_stack: self.call_stack,
+            importer: self.importer,
             modules: self.modules,
             module_config: self.module_config,
         }
@@ -48,8 +53,13 @@ impl<'a, 'b> Parser<'a, 'b> {
             at_root: self.at_root,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
+            custom_functions: self.custom_functions,
+            loaded_files: self.loaded_files,
+            call_stack: self.call_stack,
+            importer: self.importer,
             modules: self.modules,
             module_config: self.module_config,
         }
@@ -194,13 +204,12 @@ impl<'a, 'b> Parser<'a, 'b> {
         };
 
         let from = match from_val.node {
-            Value::Dimension(Some(n), ..) => match n.to_i32() {
+            Value::Dimension(n, ..) => match n.to_i32() {
                 Some(std::i32::MAX) | Some(std::i32::MIN) | None => {
                     return Err((format!("{} is not an int.", n.inspect()), from_val.span).into())
                 }
                 Some(v) => v,
             },
-            Value::Dimension(None, ..) => return Err(("NaN is not an int.", from_val.span).into()),
             v => {
                 return Err((
                     format!("{} is not a number.", v.inspect(from_val.span)?),
@@ -212,13 +221,12 @@ impl<'a, 'b> Parser<'a, 'b> {
 
         let to_val = self.parse_value(true, &|_| false)?;
         let to = match to_val.node {
-            Value::Dimension(Some(n), ..) => match n.to_i32() {
+            Value::Dimension(n, ..) => match n.to_i32() {
                 Some(std::i32::MAX) | Some(std::i32::MIN) | None => {
                     return Err((format!("{} is not an int.", n.inspect()), to_val.span).into())
                 }
                 Some(v) => v,
             },
-            Value::Dimension(None, ..) => return Err(("NaN is not an int.", from_val.span).into()),
             v => {
                 return Err((
                     format!(
@@ -255,7 +263,7 @@ impl<'a, 'b> Parser<'a, 'b> {
         for i in iter {
             self.scopes.insert_var_last(
                 var.node,
-                Value::Dimension(Some(Number::from(i)), Unit::None, true),
+                Value::Dimension(Number::from(i), Unit::None, true),
             );
             let mut these_stmts = self
                 .subparser_with_in_control_flow_flag()
@@ -4,11 +4,12 @@ use crate::{
     args::CallArgs,
     atrule::Function,
     common::{unvendor, Identifier},
+    custom_function::CustomFunctionSignature,
     error::SassResult,
     lexer::Lexer,
     scope::Scopes,
     utils::read_until_closing_curly_brace,
-    value::{SassFunction, Value},
+    value::{SassFunction, SassValue, Value},
 };
 
 use super::{common::ContextFlags, Parser, Stmt};
@@ -77,6 +78,43 @@ impl<'a, 'b> Parser<'a, 'b> {
         Ok(())
     }
 
+    /// Parse the signature of a function registered with `Options::add_function`,
+    /// such as `asset-url($path, $hash: true)`
+    pub(crate) fn parse_custom_function_signature(
+        &mut self,
+    ) -> SassResult<CustomFunctionSignature> {
+        self.whitespace_or_comment();
+        let Spanned { node: name, span } = self.parse_identifier_no_interpolation(false)?;
+
+        if RESERVED_IDENTIFIERS.contains(&unvendor(&name)) {
+            return Err(("Invalid function name.", span).into());
+        }
+
+        self.whitespace_or_comment();
+        self.expect_char('(')?;
+
+        let (args, ..) = self.parse_func_arg_list()?;
+
+        self.whitespace_or_comment();
+
+        if let Some(tok) = self.toks.peek() {
+            return Err(("expected no more input.", tok.pos).into());
+        }
+
+        Ok(CustomFunctionSignature {
+            name: Identifier::from(name),
+            args,
+        })
+    }
+
+    /// Find the custom function named `name`, preferring the one registered last
+    pub fn get_custom_function(&self, name: Identifier) -> Option<SassFunction> {
+        self.custom_functions
+            .iter()
+            .rposition(|function| function.name == name)
+            .map(|idx| SassFunction::Custom(idx, name))
+    }
+
     pub(super) fn parse_return(&mut self) -> SassResult<Box<Value>> {
         let v = self.parse_value(true, &|_| false)?;
 
@@ -85,12 +123,49 @@ impl<'a, 'b> Parser<'a, 'b> {
         Ok(Box::new(v.node))
     }
 
+    pub fn eval_custom_function(&mut self, idx: usize, args: CallArgs) -> SassResult<Value> {
+        let span = args.span();
+        let (custom_functions, options) = (self.custom_functions, self.options);
+        let signature = &custom_functions[idx];
+        let function = &options.functions[idx];
+
+        let (mut scope, rest) = self.eval_args(&signature.args, args)?;
+
+        // keyword arguments can't be passed on to custom functions
+        if let Some(rest) = rest {
+            rest.assert_keywords_accessed(span)?;
+        }
+
+        let args: Vec<SassValue> = signature
+            .args
+            .0
+            .iter()
+            .map(|arg| SassValue::from_value(scope.vars.remove(&arg.name).unwrap_or(Value::Null)))
+            .collect();
+
+        match (function.callback)(&args) {
+            Ok(value) => Ok(value.into_value()),
+            Err(message) => Err((message, span).into()),
+        }
+    }
+
     pub fn eval_function(
         &mut self,
         function: Function,
+        name: Identifier,
         args: CallArgs,
         module: Option<Spanned<Identifier>>,
     ) -> SassResult<Value> {
+        let module_scope = match module {
+            Some(module) => Some(
+                self.modules
+                    .get(module.node, module.span)?
+                    .fn_scope(&function)
+                    .clone(),
+            ),
+            None => None,
+        };
+
         let Function {
             body,
             args: fn_args,
@@ -98,7 +173,8 @@ impl<'a, 'b> Parser<'a, 'b> {
             ..
         } = function;
 
-        let scope = self.eval_args(&fn_args, args)?;
+        let span = args.span();
+        let (scope, rest) = self.eval_args(&fn_args, args)?;
 
         let mut new_scope = Scopes::new();
         let mut entered_scope = false;
@@ -109,17 +185,17 @@ impl<'a, 'b> Parser<'a, 'b> {
             self.scopes.enter_scope(scope);
         };
 
-        if let Some(module) = module {
-            let module = self.modules.get(module.node, module.span)?;
-
+        if let Some(module_scope) = module_scope {
             if declared_at_root {
-                new_scope.enter_scope(module.scope.clone());
+                new_scope.enter_scope(module_scope);
             } else {
-                self.scopes.enter_scope(module.scope.clone());
+                self.scopes.enter_scope(module_scope);
             }
         }
 
-        let mut return_value = Parser {
+        self.call_stack.push((name.to_string(), span));
+
+        let return_value = Parser {
             toks: &mut Lexer::new(body),
             map: self.map,
             path: self.path,
@@ -136,12 +212,21 @@ impl<'a, 'b> Parser<'a, 'b> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
+            custom_functions: self.custom_functions,
+            loaded_files: self.loaded_files,
+            call_stack: self.call_stack,
+            importer: self.importer,
             modules: self.modules,
             module_config: self.module_config,
         }
-        .parse_stmt()?;
+        .parse_stmt();
+
+        self.call_stack.pop();
+
+        let mut return_value = return_value?;
 
         if entered_scope {
             self.scopes.exit_scope();
@@ -151,6 +236,10 @@ impl<'a, 'b> Parser<'a, 'b> {
             self.scopes.exit_scope();
         }
 
+        if let Some(rest) = rest {
+            rest.assert_keywords_accessed(span)?;
+        }
+
         debug_assert!(
             return_value.len() <= 1,
             "we expect there to be only one return value"
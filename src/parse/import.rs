@@ -5,6 +5,8 @@ use codemap::{Span, Spanned};
 use crate::{
     common::{ListSeparator::Comma, QuoteKind},
     error::SassResult,
+    importer::Syntax,
+    indented::is_indented_path,
     lexer::Lexer,
     value::Value,
     Token,
@@ -12,6 +14,16 @@ use crate::{
 
 use super::{Parser, Stmt};
 
+/// A stylesheet that has been located and read, but not yet parsed
+pub(super) struct LoadedStylesheet {
+    /// The canonical path or URL of the stylesheet
+    pub path: PathBuf,
+    pub contents: String,
+    pub indented: bool,
+    /// The index of the importer which loaded the stylesheet, if any
+    pub importer: Option<usize>,
+}
+
 #[allow(clippy::case_sensitive_file_extension_comparisons)]
 fn is_plain_css_import(url: &str) -> bool {
     if url.len() < 5 {
@@ -27,12 +39,10 @@ fn is_plain_css_import(url: &str) -> bool {
 }
 
 impl<'a, 'b> Parser<'a, 'b> {
-    /// Searches the current directory of the file then searches in `load_paths` directories
-    /// if the import has not yet been found.
+    /// Searches the directory of the current file for the stylesheet at `path`
     ///
     /// <https://sass-lang.com/documentation/at-rules/import#finding-the-file>
-    /// <https://sass-lang.com/documentation/at-rules/import#load-paths>
-    pub(super) fn find_import(&self, path: &Path) -> Option<PathBuf> {
+    fn find_relative_import(&self, path: &Path) -> Option<PathBuf> {
         let path_buf = if path.is_absolute() {
             // todo: test for absolute path imports
             path.into()
@@ -54,56 +64,166 @@ impl<'a, 'b> Parser<'a, 'b> {
             };
         }
 
-        try_path!(path_buf.with_file_name(name).with_extension("scss"));
-        try_path!(path_buf
-            .with_file_name(format!("_{}", name.to_str().unwrap()))
-            .with_extension("scss"));
+        for ext in ["scss", "sass"] {
+            try_path!(path_buf.with_file_name(name).with_extension(ext));
+            try_path!(path_buf
+                .with_file_name(format!("_{}", name.to_str().unwrap()))
+                .with_extension(ext));
+        }
         try_path!(path_buf.clone());
-        try_path!(path_buf.join("index.scss"));
-        try_path!(path_buf.join("_index.scss"));
-
-        for path in &self.options.load_paths {
-            if self.options.fs.is_dir(path) {
-                try_path!(path
-                    .join(&path_buf)
-                    .with_file_name(name)
-                    .with_extension("scss"));
-                try_path!(path
-                    .join(&path_buf)
-                    .with_file_name(format!("_{}", name.to_str().unwrap()))
-                    .with_extension("scss"));
-                try_path!(path.join(&path_buf).join("index.scss"));
-                try_path!(path.join(&path_buf).join("_index.scss"));
-            } else {
-                try_path!(path.to_path_buf());
-                try_path!(path.with_file_name(name).with_extension("scss"));
-                try_path!(path
-                    .with_file_name(format!("_{}", name.to_str().unwrap()))
-                    .with_extension("scss"));
-                try_path!(path.join("index.scss"));
-                try_path!(path.join("_index.scss"));
+        for ext in ["scss", "sass"] {
+            try_path!(path_buf.join(format!("index.{}", ext)));
+            try_path!(path_buf.join(format!("_index.{}", ext)));
+        }
+
+        None
+    }
+
+    /// Searches the `load_paths` directories for the stylesheet at `path`
+    ///
+    /// <https://sass-lang.com/documentation/at-rules/import#load-paths>
+    fn find_load_path_import(&self, path: &Path) -> Option<PathBuf> {
+        let name = path.file_name().unwrap_or_else(|| OsStr::new(".."));
+
+        macro_rules! try_path {
+            ($name:expr) => {
+                let name = $name;
+                if self.options.fs.is_file(&name) {
+                    return Some(name);
+                }
+            };
+        }
+
+        for load_path in &self.options.load_paths {
+            for ext in ["scss", "sass"] {
+                if self.options.fs.is_dir(load_path) {
+                    try_path!(load_path
+                        .join(path)
+                        .with_file_name(name)
+                        .with_extension(ext));
+                    try_path!(load_path
+                        .join(path)
+                        .with_file_name(format!("_{}", name.to_str().unwrap()))
+                        .with_extension(ext));
+                    try_path!(load_path.join(path).join(format!("index.{}", ext)));
+                    try_path!(load_path.join(path).join(format!("_index.{}", ext)));
+                } else {
+                    try_path!(load_path.to_path_buf());
+                    try_path!(load_path.with_file_name(name).with_extension(ext));
+                    try_path!(load_path
+                        .with_file_name(format!("_{}", name.to_str().unwrap()))
+                        .with_extension(ext));
+                    try_path!(load_path.join(format!("index.{}", ext)));
+                    try_path!(load_path.join(format!("_index.{}", ext)));
+                }
             }
         }
 
         None
     }
 
+    /// Locate and read the stylesheet at `url`, recording it as a dependency
+    /// of this compilation
+    pub(super) fn load_stylesheet(&mut self, url: &str) -> SassResult<Option<LoadedStylesheet>> {
+        let stylesheet = self.resolve_stylesheet(url)?;
+
+        if let Some(LoadedStylesheet { path, .. }) = &stylesheet {
+            if !self.loaded_files.contains(path) {
+                self.loaded_files.push(path.clone());
+            }
+        }
+
+        Ok(stylesheet)
+    }
+
+    /// Locate and read the stylesheet at `url`
+    ///
+    /// The stylesheet is first looked for relative to the current one: through
+    /// the importer which loaded it, or otherwise in its directory. This is
+    /// followed by each of the custom importers in order, and finally the load
+    /// paths.
+    fn resolve_stylesheet(&self, url: &str) -> SassResult<Option<LoadedStylesheet>> {
+        let path: &Path = url.as_ref();
+
+        let containing_url = if self.path.as_os_str().is_empty() {
+            None
+        } else {
+            Some(self.path.to_string_lossy())
+        };
+
+        let relative = match self.importer {
+            Some(idx) => self.import_with(idx, url, containing_url.as_deref())?,
+            None => match self.find_relative_import(path) {
+                Some(path) => Some(self.read_stylesheet(path)?),
+                None => None,
+            },
+        };
+
+        if relative.is_some() {
+            return Ok(relative);
+        }
+
+        for idx in 0..self.options.importers.len() {
+            if Some(idx) == self.importer {
+                continue;
+            }
+
+            if let Some(stylesheet) = self.import_with(idx, url, containing_url.as_deref())? {
+                return Ok(Some(stylesheet));
+            }
+        }
+
+        if let Some(path) = self.find_load_path_import(path) {
+            return self.read_stylesheet(path).map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// Load the stylesheet at `url` using the importer at `idx` in
+    /// `Options::importers`
+    fn import_with(
+        &self,
+        idx: usize,
+        url: &str,
+        containing_url: Option<&str>,
+    ) -> SassResult<Option<LoadedStylesheet>> {
+        Ok(self.options.importers[idx]
+            .import(url, containing_url)?
+            .map(|result| LoadedStylesheet {
+                path: result.canonical_url.into(),
+                contents: result.contents,
+                indented: result.syntax == Syntax::Indented,
+                importer: Some(idx),
+            }))
+    }
+
+    fn read_stylesheet(&self, path: PathBuf) -> SassResult<LoadedStylesheet> {
+        Ok(LoadedStylesheet {
+            contents: String::from_utf8(self.options.fs.read(&path)?)?,
+            indented: is_indented_path(&path),
+            importer: None,
+            path,
+        })
+    }
+
     pub(crate) fn parse_single_import(
         &mut self,
         file_name: &str,
         span: Span,
     ) -> SassResult<Vec<Stmt>> {
-        let path: &Path = file_name.as_ref();
-
-        if let Some(name) = self.find_import(path) {
-            let file = self.map.add_file(
-                name.to_string_lossy().into(),
-                String::from_utf8(self.options.fs.read(&name)?)?,
-            );
+        if let Some(LoadedStylesheet {
+            path,
+            contents,
+            indented,
+            importer,
+        }) = self.load_stylesheet(file_name)?
+        {
+            let file = self.map.add_file(path.to_string_lossy().into(), contents);
             return Parser {
-                toks: &mut Lexer::new_from_file(&file),
+                toks: &mut Lexer::new_from_file_with_syntax(&file, indented)?,
                 map: self.map,
-                path: &name,
+                path: &path,
                 scopes: self.scopes,
                 global_scope: self.global_scope,
                 super_selectors: self.super_selectors,
@@ -113,8 +233,13 @@ impl<'a, 'b> Parser<'a, 'b> {
                 at_root: self.at_root,
                 at_root_has_selector: self.at_root_has_selector,
                 extender: self.extender,
+                media_queries: self.media_queries,
                 content_scopes: self.content_scopes,
                 options: self.options,
+                custom_functions: self.custom_functions,
+                loaded_files: self.loaded_files,
+                call_stack: self.call_stack,
+                importer,
                 modules: self.modules,
                 module_config: self.module_config,
             }
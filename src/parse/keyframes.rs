@@ -170,8 +170,13 @@ impl<'a, 'b> Parser<'a, 'b> {
                         at_root: self.at_root,
                         at_root_has_selector: self.at_root_has_selector,
                         extender: self.extender,
+                        media_queries: self.media_queries,
                         content_scopes: self.content_scopes,
                         options: self.options,
+                        custom_functions: self.custom_functions,
+                        loaded_files: self.loaded_files,
+                        call_stack: self.call_stack,
+                        importer: self.importer,
                         modules: self.modules,
                         module_config: self.module_config,
                     })
@@ -208,8 +213,13 @@ impl<'a, 'b> Parser<'a, 'b> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
+            custom_functions: self.custom_functions,
+            loaded_files: self.loaded_files,
+            call_stack: self.call_stack,
+            importer: self.importer,
             modules: self.modules,
             module_config: self.module_config,
         }
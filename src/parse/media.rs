@@ -111,7 +111,8 @@ impl<'a, 'b> Parser<'a, 'b> {
         if is_angle || matches!(next_tok, Some(Token { kind: '=', .. })) {
             buf.push(' ');
             // todo: remove this unwrap
-            buf.push(self.toks.next().unwrap().kind);
+            let comparison = self.toks.next().unwrap().kind;
+            buf.push(comparison);
             if is_angle && self.consume_char_if_exists('=') {
                 buf.push('=');
             }
@@ -120,6 +121,21 @@ impl<'a, 'b> Parser<'a, 'b> {
             self.whitespace_or_comment();
 
             buf.push_str(&self.expression_until_comparison()?);
+
+            // a range such as `(400px < width <= 700px)` may have a second
+            // comparison in the same direction as the first
+            if is_angle && self.consume_char_if_exists(comparison) {
+                buf.push(' ');
+                buf.push(comparison);
+                if self.consume_char_if_exists('=') {
+                    buf.push('=');
+                }
+                buf.push(' ');
+
+                self.whitespace_or_comment();
+
+                buf.push_str(&self.expression_until_comparison()?);
+            }
         }
 
         self.expect_char(')')?;
@@ -132,10 +148,20 @@ impl<'a, 'b> Parser<'a, 'b> {
         let mut buf = String::new();
 
         if !matches!(self.toks.peek(), Some(Token { kind: '(', .. })) {
-            buf.push_str(&self.parse_identifier()?);
+            let identifier = self.parse_identifier()?;
+            buf.push_str(&identifier);
 
             self.whitespace_or_comment();
 
+            // a negated condition, such as `not (color)`
+            if identifier.eq_ignore_ascii_case("not")
+                && matches!(self.toks.peek(), Some(Token { kind: '(', .. }))
+            {
+                buf.push(' ');
+                buf.push_str(&self.parse_media_feature()?);
+                return Ok(buf);
+            }
+
             if let Some(tok) = self.toks.peek() {
                 if !is_name_start(tok.kind) {
                     return Ok(buf);
@@ -161,14 +187,28 @@ impl<'a, 'b> Parser<'a, 'b> {
             }
         }
 
+        // a condition on its own may instead be a disjunction, such as
+        // `(color) or (hover)`
+        let is_condition = buf.is_empty();
+        let mut operator = None;
+
         loop {
             self.whitespace_or_comment();
             buf.push_str(&self.parse_media_feature()?);
             self.whitespace_or_comment();
-            if !self.scan_identifier("and", true) {
+
+            let next = if operator != Some("or") && self.scan_identifier("and", true) {
+                "and"
+            } else if is_condition && operator != Some("and") && self.scan_identifier("or", true) {
+                "or"
+            } else {
                 break;
-            }
-            buf.push_str(" and ");
+            };
+
+            operator = Some(next);
+            buf.push(' ');
+            buf.push_str(next);
+            buf.push(' ');
         }
         Ok(buf)
     }
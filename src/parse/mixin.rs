@@ -9,6 +9,7 @@ use crate::{
     lexer::Lexer,
     scope::Scopes,
     utils::read_until_closing_curly_brace,
+    value::SassMixin,
     Token,
 };
 
@@ -51,11 +52,9 @@ impl<'a, 'b> Parser<'a, 'b> {
         // todo: `@include` can only give content when `@content` is present within the body
         // if `@content` is *not* present and `@include` attempts to give a body, we throw an error
         // `Error: Mixin doesn't accept a content block.`
-        //
-        // this is blocked on figuring out just how to check for this. presumably we could have a check
-        // not when parsing initially, but rather when `@include`ing to see if an `@content` was found.
+        let accepts_content_block = contains_content_rule(&body);
 
-        let mixin = Mixin::new_user_defined(args, body, false, self.at_root);
+        let mixin = Mixin::new_user_defined(args, body, accepts_content_block, self.at_root);
 
         if self.at_root {
             self.global_scope.insert_mixin(name, mixin);
@@ -73,18 +72,23 @@ impl<'a, 'b> Parser<'a, 'b> {
         self.whitespace_or_comment();
         let name = self.parse_identifier()?.map_node(Into::into);
 
-        let (mixin, module) = if self.consume_char_if_exists('.') {
+        let mixin = if self.consume_char_if_exists('.') {
             let module = name;
             let name = self.parse_identifier()?.map_node(Into::into);
 
-            (
+            SassMixin::new(
                 self.modules
                     .get(module.node, module.span)?
                     .get_mixin(name)?,
+                name.node,
                 Some(module),
             )
         } else {
-            (self.scopes.get_mixin(name, self.global_scope)?, None)
+            SassMixin::new(
+                self.scopes.get_mixin(name, self.global_scope)?,
+                name.node,
+                None,
+            )
         };
 
         self.whitespace_or_comment();
@@ -133,19 +137,52 @@ impl<'a, 'b> Parser<'a, 'b> {
 
         self.consume_char_if_exists(';');
 
+        self.include_mixin(mixin, args, content, content_args)
+    }
+
+    /// Evaluate the body of `mixin`, passing it the content block of the
+    /// `@include` rule, if any
+    ///
+    /// This is used by both `@include` and the builtin mixin `meta.apply()`
+    pub(crate) fn include_mixin(
+        &mut self,
+        mixin: SassMixin,
+        args: CallArgs,
+        content: Option<Vec<Token>>,
+        content_args: Option<FuncArgs>,
+    ) -> SassResult<Vec<Stmt>> {
+        let SassMixin {
+            mixin,
+            name,
+            module,
+        } = mixin;
+
         let UserDefinedMixin {
             body,
             args: fn_args,
             declared_at_root,
             ..
-        } = match mixin {
+        } = match *mixin {
             Mixin::UserDefined(u) => u,
-            Mixin::Builtin(b) => {
-                return b(args, self);
+            Mixin::Builtin(b, ..) => {
+                // builtin mixins may forward the content block to another mixin
+                self.content.push(Content {
+                    content,
+                    content_args,
+                    scope_len: self.scopes.len(),
+                    declared_at_root: false,
+                });
+
+                let stmts = b(args, self)?;
+
+                self.content.pop();
+
+                return Ok(stmts);
             }
         };
 
-        let scope = self.eval_args(&fn_args, args)?;
+        let span = args.span();
+        let (scope, rest) = self.eval_args(&fn_args, args)?;
 
         let scope_len = self.scopes.len();
 
@@ -157,7 +194,7 @@ impl<'a, 'b> Parser<'a, 'b> {
 
         if let Some(module) = module {
             let module = self.modules.get(module.node, module.span)?;
-            self.scopes.enter_scope(module.scope.clone());
+            self.scopes.enter_scope(module.mixin_scope(name).clone());
         }
 
         self.content.push(Content {
@@ -167,6 +204,8 @@ impl<'a, 'b> Parser<'a, 'b> {
             declared_at_root,
         });
 
+        self.call_stack.push((name.to_string(), span));
+
         let body = Parser {
             toks: &mut Lexer::new(body),
             map: self.map,
@@ -180,12 +219,21 @@ impl<'a, 'b> Parser<'a, 'b> {
             at_root: false,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
+            custom_functions: self.custom_functions,
+            loaded_files: self.loaded_files,
+            call_stack: self.call_stack,
+            importer: self.importer,
             modules: self.modules,
             module_config: self.module_config,
         }
-        .parse_stmt()?;
+        .parse_stmt();
+
+        self.call_stack.pop();
+
+        let body = body?;
 
         self.content.pop();
 
@@ -199,6 +247,10 @@ impl<'a, 'b> Parser<'a, 'b> {
             mem::swap(self.scopes, self.content_scopes);
         }
 
+        if let Some(rest) = rest {
+            rest.assert_keywords_accessed(span)?;
+        }
+
         Ok(body)
     }
 
@@ -228,10 +280,14 @@ impl<'a, 'b> Parser<'a, 'b> {
                 CallArgs::new(self.span_before)
             };
 
+            let span = call_args.span();
+            let mut rest = None;
+
             if let Some(ref content_args) = content.content_args {
                 call_args.max_args(content_args.len())?;
 
-                let scope = self.eval_args(content_args, call_args)?;
+                let (scope, content_rest) = self.eval_args(content_args, call_args)?;
+                rest = content_rest;
                 scope_at_decl.enter_scope(scope);
                 entered_scope = true;
             } else {
@@ -252,8 +308,13 @@ impl<'a, 'b> Parser<'a, 'b> {
                     at_root: self.at_root,
                     at_root_has_selector: self.at_root_has_selector,
                     extender: self.extender,
+                    media_queries: self.media_queries,
                     content_scopes: self.scopes,
                     options: self.options,
+                    custom_functions: self.custom_functions,
+                    loaded_files: self.loaded_files,
+                    call_stack: self.call_stack,
+                    importer: self.importer,
                     modules: self.modules,
                     module_config: self.module_config,
                 }
@@ -276,9 +337,65 @@ impl<'a, 'b> Parser<'a, 'b> {
 
             self.content.push(content);
 
+            if let Some(rest) = rest {
+                rest.assert_keywords_accessed(span)?;
+            }
+
             stmts
         } else {
             Vec::new()
         })
     }
 }
+
+/// Whether the body of a mixin contains an `@content` rule, ignoring any that
+/// appear within strings or comments
+fn contains_content_rule(body: &[Token]) -> bool {
+    let mut toks = body.iter().map(|tok| tok.kind).peekable();
+
+    while let Some(kind) = toks.next() {
+        match kind {
+            quote @ ('"' | '\'') => {
+                while let Some(kind) = toks.next() {
+                    match kind {
+                        '\\' => {
+                            toks.next();
+                        }
+                        _ if kind == quote => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if toks.peek() == Some(&'/') => {
+                for kind in toks.by_ref() {
+                    if kind == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if toks.peek() == Some(&'*') => {
+                toks.next();
+
+                while let Some(kind) = toks.next() {
+                    if kind == '*' && toks.peek() == Some(&'/') {
+                        toks.next();
+                        break;
+                    }
+                }
+            }
+            '@' => {
+                let name: String = toks
+                    .clone()
+                    .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+                    .collect();
+
+                if name == "content" {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
@@ -1,17 +1,23 @@
-use std::{convert::TryFrom, path::Path};
+use std::{
+    convert::TryFrom,
+    mem,
+    path::{Path, PathBuf},
+};
 
 use codemap::{CodeMap, Span, Spanned};
 
 use crate::{
     atrule::{
         keyframes::{Keyframes, KeyframesRuleSet},
-        media::MediaRule,
+        media::{MediaQuery, MediaRule},
         mixin::Content,
-        AtRuleKind, SupportsRule, UnknownAtRule,
+        AtRootQuery, AtRuleKind, SupportsRule, UnknownAtRule,
     },
     builtin::modules::{ModuleConfig, Modules},
+    custom_function::CustomFunctionSignature,
     error::SassResult,
     lexer::Lexer,
+    logger::{SourceLocation, StackFrame},
     scope::{Scope, Scopes},
     selector::{
         ComplexSelectorComponent, ExtendRule, ExtendedSelector, Extender, Selector, SelectorParser,
@@ -52,6 +58,7 @@ pub(crate) enum Stmt {
     UnknownAtRule(Box<UnknownAtRule>),
     Supports(Box<SupportsRule>),
     AtRoot {
+        query: AtRootQuery,
         body: Vec<Stmt>,
     },
     Comment(String),
@@ -82,8 +89,21 @@ pub(crate) struct Parser<'a, 'b> {
     /// not the `@at-rule` block has a super selector
     pub at_root_has_selector: bool,
     pub extender: &'a mut Extender,
+    /// The queries of the `@media` rules enclosing the current statement,
+    /// merged together, or `None` outside of `@media`
+    pub media_queries: &'a mut Option<Vec<MediaQuery>>,
 
     pub options: &'a Options<'a>,
+    /// The signatures of the functions registered with `Options::add_function`
+    pub custom_functions: &'a [CustomFunctionSignature],
+    /// Every stylesheet that has been loaded during this compilation
+    pub loaded_files: &'a mut Vec<PathBuf>,
+    /// The functions and mixins being evaluated, innermost last, along with
+    /// the span of the call to each
+    pub call_stack: &'a mut Vec<(String, Span)>,
+    /// The index of the importer in `Options::importers` which loaded the
+    /// current stylesheet, or `None` if it was not loaded by an importer
+    pub importer: Option<usize>,
 
     pub modules: &'a mut Modules,
     pub module_config: &'a mut ModuleConfig,
@@ -110,6 +130,21 @@ impl<'a, 'b> Parser<'a, 'b> {
         Ok(stmts)
     }
 
+    /// Parse a single expression that makes up the entire input, such as a
+    /// line entered in the interactive shell
+    pub fn parse_entire_value(&mut self) -> SassResult<Spanned<Value>> {
+        let value = self.parse_value(false, &|_| false)?;
+
+        self.consume_char_if_exists(';');
+        self.whitespace_or_comment();
+
+        if let Some(Token { pos, .. }) = self.toks.peek() {
+            return Err(("expected no more input.", pos).into());
+        }
+
+        Ok(value)
+    }
+
     pub fn expect_char(&mut self, c: char) -> SassResult<()> {
         match self.toks.peek() {
             Some(Token { kind, pos }) if kind == c => {
@@ -181,11 +216,14 @@ impl<'a, 'b> Parser<'a, 'b> {
                                     .into());
                             }
 
-                            if self.at_root {
-                                stmts.append(&mut self.parse_at_root()?);
+                            let (query, mut body) = self.parse_at_root()?;
+
+                            // a query may move the contents outside of enclosing
+                            // at-rules, which are only known once the output is built
+                            if self.at_root && query.is_default() {
+                                stmts.append(&mut body);
                             } else {
-                                let body = self.parse_at_root()?;
-                                stmts.push(Stmt::AtRoot { body });
+                                stmts.push(Stmt::AtRoot { query, body });
                             }
                         }
                         AtRuleKind::Error => {
@@ -262,7 +300,13 @@ impl<'a, 'b> Parser<'a, 'b> {
                             )
                                 .into())
                         }
-                        AtRuleKind::Forward => todo!("@forward not yet implemented"),
+                        AtRuleKind::Forward => {
+                            return Err((
+                                "@forward rules must be written before any other rules.",
+                                kind_string.span,
+                            )
+                                .into())
+                        }
                         AtRuleKind::Extend => self.parse_extend()?,
                         AtRuleKind::Supports => stmts.push(self.parse_supports()?),
                         AtRuleKind::Keyframes => {
@@ -354,16 +398,21 @@ impl<'a, 'b> Parser<'a, 'b> {
                         SelectorOrStyle::Selector(init) => {
                             let at_root = self.at_root;
                             self.at_root = false;
-                            let selector = self
-                                .parse_selector(true, false, init)?
-                                .0
-                                .resolve_parent_selectors(
-                                    &self.super_selectors.last().clone().into_selector(),
-                                    !at_root || self.at_root_has_selector,
-                                )?;
+                            let mut selector = self.parse_selector(true, false, init)?.0;
+
+                            // `init` has already been consumed, so the span must be
+                            // widened to include it
+                            selector.0.span = pos.merge(selector.0.span);
+
+                            let selector = selector.resolve_parent_selectors(
+                                &self.super_selectors.last().clone().into_selector(),
+                                !at_root || self.at_root_has_selector,
+                            )?;
                             self.scopes.enter_new_scope();
 
-                            let extended_selector = self.extender.add_selector(selector.0, None);
+                            let extended_selector = self
+                                .extender
+                                .add_selector(selector.0, self.media_queries.clone())?;
 
                             self.super_selectors.push(extended_selector.clone());
 
@@ -471,8 +520,13 @@ impl<'a, 'b> Parser<'a, 'b> {
                 at_root: self.at_root,
                 at_root_has_selector: self.at_root_has_selector,
                 extender: self.extender,
+                media_queries: self.media_queries,
                 content_scopes: self.content_scopes,
                 options: self.options,
+                custom_functions: self.custom_functions,
+                loaded_files: self.loaded_files,
+                call_stack: self.call_stack,
+                importer: self.importer,
                 modules: self.modules,
                 module_config: self.module_config,
             },
@@ -703,18 +757,36 @@ impl<'a, 'b> Parser<'a, 'b> {
             return Err(("This at-rule is not allowed here.", self.span_before).into());
         }
 
+        let start = self.toks.peek().map_or(self.span_before, |tok| tok.pos);
+
         let query = self.parse_media_query_list()?;
 
+        let queries = MediaQuery::parse_list(&query)
+            .map_err(|message| (message, start.merge(self.span_before)))?;
+
         self.whitespace();
 
         self.expect_char('{')?;
 
-        let raw_body = self.parse_stmt()?;
+        // style rules inside this rule may only be extended from within the
+        // same media context, which includes the queries of enclosing rules
+        let media_queries = match self.media_queries.as_ref() {
+            Some(outer) => match MediaQuery::merge_lists(outer, &queries) {
+                Some(merged) if !merged.is_empty() => merged,
+                Some(..) | None => queries.clone(),
+            },
+            None => queries.clone(),
+        };
+        let outer_media_queries = mem::replace(self.media_queries, Some(media_queries));
 
-        let mut rules = Vec::with_capacity(raw_body.len());
+        let raw_body = self.parse_stmt();
+
+        *self.media_queries = outer_media_queries;
+
+        let mut rules = Vec::new();
         let mut body = Vec::new();
 
-        for stmt in raw_body {
+        for stmt in raw_body? {
             match stmt {
                 Stmt::Style(..) => body.push(stmt),
                 _ => rules.push(stmt),
@@ -732,15 +804,57 @@ impl<'a, 'b> Parser<'a, 'b> {
 
         Ok(Stmt::Media(Box::new(MediaRule {
             super_selector: Selector::new(self.span_before),
-            query,
+            queries,
             body,
         })))
     }
 
-    fn parse_at_root(&mut self) -> SassResult<Vec<Stmt>> {
+    fn parse_at_root_query(&mut self) -> SassResult<AtRootQuery> {
+        self.expect_char('(')?;
+        self.whitespace_or_comment();
+
+        let Spanned { node: kind, span } = self.parse_identifier()?;
+
+        let include = match kind.to_ascii_lowercase().as_str() {
+            "with" => true,
+            "without" => false,
+            _ => return Err(("Expected \"with\" or \"without\".", span).into()),
+        };
+
+        self.whitespace_or_comment();
+        self.expect_char(':')?;
+        self.whitespace_or_comment();
+
+        let mut names = Vec::new();
+
+        loop {
+            names.push(self.parse_identifier()?.node.to_ascii_lowercase());
+            self.whitespace_or_comment();
+
+            if self.consume_char_if_exists(')') {
+                break;
+            }
+        }
+
+        Ok(AtRootQuery { include, names })
+    }
+
+    fn parse_at_root(&mut self) -> SassResult<(AtRootQuery, Vec<Stmt>)> {
         self.whitespace();
         let mut at_root_has_selector = false;
+        let mut query = AtRootQuery::default();
         let at_rule_selector = if self.consume_char_if_exists('{') {
+            self.super_selectors.last().clone()
+        } else if matches!(self.toks.peek(), Some(Token { kind: '(', .. })) {
+            query = self.parse_at_root_query()?;
+            self.whitespace_or_comment();
+            self.expect_char('{')?;
+
+            // if style rules are kept, the contents are nested in the
+            // enclosing style rule as usual
+            at_root_has_selector = !query.excludes_style_rules()
+                && !self.super_selectors.last().as_selector_list().is_empty();
+
             self.super_selectors.last().clone()
         } else {
             at_root_has_selector = true;
@@ -752,12 +866,15 @@ impl<'a, 'b> Parser<'a, 'b> {
                     false,
                 )?;
 
-            self.extender.add_selector(selector.0, None)
+            self.extender
+                .add_selector(selector.0, self.media_queries.clone())?
         };
 
         self.whitespace();
 
         let mut styles = Vec::new();
+        // the contents of `@at-root (without: media)` aren't in any media context
+        let mut no_media_queries = None;
         #[allow(clippy::unnecessary_filter_map)]
         let raw_stmts = Parser {
             toks: self.toks,
@@ -772,8 +889,17 @@ impl<'a, 'b> Parser<'a, 'b> {
             at_root: true,
             at_root_has_selector,
             extender: self.extender,
+            media_queries: if query.excludes_name("media") {
+                &mut no_media_queries
+            } else {
+                self.media_queries
+            },
             content_scopes: self.content_scopes,
             options: self.options,
+            custom_functions: self.custom_functions,
+            loaded_files: self.loaded_files,
+            call_stack: self.call_stack,
+            importer: self.importer,
             modules: self.modules,
             module_config: self.module_config,
         }
@@ -808,7 +934,7 @@ impl<'a, 'b> Parser<'a, 'b> {
             raw_stmts
         };
 
-        Ok(stmts)
+        Ok((query, stmts))
     }
 
     fn parse_extend(&mut self) -> SassResult<()> {
@@ -832,8 +958,13 @@ impl<'a, 'b> Parser<'a, 'b> {
             at_root: self.at_root,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
+            custom_functions: self.custom_functions,
+            loaded_files: self.loaded_files,
+            call_stack: self.call_stack,
+            importer: self.importer,
             modules: self.modules,
             module_config: self.module_config,
         }
@@ -872,9 +1003,9 @@ impl<'a, 'b> Parser<'a, 'b> {
                 super_selector.clone().into_selector().0,
                 compound.components.first().unwrap(),
                 &extend_rule,
-                &None,
+                self.media_queries,
                 self.span_before,
-            );
+            )?;
         }
 
         Ok(())
@@ -958,26 +1089,48 @@ impl<'a, 'b> Parser<'a, 'b> {
         if self.options.quiet {
             return;
         }
-        let loc = self.map.look_up_span(message.span);
-        eprintln!(
-            "{}:{} DEBUG: {}",
-            loc.file.name(),
-            loc.begin.line + 1,
-            message.node
-        );
+        self.options
+            .logger
+            .debug(&message.node, &self.source_location(message.span));
     }
 
     fn warn(&self, message: &Spanned<Cow<'a, str>>) {
         if self.options.quiet {
             return;
         }
-        let loc = self.map.look_up_span(message.span);
-        eprintln!(
-            "Warning: {}\n    {} {}:{}  root stylesheet",
-            message.node,
-            loc.file.name(),
-            loc.begin.line + 1,
-            loc.begin.column + 1
-        );
+        self.options
+            .logger
+            .warn(&message.node, &self.source_location(message.span), false);
+    }
+
+    pub(super) fn deprecation_warning(&self, message: &str, span: Span) {
+        if self.options.quiet {
+            return;
+        }
+        self.options
+            .logger
+            .warn(message, &self.source_location(span), true);
+    }
+
+    /// The location of `span`, along with the functions and mixins through
+    /// which it was reached
+    fn source_location(&self, span: Span) -> SourceLocation {
+        let mut stack_trace = Vec::with_capacity(self.call_stack.len() + 1);
+        let mut frame_span = span;
+
+        for (name, call_span) in self.call_stack.iter().rev() {
+            stack_trace.push(StackFrame::from_span_loc(
+                &self.map.look_up_span(frame_span),
+                format!("{}()", name),
+            ));
+            frame_span = *call_span;
+        }
+
+        stack_trace.push(StackFrame::from_span_loc(
+            &self.map.look_up_span(frame_span),
+            "root stylesheet".to_owned(),
+        ));
+
+        SourceLocation::from_span_loc(&self.map.look_up_span(span), stack_trace)
     }
 }
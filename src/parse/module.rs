@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::{collections::BTreeSet, convert::TryFrom};
 
 use codemap::Spanned;
 
@@ -6,17 +6,36 @@ use crate::{
     atrule::AtRuleKind,
     builtin::modules::{
         declare_module_color, declare_module_list, declare_module_map, declare_module_math,
-        declare_module_meta, declare_module_selector, declare_module_string, Module, ModuleConfig,
-        Modules,
+        declare_module_meta, declare_module_selector, declare_module_string, ForwardFilter,
+        ForwardVisibility, ForwardedModule, MemberNames, Module, ModuleConfig, Modules,
     },
     common::Identifier,
     error::SassResult,
     lexer::Lexer,
-    parse::{common::Comment, Parser, Stmt, VariableValue},
+    parse::{common::Comment, import::LoadedStylesheet, Parser, Stmt, VariableValue},
     scope::Scope,
+    utils::{is_name, is_name_start},
     Token,
 };
 
+/// The namespace of a module loaded without `as` is the last component of
+/// its URL, without a leading underscore or file extension
+fn default_namespace(url: &str) -> &str {
+    let basename = url.rsplit(|c| c == '/' || c == ':').next().unwrap_or(url);
+    let basename = basename.strip_prefix('_').unwrap_or(basename);
+    basename.split('.').next().unwrap_or(basename)
+}
+
+fn is_identifier(s: &str) -> bool {
+    let name = s
+        .strip_prefix("--")
+        .or_else(|| s.strip_prefix('-'))
+        .unwrap_or(s);
+    let mut chars = name.chars();
+
+    matches!(chars.next(), Some(c) if is_name_start(c) && !c.is_ascii_digit()) && chars.all(is_name)
+}
+
 impl<'a, 'b> Parser<'a, 'b> {
     fn parse_module_alias(&mut self) -> SassResult<Option<String>> {
         if !matches!(
@@ -45,14 +64,99 @@ impl<'a, 'b> Parser<'a, 'b> {
         Ok(Some(name.node))
     }
 
-    fn parse_module_config(&mut self) -> SassResult<ModuleConfig> {
+    /// Parses the `as <prefix>-*` clause of an `@forward` rule
+    fn parse_forward_prefix(&mut self) -> SassResult<Option<String>> {
+        if !matches!(
+            self.toks.peek(),
+            Some(Token { kind: 'a', .. }) | Some(Token { kind: 'A', .. })
+        ) {
+            return Ok(None);
+        }
+
+        let mut ident = self.parse_identifier_no_interpolation(false)?;
+
+        ident.node.make_ascii_lowercase();
+
+        if ident.node != "as" {
+            return Err(("expected \";\".", ident.span).into());
+        }
+
+        self.whitespace_or_comment();
+
+        let prefix = self.parse_identifier_no_interpolation(false)?;
+
+        self.span_before = prefix.span;
+        self.expect_char('*')?;
+
+        Ok(Some(prefix.node))
+    }
+
+    /// Parses the `show` or `hide` clause of an `@forward` rule
+    fn parse_forward_visibility(&mut self) -> SassResult<ForwardVisibility> {
+        if !matches!(
+            self.toks.peek(),
+            Some(Token { kind: 's', .. })
+                | Some(Token { kind: 'S', .. })
+                | Some(Token { kind: 'h', .. })
+                | Some(Token { kind: 'H', .. })
+        ) {
+            return Ok(ForwardVisibility::All);
+        }
+
+        let mut ident = self.parse_identifier_no_interpolation(false)?;
+
+        ident.node.make_ascii_lowercase();
+
+        let is_show = match ident.node.as_str() {
+            "show" => true,
+            "hide" => false,
+            _ => return Err(("expected \";\".", ident.span).into()),
+        };
+
+        let mut names = MemberNames::default();
+
+        loop {
+            self.whitespace_or_comment();
+
+            if self.consume_char_if_exists('$') {
+                let name = self.parse_identifier_no_interpolation(false)?;
+                names.variables.insert(name.node.into());
+            } else {
+                let name = self.parse_identifier_no_interpolation(false)?;
+                names.mixins_and_functions.insert(name.node.into());
+            }
+
+            self.whitespace_or_comment();
+
+            if !self.consume_char_if_exists(',') {
+                break;
+            }
+        }
+
+        Ok(if is_show {
+            ForwardVisibility::Show(names)
+        } else {
+            ForwardVisibility::Hide(names)
+        })
+    }
+
+    /// Parses the `with` clause of an `@use` or `@forward` rule
+    ///
+    /// Only `@forward` allows variables to be marked `!default`.
+    /// The names of these variables are returned alongside the
+    /// configuration
+    fn parse_module_config(
+        &mut self,
+        allows_default: bool,
+    ) -> SassResult<(ModuleConfig, BTreeSet<Identifier>)> {
         let mut config = ModuleConfig::default();
+        let mut guarded = BTreeSet::new();
 
         if !matches!(
             self.toks.peek(),
             Some(Token { kind: 'w', .. }) | Some(Token { kind: 'W', .. })
         ) {
-            return Ok(config);
+            return Ok((config, guarded));
         }
 
         let mut ident = self.parse_identifier_no_interpolation(false)?;
@@ -81,10 +185,18 @@ impl<'a, 'b> Parser<'a, 'b> {
             let value = self.parse_value(false, &|parser| {
                 matches!(
                     parser.toks.peek(),
-                    Some(Token { kind: ',', .. }) | Some(Token { kind: ')', .. })
+                    Some(Token { kind: ',', .. })
+                        | Some(Token { kind: ')', .. })
+                        | Some(Token { kind: '!', .. })
                 )
             })?;
 
+            if allows_default && self.consume_char_if_exists('!') {
+                self.expect_identifier("default")?;
+                self.whitespace_or_comment();
+                guarded.insert(name.node.clone().into());
+            }
+
             config.insert(name.map_node(Into::into), value)?;
 
             match self.toks.next() {
@@ -100,7 +212,7 @@ impl<'a, 'b> Parser<'a, 'b> {
             }
         }
 
-        Ok(config)
+        Ok((config, guarded))
     }
 
     pub fn load_module(
@@ -117,20 +229,23 @@ impl<'a, 'b> Parser<'a, 'b> {
             "sass:selector" => (declare_module_selector(), Vec::new()),
             "sass:string" => (declare_module_string(), Vec::new()),
             _ => {
-                if let Some(import) = self.find_import(name.as_ref()) {
+                if let Some(LoadedStylesheet {
+                    path,
+                    contents,
+                    indented,
+                    importer,
+                }) = self.load_stylesheet(name)?
+                {
                     let mut global_scope = Scope::new();
 
-                    let file = self.map.add_file(
-                        name.to_owned(),
-                        String::from_utf8(self.options.fs.read(&import)?)?,
-                    );
+                    let file = self.map.add_file(path.to_string_lossy().into(), contents);
 
                     let mut modules = Modules::default();
 
                     let stmts = Parser {
-                        toks: &mut Lexer::new_from_file(&file),
+                        toks: &mut Lexer::new_from_file_with_syntax(&file, indented)?,
                         map: self.map,
-                        path: &import,
+                        path: &path,
                         scopes: self.scopes,
                         global_scope: &mut global_scope,
                         super_selectors: self.super_selectors,
@@ -140,21 +255,18 @@ impl<'a, 'b> Parser<'a, 'b> {
                         at_root: self.at_root,
                         at_root_has_selector: self.at_root_has_selector,
                         extender: self.extender,
+                        media_queries: self.media_queries,
                         content_scopes: self.content_scopes,
                         options: self.options,
+                        custom_functions: self.custom_functions,
+                        loaded_files: self.loaded_files,
+                        call_stack: self.call_stack,
+                        importer,
                         modules: &mut modules,
                         module_config: config,
                     }
                     .parse()?;
 
-                    if !config.is_empty() {
-                        return Err((
-                            "This variable was not declared with !default in the @used module.",
-                            self.span_before,
-                        )
-                            .into());
-                    }
-
                     (Module::new_from_scope(global_scope, modules, false), stmts)
                 } else {
                     return Err(("Can't find stylesheet to import.", self.span_before).into());
@@ -163,6 +275,136 @@ impl<'a, 'b> Parser<'a, 'b> {
         })
     }
 
+    fn parse_module_url(&mut self) -> SassResult<Spanned<String>> {
+        let quote = match self.toks.next() {
+            Some(Token { kind: q @ '"', .. }) | Some(Token { kind: q @ '\'', .. }) => q,
+            Some(..) | None => return Err(("Expected string.", self.span_before).into()),
+        };
+
+        let Spanned { node: module, span } = self.parse_quoted_string(quote)?;
+        let module_name = module
+            .unquote()
+            .to_css_string(span, self.options.is_compressed())?;
+
+        Ok(Spanned {
+            node: module_name.into_owned(),
+            span,
+        })
+    }
+
+    fn parse_use_rule(&mut self) -> SassResult<Vec<Stmt>> {
+        self.whitespace_or_comment();
+
+        let Spanned {
+            node: module_name,
+            span,
+        } = self.parse_module_url()?;
+
+        self.whitespace_or_comment();
+
+        let module_alias = self.parse_module_alias()?;
+
+        if module_alias.is_none() && !is_identifier(default_namespace(&module_name)) {
+            return Err((
+                format!(
+                    "The default namespace \"{}\" is not a valid Sass identifier.\n\nRecommendation: add an \"as\" clause to define an explicit namespace.",
+                    default_namespace(&module_name)
+                ),
+                span,
+            )
+                .into());
+        }
+
+        self.whitespace_or_comment();
+
+        let (mut config, _) = self.parse_module_config(false)?;
+
+        self.whitespace_or_comment();
+        self.expect_char(';')?;
+
+        let (module, stmts) = self.load_module(&module_name, &mut config)?;
+
+        if !config.is_empty() {
+            if module.is_builtin() {
+                return Err(("Built-in modules can't be configured.", span).into());
+            }
+
+            return Err((
+                "This variable was not declared with !default in the @used module.",
+                self.span_before,
+            )
+                .into());
+        }
+
+        let module_name = match module_alias.as_deref() {
+            Some("*") => {
+                let scope = module.flattened_scope();
+                self.modules.merge(module.modules);
+                self.global_scope.merge_module_scope(scope);
+                return Ok(stmts);
+            }
+            Some(..) => module_alias.unwrap(),
+            None => default_namespace(&module_name).to_owned(),
+        };
+
+        self.modules.insert(module_name.into(), module, span)?;
+
+        Ok(stmts)
+    }
+
+    fn parse_forward_rule(&mut self) -> SassResult<Vec<Stmt>> {
+        self.whitespace_or_comment();
+
+        let Spanned {
+            node: module_name,
+            span,
+        } = self.parse_module_url()?;
+
+        self.whitespace_or_comment();
+
+        let prefix = self.parse_forward_prefix()?;
+
+        self.whitespace_or_comment();
+
+        let visibility = self.parse_forward_visibility()?;
+
+        self.whitespace_or_comment();
+
+        let (own_config, guarded) = self.parse_module_config(true)?;
+
+        self.whitespace_or_comment();
+        self.expect_char(';')?;
+
+        let filter = ForwardFilter::new(prefix, visibility);
+
+        // configuration passed to this module is passed through to the
+        // forwarded module, with any prefix removed
+        let mut config = self.module_config.take_forwarded(&filter);
+        let passed_through = config.names();
+        config.configure_forward(own_config, &guarded);
+
+        let (module, stmts) = self.load_module(&module_name, &mut config)?;
+
+        if !self
+            .module_config
+            .restore_forwarded(config, &passed_through, &filter)
+        {
+            if module.is_builtin() {
+                return Err(("Built-in modules can't be configured.", span).into());
+            }
+
+            return Err((
+                "This variable was not declared with !default in the @used module.",
+                span,
+            )
+                .into());
+        }
+
+        self.modules.forward(ForwardedModule::new(module, filter));
+
+        Ok(stmts)
+    }
+
     /// Returns any multiline comments that may have been found
     /// while loading modules
     pub(super) fn load_modules(&mut self) -> SassResult<Vec<Stmt>> {
@@ -178,7 +420,7 @@ impl<'a, 'b> Parser<'a, 'b> {
                     self.toks.next();
 
                     if let Some(Token { kind, .. }) = self.toks.peek() {
-                        if !matches!(kind, 'u' | 'U' | '\\') {
+                        if !matches!(kind, 'u' | 'U' | 'f' | 'F' | '\\') {
                             self.toks.set_cursor(start);
                             break;
                         }
@@ -186,67 +428,14 @@ impl<'a, 'b> Parser<'a, 'b> {
 
                     let ident = self.parse_identifier_no_interpolation(false)?;
 
-                    if AtRuleKind::try_from(&ident)? != AtRuleKind::Use {
-                        self.toks.set_cursor(start);
-                        break;
-                    }
-
-                    self.whitespace_or_comment();
-
-                    let quote = match self.toks.next() {
-                        Some(Token { kind: q @ '"', .. }) | Some(Token { kind: q @ '\'', .. }) => q,
-                        Some(..) | None => {
-                            return Err(("Expected string.", self.span_before).into())
+                    match AtRuleKind::try_from(&ident)? {
+                        AtRuleKind::Use => comments.append(&mut self.parse_use_rule()?),
+                        AtRuleKind::Forward => comments.append(&mut self.parse_forward_rule()?),
+                        _ => {
+                            self.toks.set_cursor(start);
+                            break;
                         }
-                    };
-
-                    let Spanned { node: module, span } = self.parse_quoted_string(quote)?;
-                    let module_name = module
-                        .unquote()
-                        .to_css_string(span, self.options.is_compressed())?;
-
-                    self.whitespace_or_comment();
-
-                    let module_alias = self.parse_module_alias()?;
-
-                    self.whitespace_or_comment();
-
-                    let mut config = self.parse_module_config()?;
-
-                    self.whitespace_or_comment();
-                    self.expect_char(';')?;
-
-                    let (module, mut stmts) =
-                        self.load_module(module_name.as_ref(), &mut config)?;
-
-                    comments.append(&mut stmts);
-
-                    // if the config isn't empty here, that means
-                    // variables were passed to a builtin module
-                    if !config.is_empty() {
-                        return Err(("Built-in modules can't be configured.", span).into());
                     }
-
-                    let module_name = match module_alias.as_deref() {
-                        Some("*") => {
-                            self.modules.merge(module.modules);
-                            self.global_scope.merge_module_scope(module.scope);
-                            continue;
-                        }
-                        Some(..) => module_alias.unwrap(),
-                        None => match module_name.as_ref() {
-                            "sass:color" => "color".to_owned(),
-                            "sass:list" => "list".to_owned(),
-                            "sass:map" => "map".to_owned(),
-                            "sass:math" => "math".to_owned(),
-                            "sass:meta" => "meta".to_owned(),
-                            "sass:selector" => "selector".to_owned(),
-                            "sass:string" => "string".to_owned(),
-                            _ => module_name.into_owned(),
-                        },
-                    };
-
-                    self.modules.insert(module_name.into(), module, span)?;
                 }
                 Some(Token { kind: '/', .. }) => {
                     self.toks.next();
@@ -108,7 +108,10 @@ impl<'a, 'b> Parser<'a, 'b> {
             }
         }
 
-        let mut property = self.parse_identifier()?.node;
+        let Spanned {
+            node: mut property,
+            span: property_span,
+        } = self.parse_property_name()?;
         let whitespace_after_property = self.whitespace_or_comment();
 
         match self.toks.peek() {
@@ -131,7 +134,10 @@ impl<'a, 'b> Parser<'a, 'b> {
                                 if let Ok(val) = self.parse_value_from_vec(&toks, false) {
                                     self.toks.take(len).for_each(drop);
                                     return Ok(SelectorOrStyle::Style(
-                                        InternedString::get_or_intern(property),
+                                        Spanned {
+                                            node: InternedString::get_or_intern(property),
+                                            span: property_span,
+                                        },
                                         Some(Box::new(val)),
                                     ));
                                 }
@@ -143,7 +149,13 @@ impl<'a, 'b> Parser<'a, 'b> {
                             property.push(':');
                             return Ok(SelectorOrStyle::Selector(property));
                         }
-                        _ => SelectorOrStyle::Style(InternedString::get_or_intern(property), None),
+                        _ => SelectorOrStyle::Style(
+                            Spanned {
+                                node: InternedString::get_or_intern(property),
+                                span: property_span,
+                            },
+                            None,
+                        ),
                     });
                 }
             }
@@ -171,8 +183,30 @@ impl<'a, 'b> Parser<'a, 'b> {
         Err(("expected \"{\".", self.span_before).into())
     }
 
-    fn parse_property(&mut self, mut super_property: String) -> SassResult<String> {
-        let property = self.parse_identifier()?;
+    /// Parse an identifier, spanning exactly its own tokens so that source maps
+    /// point at the name of the property
+    fn parse_property_name(&mut self) -> SassResult<Spanned<String>> {
+        let start = self.toks.peek().map(|tok| tok.pos);
+        let name = self.parse_identifier()?;
+
+        let span = match (start, self.toks.peek_previous()) {
+            (Some(start), Some(end)) => start.merge(end.pos),
+            _ => name.span,
+        };
+
+        Ok(Spanned {
+            node: name.node,
+            span,
+        })
+    }
+
+    /// Parse the name of a nested property, prefixed by the name of the
+    /// property containing it
+    fn parse_property(
+        &mut self,
+        mut super_property: String,
+    ) -> SassResult<Spanned<InternedString>> {
+        let property = self.parse_property_name()?;
         self.whitespace_or_comment();
         // todo: expect_char(':')?;
         if self.consume_char_if_exists(':') {
@@ -181,14 +215,19 @@ impl<'a, 'b> Parser<'a, 'b> {
             return Err(("Expected \":\".", property.span).into());
         }
 
-        if super_property.is_empty() {
-            Ok(property.node)
+        let name = if super_property.is_empty() {
+            property.node
         } else {
             super_property.reserve(1 + property.node.len());
             super_property.push('-');
             super_property.push_str(&property.node);
-            Ok(super_property)
-        }
+            super_property
+        };
+
+        Ok(Spanned {
+            node: InternedString::get_or_intern(name),
+            span: property.span,
+        })
     }
 
     fn parse_style_value(&mut self) -> SassResult<Spanned<Value>> {
@@ -197,7 +236,7 @@ impl<'a, 'b> Parser<'a, 'b> {
 
     pub(super) fn parse_style_group(
         &mut self,
-        super_property: InternedString,
+        super_property: Spanned<InternedString>,
     ) -> SassResult<Vec<Style>> {
         let mut styles = Vec::new();
         self.whitespace();
@@ -207,9 +246,7 @@ impl<'a, 'b> Parser<'a, 'b> {
                     self.toks.next();
                     self.whitespace();
                     loop {
-                        let property = InternedString::get_or_intern(
-                            self.parse_property(super_property.resolve())?,
-                        );
+                        let property = self.parse_property(super_property.node.resolve())?;
                         if let Some(tok) = self.toks.peek() {
                             if tok.kind == '{' {
                                 styles.append(&mut self.parse_style_group(property)?);
@@ -0,0 +1,354 @@
+use codemap::{Span, Spanned};
+use num_traits::Signed;
+
+use crate::{
+    common::Op,
+    error::SassResult,
+    unit::Unit,
+    value::{
+        calculation::{has_compatible_units, verify_compatible},
+        Calculation, CalculationArg, CalculationName, Number, Value,
+    },
+    Token,
+};
+
+use super::{
+    super::Parser,
+    eval::{HigherIntermediateValue, ValueVisitor},
+    parse::IntermediateValue,
+};
+
+impl<'a, 'b> Parser<'a, 'b> {
+    /// Parse the arguments to the calculation `name`, after its opening
+    /// parenthesis, and simplify them as far as possible
+    ///
+    /// Returns `None`, leaving the cursor untouched, if the arguments aren't
+    /// valid calculation syntax, in which case the call should be parsed as a
+    /// plain function instead
+    pub(super) fn try_parse_calculation(
+        &mut self,
+        name: CalculationName,
+    ) -> SassResult<Option<Value>> {
+        let start = self.toks.cursor();
+        let span = self.span_before;
+
+        if self.calculation_contains_interpolation() {
+            if matches!(name, CalculationName::Min | CalculationName::Max) {
+                return Ok(None);
+            }
+
+            // interpolated contents are passed through as-is, since they may
+            // contain any number of arguments
+            let mut buf = String::new();
+            self.parse_calc_args(&mut buf)?;
+
+            return Ok(Some(Value::Calculation(Calculation {
+                name,
+                args: vec![CalculationArg::String(buf[1..buf.len() - 1].to_owned())],
+            })));
+        }
+
+        let args = match self.parse_calculation_args(name)? {
+            Some(args) => args,
+            None => {
+                self.toks.set_cursor(start);
+                return Ok(None);
+            }
+        };
+
+        Ok(Some(match name {
+            CalculationName::Calc => Calculation::calc(args.into_iter().next().unwrap()),
+            CalculationName::Min | CalculationName::Max => Calculation::min_max(name, args, span)?,
+            CalculationName::Clamp => Calculation::clamp(args, span)?,
+        }))
+    }
+
+    fn calculation_contains_interpolation(&self) -> bool {
+        let mut nesting = 0;
+        let mut idx = 0;
+
+        while let Some(Token { kind, .. }) = self.toks.peek_n(idx) {
+            match kind {
+                '(' => nesting += 1,
+                ')' if nesting == 0 => return false,
+                ')' => nesting -= 1,
+                '#' if matches!(self.toks.peek_n(idx + 1), Some(Token { kind: '{', .. })) => {
+                    return true
+                }
+                _ => {}
+            }
+
+            idx += 1;
+        }
+
+        false
+    }
+
+    fn parse_calculation_args(
+        &mut self,
+        name: CalculationName,
+    ) -> SassResult<Option<Vec<CalculationArg>>> {
+        let in_min_max = matches!(name, CalculationName::Min | CalculationName::Max);
+        let mut args = Vec::new();
+
+        loop {
+            self.whitespace_or_comment();
+
+            match self.parse_calculation_sum(in_min_max)? {
+                Some(arg) => args.push(arg),
+                None => return Ok(None),
+            }
+
+            self.whitespace_or_comment();
+
+            match self.toks.next() {
+                Some(Token { kind: ')', .. }) => return Ok(Some(args)),
+                Some(Token { kind: ',', .. })
+                    if name.max_args().map_or(true, |max| args.len() < max) => {}
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    fn parse_calculation_sum(&mut self, in_min_max: bool) -> SassResult<Option<CalculationArg>> {
+        let mut sum = match self.parse_calculation_product(in_min_max)? {
+            Some(arg) => arg,
+            None => return Ok(None),
+        };
+
+        loop {
+            let whitespace_before = self.whitespace_or_comment();
+
+            let Token { kind, pos } = match self.toks.peek() {
+                Some(tok @ Token { kind: '+', .. }) | Some(tok @ Token { kind: '-', .. }) => tok,
+                _ => return Ok(Some(sum)),
+            };
+
+            self.toks.next();
+
+            // `+` and `-` must be surrounded by whitespace, so that they can't
+            // be confused with the sign of a number
+            if !whitespace_before || !self.whitespace_or_comment() {
+                return Ok(None);
+            }
+
+            let rhs = match self.parse_calculation_product(in_min_max)? {
+                Some(arg) => arg,
+                None => return Ok(None),
+            };
+
+            let op = if kind == '+' { Op::Plus } else { Op::Minus };
+
+            sum = self.operate_calculation(sum, op, rhs, in_min_max, pos)?;
+        }
+    }
+
+    fn parse_calculation_product(
+        &mut self,
+        in_min_max: bool,
+    ) -> SassResult<Option<CalculationArg>> {
+        let mut product = match self.parse_calculation_value(in_min_max)? {
+            Some(arg) => arg,
+            None => return Ok(None),
+        };
+
+        loop {
+            // leave whitespace before `+` and `-` for `parse_calculation_sum`
+            let before_whitespace = self.toks.cursor();
+            self.whitespace_or_comment();
+
+            let Token { kind, pos } = match self.toks.peek() {
+                Some(tok @ Token { kind: '*', .. }) | Some(tok @ Token { kind: '/', .. }) => tok,
+                _ => {
+                    self.toks.set_cursor(before_whitespace);
+                    return Ok(Some(product));
+                }
+            };
+
+            self.toks.next();
+            self.whitespace_or_comment();
+
+            let rhs = match self.parse_calculation_value(in_min_max)? {
+                Some(arg) => arg,
+                None => return Ok(None),
+            };
+
+            let op = if kind == '*' { Op::Mul } else { Op::Div };
+
+            product = self.operate_calculation(product, op, rhs, in_min_max, pos)?;
+        }
+    }
+
+    fn parse_calculation_value(&mut self, in_min_max: bool) -> SassResult<Option<CalculationArg>> {
+        let Token { kind, pos } = match self.toks.peek() {
+            Some(tok) => tok,
+            None => return Ok(None),
+        };
+
+        let is_signed_number = matches!(kind, '+' | '-')
+            && matches!(
+                self.toks.peek_n(1),
+                Some(Token { kind: next, .. }) if next.is_ascii_digit() || next == '.'
+            );
+
+        match kind {
+            '0'..='9' | '.' => self.parse_calculation_number(false).map(Some),
+            '+' | '-' if is_signed_number => {
+                self.toks.next();
+                self.parse_calculation_number(kind == '-').map(Some)
+            }
+            '(' => {
+                self.toks.next();
+                self.whitespace_or_comment();
+
+                let value = match self.parse_calculation_sum(in_min_max)? {
+                    Some(value) => value,
+                    None => return Ok(None),
+                };
+
+                self.whitespace_or_comment();
+
+                if !self.consume_char_if_exists(')') {
+                    return Ok(None);
+                }
+
+                Ok(Some(match value {
+                    CalculationArg::String(s) => CalculationArg::String(format!("({})", s)),
+                    value => value,
+                }))
+            }
+            '$' => {
+                self.toks.next();
+
+                let name = self
+                    .parse_identifier_no_interpolation(false)?
+                    .map_node(Into::into);
+
+                let value = self.scopes.get_var(name, self.global_scope)?.clone();
+
+                CalculationArg::from_value(value, pos.merge(name.span)).map(Some)
+            }
+            'a'..='z' | 'A'..='Z' | '_' | '-' | '\\' | '\u{7f}'..=std::char::MAX => {
+                let Spanned { node: ident, span } = self.parse_identifier()?;
+
+                let value = match self.toks.peek() {
+                    Some(Token { kind: '(', .. }) => {
+                        self.toks.next();
+                        self.span_before = span;
+
+                        let lower = ident.to_ascii_lowercase();
+                        self.parse_fn_call(ident, lower)?
+                    }
+                    Some(Token { kind: '.', .. }) => {
+                        self.toks.next();
+                        self.parse_module_item(Spanned {
+                            node: ident.into(),
+                            span,
+                        })?
+                    }
+                    _ => return Ok(Self::calculation_constant(&ident)),
+                };
+
+                let span = value.span;
+
+                let value = match value.node {
+                    IntermediateValue::Value(value) => {
+                        ValueVisitor::new(self, span).eval(value, false)?
+                    }
+                    _ => unreachable!("function calls always produce a value"),
+                };
+
+                CalculationArg::from_value(value, span).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// The numbers that can be written by name in a calculation
+    fn calculation_constant(ident: &str) -> Option<CalculationArg> {
+        let number = match ident.to_ascii_lowercase().as_str() {
+            "infinity" => Number::Infinity,
+            "-infinity" => Number::NegativeInfinity,
+            "nan" => Number::NaN,
+            _ => return None,
+        };
+
+        Some(CalculationArg::Number(number, Unit::None))
+    }
+
+    fn parse_calculation_number(&mut self, is_negative: bool) -> SassResult<CalculationArg> {
+        let Spanned { node, .. } = self.parse_dimension(&|_| false)?;
+
+        match node {
+            Value::Dimension(n, unit, _) => Ok(CalculationArg::Number(
+                if is_negative { -n } else { n },
+                unit,
+            )),
+            _ => unreachable!("`parse_dimension` always produces a number"),
+        }
+    }
+
+    /// Simplify `lhs op rhs`, evaluating it if both sides are numbers that
+    /// can be combined
+    fn operate_calculation(
+        &mut self,
+        lhs: CalculationArg,
+        op: Op,
+        rhs: CalculationArg,
+        in_min_max: bool,
+        span: Span,
+    ) -> SassResult<CalculationArg> {
+        if let (CalculationArg::Number(n, unit), CalculationArg::Number(n2, unit2)) = (&lhs, &rhs) {
+            let can_evaluate = match op {
+                // `min()` and `max()` follow the Sass rules for arithmetic, so
+                // unitless numbers may be added to numbers with units
+                Op::Plus | Op::Minus if in_min_max => unit.comparable(unit2),
+                Op::Plus | Op::Minus => has_compatible_units(unit, unit2),
+                _ => true,
+            };
+
+            if can_evaluate {
+                let value = ValueVisitor::new(self, span).eval(
+                    HigherIntermediateValue::BinaryOp(
+                        Box::new(HigherIntermediateValue::Literal(Value::Dimension(
+                            n.clone(),
+                            unit.clone(),
+                            true,
+                        ))),
+                        op,
+                        Box::new(HigherIntermediateValue::Literal(Value::Dimension(
+                            n2.clone(),
+                            unit2.clone(),
+                            true,
+                        ))),
+                    ),
+                    true,
+                )?;
+
+                return CalculationArg::from_value(value, span);
+            }
+        }
+
+        let (op, rhs) = match (op, rhs) {
+            (Op::Plus | Op::Minus, rhs) => {
+                verify_compatible(&lhs, &rhs, span)?;
+
+                // `1% + -2px` is written as `1% - 2px`
+                match rhs {
+                    CalculationArg::Number(n, unit) if n.is_negative() => (
+                        if op == Op::Plus { Op::Minus } else { Op::Plus },
+                        CalculationArg::Number(-n, unit),
+                    ),
+                    rhs => (op, rhs),
+                }
+            }
+            (op, rhs) => (op, rhs),
+        };
+
+        Ok(CalculationArg::Operation {
+            lhs: Box::new(lhs),
+            op,
+            rhs: Box::new(rhs),
+        })
+    }
+}
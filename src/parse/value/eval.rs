@@ -10,7 +10,7 @@ use crate::{
     common::{Identifier, Op, QuoteKind},
     error::SassResult,
     unit::Unit,
-    value::{SassFunction, Value},
+    value::{Number, SassFunction, Value},
 };
 
 use super::super::Parser;
@@ -181,10 +181,7 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
 
     fn unary_minus(&self, val: Value) -> SassResult<Value> {
         Ok(match val {
-            Value::Dimension(Some(n), u, should_divide) => {
-                Value::Dimension(Some(-n), u, should_divide)
-            }
-            Value::Dimension(None, u, should_divide) => Value::Dimension(None, u, should_divide),
+            Value::Dimension(n, u, should_divide) => Value::Dimension(-n, u, should_divide),
             v => Value::String(
                 format!(
                     "-{}",
@@ -241,14 +238,48 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
             HigherIntermediateValue::Literal(v) => v,
             v => panic!("{:?}", v),
         };
+        // calculations may only be concatenated with strings
+        if matches!(right, Value::Calculation(..)) && !matches!(left, Value::String(..)) {
+            return Err((
+                format!(
+                    "Undefined operation \"{} + {}\".",
+                    left.inspect(self.span)?,
+                    right.inspect(self.span)?
+                ),
+                self.span,
+            )
+                .into());
+        }
+
         Ok(match left {
-            Value::Map(..) | Value::FunctionRef(..) => {
+            Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                 return Err((
                     format!("{} isn't a valid CSS value.", left.inspect(self.span)?),
                     self.span,
                 )
                     .into())
             }
+            Value::Calculation(..) => match right {
+                Value::String(s, q) => Value::String(
+                    format!(
+                        "{}{}",
+                        left.to_css_string(self.span, self.parser.options.is_compressed())?,
+                        s
+                    ),
+                    q,
+                ),
+                _ => {
+                    return Err((
+                        format!(
+                            "Undefined operation \"{} + {}\".",
+                            left.inspect(self.span)?,
+                            right.inspect(self.span)?
+                        ),
+                        self.span,
+                    )
+                        .into())
+                }
+            },
             Value::True | Value::False => match right {
                 Value::String(s, QuoteKind::Quoted) => Value::String(
                     format!(
@@ -294,10 +325,8 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                     QuoteKind::None,
                 ),
             },
-            v @ Value::Dimension(None, ..) => v,
-            Value::Dimension(Some(num), unit, _) => match right {
-                v @ Value::Dimension(None, ..) => v,
-                Value::Dimension(Some(num2), unit2, _) => {
+            Value::Dimension(num, unit, _) => match right {
+                Value::Dimension(num2, unit2, _) => {
                     if !unit.comparable(&unit2) {
                         return Err((
                             format!("Incompatible units {} and {}.", unit2, unit),
@@ -306,29 +335,27 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                             .into());
                     }
                     if unit == unit2 {
-                        Value::Dimension(Some(num + num2), unit, true)
+                        Value::Dimension(num + num2, unit, true)
                     } else if unit == Unit::None {
-                        Value::Dimension(Some(num + num2), unit2, true)
+                        Value::Dimension(num + num2, unit2, true)
                     } else if unit2 == Unit::None {
-                        Value::Dimension(Some(num + num2), unit, true)
+                        Value::Dimension(num + num2, unit, true)
                     } else {
-                        Value::Dimension(Some(num + num2.convert(&unit2, &unit)), unit, true)
+                        Value::Dimension(num + num2.convert(&unit2, &unit), unit, true)
                     }
                 }
                 Value::String(s, q) => Value::String(
                     format!(
-                        "{}{}{}",
-                        num.to_string(self.parser.options.is_compressed()),
-                        unit,
+                        "{}{}",
+                        num.to_css_string(&unit, self.parser.options.is_compressed()),
                         s
                     ),
                     q,
                 ),
                 Value::Null => Value::String(
                     format!(
-                        "{}{}",
-                        num.to_string(self.parser.options.is_compressed()),
-                        unit
+                        "{}",
+                        num.to_css_string(&unit, self.parser.options.is_compressed())
                     ),
                     QuoteKind::None,
                 ),
@@ -338,21 +365,20 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                 | Value::Important
                 | Value::ArgList(..) => Value::String(
                     format!(
-                        "{}{}{}",
-                        num.to_string(self.parser.options.is_compressed()),
-                        unit,
+                        "{}{}",
+                        num.to_css_string(&unit, self.parser.options.is_compressed()),
                         right.to_css_string(self.span, self.parser.options.is_compressed())?
                     ),
                     QuoteKind::None,
                 ),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
                     )
                         .into())
                 }
-                Value::Color(..) => {
+                Value::Color(..) | Value::Calculation(..) => {
                     return Err((
                         format!(
                             "Undefined operation \"{}{} + {}\".",
@@ -437,10 +463,8 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                 ),
                 QuoteKind::None,
             ),
-            v @ Value::Dimension(None, ..) => v,
-            Value::Dimension(Some(num), unit, _) => match right {
-                v @ Value::Dimension(None, ..) => v,
-                Value::Dimension(Some(num2), unit2, _) => {
+            Value::Dimension(num, unit, _) => match right {
+                Value::Dimension(num2, unit2, _) => {
                     if !unit.comparable(&unit2) {
                         return Err((
                             format!("Incompatible units {} and {}.", unit2, unit),
@@ -449,13 +473,13 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                             .into());
                     }
                     if unit == unit2 {
-                        Value::Dimension(Some(num - num2), unit, true)
+                        Value::Dimension(num - num2, unit, true)
                     } else if unit == Unit::None {
-                        Value::Dimension(Some(num - num2), unit2, true)
+                        Value::Dimension(num - num2, unit2, true)
                     } else if unit2 == Unit::None {
-                        Value::Dimension(Some(num - num2), unit, true)
+                        Value::Dimension(num - num2, unit, true)
                     } else {
-                        Value::Dimension(Some(num - num2.convert(&unit2, &unit)), unit, true)
+                        Value::Dimension(num - num2.convert(&unit2, &unit), unit, true)
                     }
                 }
                 Value::List(..)
@@ -463,16 +487,16 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                 | Value::Important
                 | Value::True
                 | Value::False
-                | Value::ArgList(..) => Value::String(
+                | Value::ArgList(..)
+                | Value::Calculation(..) => Value::String(
                     format!(
-                        "{}{}-{}",
-                        num.to_string(self.parser.options.is_compressed()),
-                        unit,
+                        "{}-{}",
+                        num.to_css_string(&unit, self.parser.options.is_compressed()),
                         right.to_css_string(self.span, self.parser.options.is_compressed())?
                     ),
                     QuoteKind::None,
                 ),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
@@ -493,9 +517,8 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                 }
                 Value::Null => Value::String(
                     format!(
-                        "{}{}-",
-                        num.to_string(self.parser.options.is_compressed()),
-                        unit
+                        "{}-",
+                        num.to_css_string(&unit, self.parser.options.is_compressed())
                     ),
                     QuoteKind::None,
                 ),
@@ -533,6 +556,17 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                 ),
                 QuoteKind::None,
             ),
+            Value::Calculation(..) => {
+                return Err((
+                    format!(
+                        "Undefined operation \"{} - {}\".",
+                        left.inspect(self.span)?,
+                        right.inspect(self.span)?
+                    ),
+                    self.span,
+                )
+                    .into())
+            }
             _ => match right {
                 Value::String(s, q) => Value::String(
                     format!(
@@ -577,16 +611,14 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
             v => panic!("{:?}", v),
         };
         Ok(match left {
-            Value::Dimension(None, ..) => todo!(),
-            Value::Dimension(Some(num), unit, _) => match right {
-                Value::Dimension(None, ..) => todo!(),
-                Value::Dimension(Some(num2), unit2, _) => {
+            Value::Dimension(num, unit, _) => match right {
+                Value::Dimension(num2, unit2, _) => {
                     if unit == Unit::None {
-                        Value::Dimension(Some(num * num2), unit2, true)
+                        Value::Dimension(num * num2, unit2, true)
                     } else if unit2 == Unit::None {
-                        Value::Dimension(Some(num * num2), unit, true)
+                        Value::Dimension(num * num2, unit, true)
                     } else {
-                        Value::Dimension(Some(num * num2), unit * unit2, true)
+                        Value::Dimension(num * num2, unit * unit2, true)
                     }
                 }
                 _ => {
@@ -638,39 +670,24 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                 ),
                 QuoteKind::None,
             ),
-            Value::Dimension(None, ..) => todo!(),
-            Value::Dimension(Some(num), unit, should_divide1) => match right {
-                Value::Dimension(None, ..) => todo!(),
-                Value::Dimension(Some(num2), unit2, should_divide2) => {
+            Value::Dimension(num, unit, should_divide1) => match right {
+                Value::Dimension(num2, unit2, should_divide2) => {
                     if should_divide1 || should_divide2 || in_parens {
-                        if num.is_zero() && num2.is_zero() {
-                            return Ok(Value::Dimension(None, Unit::None, true));
-                        }
-
-                        if num2.is_zero() {
-                            // todo: Infinity and -Infinity
-                            return Err(("Infinity not yet implemented.", self.span).into());
-                        }
-
                         // `unit(1em / 1em)` => `""`
                         if unit == unit2 {
-                            Value::Dimension(Some(num / num2), Unit::None, true)
+                            Value::Dimension(num / num2, Unit::None, true)
 
                         // `unit(1 / 1em)` => `"em^-1"`
                         } else if unit == Unit::None {
-                            Value::Dimension(Some(num / num2), Unit::None / unit2, true)
+                            Value::Dimension(num / num2, Unit::None / unit2, true)
 
                         // `unit(1em / 1)` => `"em"`
                         } else if unit2 == Unit::None {
-                            Value::Dimension(Some(num / num2), unit, true)
+                            Value::Dimension(num / num2, unit, true)
 
                         // `unit(1in / 1px)` => `""`
                         } else if unit.comparable(&unit2) {
-                            Value::Dimension(
-                                Some(num / num2.convert(&unit2, &unit)),
-                                Unit::None,
-                                true,
-                            )
+                            Value::Dimension(num / num2.convert(&unit2, &unit), Unit::None, true)
                         // `unit(1em / 1px)` => `"em/px"`
                         // todo: this should probably be its own variant
                         // within the `Value` enum
@@ -686,11 +703,9 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                     } else {
                         Value::String(
                             format!(
-                                "{}{}/{}{}",
-                                num.to_string(self.parser.options.is_compressed()),
-                                unit,
-                                num2.to_string(self.parser.options.is_compressed()),
-                                unit2
+                                "{}/{}",
+                                num.to_css_string(&unit, self.parser.options.is_compressed()),
+                                num2.to_css_string(&unit2, self.parser.options.is_compressed())
                             ),
                             QuoteKind::None,
                         )
@@ -698,9 +713,8 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                 }
                 Value::String(s, q) => Value::String(
                     format!(
-                        "{}{}/{}{}{}",
-                        num.to_string(self.parser.options.is_compressed()),
-                        unit,
+                        "{}/{}{}{}",
+                        num.to_css_string(&unit, self.parser.options.is_compressed()),
                         q,
                         s,
                         q
@@ -712,24 +726,23 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                 | Value::False
                 | Value::Important
                 | Value::Color(..)
-                | Value::ArgList(..) => Value::String(
+                | Value::ArgList(..)
+                | Value::Calculation(..) => Value::String(
                     format!(
-                        "{}{}/{}",
-                        num.to_string(self.parser.options.is_compressed()),
-                        unit,
+                        "{}/{}",
+                        num.to_css_string(&unit, self.parser.options.is_compressed()),
                         right.to_css_string(self.span, self.parser.options.is_compressed())?
                     ),
                     QuoteKind::None,
                 ),
                 Value::Null => Value::String(
                     format!(
-                        "{}{}/",
-                        num.to_string(self.parser.options.is_compressed()),
-                        unit
+                        "{}/",
+                        num.to_css_string(&unit, self.parser.options.is_compressed())
                     ),
                     QuoteKind::None,
                 ),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
@@ -773,7 +786,8 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                 | Value::Dimension(..)
                 | Value::Color(..)
                 | Value::List(..)
-                | Value::ArgList(..) => Value::String(
+                | Value::ArgList(..)
+                | Value::Calculation(..) => Value::String(
                     format!(
                         "{}{}{}/{}",
                         q1,
@@ -784,7 +798,7 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                     QuoteKind::None,
                 ),
                 Value::Null => Value::String(format!("{}{}{}/", q1, s1, q1), QuoteKind::None),
-                Value::Map(..) | Value::FunctionRef(..) => {
+                Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                     return Err((
                         format!("{} isn't a valid CSS value.", right.inspect(self.span)?),
                         self.span,
@@ -792,6 +806,17 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
                         .into())
                 }
             },
+            Value::Calculation(..) => {
+                return Err((
+                    format!(
+                        "Undefined operation \"{} / {}\".",
+                        left.inspect(self.span)?,
+                        right.inspect(self.span)?
+                    ),
+                    self.span,
+                )
+                    .into())
+            }
             _ => match right {
                 Value::String(s, q) => Value::String(
                     format!(
@@ -836,10 +861,8 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
             v => panic!("{:?}", v),
         };
         Ok(match left {
-            v @ Value::Dimension(None, ..) => v,
-            Value::Dimension(Some(n), u, _) => match right {
-                v @ Value::Dimension(None, ..) => v,
-                Value::Dimension(Some(n2), u2, _) => {
+            Value::Dimension(n, u, _) => match right {
+                Value::Dimension(n2, u2, _) => {
                     if !u.comparable(&u2) {
                         return Err(
                             (format!("Incompatible units {} and {}.", u, u2), self.span).into()
@@ -848,27 +871,27 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
 
                     if n2.is_zero() {
                         return Ok(Value::Dimension(
-                            None,
+                            Number::NaN,
                             if u == Unit::None { u2 } else { u },
                             true,
                         ));
                     }
 
                     if u == u2 {
-                        Value::Dimension(Some(n % n2), u, true)
+                        Value::Dimension(n % n2, u, true)
                     } else if u == Unit::None {
-                        Value::Dimension(Some(n % n2), u2, true)
+                        Value::Dimension(n % n2, u2, true)
                     } else if u2 == Unit::None {
-                        Value::Dimension(Some(n % n2), u, true)
+                        Value::Dimension(n % n2, u, true)
                     } else {
-                        Value::Dimension(Some(n), u, true)
+                        Value::Dimension(n, u, true)
                     }
                 }
                 _ => {
                     return Err((
                         format!(
                             "Undefined operation \"{} % {}\".",
-                            Value::Dimension(Some(n), u, true).inspect(self.span)?,
+                            Value::Dimension(n, u, true).inspect(self.span)?,
                             right.inspect(self.span)?
                         ),
                         self.span,
@@ -961,27 +984,18 @@ impl<'a, 'b: 'a, 'c> ValueVisitor<'a, 'b, 'c> {
             v => panic!("{:?}", v),
         };
 
+        // `NaN` is unordered, so every comparison involving it is false
         let ordering = left.cmp(&right, self.span, op)?;
 
-        Ok(match op {
-            Op::GreaterThan => match ordering {
-                Ordering::Greater => Value::True,
-                Ordering::Less | Ordering::Equal => Value::False,
-            },
-            Op::GreaterThanEqual => match ordering {
-                Ordering::Greater | Ordering::Equal => Value::True,
-                Ordering::Less => Value::False,
-            },
-            Op::LessThan => match ordering {
-                Ordering::Less => Value::True,
-                Ordering::Greater | Ordering::Equal => Value::False,
-            },
-            Op::LessThanEqual => match ordering {
-                Ordering::Less | Ordering::Equal => Value::True,
-                Ordering::Greater => Value::False,
-            },
+        Ok(Value::bool(match op {
+            Op::GreaterThan => ordering == Some(Ordering::Greater),
+            Op::GreaterThanEqual => {
+                matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+            }
+            Op::LessThan => ordering == Some(Ordering::Less),
+            Op::LessThanEqual => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
             _ => unreachable!(),
-        })
+        }))
     }
 
     pub fn greater_than(
@@ -14,7 +14,7 @@ use crate::{
     lexer::Lexer,
     unit::Unit,
     utils::{is_name, IsWhitespace, ParsedNumber},
-    value::{Number, SassFunction, SassMap, Value},
+    value::{CalculationName, Number, SassFunction, SassMap, Value},
     Token,
 };
 
@@ -23,7 +23,7 @@ use super::eval::{HigherIntermediateValue, ValueVisitor};
 use super::super::Parser;
 
 #[derive(Clone, Debug)]
-enum IntermediateValue {
+pub(super) enum IntermediateValue {
     Value(HigherIntermediateValue),
     Op(Op),
     Comma,
@@ -48,6 +48,41 @@ impl IsWhitespace for IntermediateValue {
 /// We parse a value until the predicate returns true
 type Predicate<'a> = &'a dyn Fn(&mut Parser<'_, '_>) -> bool;
 
+/// Whether `value` is a number literal or the keyword `none`, either of which
+/// may appear on each side of the slash before the alpha channel
+fn is_slash_channel(value: &Value) -> bool {
+    match value {
+        Value::Dimension(_, _, false) => true,
+        Value::String(s, QuoteKind::None) => s.eq_ignore_ascii_case("none"),
+        _ => false,
+    }
+}
+
+/// If the last element of a space separated list is a division of two number
+/// literals, such as `30 / 50%`, replace it with its left hand side and return
+/// its right hand side
+///
+/// Either side may also be `none`, as in `30 / none`
+fn split_slash_alpha(space_separated: &mut Vec<Spanned<HigherIntermediateValue>>) -> Option<Value> {
+    let last = space_separated.pop()?;
+
+    if let HigherIntermediateValue::BinaryOp(left, Op::Div, right) = &last.node {
+        if let (HigherIntermediateValue::Literal(left), HigherIntermediateValue::Literal(right)) =
+            (&**left, &**right)
+        {
+            if is_slash_channel(left) && is_slash_channel(right) {
+                space_separated
+                    .push(HigherIntermediateValue::Literal(left.clone()).span(last.span));
+                return Some(right.clone());
+            }
+        }
+    }
+
+    space_separated.push(last);
+
+    None
+}
+
 impl<'a, 'b> Parser<'a, 'b> {
     /// Parse a value from a stream of tokens
     ///
@@ -56,6 +91,28 @@ impl<'a, 'b> Parser<'a, 'b> {
         &mut self,
         in_paren: bool,
         predicate: Predicate<'_>,
+    ) -> SassResult<Spanned<Value>> {
+        self.parse_value_inner(in_paren, false, predicate)
+    }
+
+    /// Parse the `$channels` argument to a color function such as `rgb()`
+    ///
+    /// This differs from `parse_value` in that a space separated list ending
+    /// in a division of two number literals, as in `10 20 30 / 50%`, is not
+    /// divided. Instead, the result is a slash separated list of the color
+    /// channels and the alpha channel
+    pub(crate) fn parse_color_channels(
+        &mut self,
+        predicate: Predicate<'_>,
+    ) -> SassResult<Spanned<Value>> {
+        self.parse_value_inner(true, true, predicate)
+    }
+
+    fn parse_value_inner(
+        &mut self,
+        in_paren: bool,
+        is_color_channels: bool,
+        predicate: Predicate<'_>,
     ) -> SassResult<Spanned<Value>> {
         self.whitespace();
 
@@ -157,14 +214,25 @@ impl<'a, 'b> Parser<'a, 'b> {
                 .eval(space_separated.pop().unwrap().node, in_paren)?
                 .span(span)
         } else {
-            Value::List(
+            let alpha = if is_color_channels {
+                split_slash_alpha(&mut space_separated)
+            } else {
+                None
+            };
+
+            let list = Value::List(
                 space_separated
                     .into_iter()
                     .map(|a| ValueVisitor::new(self, span).eval(a.node, in_paren))
                     .collect::<SassResult<Vec<Value>>>()?,
                 ListSeparator::Space,
                 Brackets::None,
-            )
+            );
+
+            match alpha {
+                Some(alpha) => Value::List(vec![list, alpha], ListSeparator::Slash, Brackets::None),
+                None => list,
+            }
             .span(span)
         })
     }
@@ -187,8 +255,13 @@ impl<'a, 'b> Parser<'a, 'b> {
             at_root: self.at_root,
             at_root_has_selector: self.at_root_has_selector,
             extender: self.extender,
+            media_queries: self.media_queries,
             content_scopes: self.content_scopes,
             options: self.options,
+            custom_functions: self.custom_functions,
+            loaded_files: self.loaded_files,
+            call_stack: self.call_stack,
+            importer: self.importer,
             modules: self.modules,
             module_config: self.module_config,
         }
@@ -196,7 +269,7 @@ impl<'a, 'b> Parser<'a, 'b> {
     }
 
     #[allow(clippy::eval_order_dependence)]
-    fn parse_module_item(
+    pub(super) fn parse_module_item(
         &mut self,
         mut module: Spanned<Identifier>,
     ) -> SassResult<Spanned<IntermediateValue>> {
@@ -224,7 +297,12 @@ impl<'a, 'b> Parser<'a, 'b> {
 
             self.expect_char('(')?;
 
-            let call_args = self.parse_call_args()?;
+            let call_args = match &function {
+                SassFunction::Builtin(_, name) if name.as_str() == "hwb" => {
+                    self.parse_color_call_args()?
+                }
+                _ => self.parse_call_args()?,
+            };
 
             HigherIntermediateValue::Function(function, call_args, Some(module))
         };
@@ -232,11 +310,20 @@ impl<'a, 'b> Parser<'a, 'b> {
         Ok(IntermediateValue::Value(value).span(module.span))
     }
 
-    fn parse_fn_call(
+    pub(super) fn parse_fn_call(
         &mut self,
         mut s: String,
         lower: String,
     ) -> SassResult<Spanned<IntermediateValue>> {
+        if let Some(name) = CalculationName::from_fn_name(&s) {
+            if let Some(val) = self.try_parse_calculation(name)? {
+                return Ok(
+                    IntermediateValue::Value(HigherIntermediateValue::Literal(val))
+                        .span(self.span_before),
+                );
+            }
+        }
+
         if lower == "min" || lower == "max" {
             let start = self.toks.cursor();
             match self.try_parse_min_max(&lower, true)? {
@@ -256,10 +343,25 @@ impl<'a, 'b> Parser<'a, 'b> {
         let func = match self.scopes.get_fn(as_ident, self.global_scope) {
             Some(f) => f,
             None => {
+                if let Some(f) = self.get_custom_function(as_ident) {
+                    return Ok(IntermediateValue::Value(HigherIntermediateValue::Function(
+                        f,
+                        self.parse_call_args()?,
+                        None,
+                    ))
+                    .span(self.span_before));
+                }
+
                 if let Some(f) = GLOBAL_FUNCTIONS.get(as_ident.as_str()) {
+                    let call_args = match as_ident.as_str() {
+                        "rgb" | "rgba" | "hsl" | "hsla" | "hwb" | "lab" | "lch" | "oklab"
+                        | "oklch" | "color" => self.parse_color_call_args()?,
+                        _ => self.parse_call_args()?,
+                    };
+
                     return Ok(IntermediateValue::Value(HigherIntermediateValue::Function(
                         SassFunction::Builtin(f.clone(), as_ident),
-                        self.parse_call_args()?,
+                        call_args,
                         None,
                     ))
                     .span(self.span_before));
@@ -562,19 +664,19 @@ impl<'a, 'b> Parser<'a, 'b> {
         let n = if val.dec_len == 0 {
             if val.num.len() <= 18 && val.times_ten.is_empty() {
                 let n = Rational64::new_raw(parse_i64(&val.num), 1);
-                return Ok(Value::Dimension(Some(Number::new_small(n)), unit, false).span(span));
+                return Ok(Value::Dimension(Number::new_small(n), unit, false).span(span));
             }
             BigRational::new_raw(val.num.parse::<BigInt>().unwrap(), BigInt::one())
         } else {
             if val.num.len() <= 18 && val.times_ten.is_empty() {
                 let n = Rational64::new(parse_i64(&val.num), pow(10, val.dec_len));
-                return Ok(Value::Dimension(Some(Number::new_small(n)), unit, false).span(span));
+                return Ok(Value::Dimension(Number::new_small(n), unit, false).span(span));
             }
             BigRational::new(val.num.parse().unwrap(), pow(BigInt::from(10), val.dec_len))
         };
 
         if val.times_ten.is_empty() {
-            return Ok(Value::Dimension(Some(Number::new_big(n)), unit, false).span(span));
+            return Ok(Value::Dimension(Number::new_big(n), unit, false).span(span));
         }
 
         let times_ten = pow(
@@ -592,7 +694,7 @@ impl<'a, 'b> Parser<'a, 'b> {
             BigRational::new(BigInt::one(), times_ten)
         };
 
-        Ok(Value::Dimension(Some(Number::new_big(n * times_ten)), unit, false).span(span))
+        Ok(Value::Dimension(Number::new_big(n * times_ten), unit, false).span(span))
     }
 
     fn parse_paren(&mut self) -> SassResult<Spanned<IntermediateValue>> {
@@ -25,7 +25,11 @@ impl<'a, 'b> Parser<'a, 'b> {
     pub(super) fn parse_variable_declaration(&mut self) -> SassResult<()> {
         let next = self.toks.next();
         assert!(matches!(next, Some(Token { kind: '$', .. })));
-        let ident: Identifier = self.parse_identifier_no_interpolation(false)?.node.into();
+        let Spanned {
+            node: name,
+            span: name_span,
+        } = self.parse_identifier_no_interpolation(false)?;
+        let ident: Identifier = name.into();
         self.whitespace_or_comment();
 
         self.expect_char(':')?;
@@ -36,6 +40,19 @@ impl<'a, 'b> Parser<'a, 'b> {
             default,
         } = self.parse_variable_value()?;
 
+        if global && !self.global_scope.var_exists(ident) {
+            let span = next.map_or(name_span, |tok| tok.pos.merge(name_span));
+
+            self.deprecation_warning(
+                &if self.at_root {
+                    "As of Dart Sass 2.0.0, !global assignments won't be able to declare new variables.\n\nSince this assignment is at the root of the stylesheet, the !global flag is\nunnecessary and can safely be removed.".to_owned()
+                } else {
+                    format!("As of Dart Sass 2.0.0, !global assignments won't be able to declare new variables.\n\nRecommendation: add `${}: null` at the stylesheet root.", ident)
+                },
+                span,
+            );
+        }
+
         if default {
             let config_val = self.module_config.get(ident).filter(|v| !v.is_null());
 
@@ -0,0 +1,157 @@
+//! Evaluation of SassScript one line at a time, as used by `grass --interactive`
+//!
+//! Variables, functions, mixins and modules loaded with `@use` persist between
+//! lines, as if each line were the next statement of a single stylesheet.
+
+use std::path::{Path, PathBuf};
+
+use codemap::{CodeMap, Spanned};
+
+use crate::{
+    builtin::modules::{ModuleConfig, Modules},
+    common::Identifier,
+    custom_function::{self, CustomFunctionSignature},
+    error::SassResult,
+    lexer::Lexer,
+    parse::{
+        common::{ContextFlags, NeverEmptyVec},
+        Parser,
+    },
+    raw_to_parse_error,
+    scope::{Scope, Scopes},
+    selector::{ExtendedSelector, Extender, SelectorList},
+    Options, Result,
+};
+
+/// An interactive SassScript shell
+///
+/// ```
+/// fn main() -> Result<(), Box<grass::Error>> {
+///     let options = grass::Options::default();
+///     let mut repl = grass::Repl::new(&options)?;
+///     repl.evaluate("@use \"sass:math\"")?;
+///     assert_eq!(Some("2px".to_owned()), repl.evaluate("$a: math.div(4px, 2)")?);
+///     assert_eq!(Some("3px".to_owned()), repl.evaluate("$a + 1")?);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Repl<'a> {
+    options: &'a Options<'a>,
+    /// Every line evaluated so far. Functions and mixins keep the tokens of
+    /// their body, so these must outlive the line that declared them
+    map: CodeMap,
+    global_scope: Scope,
+    modules: Modules,
+    custom_functions: Vec<CustomFunctionSignature>,
+    loaded_files: Vec<PathBuf>,
+}
+
+impl<'a> Repl<'a> {
+    /// Create a shell with no variables or modules
+    ///
+    /// This fails if the signature of a function registered with
+    /// [`Options::add_function`] is invalid.
+    #[inline]
+    pub fn new(options: &'a Options<'a>) -> Result<Self> {
+        let mut map = CodeMap::new();
+
+        let custom_functions = custom_function::parse_signatures(&mut map, options)
+            .map_err(|e| raw_to_parse_error(&map, *e, options.unicode_error_messages))?;
+
+        Ok(Self {
+            options,
+            map,
+            global_scope: Scope::new(),
+            modules: Modules::default(),
+            custom_functions,
+            loaded_files: Vec::new(),
+        })
+    }
+
+    /// Evaluate a single line.
+    ///
+    /// Expressions and variable declarations evaluate to the inspected
+    /// value, like the output of `@debug`. Other statements, such as `@use`
+    /// or `@function`, evaluate to `None`.
+    #[inline]
+    pub fn evaluate(&mut self, line: &str) -> Result<Option<String>> {
+        let options = self.options;
+
+        self.evaluate_line(line)
+            .map_err(|e| raw_to_parse_error(&self.map, *e, options.unicode_error_messages))
+    }
+
+    fn evaluate_line(&mut self, line: &str) -> SassResult<Option<String>> {
+        let variable = declared_variable(line);
+        let is_statement = variable.is_some() || line.trim_start().starts_with('@');
+
+        let mut source = line.trim_end().to_owned();
+
+        // statements on their own line don't need to be terminated
+        if is_statement && !source.ends_with(';') && !source.ends_with('}') {
+            source.push(';');
+        }
+
+        let file = self.map.add_file("stdin".to_owned(), source);
+        let empty_span = file.span.subspan(0, 0);
+
+        let mut parser = Parser {
+            toks: &mut Lexer::new_from_file(&file),
+            map: &mut self.map,
+            path: Path::new(""),
+            scopes: &mut Scopes::new(),
+            global_scope: &mut self.global_scope,
+            super_selectors: &mut NeverEmptyVec::new(ExtendedSelector::new(SelectorList::new(
+                empty_span,
+            ))),
+            span_before: empty_span,
+            content: &mut Vec::new(),
+            flags: ContextFlags::empty(),
+            at_root: true,
+            at_root_has_selector: false,
+            extender: &mut Extender::new(empty_span),
+            media_queries: &mut None,
+            content_scopes: &mut Scopes::new(),
+            options: self.options,
+            custom_functions: &self.custom_functions,
+            loaded_files: &mut self.loaded_files,
+            call_stack: &mut Vec::new(),
+            importer: None,
+            modules: &mut self.modules,
+            module_config: &mut ModuleConfig::default(),
+        };
+
+        if is_statement {
+            // any CSS emitted by the statement is discarded
+            parser.parse()?;
+
+            return match variable {
+                Some(name) => match parser.global_scope.vars.get(&name) {
+                    Some(value) => Ok(Some(value.inspect(empty_span)?.into_owned())),
+                    None => Ok(None),
+                },
+                None => Ok(None),
+            };
+        }
+
+        let Spanned { node: value, span } = parser.parse_entire_value()?;
+
+        Ok(Some(value.inspect(span)?.into_owned()))
+    }
+}
+
+/// The name of the variable declared by `line`, if it is a declaration such
+/// as `$a: 1`
+fn declared_variable(line: &str) -> Option<Identifier> {
+    let rest = line.trim_start().strip_prefix('$')?;
+    let name_len = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()))
+        .unwrap_or_else(|| rest.len());
+
+    if name_len == 0 || !rest[name_len..].trim_start().starts_with(':') {
+        return None;
+    }
+
+    Some(Identifier::from(&rest[..name_len]))
+}
@@ -1,6 +1,8 @@
 use codemap::Span;
 
-use super::{ComplexSelector, CssMediaQuery, SimpleSelector};
+use crate::{atrule::media::MediaQuery, error::SassResult};
+
+use super::{ComplexSelector, SimpleSelector};
 
 #[derive(Clone, Debug)]
 pub(crate) struct Extension {
@@ -25,15 +27,15 @@ pub(crate) struct Extension {
 
     /// The media query context to which this extend is restricted, or `None` if
     /// it can apply within any context.
-    pub media_context: Option<Vec<CssMediaQuery>>,
+    pub media_context: Option<Vec<MediaQuery>>,
 
     /// The span in which `extender` was defined.
     pub span: Span,
 
-    #[allow(dead_code)]
+    /// The extensions this was created from, if it was created by
+    /// `MergedExtension::merge`
     pub left: Option<Box<Extension>>,
 
-    #[allow(dead_code)]
     pub right: Option<Box<Extension>>,
 }
 
@@ -59,16 +61,36 @@ impl Extension {
 
     /// Asserts that the `media_context` for a selector is compatible with the
     /// query context for this extender.
-    // todo: this should return a `Result`. it currently does not because the cascade effect
-    // from this returning a `Result` will make some code returning `Option`s much uglier (we can't
-    // use `?` to return both `Option` and `Result` from the same function)
-    #[allow(clippy::needless_return)]
-    pub fn assert_compatible_media_context(&self, media_context: &Option<Vec<CssMediaQuery>>) {
-        if &self.media_context == media_context {
-            return;
+    pub fn assert_compatible_media_context(
+        &self,
+        media_context: &Option<Vec<MediaQuery>>,
+    ) -> SassResult<()> {
+        if self.media_context.is_none() || &self.media_context == media_context {
+            return Ok(());
         }
 
-        // Err(("You may not @extend selectors across media queries.", self.span).into())
+        Err((
+            "You may not @extend selectors across media queries.",
+            self.span,
+        )
+            .into())
+    }
+
+    /// Returns all leaf-node `Extension`s in the tree of merged extensions
+    /// this was created from, or only itself if it wasn't merged.
+    pub fn unmerge(self) -> Vec<Extension> {
+        match (self.left, self.right) {
+            (Some(left), Some(right)) => {
+                let mut extensions = left.unmerge();
+                extensions.append(&mut right.unmerge());
+                extensions
+            }
+            (left, right) => vec![Self {
+                left,
+                right,
+                ..self
+            }],
+        }
     }
 
     #[allow(clippy::missing_const_for_fn)]
@@ -7,7 +7,7 @@ use codemap::Span;
 
 use indexmap::IndexMap;
 
-use crate::error::SassResult;
+use crate::{atrule::media::MediaQuery, error::SassResult};
 
 use super::{
     ComplexSelector, ComplexSelectorComponent, ComplexSelectorHashSet, CompoundSelector, Pseudo,
@@ -28,9 +28,6 @@ mod functions;
 mod merged;
 mod rule;
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub(crate) struct CssMediaQuery;
-
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 /// Different modes in which extension can run.
 enum ExtendMode {
@@ -79,7 +76,7 @@ pub(crate) struct Extender {
     ///
     /// This tracks the contexts in which each selector's style rule is defined.
     /// If a rule is defined at the top level, it doesn't have an entry.
-    media_contexts: HashMap<SelectorList, Vec<CssMediaQuery>>,
+    media_contexts: HashMap<ExtendedSelector, Vec<MediaQuery>>,
 
     /// A map from `SimpleSelector`s to the specificity of their source
     /// selectors.
@@ -191,7 +188,7 @@ impl Extender {
             extender.originals.extend(selector.components.iter());
         }
 
-        Ok(extender.extend_list(selector, Some(&extensions), &None))
+        extender.extend_list(selector, Some(&extensions), &None)
     }
 
     fn with_mode(mode: ExtendMode, span: Span) -> Self {
@@ -206,14 +203,14 @@ impl Extender {
         &mut self,
         list: SelectorList,
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
-        media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> SelectorList {
+        media_query_context: &Option<Vec<MediaQuery>>,
+    ) -> SassResult<SelectorList> {
         // This could be written more simply using Vec<Vec<T>>, but we want to avoid
         // any allocations in the common case where no extends apply.
         let mut extended: Option<Vec<ComplexSelector>> = None;
         for (i, complex) in list.components.iter().enumerate() {
             if let Some(result) =
-                self.extend_complex(complex.clone(), extensions, media_query_context)
+                self.extend_complex(complex.clone(), extensions, media_query_context)?
             {
                 if extended.is_none() {
                     extended = Some(if i == 0 {
@@ -233,13 +230,13 @@ impl Extender {
 
         let extended = match extended {
             Some(v) => v,
-            None => return list,
+            None => return Ok(list),
         };
 
-        SelectorList {
+        Ok(SelectorList {
             components: self.trim(extended, &|complex| self.originals.contains(complex)),
             span: self.span,
-        }
+        })
     }
 
     /// Extends `complex` using `extensions`, and returns the contents of a
@@ -248,8 +245,8 @@ impl Extender {
         &mut self,
         complex: ComplexSelector,
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
-        media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> Option<Vec<ComplexSelector>> {
+        media_query_context: &Option<Vec<MediaQuery>>,
+    ) -> SassResult<Option<Vec<ComplexSelector>>> {
         // The complex selectors that each compound selector in `complex.components`
         // can expand to.
         //
@@ -276,7 +273,7 @@ impl Extender {
         for (i, component) in complex.components.iter().enumerate() {
             if let ComplexSelectorComponent::Compound(component) = component {
                 if let Some(extended) =
-                    self.extend_compound(component, extensions, media_query_context, is_original)
+                    self.extend_compound(component, extensions, media_query_context, is_original)?
                 {
                     if extended_not_expanded.is_none() {
                         extended_not_expanded = Some(
@@ -312,11 +309,14 @@ impl Extender {
             }
         }
 
-        let extended_not_expanded = extended_not_expanded?;
+        let extended_not_expanded = match extended_not_expanded {
+            Some(v) => v,
+            None => return Ok(None),
+        };
 
         let mut first = true;
 
-        Some(
+        Ok(Some(
             paths(extended_not_expanded)
                 .into_iter()
                 .flat_map(move |path| {
@@ -347,7 +347,7 @@ impl Extender {
                     .collect::<Vec<ComplexSelector>>()
                 })
                 .collect(),
-        )
+        ))
     }
 
     /// Extends `compound` using `extensions`, and returns the contents of a
@@ -359,9 +359,9 @@ impl Extender {
         &mut self,
         compound: &CompoundSelector,
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
-        media_query_context: &Option<Vec<CssMediaQuery>>,
+        media_query_context: &Option<Vec<MediaQuery>>,
         in_original: bool,
-    ) -> Option<Vec<ComplexSelector>> {
+    ) -> SassResult<Option<Vec<ComplexSelector>>> {
         // If there's more than one target and they all need to match, we track
         // which targets are actually extended.
         let mut targets_used: HashSet<SimpleSelector> = HashSet::new();
@@ -376,7 +376,7 @@ impl Extender {
                 extensions,
                 media_query_context,
                 &mut targets_used,
-            ) {
+            )? {
                 Some(extended) => {
                     if options.is_none() {
                         let mut new_options = Vec::new();
@@ -400,7 +400,10 @@ impl Extender {
             }
         }
 
-        let options = options?;
+        let options = match options {
+            Some(v) => v,
+            None => return Ok(None),
+        };
 
         // If `self.mode` isn't `ExtendMode::Normal` and we didn't use all the targets in
         // `extensions`, extension fails for `compound`.
@@ -409,23 +412,23 @@ impl Extender {
             && targets_used.len() != extensions.map_or(self.extensions.len(), HashMap::len)
             && self.mode != ExtendMode::Normal
         {
-            return None;
+            return Ok(None);
         }
 
         // Optimize for the simple case of a single simple selector that doesn't
         // need any unification.
         if options.len() == 1 {
-            return Some(
-                options
-                    .first()?
-                    .clone()
-                    .into_iter()
-                    .map(|state| {
-                        state.assert_compatible_media_context(media_query_context);
-                        state.extender
-                    })
-                    .collect(),
-            );
+            return options
+                .into_iter()
+                .next()
+                .unwrap()
+                .into_iter()
+                .map(|state| {
+                    state.assert_compatible_media_context(media_query_context)?;
+                    Ok(state.extender)
+                })
+                .collect::<SassResult<Vec<ComplexSelector>>>()
+                .map(Some);
         }
 
         // Find all paths through `options`. In this case, each path represents a
@@ -496,41 +499,49 @@ impl Extender {
                     )]);
                 }
 
-                unify_complex(Vec::from(to_unify))?
+                match unify_complex(Vec::from(to_unify)) {
+                    Some(v) => v,
+                    None => return Ok(None),
+                }
             };
 
             let mut line_break = false;
 
             for state in path {
-                state.assert_compatible_media_context(media_query_context);
+                state.assert_compatible_media_context(media_query_context)?;
                 line_break = line_break || state.extender.line_break;
             }
 
-            Some(
+            Ok(Some(
                 complexes
                     .into_iter()
                     .map(|components| ComplexSelector::new(components, line_break))
                     .collect::<Vec<ComplexSelector>>(),
-            )
+            ))
         });
 
-        let unified_paths: Vec<ComplexSelector> = unified_paths.flatten().flatten().collect();
+        let unified_paths: Vec<ComplexSelector> = unified_paths
+            .collect::<SassResult<Vec<Option<Vec<ComplexSelector>>>>>()?
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect();
 
-        Some(if in_original && self.mode != ExtendMode::Replace {
+        Ok(Some(if in_original && self.mode != ExtendMode::Replace {
             let original = unified_paths.first().cloned();
             self.trim(unified_paths, &|complex| Some(complex) == original.as_ref())
         } else {
             self.trim(unified_paths, &|_| false)
-        })
+        }))
     }
 
     fn extend_simple(
         &mut self,
         simple: SimpleSelector,
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
-        media_query_context: &Option<Vec<CssMediaQuery>>,
+        media_query_context: &Option<Vec<MediaQuery>>,
         targets_used: &mut HashSet<SimpleSelector>,
-    ) -> Option<Vec<Vec<Extension>>> {
+    ) -> SassResult<Option<Vec<Vec<Extension>>>> {
         if let SimpleSelector::Pseudo(Pseudo {
             selector: Some(..), ..
         }) = &simple
@@ -540,8 +551,8 @@ impl Extender {
             } else {
                 unreachable!()
             };
-            if let Some(extended) = self.extend_pseudo(simple, extensions, media_query_context) {
-                return Some(
+            if let Some(extended) = self.extend_pseudo(simple, extensions, media_query_context)? {
+                return Ok(Some(
                     extended
                         .into_iter()
                         .map(move |pseudo| {
@@ -556,12 +567,13 @@ impl Extender {
                             })
                         })
                         .collect(),
-                );
+                ));
             }
         }
 
-        self.without_pseudo(simple, extensions, targets_used, self.mode)
-            .map(|v| vec![v])
+        Ok(self
+            .without_pseudo(simple, extensions, targets_used, self.mode)
+            .map(|v| vec![v]))
     }
 
     /// Extends `pseudo` using `extensions`, and returns a list of resulting
@@ -570,8 +582,8 @@ impl Extender {
         &mut self,
         pseudo: Pseudo,
         extensions: Option<&HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>,
-        media_query_context: &Option<Vec<CssMediaQuery>>,
-    ) -> Option<Vec<Pseudo>> {
+        media_query_context: &Option<Vec<MediaQuery>>,
+    ) -> SassResult<Option<Vec<Pseudo>>> {
         let extended = self.extend_list(
             pseudo
                 .selector
@@ -580,10 +592,10 @@ impl Extender {
                 .unwrap_or_else(|| SelectorList::new(self.span)),
             extensions,
             media_query_context,
-        );
+        )?;
         /*todo: identical(extended, pseudo.selector)*/
         if Some(&extended) == pseudo.selector.as_deref() {
-            return None;
+            return Ok(None);
         }
 
         // For `:not()`, we usually want to get rid of any complex selectors because
@@ -690,16 +702,18 @@ impl Extender {
                     })))
                 })
                 .collect::<Vec<Pseudo>>();
-            if result.is_empty() {
+            Ok(if result.is_empty() {
                 None
             } else {
                 Some(result)
-            }
+            })
         } else {
-            Some(vec![pseudo.with_selector(Some(Box::new(SelectorList {
-                components: complexes,
-                span: self.span,
-            })))])
+            Ok(Some(vec![pseudo.with_selector(Some(Box::new(
+                SelectorList {
+                    components: complexes,
+                    span: self.span,
+                },
+            )))]))
         }
     }
 
@@ -865,8 +879,8 @@ impl Extender {
         &mut self,
         mut selector: SelectorList,
         // span: Span,
-        media_query_context: Option<Vec<CssMediaQuery>>,
-    ) -> ExtendedSelector {
+        media_query_context: Option<Vec<MediaQuery>>,
+    ) -> SassResult<ExtendedSelector> {
         if !selector.is_invisible() {
             for complex in selector.components.clone() {
                 self.originals.insert(&complex);
@@ -874,7 +888,7 @@ impl Extender {
         }
 
         if !self.extensions.is_empty() {
-            selector = self.extend_list(selector, None, &media_query_context);
+            selector = self.extend_list(selector, None, &media_query_context)?;
             /*
               todo: when we have error handling
                   } on SassException catch (error) {
@@ -885,14 +899,13 @@ impl Extender {
             }
               */
         }
-        if let Some(mut media_query_context) = media_query_context {
+        let extended_selector = ExtendedSelector::new(selector.clone());
+        if let Some(media_query_context) = media_query_context {
             self.media_contexts
-                .get_mut(&selector)
-                .replace(&mut media_query_context);
+                .insert(extended_selector.clone(), media_query_context);
         }
-        let extended_selector = ExtendedSelector::new(selector.clone());
         self.register_selector(selector, &extended_selector);
-        extended_selector
+        Ok(extended_selector)
     }
 
     /// Registers the `SimpleSelector`s in `list` to point to `selector` in
@@ -941,9 +954,9 @@ impl Extender {
         extender: SelectorList,
         target: &SimpleSelector,
         extend: &ExtendRule,
-        media_context: &Option<Vec<CssMediaQuery>>,
+        media_context: &Option<Vec<MediaQuery>>,
         span: Span,
-    ) {
+    ) -> SassResult<()> {
         let selectors = self.selectors.get(target).cloned();
         let existing_extensions = self.extensions_by_extender.get(target).cloned();
 
@@ -971,7 +984,7 @@ impl Extender {
                 // If there's already an extend from `extender` to `target`, we don't need
                 // to re-run the extension. We may need to mark the extension as
                 // mandatory, though.
-                let mut new_val = MergedExtension::merge(existing_state.clone(), state).unwrap();
+                let mut new_val = MergedExtension::merge(existing_state.clone(), state)?;
                 sources.get_mut(&complex).replace(&mut new_val);
                 continue;
             }
@@ -1004,7 +1017,7 @@ impl Extender {
         let new_extensions = if let Some(new) = new_extensions {
             new
         } else {
-            return;
+            return Ok(());
         };
 
         let mut new_extensions_by_target = HashMap::new();
@@ -1012,14 +1025,48 @@ impl Extender {
 
         if let Some(existing_extensions) = existing_extensions {
             let additional_extensions =
-                self.extend_existing_extensions(existing_extensions, &new_extensions_by_target);
+                self.extend_existing_extensions(existing_extensions, &new_extensions_by_target)?;
             if let Some(additional_extensions) = additional_extensions {
                 map_add_all_2(&mut new_extensions_by_target, additional_extensions);
             }
         }
 
         if let Some(selectors) = selectors {
-            self.extend_existing_selectors(selectors, &new_extensions_by_target);
+            self.extend_existing_selectors(selectors, &new_extensions_by_target)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error for the first `@extend` that isn't `!optional` and whose
+    /// target doesn't appear in any style rule.
+    ///
+    /// This must only be called once the entire stylesheet has been parsed,
+    /// since a target may be defined after the `@extend` that refers to it.
+    pub fn check_unsatisfied_extensions(&self) -> SassResult<()> {
+        let unsatisfied = self
+            .extensions
+            .iter()
+            .filter(|(target, ..)| !self.selectors.contains_key(target))
+            .flat_map(|(.., sources)| sources.values())
+            .flat_map(|extension| extension.clone().unmerge())
+            .filter(|extension| !extension.is_optional)
+            .min_by_key(|extension| extension.span.low());
+
+        match unsatisfied {
+            Some(Extension {
+                target: Some(target),
+                span,
+                ..
+            }) => Err((
+                format!(
+                    "The target selector was not found.\nUse \"@extend {} !optional\" to avoid this error.",
+                    target
+                ),
+                span,
+            )
+                .into()),
+            Some(..) | None => Ok(()),
         }
     }
 
@@ -1042,7 +1089,7 @@ impl Extender {
         &mut self,
         extensions: Vec<Extension>,
         new_extensions: &HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
-    ) -> Option<HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>> {
+    ) -> SassResult<Option<HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>>> {
         let mut additional_extensions: Option<
             HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
         > = None;
@@ -1058,7 +1105,7 @@ impl Extender {
                 extension.extender.clone(),
                 Some(new_extensions),
                 &extension.media_context,
-            ) {
+            )? {
                 v
             } else {
                 continue;
@@ -1087,10 +1134,12 @@ impl Extender {
                 let with_extender = extension.clone().with_extender(complex.clone());
                 let existing_extension = sources.get(&complex);
                 if let Some(existing_extension) = existing_extension.cloned() {
-                    sources.get_mut(&complex).replace(
-                        &mut MergedExtension::merge(existing_extension.clone(), with_extender)
-                            .unwrap(),
-                    );
+                    sources
+                        .get_mut(&complex)
+                        .replace(&mut MergedExtension::merge(
+                            existing_extension.clone(),
+                            with_extender,
+                        )?);
                 } else {
                     sources
                         .get_mut(&complex)
@@ -1123,7 +1172,7 @@ impl Extender {
                 sources.remove(&extension.extender);
             }
         }
-        additional_extensions
+        Ok(additional_extensions)
     }
 
     /// Extend `extensions` using `new_extensions`.
@@ -1131,14 +1180,14 @@ impl Extender {
         &mut self,
         selectors: SelectorHashSet,
         new_extensions: &HashMap<SimpleSelector, IndexMap<ComplexSelector, Extension>>,
-    ) {
+    ) -> SassResult<()> {
         for mut selector in selectors {
             let old_value = selector.clone().into_selector().0;
             selector.set_inner(self.extend_list(
                 old_value.clone(),
                 Some(new_extensions),
-                &self.media_contexts.get(&old_value).cloned(),
-            ));
+                &self.media_contexts.get(&selector).cloned(),
+            )?);
             /*
             todo: error handling
             } on SassException catch (error) {
@@ -1158,6 +1207,8 @@ impl Extender {
             }
             self.register_selector(selector_as_selector, &selector);
         }
+
+        Ok(())
     }
 }
 
@@ -0,0 +1,294 @@
+//! # Generation of source maps
+//!
+//! Implements revision 3 of the [source map format](https://sourcemaps.info/spec.html).
+//!
+//! Mappings are collected by the formatters in `crate::output` as pairs of a
+//! byte offset into the generated CSS and the `Span` of the Sass that produced
+//! it. These are then resolved into lines and columns and serialized here.
+use std::{
+    fmt::Write,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use codemap::{CodeMap, File, Span};
+
+use crate::Options;
+
+/// How to link from source maps to source files
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceMapUrls {
+    /// Source files are referenced by paths relative to the location of the
+    /// source map. See [`Options::source_map_path`](crate::Options::source_map_path).
+    Relative,
+    /// Source files are referenced by absolute `file:` URLs
+    Absolute,
+}
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Append `value` to `buf` as a base64 VLQ
+fn write_vlq(buf: &mut String, value: i64) {
+    let mut vlq = if value < 0 {
+        ((-value) << 1) | 1
+    } else {
+        value << 1
+    };
+
+    loop {
+        let mut digit = vlq & 0b1_1111;
+        vlq >>= 5;
+
+        if vlq > 0 {
+            digit |= 0b10_0000;
+        }
+
+        buf.push(BASE64_CHARS[digit as usize] as char);
+
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+fn write_json_string(buf: &mut String, s: &str) {
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            '\u{0}'..='\u{1f}' => {
+                write!(buf, "\\u{:04x}", c as u32).unwrap();
+            }
+            _ => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
+fn percent_encode(buf: &mut String, s: &str) {
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' => {
+                buf.push(b as char);
+            }
+            _ => {
+                write!(buf, "%{:02X}", b).unwrap();
+            }
+        }
+    }
+}
+
+fn absolute_path(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+
+    std::env::current_dir()
+        .map(|dir| dir.join(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Resolve `.` and `..` components without touching the file system
+fn normalize(path: &Path) -> Vec<Component<'_>> {
+    let mut components = Vec::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match components.last() {
+                Some(Component::Normal(..)) => {
+                    components.pop();
+                }
+                Some(..) | None => components.push(component),
+            },
+            _ => components.push(component),
+        }
+    }
+
+    components
+}
+
+fn url_from_components<'a>(components: impl Iterator<Item = Component<'a>>) -> String {
+    components
+        .map(|c| match c {
+            Component::ParentDir => "..".to_owned(),
+            Component::Normal(s) => s.to_string_lossy().into_owned(),
+            Component::RootDir | Component::CurDir | Component::Prefix(..) => String::new(),
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+fn relative_url(path: &Path, base_dir: &Path) -> String {
+    let path = absolute_path(path);
+    let base_dir = absolute_path(base_dir);
+
+    let path = normalize(&path);
+    let base = normalize(&base_dir);
+
+    let common = path
+        .iter()
+        .zip(base.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    // paths on different windows drives can't be made relative
+    if common == 0 {
+        return absolute_url(&path);
+    }
+
+    let parents = std::iter::repeat(Component::ParentDir).take(base.len() - common);
+
+    let mut url = String::new();
+    percent_encode(
+        &mut url,
+        &url_from_components(parents.chain(path[common..].iter().copied())),
+    );
+    url
+}
+
+fn absolute_url(components: &[Component]) -> String {
+    let mut url = String::from("file://");
+
+    let mut path = url_from_components(components.iter().copied());
+
+    if let Some(Component::Prefix(prefix)) = components.first() {
+        path = format!("{}{}", prefix.as_os_str().to_string_lossy(), path);
+    }
+
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+
+    percent_encode(&mut url, &path.replace('\\', "/"));
+    url
+}
+
+/// Build a source map describing `css`
+///
+/// `mappings` must be sorted by their offset into `css`. `input_name` is the
+/// name given to the entrypoint if it was not read from disk.
+pub(crate) fn build_source_map(
+    css: &str,
+    mappings: &[(usize, Span)],
+    map: &CodeMap,
+    input_name: Option<&str>,
+    options: &Options,
+) -> String {
+    let mut sources: Vec<Arc<File>> = Vec::new();
+    let mut encoded = String::new();
+
+    let mut generated_column = 0_i64;
+
+    let mut prev_column = 0_i64;
+    let mut prev_source = 0_i64;
+    let mut prev_source_line = 0_i64;
+    let mut prev_source_column = 0_i64;
+
+    let mut is_first_in_line = true;
+
+    let mut last_offset = 0;
+
+    for &(offset, span) in mappings {
+        // advance the generated position to `offset`, counting columns in
+        // UTF-16 code units as required by the spec
+        for c in css[last_offset..offset].chars() {
+            if c == '\n' {
+                generated_column = 0;
+                encoded.push(';');
+                prev_column = 0;
+                is_first_in_line = true;
+            } else {
+                generated_column += c.len_utf16() as i64;
+            }
+        }
+        last_offset = offset;
+
+        let loc = map.look_up_span(span);
+        let name = loc.file.name();
+
+        let source_idx = match sources.iter().position(|file| file.name() == name) {
+            Some(idx) => idx,
+            None => {
+                sources.push(Arc::clone(&loc.file));
+                sources.len() - 1
+            }
+        } as i64;
+
+        let source_line = loc.begin.line as i64;
+        let source_column = loc
+            .file
+            .source_line(loc.begin.line)
+            .chars()
+            .take(loc.begin.column)
+            .map(char::len_utf16)
+            .sum::<usize>() as i64;
+
+        if !is_first_in_line {
+            encoded.push(',');
+        }
+        is_first_in_line = false;
+
+        write_vlq(&mut encoded, generated_column - prev_column);
+        write_vlq(&mut encoded, source_idx - prev_source);
+        write_vlq(&mut encoded, source_line - prev_source_line);
+        write_vlq(&mut encoded, source_column - prev_source_column);
+
+        prev_column = generated_column;
+        prev_source = source_idx;
+        prev_source_line = source_line;
+        prev_source_column = source_column;
+    }
+
+    let base_dir = options
+        .source_map_path
+        .and_then(Path::parent)
+        .unwrap_or_else(|| Path::new(""));
+
+    let mut buf = String::from("{\"version\":3,\"sourceRoot\":\"\",\"sources\":[");
+
+    for (idx, file) in sources.iter().enumerate() {
+        if idx != 0 {
+            buf.push(',');
+        }
+
+        let name = file.name();
+        let path = Path::new(name);
+
+        // input that was not loaded from disk keeps its name as-is
+        let url = if Some(name) == input_name || !options.fs.is_file(path) {
+            name.to_owned()
+        } else {
+            match options.source_map_urls {
+                SourceMapUrls::Relative => relative_url(path, base_dir),
+                SourceMapUrls::Absolute => absolute_url(&normalize(&absolute_path(path))),
+            }
+        };
+
+        write_json_string(&mut buf, &url);
+    }
+
+    buf.push(']');
+
+    if options.embed_sources {
+        buf.push_str(",\"sourcesContent\":[");
+        for (idx, file) in sources.iter().enumerate() {
+            if idx != 0 {
+                buf.push(',');
+            }
+            write_json_string(&mut buf, file.source());
+        }
+        buf.push(']');
+    }
+
+    buf.push_str(",\"names\":[],\"mappings\":");
+    write_json_string(&mut buf, &encoded);
+    buf.push('}');
+
+    buf
+}
@@ -5,7 +5,7 @@ use crate::{error::SassResult, interner::InternedString, value::Value};
 /// A style: `color: red`
 #[derive(Clone, Debug)]
 pub(crate) struct Style {
-    pub property: InternedString,
+    pub property: Spanned<InternedString>,
     pub value: Box<Spanned<Value>>,
 }
 
@@ -13,7 +13,7 @@ impl Style {
     pub fn to_string(&self) -> SassResult<String> {
         Ok(format!(
             "{}: {};",
-            self.property,
+            self.property.node,
             self.value.node.to_css_string(self.value.span, false)?
         ))
     }
@@ -246,6 +246,32 @@ impl Unit {
         }
     }
 
+    /// Whether numbers with these units could be added together once the
+    /// browser resolves them, as in `calc(100% - 10px)`
+    ///
+    /// Units that Sass doesn't know how to convert, such as `%`, are assumed
+    /// to be compatible with any other unit
+    pub fn is_possibly_compatible(&self, other: &Unit) -> bool {
+        if (self == &Unit::None) != (other == &Unit::None) {
+            return false;
+        }
+
+        if matches!(self, Unit::Mul(..) | Unit::Div(..))
+            || matches!(other, Unit::Mul(..) | Unit::Div(..))
+        {
+            return false;
+        }
+
+        match (self.kind(), other.kind()) {
+            (UnitKind::Other, _) | (_, UnitKind::Other) => true,
+            (
+                UnitKind::Absolute | UnitKind::FontRelative | UnitKind::ViewportRelative,
+                UnitKind::Absolute | UnitKind::FontRelative | UnitKind::ViewportRelative,
+            ) => true,
+            (kind, other_kind) => kind == other_kind,
+        }
+    }
+
     /// Used internally to determine if two units are comparable or not
     fn kind(&self) -> UnitKind {
         match self {
@@ -0,0 +1,67 @@
+use std::{cell::Cell, rc::Rc};
+
+use codemap::{Span, Spanned};
+use indexmap::IndexMap;
+
+use crate::{common::Identifier, error::SassResult, value::Value};
+
+/// The value of a rest argument such as `$args...`
+///
+/// Arguments passed by position are its elements, and arguments passed by
+/// name that don't match any other parameter are its keywords.
+#[derive(Debug, Clone)]
+pub(crate) struct ArgList {
+    pub elems: Vec<Spanned<Value>>,
+    keywords: IndexMap<Identifier, Spanned<Value>>,
+    /// Shared between copies of this list, so that accessing the keywords of
+    /// a copy, such as by passing it to `keywords()`, marks the original too
+    were_keywords_accessed: Rc<Cell<bool>>,
+}
+
+impl ArgList {
+    pub fn new(elems: Vec<Spanned<Value>>, keywords: IndexMap<Identifier, Spanned<Value>>) -> Self {
+        Self {
+            elems,
+            keywords,
+            were_keywords_accessed: Rc::new(Cell::new(false)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.elems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// The arguments passed by name, in the order they were passed
+    pub fn keywords(&self) -> &IndexMap<Identifier, Spanned<Value>> {
+        self.were_keywords_accessed.set(true);
+        &self.keywords
+    }
+
+    /// Returns an error if arguments were passed by name but the keywords of
+    /// this list were never accessed, since they would otherwise be silently
+    /// ignored
+    pub fn assert_keywords_accessed(&self, span: Span) -> SassResult<()> {
+        if self.keywords.is_empty() || self.were_keywords_accessed.get() {
+            return Ok(());
+        }
+
+        let mut names: Vec<String> = self
+            .keywords
+            .keys()
+            .map(|name| format!("${}", name))
+            .collect();
+
+        let message = if names.len() == 1 {
+            format!("No argument named {}.", names[0])
+        } else {
+            let last = names.pop().unwrap();
+            format!("No arguments named {} or {}.", names.join(", "), last)
+        };
+
+        Err((message, span).into())
+    }
+}
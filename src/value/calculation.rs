@@ -0,0 +1,357 @@
+use std::cmp::Ordering;
+
+use codemap::Span;
+
+use crate::{
+    common::{Op, QuoteKind},
+    error::SassResult,
+    unit::Unit,
+    value::{Number, Value},
+};
+
+/// The name of a calculation function
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum CalculationName {
+    Calc,
+    Min,
+    Max,
+    Clamp,
+}
+
+impl CalculationName {
+    /// Calculations are only parsed from lowercase function names, so `CALC()`
+    /// and `-webkit-calc()` remain plain CSS functions
+    pub fn from_fn_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "calc" => Self::Calc,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "clamp" => Self::Clamp,
+            _ => return None,
+        })
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Calc => "calc",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Clamp => "clamp",
+        }
+    }
+
+    /// The largest number of arguments this calculation accepts, if any
+    pub const fn max_args(self) -> Option<usize> {
+        match self {
+            Self::Calc => Some(1),
+            Self::Clamp => Some(3),
+            Self::Min | Self::Max => None,
+        }
+    }
+}
+
+/// A calculation that couldn't be simplified to a single number, such as
+/// `calc(100% - 10px)` or `min(1px, var(--width))`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Calculation {
+    pub name: CalculationName,
+    pub args: Vec<CalculationArg>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum CalculationArg {
+    Number(Number, Unit),
+    Calculation(Calculation),
+    /// An unquoted string, such as `var(--gap)` or the result of interpolation
+    String(String),
+    Operation {
+        lhs: Box<CalculationArg>,
+        op: Op,
+        rhs: Box<CalculationArg>,
+    },
+}
+
+impl CalculationArg {
+    /// Convert the result of evaluating part of a calculation, such as a
+    /// variable or function call, into an argument
+    pub fn from_value(value: Value, span: Span) -> SassResult<Self> {
+        Ok(match value {
+            Value::Dimension(n, unit, _) => CalculationArg::Number(n, unit),
+            Value::Calculation(Calculation {
+                name: CalculationName::Calc,
+                mut args,
+            }) => args.pop().unwrap(),
+            Value::Calculation(calc) => CalculationArg::Calculation(calc),
+            Value::String(s, QuoteKind::None) => CalculationArg::String(s),
+            Value::String(..) => {
+                return Err((
+                    format!(
+                        "Quoted string {} can't be used in a calculation.",
+                        value.inspect(span)?
+                    ),
+                    span,
+                )
+                    .into())
+            }
+            _ => {
+                return Err((
+                    format!(
+                        "Value {} can't be used in a calculation.",
+                        value.inspect(span)?
+                    ),
+                    span,
+                )
+                    .into())
+            }
+        })
+    }
+
+    fn to_css_string(&self, span: Span, is_compressed: bool) -> SassResult<String> {
+        Ok(match self {
+            CalculationArg::Number(n, unit) if !n.is_finite() => {
+                n.to_calculation_string(unit, is_compressed)
+            }
+            CalculationArg::Number(n, unit) => Value::Dimension(n.clone(), unit.clone(), true)
+                .to_css_string(span, is_compressed)?
+                .into_owned(),
+            CalculationArg::Calculation(calc) => calc.to_css_string(span, is_compressed)?,
+            CalculationArg::String(s) => s.clone(),
+            CalculationArg::Operation { lhs, op, rhs } => {
+                let mut buf = String::new();
+
+                write_operand(
+                    &mut buf,
+                    lhs,
+                    needs_parens(*op, lhs, false),
+                    span,
+                    is_compressed,
+                )?;
+
+                if is_compressed && matches!(op, Op::Mul | Op::Div) {
+                    buf.push_str(&op.to_string());
+                } else {
+                    buf.push(' ');
+                    buf.push_str(&op.to_string());
+                    buf.push(' ');
+                }
+
+                write_operand(
+                    &mut buf,
+                    rhs,
+                    needs_parens(*op, rhs, true),
+                    span,
+                    is_compressed,
+                )?;
+
+                buf
+            }
+        })
+    }
+}
+
+/// Whether `arg` must be wrapped in parentheses to be used as the left or right
+/// operand of `op`
+fn needs_parens(op: Op, arg: &CalculationArg, is_rhs: bool) -> bool {
+    let arg_op = match arg {
+        CalculationArg::Operation { op, .. } => *op,
+        // `infinity * 1px`
+        CalculationArg::Number(n, unit) if !n.is_finite() && *unit != Unit::None => Op::Mul,
+        _ => return false,
+    };
+
+    if is_rhs {
+        op == Op::Div || (op != Op::Plus && matches!(arg_op, Op::Plus | Op::Minus))
+    } else {
+        arg_op.precedence() < op.precedence()
+    }
+}
+
+fn write_operand(
+    buf: &mut String,
+    arg: &CalculationArg,
+    parenthesize: bool,
+    span: Span,
+    is_compressed: bool,
+) -> SassResult<()> {
+    if parenthesize {
+        buf.push('(');
+    }
+
+    buf.push_str(&arg.to_css_string(span, is_compressed)?);
+
+    if parenthesize {
+        buf.push(')');
+    }
+
+    Ok(())
+}
+
+impl Calculation {
+    /// Simplify `calc(arg)`
+    pub fn calc(arg: CalculationArg) -> Value {
+        match arg {
+            CalculationArg::Number(n, unit) => Value::Dimension(n, unit, true),
+            CalculationArg::Calculation(calc) => Value::Calculation(calc),
+            arg => Value::Calculation(Calculation {
+                name: CalculationName::Calc,
+                args: vec![arg],
+            }),
+        }
+    }
+
+    /// Simplify `min(args...)` or `max(args...)`
+    ///
+    /// If every argument is a number and they can all be compared, this
+    /// returns the smallest or largest of them
+    pub fn min_max(
+        name: CalculationName,
+        args: Vec<CalculationArg>,
+        span: Span,
+    ) -> SassResult<Value> {
+        let wanted = if name == CalculationName::Min {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        };
+
+        let mut extremum: Option<(&Number, &Unit)> = None;
+
+        for arg in &args {
+            match (arg, extremum) {
+                (CalculationArg::Number(n, unit), None) => extremum = Some((n, unit)),
+                (CalculationArg::Number(n, unit), Some((n2, unit2))) if unit.comparable(unit2) => {
+                    if compare(n, unit, n2, unit2) == Some(wanted) {
+                        extremum = Some((n, unit));
+                    }
+                }
+                _ => {
+                    extremum = None;
+                    break;
+                }
+            }
+        }
+
+        if let Some((n, unit)) = extremum {
+            return Ok(Value::Dimension(n.clone(), unit.clone(), true));
+        }
+
+        verify_compatible_numbers(&args, span)?;
+
+        Ok(Value::Calculation(Calculation { name, args }))
+    }
+
+    /// Simplify `clamp(min, value, max)`
+    pub fn clamp(args: Vec<CalculationArg>, span: Span) -> SassResult<Value> {
+        if let [min, value, max] = args.as_slice() {
+            if let (
+                CalculationArg::Number(min, min_unit),
+                CalculationArg::Number(n, unit),
+                CalculationArg::Number(max, max_unit),
+            ) = (min, value, max)
+            {
+                if has_compatible_units(min_unit, unit) && has_compatible_units(min_unit, max_unit)
+                {
+                    let at_least =
+                        |ordering| matches!(ordering, Some(Ordering::Greater | Ordering::Equal));
+
+                    let (n, unit) = if at_least(compare(min, min_unit, n, unit)) {
+                        (min, min_unit)
+                    } else if at_least(compare(n, unit, max, max_unit)) {
+                        (max, max_unit)
+                    } else {
+                        (n, unit)
+                    };
+
+                    return Ok(Value::Dimension(n.clone(), unit.clone(), true));
+                }
+            }
+        }
+
+        verify_compatible_numbers(&args, span)?;
+
+        // a single string, such as the result of interpolation, may stand in
+        // for several arguments
+        if args.len() < 3
+            && !args
+                .iter()
+                .any(|arg| matches!(arg, CalculationArg::String(..)))
+        {
+            return Err((
+                format!(
+                    "3 arguments required, but only {} {} passed.",
+                    args.len(),
+                    if args.len() == 1 { "was" } else { "were" }
+                ),
+                span,
+            )
+                .into());
+        }
+
+        Ok(Value::Calculation(Calculation {
+            name: CalculationName::Clamp,
+            args,
+        }))
+    }
+
+    pub fn to_css_string(&self, span: Span, is_compressed: bool) -> SassResult<String> {
+        Ok(format!(
+            "{}({})",
+            self.name.as_str(),
+            self.args
+                .iter()
+                .map(|arg| arg.to_css_string(span, is_compressed))
+                .collect::<SassResult<Vec<String>>>()?
+                .join(if is_compressed { "," } else { ", " })
+        ))
+    }
+}
+
+/// Whether two numbers can be added or subtracted within `calc()`, which
+/// unlike Sass arithmetic doesn't allow mixing unitless numbers with units
+pub(crate) fn has_compatible_units(unit: &Unit, other: &Unit) -> bool {
+    unit == other || (*unit != Unit::None && *other != Unit::None && unit.comparable(other))
+}
+
+/// Returns an error if any two numbers in `args` could never be compatible,
+/// even once the browser resolves units such as `%`
+pub(crate) fn verify_compatible_numbers(args: &[CalculationArg], span: Span) -> SassResult<()> {
+    for (idx, arg) in args.iter().enumerate() {
+        for other in &args[idx + 1..] {
+            verify_compatible(arg, other, span)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn verify_compatible(
+    arg: &CalculationArg,
+    other: &CalculationArg,
+    span: Span,
+) -> SassResult<()> {
+    match (arg, other) {
+        (CalculationArg::Number(n, unit), CalculationArg::Number(n2, unit2))
+            if !unit.is_possibly_compatible(unit2) =>
+        {
+            Err((
+                format!(
+                    "{} and {} are incompatible.",
+                    Value::Dimension(n.clone(), unit.clone(), true).inspect(span)?,
+                    Value::Dimension(n2.clone(), unit2.clone(), true).inspect(span)?
+                ),
+                span,
+            )
+                .into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns `None` if either number is `NaN`
+fn compare(n: &Number, unit: &Unit, n2: &Number, unit2: &Unit) -> Option<Ordering> {
+    if unit == unit2 || *unit == Unit::None || *unit2 == Unit::None {
+        n.partial_cmp(n2)
+    } else {
+        n.partial_cmp(&n2.clone().convert(unit2, unit))
+    }
+}
@@ -51,6 +51,12 @@ impl SassMap {
         None
     }
 
+    pub fn get_mut(&mut self, key: &Value) -> Option<&mut Value> {
+        self.0
+            .iter_mut()
+            .find_map(|(k, v)| if k == key { Some(v) } else { None })
+    }
+
     pub fn remove(&mut self, key: &Value) {
         self.0.retain(|(ref k, ..)| k.not_equals(key));
     }
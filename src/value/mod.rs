@@ -14,15 +14,23 @@ use crate::{
     {Cow, Token},
 };
 
+pub(crate) use arg_list::ArgList;
+pub(crate) use calculation::{Calculation, CalculationArg, CalculationName};
 use css_function::is_special_function;
 pub(crate) use map::SassMap;
 pub(crate) use number::Number;
 pub(crate) use sass_function::SassFunction;
+pub(crate) use sass_mixin::SassMixin;
+pub(crate) use sass_value::SassValue;
 
+mod arg_list;
+pub(crate) mod calculation;
 pub(crate) mod css_function;
 mod map;
 mod number;
 mod sass_function;
+mod sass_mixin;
+pub(crate) mod sass_value;
 
 #[derive(Debug, Clone)]
 pub(crate) enum Value {
@@ -30,15 +38,17 @@ pub(crate) enum Value {
     True,
     False,
     Null,
-    /// A `None` value for `Number` indicates a `NaN` value
-    Dimension(Option<Number>, Unit, bool),
+    Dimension(Number, Unit, bool),
     List(Vec<Value>, ListSeparator, Brackets),
     Color(Box<Color>),
     String(String, QuoteKind),
     Map(SassMap),
-    ArgList(Vec<Spanned<Value>>),
+    ArgList(ArgList),
     /// Returned by `get-function()`
     FunctionRef(SassFunction),
+    /// Returned by `meta.get-mixin()`
+    MixinRef(SassMixin),
+    Calculation(Calculation),
 }
 
 impl PartialEq for Value {
@@ -48,8 +58,8 @@ impl PartialEq for Value {
                 Value::String(s2, ..) => s1 == s2,
                 _ => false,
             },
-            Value::Dimension(Some(n), unit, _) => match other {
-                Value::Dimension(Some(n2), unit2, _) => {
+            Value::Dimension(n, unit, _) => match other {
+                Value::Dimension(n2, unit2, _) => {
                     if !unit.comparable(unit2) {
                         false
                     } else if unit == unit2 {
@@ -62,7 +72,6 @@ impl PartialEq for Value {
                 }
                 _ => false,
             },
-            Value::Dimension(None, ..) => false,
             Value::List(list1, sep1, brackets1) => match other {
                 Value::List(list2, sep2, brackets2) => {
                     if sep1 != sep2 || brackets1 != brackets2 || list1.len() != list2.len() {
@@ -89,6 +98,13 @@ impl PartialEq for Value {
                     false
                 }
             }
+            Value::MixinRef(mixin1) => {
+                if let Value::MixinRef(mixin2) = other {
+                    mixin1 == mixin2
+                } else {
+                    false
+                }
+            }
             Value::Map(map1) => {
                 if let Value::Map(map2) = other {
                     map1 == map2
@@ -103,14 +119,21 @@ impl PartialEq for Value {
                     false
                 }
             }
+            Value::Calculation(calc1) => {
+                if let Value::Calculation(calc2) = other {
+                    calc1 == calc2
+                } else {
+                    false
+                }
+            }
             Value::ArgList(list1) => match other {
-                Value::ArgList(list2) => list1 == list2,
+                Value::ArgList(list2) => list1.elems == list2.elems,
                 Value::List(list2, ListSeparator::Comma, ..) => {
                     if list1.len() != list2.len() {
                         return false;
                     }
 
-                    for (el1, el2) in list1.iter().zip(list2) {
+                    for (el1, el2) in list1.elems.iter().zip(list2) {
                         if &el1.node != el2 {
                             return false;
                         }
@@ -200,7 +223,7 @@ impl Value {
             Value::List(v, _, Brackets::Bracketed) if v.is_empty() => false,
             Value::List(v, ..) => v.iter().map(Value::is_null).all(|f| f),
             Value::ArgList(v, ..) if v.is_empty() => false,
-            Value::ArgList(v, ..) => v.iter().map(|v| v.node.is_null()).all(|f| f),
+            Value::ArgList(v, ..) => v.elems.iter().map(|v| v.node.is_null()).all(|f| f),
             _ => false,
         }
     }
@@ -210,29 +233,18 @@ impl Value {
             Value::Important => Cow::const_str("!important"),
             Value::Dimension(num, unit, _) => match unit {
                 Unit::Mul(..) | Unit::Div(..) => {
-                    if let Some(num) = num {
-                        return Err((
-                            format!(
-                                "{}{} isn't a valid CSS value.",
-                                num.to_string(is_compressed),
-                                unit
-                            ),
-                            span,
-                        )
-                            .into());
-                    }
-
-                    return Err((format!("NaN{} isn't a valid CSS value.", unit), span).into());
-                }
-                _ => {
-                    if let Some(num) = num {
-                        Cow::owned(format!("{}{}", num.to_string(is_compressed), unit))
-                    } else {
-                        Cow::owned(format!("NaN{}", unit))
-                    }
+                    return Err((
+                        format!(
+                            "{} isn't a valid CSS value.",
+                            num.to_css_string(unit, is_compressed)
+                        ),
+                        span,
+                    )
+                        .into());
                 }
+                _ => Cow::owned(num.to_css_string(unit, is_compressed)),
             },
-            Value::Map(..) | Value::FunctionRef(..) => {
+            Value::Map(..) | Value::FunctionRef(..) | Value::MixinRef(..) => {
                 return Err((
                     format!("{} isn't a valid CSS value.", self.inspect(span)?),
                     span,
@@ -265,6 +277,7 @@ impl Value {
                 )),
             },
             Value::Color(c) => Cow::owned(c.to_string()),
+            Value::Calculation(calc) => Cow::owned(calc.to_css_string(span, is_compressed)?),
             Value::String(string, QuoteKind::None) => {
                 let mut after_newline = false;
                 let mut buf = String::with_capacity(string.len());
@@ -299,7 +312,8 @@ impl Value {
                 return Err(("() isn't a valid CSS value.", span).into());
             }
             Value::ArgList(args) => Cow::owned(
-                args.iter()
+                args.elems
+                    .iter()
                     .filter(|x| !x.is_null())
                     .map(|a| a.node.to_css_string(span, is_compressed))
                     .collect::<SassResult<Vec<Cow<'static, str>>>>()?
@@ -337,7 +351,9 @@ impl Value {
             Value::Dimension(..) => "number",
             Value::List(..) => "list",
             Value::FunctionRef(..) => "function",
+            Value::MixinRef(..) => "mixin",
             Value::ArgList(..) => "arglist",
+            Value::Calculation(..) => "calculation",
             Value::True | Value::False => "bool",
             Value::Null => "null",
             Value::Map(..) => "map",
@@ -351,6 +367,7 @@ impl Value {
     pub fn is_special_function(&self) -> bool {
         match self {
             Value::String(s, QuoteKind::None) => is_special_function(s),
+            Value::Calculation(..) => true,
             _ => false,
         }
     }
@@ -363,21 +380,20 @@ impl Value {
         }
     }
 
-    pub fn cmp(&self, other: &Self, span: Span, op: Op) -> SassResult<Ordering> {
+    /// Returns `None` if either number is `NaN`, which can't be ordered
+    pub fn cmp(&self, other: &Self, span: Span, op: Op) -> SassResult<Option<Ordering>> {
         Ok(match self {
-            Value::Dimension(None, ..) => todo!(),
-            Value::Dimension(Some(num), unit, _) => match &other {
-                Value::Dimension(None, ..) => todo!(),
-                Value::Dimension(Some(num2), unit2, _) => {
+            Value::Dimension(num, unit, _) => match &other {
+                Value::Dimension(num2, unit2, _) => {
                     if !unit.comparable(unit2) {
                         return Err(
                             (format!("Incompatible units {} and {}.", unit2, unit), span).into(),
                         );
                     }
                     if unit == unit2 || unit == &Unit::None || unit2 == &Unit::None {
-                        num.cmp(num2)
+                        num.partial_cmp(num2)
                     } else {
-                        num.cmp(&num2.clone().convert(unit2, unit))
+                        num.partial_cmp(&num2.clone().convert(unit2, unit))
                     }
                 }
                 _ => {
@@ -414,8 +430,8 @@ impl Value {
                 Value::String(s2, ..) => s1 != s2,
                 _ => true,
             },
-            Value::Dimension(Some(n), unit, _) => match other {
-                Value::Dimension(Some(n2), unit2, _) => {
+            Value::Dimension(n, unit, _) => match other {
+                Value::Dimension(n2, unit2, _) => {
                     if !unit.comparable(unit2) {
                         true
                     } else if unit == unit2 {
@@ -455,31 +471,35 @@ impl Value {
                 Brackets::None => Cow::const_str("()"),
                 Brackets::Bracketed => Cow::const_str("[]"),
             },
-            Value::List(v, sep, brackets) if v.len() == 1 => match brackets {
-                Brackets::None => match sep {
-                    ListSeparator::Space => v[0].inspect(span)?,
-                    ListSeparator::Comma => Cow::owned(format!("({},)", v[0].inspect(span)?)),
-                },
-                Brackets::Bracketed => match sep {
-                    ListSeparator::Space => Cow::owned(format!("[{}]", v[0].inspect(span)?)),
-                    ListSeparator::Comma => Cow::owned(format!("[{},]", v[0].inspect(span)?)),
-                },
-            },
-            Value::List(vals, sep, brackets) => Cow::owned(match brackets {
-                Brackets::None => vals
+            Value::List(v, sep, brackets) if v.len() == 1 => {
+                let elem = v[0].inspect_element(*sep, span)?;
+                match brackets {
+                    Brackets::None => match sep {
+                        ListSeparator::Space => Cow::owned(elem),
+                        ListSeparator::Comma => Cow::owned(format!("({},)", elem)),
+                        ListSeparator::Slash => Cow::owned(format!("({}/)", elem)),
+                    },
+                    Brackets::Bracketed => match sep {
+                        ListSeparator::Space => Cow::owned(format!("[{}]", elem)),
+                        ListSeparator::Comma => Cow::owned(format!("[{},]", elem)),
+                        ListSeparator::Slash => Cow::owned(format!("[{}/]", elem)),
+                    },
+                }
+            }
+            Value::List(vals, sep, brackets) => {
+                let elems = vals
                     .iter()
-                    .map(|x| x.inspect(span))
-                    .collect::<SassResult<Vec<Cow<'static, str>>>>()?
-                    .join(sep.as_str()),
-                Brackets::Bracketed => format!(
-                    "[{}]",
-                    vals.iter()
-                        .map(|x| x.inspect(span))
-                        .collect::<SassResult<Vec<Cow<'static, str>>>>()?
-                        .join(sep.as_str()),
-                ),
-            }),
+                    .map(|x| x.inspect_element(*sep, span))
+                    .collect::<SassResult<Vec<String>>>()?
+                    .join(sep.as_str());
+
+                Cow::owned(match brackets {
+                    Brackets::None => elems,
+                    Brackets::Bracketed => format!("[{}]", elems),
+                })
+            }
             Value::FunctionRef(f) => Cow::owned(format!("get-function(\"{}\")", f.name())),
+            Value::MixinRef(m) => Cow::owned(format!("get-mixin(\"{}\")", m.name)),
             Value::Null => Cow::const_str("null"),
             Value::Map(map) => Cow::owned(format!(
                 "({})",
@@ -488,39 +508,65 @@ impl Value {
                     .collect::<SassResult<Vec<String>>>()?
                     .join(", ")
             )),
-            Value::Dimension(Some(num), unit, _) => {
-                Cow::owned(format!("{}{}", num.inspect(), unit))
-            }
-            Value::Dimension(None, unit, ..) => Cow::owned(format!("NaN{}", unit)),
+            Value::Dimension(num, unit, _) => Cow::owned(num.to_css_string(unit, false)),
             Value::ArgList(args) if args.is_empty() => Cow::const_str("()"),
             Value::ArgList(args) if args.len() == 1 => Cow::owned(format!(
                 "({},)",
-                args.iter()
+                args.elems
+                    .iter()
                     .filter(|x| !x.is_null())
-                    .map(|a| a.node.inspect(span))
-                    .collect::<SassResult<Vec<Cow<'static, str>>>>()?
+                    .map(|a| a.node.inspect_element(ListSeparator::Comma, span))
+                    .collect::<SassResult<Vec<String>>>()?
                     .join(", "),
             )),
             Value::ArgList(args) => Cow::owned(
-                args.iter()
+                args.elems
+                    .iter()
                     .filter(|x| !x.is_null())
-                    .map(|a| a.node.inspect(span))
-                    .collect::<SassResult<Vec<Cow<'static, str>>>>()?
+                    .map(|a| a.node.inspect_element(ListSeparator::Comma, span))
+                    .collect::<SassResult<Vec<String>>>()?
                     .join(", "),
             ),
             Value::Important
             | Value::True
             | Value::False
             | Value::Color(..)
+            | Value::Calculation(..)
             | Value::String(..) => self.to_css_string(span, false)?,
         })
     }
 
+    /// Inspect this value as an element of a list separated by `sep`, wrapping
+    /// it in parentheses if it would otherwise read back as a different list
+    ///
+    /// <https://github.com/sass/dart-sass/blob/main/lib/src/visitor/serialize.dart> (`_elementNeedsParens`)
+    fn inspect_element(&self, sep: ListSeparator, span: Span) -> SassResult<String> {
+        let elem_sep = match self {
+            Value::List(v, elem_sep, Brackets::None) if v.len() > 1 => *elem_sep,
+            Value::ArgList(args) if args.len() > 1 => ListSeparator::Comma,
+            _ => return Ok(self.inspect(span)?.into_owned()),
+        };
+
+        let needs_parens = match sep {
+            ListSeparator::Comma => elem_sep == ListSeparator::Comma,
+            ListSeparator::Slash => {
+                matches!(elem_sep, ListSeparator::Comma | ListSeparator::Slash)
+            }
+            ListSeparator::Space => true,
+        };
+
+        Ok(if needs_parens {
+            format!("({})", self.inspect(span)?)
+        } else {
+            self.inspect(span)?.into_owned()
+        })
+    }
+
     pub fn as_list(self) -> Vec<Value> {
         match self {
             Value::List(v, ..) => v,
             Value::Map(m) => m.as_list(),
-            Value::ArgList(v) => v.into_iter().map(|val| val.node).collect(),
+            Value::ArgList(v) => v.elems.into_iter().map(|val| val.node).collect(),
             v => vec![v],
         }
     }
@@ -561,8 +607,13 @@ impl Value {
             at_root: parser.at_root,
             at_root_has_selector: parser.at_root_has_selector,
             extender: parser.extender,
+            media_queries: parser.media_queries,
             content_scopes: parser.content_scopes,
             options: parser.options,
+            custom_functions: parser.custom_functions,
+            loaded_files: parser.loaded_files,
+            call_stack: parser.call_stack,
+            importer: parser.importer,
             modules: parser.modules,
             module_config: parser.module_config,
         }
@@ -599,6 +650,7 @@ impl Value {
                             }
                         }
                     }
+                    ListSeparator::Slash => return Ok(None),
                 }
 
                 result.join(sep.as_str())
@@ -23,6 +23,9 @@ const PRECISION: usize = 10;
 pub(crate) enum Number {
     Small(Rational64),
     Big(Box<BigRational>),
+    Infinity,
+    NegativeInfinity,
+    NaN,
 }
 
 impl PartialEq for Number {
@@ -36,6 +39,10 @@ impl PartialEq for Number {
                 val1.clone().into_big_rational() == **val2
             }
             (Number::Big(val1), Number::Big(val2)) => val1 == val2,
+            (Number::Infinity, Number::Infinity)
+            | (Number::NegativeInfinity, Number::NegativeInfinity) => true,
+            // `NaN` is never equal to anything, including itself
+            _ => false,
         }
     }
 }
@@ -51,6 +58,7 @@ impl Number {
         Number::Big(Box::new(val))
     }
 
+    /// Invariants: `self` must be finite
     fn into_big_rational(self) -> BigRational {
         match self {
             Number::Small(small) => {
@@ -59,13 +67,21 @@ impl Number {
                 BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1))
             }
             Number::Big(big) => *big,
+            Number::Infinity | Number::NegativeInfinity | Number::NaN => {
+                unreachable!("non-finite numbers have no rational representation")
+            }
         }
     }
 
+    /// Non-finite numbers saturate, in the same way as casting an `f64` to an
+    /// integer
     pub fn to_integer(&self) -> Integer {
         match self {
             Self::Small(val) => Integer::Small(val.to_integer()),
             Self::Big(val) => Integer::Big(val.to_integer()),
+            Self::Infinity => Integer::Small(i64::MAX),
+            Self::NegativeInfinity => Integer::Small(i64::MIN),
+            Self::NaN => Integer::Small(0),
         }
     }
 
@@ -78,10 +94,19 @@ impl Number {
         Number::new_big(BigRational::new(a.into(), b.into()))
     }
 
+    pub const fn is_finite(&self) -> bool {
+        matches!(self, Self::Small(..) | Self::Big(..))
+    }
+
+    pub const fn is_nan(&self) -> bool {
+        matches!(self, Self::NaN)
+    }
+
     pub fn round(&self) -> Self {
         match self {
             Self::Small(val) => Self::Small(val.round()),
             Self::Big(val) => Self::Big(Box::new(val.round())),
+            non_finite => non_finite.clone(),
         }
     }
 
@@ -89,6 +114,7 @@ impl Number {
         match self {
             Self::Small(val) => Self::Small(val.ceil()),
             Self::Big(val) => Self::Big(Box::new(val.ceil())),
+            non_finite => non_finite.clone(),
         }
     }
 
@@ -96,6 +122,7 @@ impl Number {
         match self {
             Self::Small(val) => Self::Small(val.floor()),
             Self::Big(val) => Self::Big(Box::new(val.floor())),
+            non_finite => non_finite.clone(),
         }
     }
 
@@ -103,13 +130,18 @@ impl Number {
         match self {
             Self::Small(val) => Self::Small(val.abs()),
             Self::Big(val) => Self::Big(Box::new(val.abs())),
+            Self::Infinity | Self::NegativeInfinity => Self::Infinity,
+            Self::NaN => Self::NaN,
         }
     }
 
+    /// Whether this number is not an integer, which is true of all non-finite
+    /// numbers
     pub fn is_decimal(&self) -> bool {
         match self {
             Self::Small(v) => !v.is_integer(),
             Self::Big(v) => !v.is_integer(),
+            Self::Infinity | Self::NegativeInfinity | Self::NaN => true,
         }
     }
 
@@ -117,6 +149,7 @@ impl Number {
         match self {
             Self::Small(v) => Number::new_small(v.fract()),
             Self::Big(v) => Number::new_big(v.fract()),
+            Self::Infinity | Self::NegativeInfinity | Self::NaN => Number::NaN,
         }
     }
 
@@ -143,41 +176,39 @@ impl Number {
         Some(match self {
             Number::Small(n) => ((*n.numer() as f64) / (*n.denom() as f64)),
             Number::Big(n) => ((n.numer().to_f64()?) / (n.denom().to_f64()?)),
+            Number::Infinity => f64::INFINITY,
+            Number::NegativeInfinity => f64::NEG_INFINITY,
+            Number::NaN => f64::NAN,
         })
     }
 
-    pub fn sqrt(self) -> Option<Self> {
-        Some(Number::Big(Box::new(BigRational::from_float(
-            self.as_float()?.sqrt(),
-        )?)))
+    /// Numbers too large to be represented as an `f64` become `NaN`
+    pub fn as_f64(self) -> f64 {
+        self.as_float().unwrap_or(f64::NAN)
+    }
+
+    pub fn sqrt(self) -> Self {
+        Number::from(self.as_f64().sqrt())
     }
 
-    pub fn ln(self) -> Option<Self> {
-        Some(Number::Big(Box::new(BigRational::from_float(
-            self.as_float()?.ln(),
-        )?)))
+    pub fn ln(self) -> Self {
+        Number::from(self.as_f64().ln())
     }
 
-    pub fn log(self, base: Number) -> Option<Self> {
-        Some(Number::Big(Box::new(BigRational::from_float(
-            self.as_float()?.log(base.as_float()?),
-        )?)))
+    pub fn log(self, base: Number) -> Self {
+        Number::from(self.as_f64().log(base.as_f64()))
     }
 
-    pub fn pow(self, exponent: Self) -> Option<Self> {
-        Some(Number::Big(Box::new(BigRational::from_float(
-            self.as_float()?.powf(exponent.as_float()?),
-        )?)))
+    pub fn pow(self, exponent: Self) -> Self {
+        Number::from(self.as_f64().powf(exponent.as_f64()))
     }
 
     pub fn pi() -> Self {
         Number::from(std::f64::consts::PI)
     }
 
-    pub fn atan2(self, other: Self) -> Option<Self> {
-        Some(Number::Big(Box::new(BigRational::from_float(
-            self.as_float()?.atan2(other.as_float()?),
-        )?)))
+    pub fn atan2(self, other: Self) -> Self {
+        Number::from(self.as_f64().atan2(other.as_f64()))
     }
 
     /// Invariants: `from.comparable(&to)` must be true
@@ -185,30 +216,30 @@ impl Number {
         debug_assert!(from.comparable(to));
         self * UNIT_CONVERSION_TABLE[to][from].clone()
     }
+
+    /// Operate on two numbers, at least one of which is not finite, using
+    /// floating point semantics
+    fn operate_non_finite(self, other: Self, op: fn(f64, f64) -> f64) -> Self {
+        Number::from(op(self.as_f64(), other.as_f64()))
+    }
 }
 
 macro_rules! trig_fn(
     ($name:ident, $name_deg:ident) => {
-        pub fn $name(self) -> Option<Self> {
-            Some(Number::Big(Box::new(BigRational::from_float(
-                self.as_float()?.$name(),
-            )?)))
+        pub fn $name(self) -> Self {
+            Number::from(self.as_f64().$name())
         }
 
-        pub fn $name_deg(self) -> Option<Self> {
-            Some(Number::Big(Box::new(BigRational::from_float(
-                self.as_float()?.to_radians().$name(),
-            )?)))
+        pub fn $name_deg(self) -> Self {
+            Number::from(self.as_f64().to_radians().$name())
         }
     }
 );
 
 macro_rules! inverse_trig_fn(
     ($name:ident) => {
-        pub fn $name(self) -> Option<Self> {
-            Some(Number::Big(Box::new(BigRational::from_float(
-                self.as_float()?.$name().to_degrees(),
-            )?)))
+        pub fn $name(self) -> Self {
+            Number::from(self.as_f64().$name().to_degrees())
         }
     }
 );
@@ -239,6 +270,7 @@ impl Zero for Number {
         match self {
             Self::Small(v) => v.is_zero(),
             Self::Big(v) => v.is_zero(),
+            Self::Infinity | Self::NegativeInfinity | Self::NaN => false,
         }
     }
 }
@@ -252,6 +284,7 @@ impl One for Number {
         match self {
             Self::Small(v) => v.is_one(),
             Self::Big(v) => v.is_one(),
+            Self::Infinity | Self::NegativeInfinity | Self::NaN => false,
         }
     }
 }
@@ -276,7 +309,9 @@ impl Signed for Number {
 
     #[cold]
     fn signum(&self) -> Self {
-        if self.is_zero() {
+        if self.is_nan() {
+            Self::NaN
+        } else if self.is_zero() {
             Self::zero()
         } else if self.is_positive() {
             Self::one()
@@ -289,6 +324,8 @@ impl Signed for Number {
         match self {
             Self::Small(v) => v.is_positive(),
             Self::Big(v) => v.is_positive(),
+            Self::Infinity => true,
+            Self::NegativeInfinity | Self::NaN => false,
         }
     }
 
@@ -296,6 +333,8 @@ impl Signed for Number {
         match self {
             Self::Small(v) => v.is_negative(),
             Self::Big(v) => v.is_negative(),
+            Self::NegativeInfinity => true,
+            Self::Infinity | Self::NaN => false,
         }
     }
 }
@@ -333,7 +372,17 @@ impl From<i64> for Number {
 #[allow(clippy::fallible_impl_from)]
 impl From<f64> for Number {
     fn from(b: f64) -> Self {
-        Number::Big(Box::new(BigRational::from_float(b).unwrap()))
+        if b.is_nan() {
+            Number::NaN
+        } else if b.is_infinite() {
+            if b.is_sign_positive() {
+                Number::Infinity
+            } else {
+                Number::NegativeInfinity
+            }
+        } else {
+            Number::Big(Box::new(BigRational::from_float(b).unwrap()))
+        }
     }
 }
 
@@ -348,6 +397,9 @@ impl fmt::Debug for Number {
         match self {
             Self::Small(..) => write!(f, "Number::Small( {} )", self.to_string(false)),
             Self::Big(..) => write!(f, "Number::Big( {} )", self.to_string(false)),
+            Self::Infinity | Self::NegativeInfinity | Self::NaN => {
+                write!(f, "Number::{}", self.to_string(false))
+            }
         }
     }
 }
@@ -367,6 +419,7 @@ impl ToPrimitive for Number {
                 }
                 n.to_u64()
             }
+            Self::Infinity | Self::NegativeInfinity | Self::NaN => None,
         }
     }
 
@@ -384,6 +437,7 @@ impl ToPrimitive for Number {
                 }
                 n.to_i64()
             }
+            Self::Infinity | Self::NegativeInfinity | Self::NaN => None,
         }
     }
 }
@@ -393,7 +447,49 @@ impl Number {
         self.to_string(false)
     }
 
+    /// Write this number followed by `unit`
+    ///
+    /// CSS has no syntax for `Infinity`, `-Infinity` and `NaN`, so they are
+    /// written using the constants of `calc()`
+    pub(crate) fn to_css_string(&self, unit: &Unit, is_compressed: bool) -> String {
+        if self.is_finite() {
+            format!("{}{}", self.to_string(is_compressed), unit)
+        } else {
+            format!("calc({})", self.to_calculation_string(unit, is_compressed))
+        }
+    }
+
+    /// Write this number followed by `unit` as part of a calculation, where
+    /// non-finite numbers don't need to be wrapped in `calc()`
+    pub(crate) fn to_calculation_string(&self, unit: &Unit, is_compressed: bool) -> String {
+        let constant = match self {
+            Self::Infinity => "infinity",
+            Self::NegativeInfinity => "-infinity",
+            Self::NaN => "NaN",
+            Self::Small(..) | Self::Big(..) => {
+                return format!("{}{}", self.to_string(is_compressed), unit)
+            }
+        };
+
+        if *unit == Unit::None {
+            constant.to_owned()
+        } else if is_compressed {
+            format!("{}*1{}", constant, unit)
+        } else {
+            format!("{} * 1{}", constant, unit)
+        }
+    }
+
+    /// Non-finite numbers are written as `Infinity`, `-Infinity` and `NaN`;
+    /// in CSS they must be wrapped in `calc()`, which is left to the caller
     pub(crate) fn to_string(&self, is_compressed: bool) -> String {
+        match self {
+            Self::Infinity => return "Infinity".to_owned(),
+            Self::NegativeInfinity => return "-Infinity".to_owned(),
+            Self::NaN => return "NaN".to_owned(),
+            Self::Small(..) | Self::Big(..) => {}
+        }
+
         let mut whole = self.to_integer().abs();
         let has_decimal = self.is_decimal();
         let mut frac = self.abs().fract();
@@ -472,49 +568,32 @@ impl Number {
 
 impl PartialOrd for Number {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => val1.partial_cmp(val2),
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = (*val1).into();
-                    BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1))
-                        .partial_cmp(val2)
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = (*val2).into();
-                    (**val1).partial_cmp(&BigRational::new_raw(
-                        BigInt::from(tuple.0),
-                        BigInt::from(tuple.1),
-                    ))
-                }
-                Self::Big(val2) => val1.partial_cmp(val2),
-            },
+        match (self, other) {
+            (Self::Small(val1), Self::Small(val2)) => val1.partial_cmp(val2),
+            (Self::Small(..), Self::Big(val2)) => {
+                self.clone().into_big_rational().partial_cmp(val2)
+            }
+            (Self::Big(val1), Self::Small(..)) => {
+                (**val1).partial_cmp(&other.clone().into_big_rational())
+            }
+            (Self::Big(val1), Self::Big(val2)) => val1.partial_cmp(val2),
+            (Self::NaN, _) | (_, Self::NaN) => None,
+            (Self::Infinity, Self::Infinity) | (Self::NegativeInfinity, Self::NegativeInfinity) => {
+                Some(Ordering::Equal)
+            }
+            (Self::Infinity, _) | (_, Self::NegativeInfinity) => Some(Ordering::Greater),
+            (Self::NegativeInfinity, _) | (_, Self::Infinity) => Some(Ordering::Less),
         }
     }
 }
 
+/// `NaN` is unordered with respect to every number, so for the purposes of
+/// sorting it is considered smaller than all of them
 impl Ord for Number {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => val1.cmp(val2),
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = (*val1).into();
-                    BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)).cmp(val2)
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = (*val2).into();
-                    (**val1).cmp(&BigRational::new_raw(
-                        BigInt::from(tuple.0),
-                        BigInt::from(tuple.1),
-                    ))
-                }
-                Self::Big(val2) => val1.cmp(val2),
-            },
+        match self.partial_cmp(other) {
+            Some(ordering) => ordering,
+            None => other.is_nan().cmp(&self.is_nan()),
         }
     }
 }
@@ -523,39 +602,21 @@ impl Add for Number {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_add(&val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = val2.into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                + BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)) + *val2,
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 + *val2)),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        (*val1)
-                            + BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
+        match (self, other) {
+            (Self::Small(val1), Self::Small(val2)) => match val1.checked_add(&val2) {
+                Some(v) => Self::Small(v),
+                None => Self::Big(Box::new(
+                    Self::Small(val1).into_big_rational() + Self::Small(val2).into_big_rational(),
+                )),
             },
+            (val1 @ Self::Small(..), Self::Big(val2)) => {
+                Self::Big(Box::new(val1.into_big_rational() + *val2))
+            }
+            (Self::Big(val1), Self::Big(val2)) => Self::Big(Box::new(*val1 + *val2)),
+            (Self::Big(val1), val2 @ Self::Small(..)) => {
+                Self::Big(Box::new(*val1 + val2.into_big_rational()))
+            }
+            (val1, val2) => val1.operate_non_finite(val2, |a, b| a + b),
         }
     }
 }
@@ -564,39 +625,21 @@ impl Add<&Self> for Number {
     type Output = Self;
 
     fn add(self, other: &Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_add(val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = (*val2).into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                + BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1))
-                            + *val2.clone(),
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 + *val2.clone())),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = (*val2).into();
-                    Self::Big(Box::new(
-                        *val1 + BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
+        match (self, other) {
+            (Self::Small(val1), Self::Small(val2)) => match val1.checked_add(val2) {
+                Some(v) => Self::Small(v),
+                None => Self::Big(Box::new(
+                    Self::Small(val1).into_big_rational() + Self::Small(*val2).into_big_rational(),
+                )),
             },
+            (val1 @ Self::Small(..), Self::Big(val2)) => {
+                Self::Big(Box::new(val1.into_big_rational() + &**val2))
+            }
+            (Self::Big(val1), Self::Big(val2)) => Self::Big(Box::new(*val1 + &**val2)),
+            (Self::Big(val1), Self::Small(val2)) => {
+                Self::Big(Box::new(*val1 + Self::Small(*val2).into_big_rational()))
+            }
+            (val1, val2) => val1.operate_non_finite(val2.clone(), |a, b| a + b),
         }
     }
 }
@@ -612,38 +655,21 @@ impl Sub for Number {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_sub(&val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = val2.into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                - BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)) - *val2,
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 - *val2)),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        *val1 - BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
+        match (self, other) {
+            (Self::Small(val1), Self::Small(val2)) => match val1.checked_sub(&val2) {
+                Some(v) => Self::Small(v),
+                None => Self::Big(Box::new(
+                    Self::Small(val1).into_big_rational() - Self::Small(val2).into_big_rational(),
+                )),
             },
+            (val1 @ Self::Small(..), Self::Big(val2)) => {
+                Self::Big(Box::new(val1.into_big_rational() - *val2))
+            }
+            (Self::Big(val1), Self::Big(val2)) => Self::Big(Box::new(*val1 - *val2)),
+            (Self::Big(val1), val2 @ Self::Small(..)) => {
+                Self::Big(Box::new(*val1 - val2.into_big_rational()))
+            }
+            (val1, val2) => val1.operate_non_finite(val2, |a, b| a - b),
         }
     }
 }
@@ -659,38 +685,21 @@ impl Mul for Number {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_mul(&val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = val2.into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                * BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)) * *val2,
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 * *val2)),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        *val1 * BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
+        match (self, other) {
+            (Self::Small(val1), Self::Small(val2)) => match val1.checked_mul(&val2) {
+                Some(v) => Self::Small(v),
+                None => Self::Big(Box::new(
+                    Self::Small(val1).into_big_rational() * Self::Small(val2).into_big_rational(),
+                )),
             },
+            (val1 @ Self::Small(..), Self::Big(val2)) => {
+                Self::Big(Box::new(val1.into_big_rational() * *val2))
+            }
+            (Self::Big(val1), Self::Big(val2)) => Self::Big(Box::new(*val1 * *val2)),
+            (Self::Big(val1), val2 @ Self::Small(..)) => {
+                Self::Big(Box::new(*val1 * val2.into_big_rational()))
+            }
+            (val1, val2) => val1.operate_non_finite(val2, |a, b| a * b),
         }
     }
 }
@@ -702,6 +711,7 @@ impl Mul<i64> for Number {
         match self {
             Self::Small(val1) => Self::Small(val1 * other),
             Self::Big(val1) => Self::Big(Box::new(*val1 * BigInt::from(other))),
+            non_finite => non_finite * Number::from(other),
         }
     }
 }
@@ -724,38 +734,26 @@ impl Div for Number {
     type Output = Self;
 
     fn div(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => match val1.checked_div(&val2) {
-                    Some(v) => Self::Small(v),
-                    None => {
-                        let tuple1: (i64, i64) = val1.into();
-                        let tuple2: (i64, i64) = val2.into();
-                        Self::Big(Box::new(
-                            BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1))
-                                / BigRational::new_raw(
-                                    BigInt::from(tuple2.0),
-                                    BigInt::from(tuple2.1),
-                                ),
-                        ))
-                    }
-                },
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
-                    Self::Big(Box::new(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)) / *val2,
-                    ))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(*val1 / *val2)),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(
-                        *val1 / BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    ))
-                }
+        // dividing by zero produces `Infinity`, `-Infinity` or `NaN`
+        if other.is_zero() {
+            return self.operate_non_finite(other, |a, b| a / b);
+        }
+
+        match (self, other) {
+            (Self::Small(val1), Self::Small(val2)) => match val1.checked_div(&val2) {
+                Some(v) => Self::Small(v),
+                None => Self::Big(Box::new(
+                    Self::Small(val1).into_big_rational() / Self::Small(val2).into_big_rational(),
+                )),
             },
+            (val1 @ Self::Small(..), Self::Big(val2)) => {
+                Self::Big(Box::new(val1.into_big_rational() / *val2))
+            }
+            (Self::Big(val1), Self::Big(val2)) => Self::Big(Box::new(*val1 / *val2)),
+            (Self::Big(val1), val2 @ Self::Small(..)) => {
+                Self::Big(Box::new(*val1 / val2.into_big_rational()))
+            }
+            (val1, val2) => val1.operate_non_finite(val2, |a, b| a / b),
         }
     }
 }
@@ -771,40 +769,35 @@ fn modulo(n1: BigRational, n2: BigRational) -> BigRational {
     (n1 % n2.clone() + n2.clone()) % n2
 }
 
+/// The floating point equivalent of `modulo`, where the result has the same
+/// sign as `n2`
+fn modulo_f64(n1: f64, n2: f64) -> f64 {
+    let result = n1 % n2;
+
+    if result != 0.0 && (result < 0.0) != (n2 < 0.0) {
+        result + n2
+    } else {
+        result
+    }
+}
+
 impl Rem for Number {
     type Output = Self;
 
     fn rem(self, other: Self) -> Self {
-        match self {
-            Self::Small(val1) => match other {
-                Self::Small(val2) => {
-                    let tuple1: (i64, i64) = val1.into();
-                    let tuple2: (i64, i64) = val2.into();
-
-                    Self::Big(Box::new(modulo(
-                        BigRational::new_raw(BigInt::from(tuple1.0), BigInt::from(tuple1.1)),
-                        BigRational::new_raw(BigInt::from(tuple2.0), BigInt::from(tuple2.1)),
-                    )))
-                }
-                Self::Big(val2) => {
-                    let tuple: (i64, i64) = val1.into();
+        if other.is_zero() {
+            return Self::NaN;
+        }
 
-                    Self::Big(Box::new(modulo(
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                        *val2,
-                    )))
-                }
-            },
-            Self::Big(val1) => match other {
-                Self::Big(val2) => Self::Big(Box::new(modulo(*val1, *val2))),
-                Self::Small(val2) => {
-                    let tuple: (i64, i64) = val2.into();
-                    Self::Big(Box::new(modulo(
-                        *val1,
-                        BigRational::new_raw(BigInt::from(tuple.0), BigInt::from(tuple.1)),
-                    )))
-                }
-            },
+        match (self, other) {
+            (
+                val1 @ (Self::Small(..) | Self::Big(..)),
+                val2 @ (Self::Small(..) | Self::Big(..)),
+            ) => Self::Big(Box::new(modulo(
+                val1.into_big_rational(),
+                val2.into_big_rational(),
+            ))),
+            (val1, val2) => val1.operate_non_finite(val2, modulo_f64),
         }
     }
 }
@@ -823,6 +816,9 @@ impl Neg for Number {
         match self {
             Self::Small(v) => Self::Small(-v),
             Self::Big(v) => Self::Big(Box::new(-*v)),
+            Self::Infinity => Self::NegativeInfinity,
+            Self::NegativeInfinity => Self::Infinity,
+            Self::NaN => Self::NaN,
         }
     }
 }
@@ -8,6 +8,9 @@
 //!
 //! Builtin functions are those that have been implemented in rust and are
 //! in the global scope.
+//!
+//! Custom functions are those that have been implemented in rust by the
+//! embedding application and registered using `Options::add_function`.
 
 use std::fmt;
 
@@ -31,6 +34,8 @@ pub(crate) enum SassFunction {
         function: Box<Function>,
         name: Identifier,
     },
+    /// The index of the function in `Options`, along with its name
+    Custom(usize, Identifier),
 }
 
 impl SassFunction {
@@ -39,7 +44,7 @@ impl SassFunction {
     /// Used mainly in debugging and `inspect()`
     pub fn name(&self) -> &Identifier {
         match self {
-            Self::Builtin(_, name) | Self::UserDefined { name, .. } => name,
+            Self::Builtin(_, name) | Self::UserDefined { name, .. } | Self::Custom(_, name) => name,
         }
     }
 
@@ -50,6 +55,7 @@ impl SassFunction {
         match &self {
             Self::Builtin(..) => "Builtin",
             Self::UserDefined { .. } => "UserDefined",
+            Self::Custom(..) => "Custom",
         }
     }
 
@@ -61,7 +67,10 @@ impl SassFunction {
     ) -> SassResult<Value> {
         match self {
             Self::Builtin(f, ..) => f.0(args, parser),
-            Self::UserDefined { function, .. } => parser.eval_function(*function, args, module),
+            Self::UserDefined { function, name } => {
+                parser.eval_function(*function, name, args, module)
+            }
+            Self::Custom(idx, ..) => parser.eval_custom_function(idx, args),
         }
     }
 }
@@ -0,0 +1,62 @@
+//! Sass mixins may be referenced as values using `meta.get-mixin()`, and later
+//! included using `meta.apply()`
+//!
+//! Like functions, mixins can be either user-defined or builtin. See the
+//! documentation of `crate::atrule::mixin::Mixin` for more information.
+
+use std::fmt;
+
+use codemap::Spanned;
+
+use crate::{atrule::mixin::Mixin, common::Identifier};
+
+/// A reference to a Sass mixin
+///
+/// The mixin name is stored in addition to the body for use in the builtin
+/// function `inspect()`
+#[derive(Clone)]
+pub(crate) struct SassMixin {
+    pub mixin: Box<Mixin>,
+    pub name: Identifier,
+    /// The module the mixin was loaded from, if any, whose scope must be
+    /// entered when including it
+    pub module: Option<Spanned<Identifier>>,
+}
+
+impl SassMixin {
+    pub fn new(mixin: Mixin, name: Identifier, module: Option<Spanned<Identifier>>) -> Self {
+        Self {
+            mixin: Box::new(mixin),
+            name,
+            module,
+        }
+    }
+
+    /// Whether the mixin may be passed a content block
+    pub fn accepts_content(&self) -> bool {
+        match &*self.mixin {
+            Mixin::UserDefined(u) => u.accepts_content_block,
+            Mixin::Builtin(_, accepts_content) => *accepts_content,
+        }
+    }
+}
+
+/// Mixins don't keep track of where they were declared, so references are
+/// considered equal if they name the same mixin from the same module
+impl PartialEq for SassMixin {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.module.as_ref().map(|m| m.node) == other.module.as_ref().map(|m| m.node)
+    }
+}
+
+impl Eq for SassMixin {}
+
+impl fmt::Debug for SassMixin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SassMixin")
+            .field("name", &self.name)
+            .field("module", &self.module.as_ref().map(|m| m.node))
+            .finish()
+    }
+}
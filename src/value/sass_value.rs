@@ -0,0 +1,508 @@
+//! The public representation of SassScript values
+//!
+//! `Value` is an implementation detail of the compiler and changes often, so
+//! values that cross the boundary between `grass` and an embedding application,
+//! such as the arguments and return values of custom functions, are exposed as
+//! a [`SassValue`] instead.
+//!
+//! Equality and serialization are defined by converting to a `Value`, so they
+//! always agree with the behavior of the compiler.
+
+use std::fmt;
+
+use codemap::{CodeMap, Span};
+
+use crate::{
+    color::Color,
+    common::{self, Brackets, QuoteKind},
+    error::SassError as Error,
+    unit::Unit,
+    value::{Calculation, Number, SassFunction, SassMap, SassMixin, Value},
+};
+
+/// A SassScript value
+///
+/// Values compare equal when they would be equal in Sass, so `1in` is equal
+/// to `96px`, and quoted strings are equal to unquoted strings with the same
+/// contents. The [`Display`](fmt::Display) implementation matches the output
+/// of `meta.inspect()`.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum SassValue {
+    /// The value `null`
+    Null,
+    /// The values `true` and `false`
+    Boolean(bool),
+    /// A number, optionally with a unit
+    Number(SassNumber),
+    /// A quoted or unquoted string
+    String {
+        /// The contents of the string, without quotes
+        text: String,
+        /// Whether the string is quoted
+        quoted: bool,
+    },
+    /// A list of values
+    List {
+        /// The elements of the list
+        elements: Vec<SassValue>,
+        /// The separator between elements
+        separator: ListSeparator,
+        /// Whether the list is surrounded by square brackets
+        bracketed: bool,
+    },
+    /// A map, as key-value pairs in insertion order
+    Map(Vec<(SassValue, SassValue)>),
+    /// A color
+    Color(SassColor),
+    /// A reference to a function, as returned by `get-function()`
+    Function(SassFunctionRef),
+    /// A reference to a mixin, as returned by `meta.get-mixin()`
+    Mixin(SassMixinRef),
+    /// A calculation that couldn't be simplified to a number, such as
+    /// `calc(100% - 10px)`
+    Calculation(SassCalculation),
+}
+
+/// The separator between the elements of a [`SassValue::List`]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListSeparator {
+    /// Elements are separated by spaces, as in `1px solid red`
+    Space,
+    /// Elements are separated by commas, as in `a, b, c`
+    Comma,
+    /// Elements are separated by slashes, as in `1 / 3`
+    Slash,
+}
+
+/// A SassScript number
+///
+/// Numbers are stored at a higher precision than `f64` internally, so values
+/// that pass through a custom function unchanged lose no precision.
+#[derive(Debug, Clone)]
+pub struct SassNumber {
+    num: Number,
+    unit: Unit,
+}
+
+impl SassNumber {
+    /// Create a number without a unit
+    #[must_use]
+    #[inline]
+    pub fn new(value: f64) -> Self {
+        Self::with_unit(value, "")
+    }
+
+    /// Create a number with the unit `unit`, such as `px` or `%`
+    #[must_use]
+    #[inline]
+    pub fn with_unit(value: f64, unit: &str) -> Self {
+        Self {
+            num: Number::from(value),
+            unit: if unit.is_empty() {
+                Unit::None
+            } else {
+                Unit::from(unit.to_owned())
+            },
+        }
+    }
+
+    /// The value of this number, ignoring its unit
+    #[must_use]
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.num.clone().as_float().unwrap_or(f64::NAN)
+    }
+
+    /// The unit of this number, or an empty string if it has none
+    #[must_use]
+    #[inline]
+    pub fn unit(&self) -> String {
+        self.unit.to_string()
+    }
+
+    /// Whether this number has no unit
+    #[must_use]
+    #[inline]
+    pub fn is_unitless(&self) -> bool {
+        self.unit == Unit::None
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Dimension(self.num.clone(), self.unit.clone(), true)
+    }
+}
+
+impl PartialEq for SassNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_value() == other.to_value()
+    }
+}
+
+impl fmt::Display for SassNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        SassValue::Number(self.clone()).fmt(f)
+    }
+}
+
+/// A SassScript color
+#[derive(Debug, Clone)]
+pub struct SassColor(Box<Color>);
+
+impl SassColor {
+    /// Create a color from its red, green and blue channels, each between
+    /// `0` and `255`, and its alpha channel, between `0` and `1`
+    ///
+    /// Channels outside of these ranges are clamped.
+    #[must_use]
+    #[inline]
+    pub fn rgba(red: f64, green: f64, blue: f64, alpha: f64) -> Self {
+        let channel = |value| number_from_f64(value).unwrap_or_default();
+
+        Self(Box::new(Color::from_rgba(
+            channel(red),
+            channel(green),
+            channel(blue),
+            channel(alpha),
+        )))
+    }
+
+    /// The red channel of this color, between `0` and `255`
+    #[must_use]
+    #[inline]
+    pub fn red(&self) -> f64 {
+        self.0.red().as_float().unwrap_or_default()
+    }
+
+    /// The green channel of this color, between `0` and `255`
+    #[must_use]
+    #[inline]
+    pub fn green(&self) -> f64 {
+        self.0.green().as_float().unwrap_or_default()
+    }
+
+    /// The blue channel of this color, between `0` and `255`
+    #[must_use]
+    #[inline]
+    pub fn blue(&self) -> f64 {
+        self.0.blue().as_float().unwrap_or_default()
+    }
+
+    /// The alpha channel of this color, between `0` and `1`
+    #[must_use]
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.0.alpha().as_float().unwrap_or_default()
+    }
+
+    /// Create a color from its hue, in degrees, its saturation and lightness,
+    /// each between `0` and `100`, and its alpha channel, between `0` and `1`
+    ///
+    /// Channels outside of these ranges are clamped.
+    #[must_use]
+    #[inline]
+    pub fn hsla(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Self {
+        let channel = |value| number_from_f64(value).unwrap_or_default();
+
+        Self(Box::new(Color::from_hsla(
+            channel(hue),
+            channel(saturation) / Number::from(100),
+            channel(lightness) / Number::from(100),
+            channel(alpha),
+        )))
+    }
+
+    /// The hue of this color, in degrees between `0` and `360`
+    #[must_use]
+    #[inline]
+    pub fn hue(&self) -> f64 {
+        self.0.hue().as_float().unwrap_or_default()
+    }
+
+    /// The HSL saturation of this color, between `0` and `100`
+    #[must_use]
+    #[inline]
+    pub fn saturation(&self) -> f64 {
+        self.0.saturation().as_float().unwrap_or_default()
+    }
+
+    /// The HSL lightness of this color, between `0` and `100`
+    #[must_use]
+    #[inline]
+    pub fn lightness(&self) -> f64 {
+        self.0.lightness().as_float().unwrap_or_default()
+    }
+}
+
+impl PartialEq for SassColor {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for SassColor {}
+
+/// A reference to a Sass function
+///
+/// Function references may only be passed back into the compilation that
+/// created them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SassFunctionRef(SassFunction);
+
+impl SassFunctionRef {
+    /// The name of the referenced function
+    #[must_use]
+    #[inline]
+    pub fn name(&self) -> String {
+        self.0.name().to_string()
+    }
+}
+
+/// A reference to a Sass mixin
+///
+/// Mixin references may only be passed back into the compilation that
+/// created them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SassMixinRef(SassMixin);
+
+impl SassMixinRef {
+    /// The name of the referenced mixin
+    #[must_use]
+    #[inline]
+    pub fn name(&self) -> String {
+        self.0.name.to_string()
+    }
+}
+
+/// A calculation such as `calc(100% - 10px)` or `min(1px, var(--width))`
+///
+/// Its arguments aren't exposed, but it can be serialized with
+/// [`SassValue::to_css_string`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SassCalculation(Calculation);
+
+impl SassCalculation {
+    /// The name of the calculation function, such as `calc` or `clamp`
+    #[must_use]
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.0.name.as_str()
+    }
+}
+
+fn number_from_f64(value: f64) -> Option<Number> {
+    if value.is_finite() {
+        Some(Number::from(value))
+    } else {
+        None
+    }
+}
+
+/// Values outside of a compilation have no source location, so errors are
+/// reported against an empty file
+fn detached_span() -> Span {
+    CodeMap::new().add_file(String::new(), String::new()).span
+}
+
+impl SassValue {
+    /// Create a quoted string
+    #[must_use]
+    #[inline]
+    pub fn quoted_string(text: impl Into<String>) -> Self {
+        SassValue::String {
+            text: text.into(),
+            quoted: true,
+        }
+    }
+
+    /// Create an unquoted string
+    #[must_use]
+    #[inline]
+    pub fn unquoted_string(text: impl Into<String>) -> Self {
+        SassValue::String {
+            text: text.into(),
+            quoted: false,
+        }
+    }
+
+    /// The name of this value's type, as returned by `meta.type-of()`
+    #[must_use]
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.to_value().kind()
+    }
+
+    /// Whether this value is `null`
+    #[must_use]
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self, SassValue::Null)
+    }
+
+    /// Whether this value is truthy; every value other than `null` and
+    /// `false` is truthy
+    #[must_use]
+    #[inline]
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, SassValue::Null | SassValue::Boolean(false))
+    }
+
+    /// A representation of this value as written in Sass, as returned by
+    /// `meta.inspect()`
+    #[inline]
+    pub fn inspect(&self) -> Result<String, Box<Error>> {
+        self.to_value()
+            .inspect(detached_span())
+            .map(|inspected| inspected.into_owned())
+    }
+
+    /// Serialize this value as it would appear in the compiled CSS
+    ///
+    /// Returns an error if the value cannot be represented in CSS, such as a
+    /// map or a number with complex units.
+    #[inline]
+    pub fn to_css_string(&self, compressed: bool) -> Result<String, Box<Error>> {
+        self.to_value()
+            .to_css_string(detached_span(), compressed)
+            .map(|css| css.into_owned())
+    }
+
+    fn to_value(&self) -> Value {
+        self.clone().into_value()
+    }
+
+    pub(crate) fn from_value(value: Value) -> Self {
+        match value {
+            Value::Important => SassValue::String {
+                text: "!important".to_owned(),
+                quoted: false,
+            },
+            Value::True => SassValue::Boolean(true),
+            Value::False => SassValue::Boolean(false),
+            Value::Null => SassValue::Null,
+            Value::Dimension(num, unit, _) => SassValue::Number(SassNumber { num, unit }),
+            Value::List(elements, separator, brackets) => SassValue::List {
+                elements: elements.into_iter().map(SassValue::from_value).collect(),
+                separator: match separator {
+                    common::ListSeparator::Space => ListSeparator::Space,
+                    common::ListSeparator::Comma => ListSeparator::Comma,
+                    common::ListSeparator::Slash => ListSeparator::Slash,
+                },
+                bracketed: brackets == Brackets::Bracketed,
+            },
+            Value::Color(color) => SassValue::Color(SassColor(color)),
+            Value::String(text, quotes) => SassValue::String {
+                text,
+                quoted: quotes == QuoteKind::Quoted,
+            },
+            Value::Map(map) => SassValue::Map(
+                map.entries()
+                    .into_iter()
+                    .map(|(key, value)| (SassValue::from_value(key), SassValue::from_value(value)))
+                    .collect(),
+            ),
+            Value::ArgList(args) => SassValue::List {
+                elements: args
+                    .elems
+                    .into_iter()
+                    .map(|arg| SassValue::from_value(arg.node))
+                    .collect(),
+                separator: ListSeparator::Comma,
+                bracketed: false,
+            },
+            Value::FunctionRef(function) => SassValue::Function(SassFunctionRef(function)),
+            Value::MixinRef(mixin) => SassValue::Mixin(SassMixinRef(mixin)),
+            Value::Calculation(calc) => SassValue::Calculation(SassCalculation(calc)),
+        }
+    }
+
+    pub(crate) fn into_value(self) -> Value {
+        match self {
+            SassValue::Null => Value::Null,
+            SassValue::Boolean(b) => Value::bool(b),
+            SassValue::Number(SassNumber { num, unit }) => Value::Dimension(num, unit, true),
+            SassValue::String { text, quoted } => Value::String(
+                text,
+                if quoted {
+                    QuoteKind::Quoted
+                } else {
+                    QuoteKind::None
+                },
+            ),
+            SassValue::List {
+                elements,
+                separator,
+                bracketed,
+            } => Value::List(
+                elements.into_iter().map(SassValue::into_value).collect(),
+                match separator {
+                    ListSeparator::Space => common::ListSeparator::Space,
+                    ListSeparator::Comma => common::ListSeparator::Comma,
+                    ListSeparator::Slash => common::ListSeparator::Slash,
+                },
+                if bracketed {
+                    Brackets::Bracketed
+                } else {
+                    Brackets::None
+                },
+            ),
+            SassValue::Map(entries) => {
+                let mut map = SassMap::new();
+                for (key, value) in entries {
+                    map.insert(key.into_value(), value.into_value());
+                }
+                Value::Map(map)
+            }
+            SassValue::Color(SassColor(color)) => Value::Color(color),
+            SassValue::Function(SassFunctionRef(function)) => Value::FunctionRef(function),
+            SassValue::Mixin(SassMixinRef(mixin)) => Value::MixinRef(mixin),
+            SassValue::Calculation(SassCalculation(calc)) => Value::Calculation(calc),
+        }
+    }
+}
+
+impl PartialEq for SassValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_value() == other.to_value()
+    }
+}
+
+/// Writes the result of [`SassValue::inspect`], or the debug representation
+/// of the value if it cannot be inspected
+impl fmt::Display for SassValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.inspect() {
+            Ok(inspected) => f.write_str(&inspected),
+            Err(..) => write!(f, "{:?}", self),
+        }
+    }
+}
+
+impl From<bool> for SassValue {
+    #[inline]
+    fn from(b: bool) -> Self {
+        SassValue::Boolean(b)
+    }
+}
+
+impl From<f64> for SassValue {
+    #[inline]
+    fn from(value: f64) -> Self {
+        SassValue::Number(SassNumber::new(value))
+    }
+}
+
+impl From<SassNumber> for SassValue {
+    #[inline]
+    fn from(number: SassNumber) -> Self {
+        SassValue::Number(number)
+    }
+}
+
+impl From<SassColor> for SassValue {
+    #[inline]
+    fn from(color: SassColor) -> Self {
+        SassValue::Color(color)
+    }
+}
@@ -92,3 +92,107 @@ test!(
     }",
     ""
 );
+test!(
+    keywords_of_arglist,
+    "@function foo($args...) {
+        @return keywords($args);
+    }
+
+    a {
+        color: inspect(foo(1, $b: 2, $c_d: 3));
+    }",
+    "a {\n  color: (b: 2, c-d: 3);\n}\n"
+);
+test!(
+    keywords_of_arglist_without_keywords,
+    "@function foo($args...) {
+        @return keywords($args);
+    }
+
+    a {
+        color: inspect(foo(1, 2));
+    }",
+    "a {\n  color: ();\n}\n"
+);
+test!(
+    keywords_in_meta_module,
+    "@use \"sass:meta\";
+
+    @mixin foo($args...) {
+        color: inspect(meta.keywords($args));
+    }
+
+    a {
+        @include foo($b: c);
+    }",
+    "a {\n  color: (b: c);\n}\n"
+);
+test!(
+    arglist_with_keywords_has_only_positional_elements,
+    "@function foo($args...) {
+        $keywords: keywords($args);
+        @return length($args);
+    }
+
+    a {
+        color: foo(1, 2, $c: 3);
+    }",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    forwarding_arglist_preserves_keywords,
+    "@function inner($a, $b) {
+        @return $a - $b;
+    }
+
+    @function outer($args...) {
+        @return inner($args...);
+    }
+
+    a {
+        color: outer($b: 1, $a: 3);
+    }",
+    "a {\n  color: 2;\n}\n"
+);
+test!(
+    forwarding_arglist_to_mixin_preserves_keywords,
+    "@mixin inner($args...) {
+        color: inspect(keywords($args));
+    }
+
+    @mixin outer($args...) {
+        @include inner($args...);
+    }
+
+    a {
+        @include outer(1, $b: 2);
+    }",
+    "a {\n  color: (b: 2);\n}\n"
+);
+error!(
+    keywords_of_non_arglist,
+    "a {
+        color: keywords((a: b));
+    }",
+    "Error: $args: (a: b) is not an argument list."
+);
+error!(
+    unused_keyword_passed_to_arglist,
+    "@mixin foo($args...) {}
+
+    a {
+        @include foo($b: c);
+    }",
+    "Error: No argument named $b."
+);
+error!(
+    unused_keywords_passed_to_arglist,
+    "@function foo($args...) {
+        @return length($args);
+    }
+
+    a {
+        color: foo($b: c, $d: e, $f: g);
+    }",
+    "Error: No arguments named $b, $d or $f."
+);
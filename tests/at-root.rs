@@ -127,6 +127,69 @@ test!(
     }",
     "@media screen {\n  a {\n    color: red;\n  }\n}\n"
 );
+test!(
+    without_media_inside_media,
+    "@media screen {\n  a {\n    @at-root (without: media) {\n      color: red;\n    }\n  }\n}\n",
+    "a {\n  color: red;\n}\n"
+);
+test!(
+    without_media_inside_media_inside_style_rule,
+    "a {\n  @media screen {\n    b: c;\n    @at-root (without: media) {\n      color: red;\n    }\n  }\n}\n",
+    "@media screen {\n  a {\n    b: c;\n  }\n}\na {\n  color: red;\n}\n"
+);
+test!(
+    with_media_removes_style_rule,
+    "@media screen {\n  a {\n    @at-root (with: media) {\n      b {\n        c: d;\n      }\n    }\n  }\n}\n",
+    "@media screen {\n  b {\n    c: d;\n  }\n}\n"
+);
+test!(
+    with_rule_keeps_style_rule,
+    "@media print {\n  a {\n    @at-root (with: rule) {\n      b: c;\n      d {\n        e: f;\n      }\n    }\n  }\n}\n",
+    "a {\n  b: c;\n}\na d {\n  e: f;\n}\n"
+);
+test!(
+    without_all,
+    "@supports (a: b) {\n  @media screen {\n    a {\n      @at-root (without: all) {\n        b {\n          c: d;\n        }\n      }\n    }\n  }\n}\n",
+    "b {\n  c: d;\n}\n"
+);
+test!(
+    without_supports_keeps_media,
+    "@media screen {\n  @supports (a: b) {\n    a {\n      @at-root (without: supports) {\n        c: d;\n      }\n    }\n  }\n}\n",
+    "@media screen {\n  a {\n    c: d;\n  }\n}\n"
+);
+test!(
+    without_media_copies_intermediate_at_rules,
+    "@media a {\n  @supports (b: c) {\n    d {\n      @at-root (without: media) {\n        e: f;\n      }\n    }\n  }\n}\n",
+    "@supports (b: c) {\n  d {\n    e: f;\n  }\n}\n"
+);
+test!(
+    without_unknown_at_rule,
+    "@foo {\n  a {\n    @at-root (without: foo) {\n      b: c;\n    }\n  }\n}\n",
+    "@foo {}\na {\n  b: c;\n}\n"
+);
+test!(
+    query_is_case_insensitive,
+    "@media screen {\n  a {\n    @at-root (WITHOUT: Media) {\n      color: red;\n    }\n  }\n}\n",
+    "a {\n  color: red;\n}\n"
+);
+test!(
+    without_rule_is_default,
+    "a {\n  @at-root (without: rule) {\n    b {\n      c: d;\n    }\n  }\n}\n",
+    "b {\n  c: d;\n}\n"
+);
+test!(
+    media_with_only_empty_style_rules_is_omitted,
+    "@media screen {\n  a {}\n}\nb {\n  c: d;\n}\n",
+    "b {\n  c: d;\n}\n"
+);
+error!(
+    invalid_query_kind,
+    "@at-root (foo: bar) {}", "Error: Expected \"with\" or \"without\"."
+);
+error!(
+    query_missing_colon,
+    "@at-root (without media) {}", "Error: expected \":\"."
+);
 error!(
     #[ignore = "we do not currently validate missing closing curly braces"]
     missing_closing_curly_brace,
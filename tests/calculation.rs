@@ -0,0 +1,156 @@
+#[macro_use]
+mod macros;
+
+test!(
+    calc_simplifies_to_number,
+    "a {\n  color: calc(1px + 2px * 3);\n}\n",
+    "a {\n  color: 7px;\n}\n"
+);
+test!(
+    calc_converts_compatible_units,
+    "a {\n  color: calc(1in + 4px);\n}\n",
+    "a {\n  color: 1.0416666667in;\n}\n"
+);
+test!(
+    calc_preserves_incompatible_units,
+    "a {\n  color: calc(100% - 10px);\n}\n",
+    "a {\n  color: calc(100% - 10px);\n}\n"
+);
+test!(
+    calc_with_variable,
+    "$gap: 10px;\na {\n  color: calc(100% - $gap);\n}\n",
+    "a {\n  color: calc(100% - 10px);\n}\n"
+);
+test!(
+    calc_with_interpolation,
+    "$gap: 10px;\na {\n  color: calc(100% - #{$gap});\n}\n",
+    "a {\n  color: calc(100% - 10px);\n}\n"
+);
+test!(
+    calc_with_function_call,
+    "@use \"sass:math\";\na {\n  color: calc(math.div(10px, 2) + 1%);\n}\n",
+    "a {\n  color: calc(5px + 1%);\n}\n"
+);
+test!(
+    calc_with_special_function,
+    "a {\n  color: calc(var(--width) * 2 + 1px);\n}\n",
+    "a {\n  color: calc(var(--width) * 2 + 1px);\n}\n"
+);
+test!(
+    calc_negative_number_after_plus,
+    "a {\n  color: calc(1% + -2px);\n}\n",
+    "a {\n  color: calc(1% - 2px);\n}\n"
+);
+test!(
+    calc_keeps_necessary_parens,
+    "a {\n  color: calc((1% + 2px) * 3 - 4px / (1vh - 5%));\n}\n",
+    "a {\n  color: calc((1% + 2px) * 3 - 4px / (1vh - 5%));\n}\n"
+);
+test!(
+    calc_removes_unnecessary_parens,
+    "a {\n  color: calc((var(--a) * 3) + (4px));\n}\n",
+    "a {\n  color: calc(var(--a) * 3 + 4px);\n}\n"
+);
+test!(
+    calc_nested_calc_is_unwrapped,
+    "a {\n  color: calc(calc(1% + 1px) * 2);\n}\n",
+    "a {\n  color: calc((1% + 1px) * 2);\n}\n"
+);
+test!(
+    calc_in_variable,
+    "$a: calc(1% + 1px);\na {\n  color: calc($a * 2);\n}\n",
+    "a {\n  color: calc((1% + 1px) * 2);\n}\n"
+);
+test!(
+    calc_compressed,
+    "a {\n  color: calc((1% + 2px) * 3);\n  color: clamp(1%, 2px, 3vh);\n}\n",
+    "a{color:calc((1% + 2px)*3);color:clamp(1%,2px,3vh)}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    type_of_calculation,
+    "a {\n  color: type-of(calc(1px + 1%));\n}\n",
+    "a {\n  color: calculation;\n}\n"
+);
+test!(
+    type_of_simplified_calculation,
+    "a {\n  color: type-of(calc(1px + 1px));\n}\n",
+    "a {\n  color: number;\n}\n"
+);
+test!(
+    inspect_calculation,
+    "a {\n  color: inspect(min(1%, 2px));\n}\n",
+    "a {\n  color: min(1%, 2px);\n}\n"
+);
+test!(
+    calculation_equality,
+    "a {\n  color: calc(1% + 1px) == calc(1% + 1px);\n  color: calc(1% + 1px) == calc(1% + 2px);\n}\n",
+    "a {\n  color: true;\n  color: false;\n}\n"
+);
+test!(
+    calculation_plus_string,
+    "a {\n  color: calc(1% + 1px) + \"a\";\n}\n",
+    "a {\n  color: \"calc(1% + 1px)a\";\n}\n"
+);
+test!(
+    min_unitless_plus_unit,
+    "a {\n  color: min(1 + 1px, 3px);\n}\n",
+    "a {\n  color: 2px;\n}\n"
+);
+test!(
+    max_with_special_function,
+    "$w: 2px;\na {\n  color: max(1%, var(--x), $w * 2);\n}\n",
+    "a {\n  color: max(1%, var(--x), 4px);\n}\n"
+);
+test!(
+    clamp_simplifies_compatible_units,
+    "a {\n  color: clamp(1px, 1in, 3px);\n}\n",
+    "a {\n  color: 3px;\n}\n"
+);
+test!(
+    clamp_preserves_incompatible_units,
+    "a {\n  color: clamp(1%, 2px, 3vh);\n}\n",
+    "a {\n  color: clamp(1%, 2px, 3vh);\n}\n"
+);
+test!(
+    clamp_interpolation_counts_as_all_args,
+    "$args: 1%, 2px, 3vh;\na {\n  color: clamp(#{$args});\n}\n",
+    "a {\n  color: clamp(1%, 2px, 3vh);\n}\n"
+);
+error!(
+    calc_unitless_plus_unit,
+    "a {\n  color: calc(1 + 1px);\n}\n", "Error: 1 and 1px are incompatible."
+);
+error!(
+    calc_incompatible_units,
+    "a {\n  color: calc(1px + 1s);\n}\n", "Error: 1px and 1s are incompatible."
+);
+error!(
+    min_incompatible_known_units,
+    "a {\n  color: min(1px, 1deg, 1%);\n}\n", "Error: 1px and 1deg are incompatible."
+);
+error!(
+    calc_quoted_string_variable,
+    "$a: \"foo\";\na {\n  color: calc($a);\n}\n",
+    "Error: Quoted string \"foo\" can't be used in a calculation."
+);
+error!(
+    calc_color_variable,
+    "$a: red;\na {\n  color: calc($a);\n}\n", "Error: Value red can't be used in a calculation."
+);
+error!(
+    calculation_plus_number,
+    "a {\n  color: calc(1% + 1px) + 1;\n}\n", "Error: Undefined operation \"calc(1% + 1px) + 1\"."
+);
+error!(
+    number_plus_calculation,
+    "a {\n  color: 1 + calc(1% + 1px);\n}\n", "Error: Undefined operation \"1 + calc(1% + 1px)\"."
+);
+error!(
+    calculation_times_number,
+    "a {\n  color: calc(1% + 1px) * 2;\n}\n", "Error: Undefined operation \"calc(1% + 1px) * 2\"."
+);
+error!(
+    clamp_too_few_args,
+    "a {\n  color: clamp(1px, 2px);\n}\n", "Error: 3 arguments required, but only 2 were passed."
+);
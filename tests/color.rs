@@ -227,7 +227,7 @@ test!(
 test!(
     plain_invert_nan,
     "a {\n  color: invert((0 / 0));\n}\n",
-    "a {\n  color: invert(NaN);\n}\n"
+    "a {\n  color: invert(calc(NaN));\n}\n"
 );
 error!(
     plain_invert_two_args,
@@ -527,19 +527,29 @@ error!(
 );
 // todo: we need many more of these tests
 test!(
-    rgba_special_fn_4th_arg_max,
+    rgba_4th_arg_max_is_simplified,
     "a {\n  color: rgba(1 2 max(3, 3));\n}\n",
-    "a {\n  color: rgba(1, 2, max(3, 3));\n}\n"
+    "a {\n  color: #010203;\n}\n"
 );
 test!(
-    rgb_special_fn_4_arg_maintains_units,
+    rgb_4_arg_max_is_simplified,
     "a {\n  color: rgb(1, 0.02, 3%, max(0.4));\n}\n",
-    "a {\n  color: rgb(1, 0.02, 3%, max(0.4));\n}\n"
+    "a {\n  color: rgba(1, 0, 8, 0.4);\n}\n"
 );
 test!(
-    rgb_special_fn_3_arg_maintains_units,
+    rgb_3_arg_max_is_simplified,
     "a {\n  color: rgb(1, 0.02, max(0.4));\n}\n",
-    "a {\n  color: rgb(1, 0.02, max(0.4));\n}\n"
+    "a {\n  color: #010000;\n}\n"
+);
+test!(
+    rgb_special_fn_3_arg_maintains_units,
+    "a {\n  color: rgb(1, 0.02, max(0.4%, 1px));\n}\n",
+    "a {\n  color: rgb(1, 0.02, max(0.4%, 1px));\n}\n"
+);
+test!(
+    rgb_special_fn_calculation,
+    "a {\n  color: rgb(1, 2, calc(1% + 1px));\n}\n",
+    "a {\n  color: rgb(1, 2, calc(1% + 1px));\n}\n"
 );
 test!(
     rgb_special_fn_2_arg_first_non_color,
@@ -587,3 +597,69 @@ test!(
     "a {\n  color: hue(rgb(1, 2, 5));\n}\n",
     "a {\n  color: 225deg;\n}\n"
 );
+test!(
+    rgb_space_separated_slash_alpha,
+    "a {\n  color: rgb(10 20 30 / 50%);\n}\n",
+    "a {\n  color: rgba(10, 20, 30, 0.5);\n}\n"
+);
+test!(
+    rgba_space_separated_slash_alpha_no_whitespace,
+    "a {\n  color: rgba(10 20 30/.5);\n}\n",
+    "a {\n  color: rgba(10, 20, 30, 0.5);\n}\n"
+);
+test!(
+    rgb_slash_list_channels,
+    "@use \"sass:list\";\na {\n  color: rgb(list.slash(10 20 30, 0.5));\n}\n",
+    "a {\n  color: rgba(10, 20, 30, 0.5);\n}\n"
+);
+test!(
+    rgb_division_in_comma_separated_args_is_not_alpha,
+    "a {\n  color: rgb(10, 20, 60/2);\n}\n",
+    "a {\n  color: #0a141e;\n}\n"
+);
+test!(
+    rgb_special_fn_channel_with_slash_alpha,
+    "a {\n  color: rgb(var(--red) 20 30 / 50%);\n}\n",
+    "a {\n  color: rgb(var(--red), 20, 30, 50%);\n}\n"
+);
+test!(
+    rgb_special_fn_slash_alpha,
+    "a {\n  color: rgb(10 20 30 / var(--alpha));\n}\n",
+    "a {\n  color: rgb(10 20 30/var(--alpha));\n}\n"
+);
+test!(
+    rgb_special_fn_before_slash,
+    "a {\n  color: rgb(var(--channels) / 0.5);\n}\n",
+    "a {\n  color: rgb(var(--channels)/0.5);\n}\n"
+);
+test!(
+    rgb_special_fn_fewer_than_three_channels,
+    "a {\n  color: rgb(10 var(--channels));\n}\n",
+    "a {\n  color: rgb(10 var(--channels));\n}\n"
+);
+error!(
+    rgb_comma_separated_channels,
+    "a {\n  color: rgb((10, 20, 30));\n}\n", "Error: $channels must be a space-separated list."
+);
+error!(
+    rgb_bracketed_channels,
+    "a {\n  color: rgb([10 20 30]);\n}\n", "Error: $channels must be an unbracketed list."
+);
+error!(
+    rgb_too_many_channels,
+    "a {\n  color: rgb(10 20 30 40);\n}\n", "Error: Only 3 elements allowed, but 4 were passed."
+);
+error!(
+    rgb_missing_channel,
+    "a {\n  color: rgb(10 20 / 0.5);\n}\n", "Error: Missing element $blue."
+);
+error!(
+    rgb_too_many_slash_separated_elements,
+    "@use \"sass:list\";\na {\n  color: rgb(list.slash(10 20 30, 0.5, 1));\n}\n",
+    "Error: Only 2 slash-separated elements allowed, but 3 were passed."
+);
+error!(
+    rgb_slash_alpha_not_a_number,
+    "@use \"sass:list\";\na {\n  color: rgb(list.slash(10 20 30, foo));\n}\n",
+    "Error: $alpha: foo is not a number."
+);
@@ -209,14 +209,19 @@ test!(
     "a {\n  color: red;\n}\n"
 );
 test!(
-    hsl_special_fn_4_arg_maintains_units,
+    hsl_4_arg_max_is_simplified,
     "a {\n  color: hsl(1, 0.02, 3%, max(0.4));\n}\n",
-    "a {\n  color: hsl(1, 0.02, 3%, max(0.4));\n}\n"
+    "a {\n  color: rgba(8, 8, 8, 0.4);\n}\n"
 );
 test!(
-    hsl_special_fn_3_arg_maintains_units,
+    hsl_3_arg_max_is_simplified,
     "a {\n  color: hsl(1, 0.02, max(0.4));\n}\n",
-    "a {\n  color: hsl(1, 0.02, max(0.4));\n}\n"
+    "a {\n  color: #010101;\n}\n"
+);
+test!(
+    hsl_special_fn_3_arg_maintains_units,
+    "a {\n  color: hsl(1, 0.02, max(0.4%, 1px));\n}\n",
+    "a {\n  color: hsl(1, 0.02, max(0.4%, 1px));\n}\n"
 );
 test!(
     hsla_special_fn_1_arg_is_not_list,
@@ -258,3 +263,27 @@ test!(
     "a {\n  color: hsl(8grad, 25%, 50%);\n}\n",
     "a {\n  color: #9f6860;\n}\n"
 );
+test!(
+    hsl_space_separated_slash_alpha,
+    "a {\n  color: hsl(120deg 50% 50% / .3);\n}\n",
+    "a {\n  color: rgba(64, 191, 64, 0.3);\n}\n"
+);
+test!(
+    hsla_space_separated_percent_alpha,
+    "a {\n  color: hsla(120 50% 50% / 30%);\n}\n",
+    "a {\n  color: rgba(64, 191, 64, 0.3);\n}\n"
+);
+test!(
+    hsl_special_fn_slash_alpha,
+    "a {\n  color: hsla(120 50% 50% / var(--alpha));\n}\n",
+    "a {\n  color: hsla(120 50% 50%/var(--alpha));\n}\n"
+);
+test!(
+    hsl_special_fn_channel_with_slash_alpha,
+    "a {\n  color: hsl(120 calc(10% + 2px) 50% / 0.5);\n}\n",
+    "a {\n  color: hsl(120, calc(10% + 2px), 50%, 0.5);\n}\n"
+);
+error!(
+    hsl_missing_channel,
+    "a {\n  color: hsl(120 50%);\n}\n", "Error: Missing element $lightness."
+);
@@ -81,3 +81,43 @@ error!(
     "@use \"sass:color\";\na {\n  color: color.hwb(0, 0, 100);\n}\n",
     "Error: $whiteness: Expected 0 to have unit \"%\"."
 );
+test!(
+    hwb_space_separated,
+    "@use \"sass:color\";\na {\n  color: color.hwb(120 10% 20%);\n}\n",
+    "a {\n  color: #1acc1a;\n}\n"
+);
+test!(
+    hwb_space_separated_slash_alpha,
+    "@use \"sass:color\";\na {\n  color: color.hwb(120 10% 20% / 0.5);\n}\n",
+    "a {\n  color: rgba(26, 204, 26, 0.5);\n}\n"
+);
+test!(
+    hwb_special_fn_channel,
+    "@use \"sass:color\";\na {\n  color: color.hwb(var(--hue) 10% 20% / 0.5);\n}\n",
+    "a {\n  color: hwb(var(--hue) 10% 20% / 0.5);\n}\n"
+);
+test!(
+    hwb_special_fn_comma_separated_args,
+    "@use \"sass:color\";\na {\n  color: color.hwb(120, 10%, calc(1% + 2px));\n}\n",
+    "a {\n  color: hwb(120 10% calc(1% + 2px));\n}\n"
+);
+error!(
+    hwb_missing_channel,
+    "@use \"sass:color\";\na {\n  color: color.hwb(120 10%);\n}\n",
+    "Error: Missing element $blackness."
+);
+test!(
+    global_hwb_slash_alpha,
+    "a {\n  color: hwb(120 10% 20% / 0.5);\n}\n",
+    "a {\n  color: rgba(26, 204, 26, 0.5);\n}\n"
+);
+test!(
+    global_hwb_space_separated,
+    "a {\n  color: hwb(120 10% 20%);\n}\n",
+    "a {\n  color: #1acc1a;\n}\n"
+);
+test!(
+    global_hwb_special_fn_channel,
+    "a {\n  color: hwb(var(--hue) 10% 20% / 0.5);\n}\n",
+    "a {\n  color: hwb(var(--hue) 10% 20% / 0.5);\n}\n"
+);
@@ -0,0 +1,153 @@
+#[macro_use]
+mod macros;
+
+test!(
+    hsl_none_hue,
+    "@use \"sass:color\";\na {\n  color: hsl(none 0% 50%);\n}\n",
+    "a {\n  color: hsl(none 0% 50%);\n}\n"
+);
+test!(
+    rgb_none_red,
+    "@use \"sass:color\";\na {\n  color: rgb(none 10 20);\n}\n",
+    "a {\n  color: rgb(none 10 20);\n}\n"
+);
+test!(
+    rgb_none_alpha,
+    "@use \"sass:color\";\na {\n  color: rgb(10 20 30 / none);\n}\n",
+    "a {\n  color: rgb(10 20 30 / none);\n}\n"
+);
+test!(
+    rgb_none_uppercase,
+    "@use \"sass:color\";\na {\n  color: rgb(NONE 10 20);\n}\n",
+    "a {\n  color: rgb(none 10 20);\n}\n"
+);
+test!(
+    hsl_none_before_slash,
+    "@use \"sass:color\";\na {\n  color: hsl(120 50% none / 0.5);\n}\n",
+    "a {\n  color: hsl(120deg 50% none / 0.5);\n}\n"
+);
+test!(
+    hwb_none_hue,
+    "@use \"sass:color\";\na {\n  color: color.hwb(none 20% 30%);\n}\n",
+    "a {\n  color: hwb(none 20% 30%);\n}\n"
+);
+test!(
+    lch_none_hue,
+    "@use \"sass:color\";\na {\n  color: lch(50% 0 none);\n}\n",
+    "a {\n  color: lch(50% 0 none);\n}\n"
+);
+test!(
+    oklch_none_lightness,
+    "@use \"sass:color\";\na {\n  color: oklch(none 0.1 200 / 0.5);\n}\n",
+    "a {\n  color: oklch(none 0.1 200deg / 0.5);\n}\n"
+);
+test!(
+    color_fn_none_channel,
+    "@use \"sass:color\";\na {\n  color: color(srgb none 0.5 1);\n}\n",
+    "a {\n  color: color(srgb none 0.5 1);\n}\n"
+);
+test!(
+    lab_all_none,
+    "@use \"sass:color\";\na {\n  color: lab(none none none / none);\n}\n",
+    "a {\n  color: lab(none none none / none);\n}\n"
+);
+test!(
+    missing_channel_is_zero,
+    "@use \"sass:color\";\na {\n  color: color.channel(hsl(none 0% 50%), \"hue\");\n}\n",
+    "a {\n  color: 0deg;\n}\n"
+);
+test!(
+    missing_not_equal_to_zero,
+    "@use \"sass:color\";\na {\n  color: hsl(none 0% 50%) == hsl(0 0% 50%);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    is_missing_hue,
+    "@use \"sass:color\";\na {\n  color: color.is-missing(hsl(none 0% 50%), \"hue\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    is_missing_present_hue,
+    "@use \"sass:color\";\na {\n  color: color.is-missing(hsl(10 0% 50%), \"hue\");\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    is_missing_alpha,
+    "@use \"sass:color\";\na {\n  color: color.is-missing(rgb(1 2 3 / none), \"alpha\");\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    adjust_preserves_missing,
+    "@use \"sass:color\";\na {\n  color: color.adjust(hsl(none 0% 50%), $lightness: 10%);\n}\n",
+    "a {\n  color: hsl(none 0% 60%);\n}\n"
+);
+test!(
+    scale_preserves_missing,
+    "@use \"sass:color\";\na {\n  color: color.scale(hsl(none 0% 50%), $alpha: -50%);\n}\n",
+    "a {\n  color: hsl(none 0% 50% / 0.5);\n}\n"
+);
+test!(
+    change_preserves_missing,
+    "@use \"sass:color\";\na {\n  color: color.change(hsl(none 0% 50%), $alpha: 0.5);\n}\n",
+    "a {\n  color: hsl(none 0% 50% / 0.5);\n}\n"
+);
+test!(
+    change_missing_channel,
+    "@use \"sass:color\";\na {\n  color: color.change(hsl(none 0% 50%), $hue: 10deg);\n}\n",
+    "a {\n  color: gray;\n}\n"
+);
+test!(
+    mix_missing_hue,
+    "@use \"sass:color\";\na {\n  color: color.mix(hsl(none 0% 50%), hsl(120 50% 50%), $method: hsl);\n}\n",
+    "a {\n  color: #609f60;\n}\n"
+);
+test!(
+    mix_missing_lightness,
+    "@use \"sass:color\";\na {\n  color: color.mix(lab(none 0 0), lab(50% 10 10), $method: lab);\n}\n",
+    "a {\n  color: lab(50% 5 5);\n}\n"
+);
+test!(
+    mix_missing_in_both,
+    "@use \"sass:color\";\na {\n  color: color.mix(oklch(50% 0 none), oklch(70% 0 none), $method: oklch);\n}\n",
+    "a {\n  color: oklch(60% 0 none);\n}\n"
+);
+test!(
+    to_space_carries_missing_hue,
+    "@use \"sass:color\";\na {\n  color: color.to-space(hsl(none 0% 50%), oklch);\n}\n",
+    "a {\n  color: oklch(59.8180730527% 0 none);\n}\n"
+);
+error!(
+    none_comma_syntax,
+    "@use \"sass:color\";\na {\n  color: rgb(none, 0, 0);\n}\n",
+    "Error: $red: none is not a number."
+);
+error!(
+    is_missing_unknown_channel,
+    "@use \"sass:color\";\na {\n  color: color.is-missing(red, \"hue\");\n}\n",
+    "Error: $channel: Color red has no channel named hue."
+);
+test!(
+    scale_oklch_keeps_missing_hue,
+    "@use \"sass:color\";\na {\n  color: color.scale(oklch(50% 0.1 none), $lightness: 10%);\n}\n",
+    "a {\n  color: oklch(55% 0.1 none);\n}\n"
+);
+test!(
+    change_lab_keeps_missing_a,
+    "@use \"sass:color\";\na {\n  color: color.change(lab(50% none 10), $lightness: 10%);\n}\n",
+    "a {\n  color: lab(10% none 10);\n}\n"
+);
+test!(
+    change_lab_sets_missing_a,
+    "@use \"sass:color\";\na {\n  color: color.change(lab(50% none 10), $a: 5);\n}\n",
+    "a {\n  color: lab(50% 5 10);\n}\n"
+);
+test!(
+    adjust_oklch_keeps_missing_alpha,
+    "@use \"sass:color\";\na {\n  color: color.adjust(oklch(50% 0.1 200 / none), $chroma: 0.05);\n}\n",
+    "a {\n  color: oklch(50% 0.15 200deg / none);\n}\n"
+);
+test!(
+    change_display_p3_keeps_missing_red,
+    "@use \"sass:color\";\na {\n  color: color.change(color(display-p3 none .5 .5), $blue: .2);\n}\n",
+    "a {\n  color: color(display-p3 none 0.5 0.2);\n}\n"
+);
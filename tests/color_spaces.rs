@@ -0,0 +1,327 @@
+#[macro_use]
+mod macros;
+
+test!(
+    lab_percent_lightness,
+    "@use \"sass:color\";\na {\n  color: lab(50% 20 30);\n}\n",
+    "a {\n  color: lab(50% 20 30);\n}\n"
+);
+test!(
+    lab_slash_alpha,
+    "@use \"sass:color\";\na {\n  color: lab(50% 20 30 / 0.5);\n}\n",
+    "a {\n  color: lab(50% 20 30 / 0.5);\n}\n"
+);
+test!(
+    lab_percent_ab,
+    "@use \"sass:color\";\na {\n  color: lab(50 10% -20%);\n}\n",
+    "a {\n  color: lab(50% 12.5 -25);\n}\n"
+);
+test!(
+    lab_lightness_clamped,
+    "@use \"sass:color\";\na {\n  color: lab(150% 0 0);\n}\n",
+    "a {\n  color: lab(100% 0 0);\n}\n"
+);
+test!(
+    lch_angle_units,
+    "@use \"sass:color\";\na {\n  color: lch(50% 40 0.5turn);\n}\n",
+    "a {\n  color: lch(50% 40 180deg);\n}\n"
+);
+test!(
+    lch_hue_normalized,
+    "@use \"sass:color\";\na {\n  color: lch(50% 40 -90deg);\n}\n",
+    "a {\n  color: lch(50% 40 270deg);\n}\n"
+);
+test!(
+    oklab_unitless_lightness,
+    "@use \"sass:color\";\na {\n  color: oklab(0.5 0.1 -0.1);\n}\n",
+    "a {\n  color: oklab(50% 0.1 -0.1);\n}\n"
+);
+test!(
+    oklch_percent_alpha,
+    "@use \"sass:color\";\na {\n  color: oklch(70% 0.1 200 / 50%);\n}\n",
+    "a {\n  color: oklch(70% 0.1 200deg / 0.5);\n}\n"
+);
+test!(
+    color_display_p3,
+    "@use \"sass:color\";\na {\n  color: color(display-p3 1 0 0);\n}\n",
+    "a {\n  color: color(display-p3 1 0 0);\n}\n"
+);
+test!(
+    color_srgb_alpha,
+    "@use \"sass:color\";\na {\n  color: color(srgb 0.5 0.5 0.5 / 0.5);\n}\n",
+    "a {\n  color: color(srgb 0.5 0.5 0.5 / 0.5);\n}\n"
+);
+test!(
+    color_xyz_d65_alias,
+    "@use \"sass:color\";\na {\n  color: color(xyz-d65 0.2 0.3 0.4);\n}\n",
+    "a {\n  color: color(xyz 0.2 0.3 0.4);\n}\n"
+);
+test!(
+    color_space_case_insensitive,
+    "@use \"sass:color\";\na {\n  color: color(Display-P3 1 0 0);\n}\n",
+    "a {\n  color: color(display-p3 1 0 0);\n}\n"
+);
+test!(
+    lab_special_function,
+    "@use \"sass:color\";\na {\n  color: lab(var(--l) 1 2);\n}\n",
+    "a {\n  color: lab(var(--l) 1 2);\n}\n"
+);
+test!(
+    color_special_function_space,
+    "@use \"sass:color\";\na {\n  color: color(var(--space) 1 0 0);\n}\n",
+    "a {\n  color: color(var(--space) 1 0 0);\n}\n"
+);
+test!(
+    to_space_red_oklch,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, oklch);\n}\n",
+    "a {\n  color: oklch(62.7955363921% 0.2576833038 29.2338802796deg);\n}\n"
+);
+test!(
+    to_space_red_lab,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, lab);\n}\n",
+    "a {\n  color: lab(54.290542947% 80.8049203346 69.890988259);\n}\n"
+);
+test!(
+    to_space_oklch_rgb,
+    "@use \"sass:color\";\na {\n  color: color.to-space(oklch(62.8% 0.2577 29.23), rgb);\n}\n",
+    "a {\n  color: red;\n}\n"
+);
+test!(
+    to_space_roundtrip,
+    "@use \"sass:color\";\na {\n  color: color.to-space(color.to-space(red, lab), rgb);\n}\n",
+    "a {\n  color: red;\n}\n"
+);
+test!(
+    to_space_srgb,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, srgb);\n}\n",
+    "a {\n  color: color(srgb 1 0 0);\n}\n"
+);
+test!(
+    space_of_lab,
+    "@use \"sass:color\";\na {\n  color: color.space(lab(50% 0 0));\n}\n",
+    "a {\n  color: lab;\n}\n"
+);
+test!(
+    space_of_hsl,
+    "@use \"sass:color\";\na {\n  color: color.space(hsl(10, 20%, 30%));\n}\n",
+    "a {\n  color: hsl;\n}\n"
+);
+test!(
+    space_of_named_color,
+    "@use \"sass:color\";\na {\n  color: color.space(red);\n}\n",
+    "a {\n  color: rgb;\n}\n"
+);
+test!(
+    channel_hue,
+    "@use \"sass:color\";\na {\n  color: color.channel(oklch(70% 0.1 200), \"hue\");\n}\n",
+    "a {\n  color: 200deg;\n}\n"
+);
+test!(
+    channel_with_space,
+    "@use \"sass:color\";\na {\n  color: color.channel(red, \"lightness\", $space: oklch);\n}\n",
+    "a {\n  color: 62.7955363921%;\n}\n"
+);
+test!(
+    channel_hsl_saturation,
+    "@use \"sass:color\";\na {\n  color: color.channel(hsl(120, 50%, 50%), \"saturation\");\n}\n",
+    "a {\n  color: 50%;\n}\n"
+);
+test!(
+    channel_alpha,
+    "@use \"sass:color\";\na {\n  color: color.channel(lab(50% 0 0 / 0.3), \"alpha\");\n}\n",
+    "a {\n  color: 0.3;\n}\n"
+);
+test!(
+    is_in_gamut_false,
+    "@use \"sass:color\";\na {\n  color: color.is-in-gamut(color(display-p3 1 0 0), srgb);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+test!(
+    is_in_gamut_true,
+    "@use \"sass:color\";\na {\n  color: color.is-in-gamut(red, display-p3);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    to_gamut_clip,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(color(display-p3 1 0 0), srgb, $method: clip);\n}\n",
+    "a {\n  color: color(display-p3 0.9174875573 0.2002868077 0.1385605912);\n}\n"
+);
+test!(
+    to_gamut_local_minde,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(oklch(70% 0.4 200), $space: srgb, $method: local-minde);\n}\n",
+    "a {\n  color: oklch(70.9011036466% 0.1205700825 201.1102666781deg);\n}\n"
+);
+test!(
+    to_gamut_already_in_gamut,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(lab(50% 0 0), srgb, $method: clip);\n}\n",
+    "a {\n  color: lab(50% 0 0);\n}\n"
+);
+test!(
+    mix_method_oklch,
+    "@use \"sass:color\";\na {\n  color: color.mix(red, blue, $method: oklch);\n}\n",
+    "a {\n  color: #ba00c2;\n}\n"
+);
+test!(
+    mix_method_longer_hue,
+    "@use \"sass:color\";\na {\n  color: color.mix(red, blue, $method: oklch longer hue);\n}\n",
+    "a {\n  color: #009300;\n}\n"
+);
+test!(
+    mix_method_weight,
+    "@use \"sass:color\";\na {\n  color: color.mix(lab(50% 20 30), lab(70% -20 10), 25%, lab);\n}\n",
+    "a {\n  color: lab(65% -10 15);\n}\n"
+);
+test!(
+    mix_method_rgb,
+    "@use \"sass:color\";\na {\n  color: color.mix(red, blue, $method: rgb);\n}\n",
+    "a {\n  color: purple;\n}\n"
+);
+test!(
+    equality_same_space,
+    "@use \"sass:color\";\na {\n  color: lab(50% 20 30) == lab(50% 20 30);\n}\n",
+    "a {\n  color: true;\n}\n"
+);
+test!(
+    equality_legacy_and_non_legacy,
+    "@use \"sass:color\";\na {\n  color: red == color(srgb 1 0 0);\n}\n",
+    "a {\n  color: false;\n}\n"
+);
+error!(
+    lab_lightness_wrong_unit,
+    "@use \"sass:color\";\na {\n  color: lab(50px 1 2);\n}\n",
+    "Error: $lightness: Expected 50px to have unit \"%\" or no units."
+);
+error!(
+    lch_hue_not_angle,
+    "@use \"sass:color\";\na {\n  color: lch(50% 1 2px);\n}\n",
+    "Error: $hue: Expected 2px to have an angle unit (deg, grad, rad, turn)."
+);
+error!(
+    lab_infinity_channel,
+    "@use \"sass:color\";\na {\n  color: lab(50% (1/0) 2);\n}\n",
+    "Error: $a: Infinity is not a finite number."
+);
+error!(
+    color_unknown_space,
+    "@use \"sass:color\";\na {\n  color: color(foo 1 2 3);\n}\n",
+    "Error: $description: Unknown color space \"foo\"."
+);
+error!(
+    color_legacy_space,
+    "@use \"sass:color\";\na {\n  color: color(rgb 1 2 3);\n}\n",
+    "Error: $description: Unknown color space \"rgb\"."
+);
+error!(
+    color_missing_space,
+    "@use \"sass:color\";\na {\n  color: color(1 2 3);\n}\n",
+    "Error: $description: Expected 1 2 3 to begin with a color space."
+);
+error!(
+    lab_missing_channel,
+    "@use \"sass:color\";\na {\n  color: lab(1 2);\n}\n", "Error: Missing element $b."
+);
+error!(
+    mix_non_legacy_without_method,
+    "@use \"sass:color\";\na {\n  color: color.mix(lab(50% 0 0), red);\n}\n",
+    "Error: To use color.mix() with non-legacy colors, you must provide a $method."
+);
+error!(
+    mix_hue_method_rectangular_space,
+    "@use \"sass:color\";\na {\n  color: color.mix(red, blue, $method: oklab longer hue);\n}\n",
+    "Error: $method: Hue interpolation method \"longer hue\" may not be set for rectangular color space oklab."
+);
+error!(
+    mix_unknown_method,
+    "@use \"sass:color\";\na {\n  color: color.mix(red, blue, $method: foo);\n}\n",
+    "Error: $method: Unknown color space \"foo\"."
+);
+error!(
+    channel_unknown,
+    "@use \"sass:color\";\na {\n  color: color.channel(red, \"foo\");\n}\n",
+    "Error: $channel: Color red has no channel named foo."
+);
+error!(
+    channel_unquoted,
+    "@use \"sass:color\";\na {\n  color: color.channel(red, red);\n}\n",
+    "Error: $channel: Expected red to be a quoted string."
+);
+error!(
+    to_gamut_no_method,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(red);\n}\n",
+    "Error: $method: color.to-gamut() requires a $method argument, either \"clip\" or \"local-minde\"."
+);
+error!(
+    to_gamut_unknown_method,
+    "@use \"sass:color\";\na {\n  color: color.to-gamut(red, srgb, $method: foo);\n}\n",
+    "Error: $method: Unknown gamut map method \"foo\"."
+);
+error!(
+    to_space_unknown,
+    "@use \"sass:color\";\na {\n  color: color.to-space(red, foo);\n}\n",
+    "Error: $space: Unknown color space \"foo\"."
+);
+test!(
+    opacify_keeps_space,
+    "@use \"sass:color\";\na {\n  color: opacify(oklch(70% 0.1 200), .1);\n}\n",
+    "a {\n  color: oklch(70% 0.1 200deg);\n}\n"
+);
+test!(
+    transparentize_keeps_space,
+    "@use \"sass:color\";\na {\n  color: transparentize(lab(50% 20 30), .5);\n}\n",
+    "a {\n  color: lab(50% 20 30 / 0.5);\n}\n"
+);
+test!(
+    scale_oklch_lightness,
+    "@use \"sass:color\";\na {\n  color: color.scale(oklch(50% 0.1 200), $lightness: 10%);\n}\n",
+    "a {\n  color: oklch(55% 0.1 200deg);\n}\n"
+);
+test!(
+    adjust_oklch_lightness_and_alpha,
+    "@use \"sass:color\";\na {\n  color: color.adjust(oklch(50% 0.1 200), $lightness: -10%, $alpha: -0.5);\n}\n",
+    "a {\n  color: oklch(40% 0.1 200deg / 0.5);\n}\n"
+);
+test!(
+    adjust_oklch_hue,
+    "@use \"sass:color\";\na {\n  color: color.adjust(oklch(50% 0.1 200), $hue: 30deg);\n}\n",
+    "a {\n  color: oklch(50% 0.1 230deg);\n}\n"
+);
+test!(
+    change_lab_lightness,
+    "@use \"sass:color\";\na {\n  color: color.change(lab(50% 10 10), $lightness: 10%);\n}\n",
+    "a {\n  color: lab(10% 10 10);\n}\n"
+);
+test!(
+    scale_display_p3_red,
+    "@use \"sass:color\";\na {\n  color: color.scale(color(display-p3 .5 .5 .5), $red: 50%);\n}\n",
+    "a {\n  color: color(display-p3 0.75 0.5 0.5);\n}\n"
+);
+test!(
+    change_legacy_in_hsl_space,
+    "@use \"sass:color\";\na {\n  color: color.change(red, $saturation: 50%, $space: hsl);\n}\n",
+    "a {\n  color: #bf4040;\n}\n"
+);
+test!(
+    adjust_legacy_in_rgb_space,
+    "@use \"sass:color\";\na {\n  color: color.adjust(red, $red: -55, $space: rgb);\n}\n",
+    "a {\n  color: #c80000;\n}\n"
+);
+error!(
+    lighten_non_legacy,
+    "@use \"sass:color\";\na {\n  color: lighten(oklch(50% 0.1 200), 10%);\n}\n",
+    "Error: lighten() is only supported for legacy colors. Please use color.adjust() instead with an explicit $space argument."
+);
+error!(
+    adjust_hue_non_legacy,
+    "@use \"sass:color\";\na {\n  color: adjust-hue(lab(50% 10 10), 10deg);\n}\n",
+    "Error: adjust-hue() is only supported for legacy colors. Please use color.adjust() instead with an explicit $space argument."
+);
+error!(
+    adjust_channel_not_in_space,
+    "@use \"sass:color\";\na {\n  color: color.adjust(lab(50% 10 10), $red: 10);\n}\n",
+    "Error: $red: Color space lab doesn't have a channel with this name."
+);
+error!(
+    scale_hue_non_legacy,
+    "@use \"sass:color\";\na {\n  color: color.scale(oklch(50% 0.1 200), $hue: 10%);\n}\n",
+    "Error: $hue: Channel isn't scalable."
+);
@@ -0,0 +1,239 @@
+use std::io::Write;
+
+use grass::{ListSeparator, SassNumber, SassValue};
+
+#[macro_use]
+mod macros;
+
+fn identity(args: &[SassValue]) -> Result<SassValue, String> {
+    Ok(args[0].clone())
+}
+
+fn asset_url(args: &[SassValue]) -> Result<SassValue, String> {
+    let path = match &args[0] {
+        SassValue::String { text, .. } => text,
+        _ => return Err("$path: expected a string.".to_owned()),
+    };
+
+    Ok(SassValue::String {
+        text: match args[1] {
+            SassValue::Boolean(true) => format!("url(\"/assets/{}?v=1\")", path),
+            _ => format!("url(\"/assets/{}\")", path),
+        },
+        quoted: false,
+    })
+}
+
+fn count(args: &[SassValue]) -> Result<SassValue, String> {
+    match &args[0] {
+        SassValue::List {
+            elements,
+            separator: ListSeparator::Comma,
+            ..
+        } => Ok(SassValue::Number(SassNumber::new(elements.len() as f64))),
+        _ => Err("expected an arglist.".to_owned()),
+    }
+}
+
+fn fail(_: &[SassValue]) -> Result<SassValue, String> {
+    Err("something went wrong".to_owned())
+}
+
+fn options() -> grass::Options<'static> {
+    grass::Options::default()
+        .add_function("identity($value)", &identity)
+        .add_function("asset-url($path, $hash: true)", &asset_url)
+        .add_function("count($args...)", &count)
+        .add_function("fail()", &fail)
+}
+
+fn error_message(input: &str, options: &grass::Options) -> String {
+    match grass::from_string(input.to_string(), options) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e
+            .to_string()
+            .chars()
+            .take_while(|c| *c != '\n')
+            .collect::<String>(),
+    }
+}
+
+test!(
+    default_argument_is_used,
+    "a {\n  background: asset-url(\"a.png\");\n}\n",
+    "a {\n  background: url(\"/assets/a.png?v=1\");\n}\n",
+    options()
+);
+test!(
+    positional_argument,
+    "a {\n  background: asset-url(\"a.png\", false);\n}\n",
+    "a {\n  background: url(\"/assets/a.png\");\n}\n",
+    options()
+);
+test!(
+    named_argument,
+    "a {\n  background: asset-url($hash: false, $path: \"a.png\");\n}\n",
+    "a {\n  background: url(\"/assets/a.png\");\n}\n",
+    options()
+);
+test!(
+    underscores_and_hyphens_are_equal,
+    "a {\n  background: asset_url(\"a.png\", false);\n}\n",
+    "a {\n  background: url(\"/assets/a.png\");\n}\n",
+    options()
+);
+test!(
+    variadic_argument,
+    "a {\n  color: count(1, 2, 3);\n}\n",
+    "a {\n  color: 3;\n}\n",
+    options()
+);
+test!(
+    number_with_unit_roundtrips,
+    "a {\n  width: identity(1px / 3);\n}\n",
+    "a {\n  width: 0.3333333333px;\n}\n",
+    options()
+);
+test!(
+    quoted_string_roundtrips,
+    "a {\n  content: identity(\"a\");\n}\n",
+    "a {\n  content: \"a\";\n}\n",
+    options()
+);
+test!(
+    bracketed_list_roundtrips,
+    "a {\n  grid-template-columns: identity([a b]);\n}\n",
+    "a {\n  grid-template-columns: [a b];\n}\n",
+    options()
+);
+test!(
+    map_roundtrips,
+    "a {\n  color: map-get(identity((a: red, b: blue)), b);\n}\n",
+    "a {\n  color: blue;\n}\n",
+    options()
+);
+test!(
+    color_roundtrips,
+    "a {\n  color: identity(red);\n}\n",
+    "a {\n  color: red;\n}\n",
+    options()
+);
+test!(
+    function_exists,
+    "a {\n  color: function-exists(identity);\n}\n",
+    "a {\n  color: true;\n}\n",
+    options()
+);
+test!(
+    get_function_and_call,
+    "a {\n  color: call(get-function(identity), red);\n}\n",
+    "a {\n  color: red;\n}\n",
+    options()
+);
+test!(
+    overrides_global_builtin,
+    "a {\n  color: red(blue);\n}\n",
+    "a {\n  color: blue;\n}\n",
+    grass::Options::default().add_function("red($color)", &identity)
+);
+test!(
+    builtin_module_function_is_not_overridden,
+    "@use \"sass:color\";\na {\n  color: color.red(blue);\n}\n",
+    "a {\n  color: 0;\n}\n",
+    grass::Options::default().add_function("red($color)", &identity)
+);
+test!(
+    user_defined_function_takes_precedence,
+    "@function identity($a) {\n  @return foo;\n}\na {\n  color: identity(red);\n}\n",
+    "a {\n  color: foo;\n}\n",
+    options()
+);
+test!(
+    last_registered_function_wins,
+    "a {\n  color: foo();\n}\n",
+    "a {\n  color: b;\n}\n",
+    grass::Options::default()
+        .add_function("foo()", &|_| Ok(SassValue::String {
+            text: "a".to_owned(),
+            quoted: false
+        }))
+        .add_function("foo()", &|_| Ok(SassValue::String {
+            text: "b".to_owned(),
+            quoted: false
+        }))
+);
+
+#[test]
+fn callback_captures_state() {
+    let prefix = String::from("/static");
+    let callback = |_: &[SassValue]| {
+        Ok(SassValue::String {
+            text: prefix.clone(),
+            quoted: true,
+        })
+    };
+    let options = grass::Options::default().add_function("prefix()", &callback);
+    assert_eq!(
+        "a {\n  content: \"/static\";\n}\n",
+        grass::from_string("a {\n  content: prefix();\n}\n".to_string(), &options).unwrap()
+    );
+}
+
+#[test]
+fn callable_from_module() {
+    tempfile!("callable_from_module.scss", "$a: identity(red);");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            "@use \"callable_from_module\" as m;\na {\n  color: m.$a;\n}\n".to_string(),
+            &options()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn callback_error_is_propagated() {
+    assert_eq!(
+        "Error: something went wrong",
+        error_message("a {\n  color: fail();\n}\n", &options())
+    );
+}
+
+#[test]
+fn missing_argument() {
+    assert_eq!(
+        "Error: Missing argument $path.",
+        error_message("a {\n  color: asset-url();\n}\n", &options())
+    );
+}
+
+#[test]
+fn too_many_arguments() {
+    assert_eq!(
+        "Error: Only 1 argument allowed, but 2 were passed.",
+        error_message("a {\n  color: identity(a, b);\n}\n", &options())
+    );
+}
+
+#[test]
+fn invalid_signature() {
+    assert_eq!(
+        "Error: expected \"(\".",
+        error_message(
+            "a {\n  color: red;\n}\n",
+            &grass::Options::default().add_function("foo", &identity)
+        )
+    );
+}
+
+#[test]
+fn reserved_name_in_signature() {
+    assert_eq!(
+        "Error: Invalid function name.",
+        error_message(
+            "a {\n  color: red;\n}\n",
+            &grass::Options::default().add_function("url($a)", &identity)
+        )
+    );
+}
@@ -1393,7 +1393,6 @@ test!(
     "@media screen {\n  @unknown {\n    .foo, .bar {\n      a: b;\n    }\n  }\n}\n"
 );
 test!(
-    #[ignore = "media queries are not yet parsed correctly"]
     extend_within_separate_media_queries,
     "@media screen {.foo {a: b}}
     @media screen {.bar {@extend .foo}}
@@ -1401,7 +1400,6 @@ test!(
     "@media screen {\n  .foo, .bar {\n    a: b;\n  }\n}\n"
 );
 test!(
-    #[ignore = "media queries are not yet parsed correctly"]
     extend_within_separate_unknown_at_rules,
     "@unknown {.foo {a: b}}
     @unknown {.bar {@extend .foo}}
@@ -1409,7 +1407,6 @@ test!(
     "@unknown {\n  .foo, .bar {\n    a: b;\n  }\n}\n@unknown {}\n"
 );
 test!(
-    #[ignore = "media queries are not yet parsed correctly"]
     extend_within_separate_nested_at_rules,
     "@media screen {@flooblehoof {.foo {a: b}}}
      @media screen {@flooblehoof {.bar {@extend .foo}}}",
@@ -1561,7 +1558,6 @@ test!(
     ".parent1 .child {\n  a: b;\n}\n"
 );
 test!(
-    #[ignore = "media queries are not yet parsed correctly"]
     extend_inside_double_nested_media,
     "@media all {
         @media (orientation: landscape) {
@@ -1741,7 +1737,6 @@ test!(
     ":not(.c):not(.a):not(.d):not(.b) {\n  a: b;\n}\n"
 );
 test!(
-    #[ignore = "media queries are not yet parsed correctly"]
     does_not_move_page_block_in_media,
     "@media screen {
         a { x:y; }
@@ -1921,6 +1916,52 @@ test!(
     }",
     "c b {\n  color: red;\n}\n"
 );
+test!(
+    extend_within_merged_media_queries,
+    "@media screen {
+        @media (color) {
+          .foo {a: b}
+        }
+        @media (color) {
+          .bar {@extend .foo}
+        }
+    }
+    ",
+    "@media screen and (color) {\n  .foo, .bar {\n    a: b;\n  }\n}\n"
+);
+test!(
+    extend_from_toplevel_into_media,
+    "@media screen {.foo {a: b}}
+    .bar {@extend .foo}
+    ",
+    "@media screen {\n  .foo, .bar {\n    a: b;\n  }\n}\n"
+);
+test!(
+    extend_inside_at_root_without_media,
+    ".foo {a: b}
+    @media screen {
+        @at-root (without: media) {
+          .bar {@extend .foo}
+        }
+    }
+    ",
+    ".foo, .bar {\n  a: b;\n}\n"
+);
+test!(
+    extend_target_defined_after_extend,
+    ".foo {@extend .bar}
+    .bar {a: b}
+    ",
+    ".bar, .foo {\n  a: b;\n}\n"
+);
+test!(
+    extend_target_only_created_by_another_extend,
+    ".foo {a: b}
+    .bar {@extend .foo}
+    .baz {@extend .bar}
+    ",
+    ".foo, .bar, .baz {\n  a: b;\n}\n"
+);
 
 error!(
     extend_optional_keyword_not_complete,
@@ -1936,6 +1977,62 @@ error!(
     }",
     "Error: Parent selectors aren't allowed here."
 );
+error!(
+    extend_from_media_into_toplevel,
+    ".foo {a: b}
+    @media screen {
+        .bar {@extend .foo}
+    }",
+    "Error: You may not @extend selectors across media queries."
+);
+error!(
+    extend_across_different_media_queries,
+    "@media screen {.foo {a: b}}
+    @media print {.bar {@extend .foo}}",
+    "Error: You may not @extend selectors across media queries."
+);
+error!(
+    extend_from_nested_media_into_outer_media,
+    "@media screen {
+        .foo {a: b}
+        @media (color) {
+          .bar {@extend .foo}
+        }
+    }",
+    "Error: You may not @extend selectors across media queries."
+);
+error!(
+    optional_extend_across_media_queries,
+    "@media screen {.bar {@extend .foo !optional}}
+    .foo {a: b}",
+    "Error: You may not @extend selectors across media queries."
+);
+
+error!(
+    extend_target_not_found,
+    ".foo {@extend .bar}", "Error: The target selector was not found."
+);
+error!(
+    extend_placeholder_not_found,
+    ".foo {@extend %bar}", "Error: The target selector was not found."
+);
+error!(
+    extend_not_found_when_also_extended_optionally,
+    ".foo {@extend .bar !optional}
+    .baz {@extend .bar}",
+    "Error: The target selector was not found."
+);
+
+#[test]
+fn extend_target_not_found_suggests_optional() {
+    let err = grass::from_string(
+        ".foo {@extend %bar}".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("Use \"@extend %bar !optional\" to avoid this error."));
+}
 
 // todo: extend_loop (massive test)
 // todo: extend tests in folders
@@ -0,0 +1,577 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+error!(
+    after_style,
+    "a {}
+    @forward \"foo\";
+    ",
+    "Error: @forward rules must be written before any other rules."
+);
+error!(
+    forward_not_quoted_string,
+    "@forward a", "Error: Expected string."
+);
+
+#[test]
+fn basic_forward() {
+    let input = r#"
+        @use "basic_forward__a" as a;
+
+        b {
+            color: a.$a;
+        }
+    "#;
+    tempfile!("basic_forward__a.scss", r#"@forward "basic_forward__b";"#);
+    tempfile!("basic_forward__b.scss", "$a: red;");
+
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_emits_css() {
+    let input = r#"@use "forward_emits_css__a";"#;
+    tempfile!(
+        "forward_emits_css__a.scss",
+        r#"@forward "forward_emits_css__b";"#
+    );
+    tempfile!("forward_emits_css__b.scss", "a { color: red; }");
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_members_not_visible_in_forwarding_module() {
+    let input = r#"@use "forward_members_not_visible_in_forwarding_module__a";"#;
+    tempfile!(
+        "forward_members_not_visible_in_forwarding_module__a.scss",
+        r#"@forward "forward_members_not_visible_in_forwarding_module__b";
+        a { color: $a; }"#
+    );
+    tempfile!(
+        "forward_members_not_visible_in_forwarding_module__b.scss",
+        "$a: red;"
+    );
+
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn forward_with_prefix() {
+    let input = r#"
+        @use "forward_with_prefix__a" as a;
+
+        b {
+            color: a.$foo-a;
+            width: a.foo-b();
+        }
+    "#;
+    tempfile!(
+        "forward_with_prefix__a.scss",
+        r#"@forward "forward_with_prefix__b" as foo-*;"#
+    );
+    tempfile!(
+        "forward_with_prefix__b.scss",
+        "$a: red; @function b() { @return 5px; }"
+    );
+
+    assert_eq!(
+        "b {\n  color: red;\n  width: 5px;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_with_prefix_original_name_inaccessible() {
+    let input = r#"
+        @use "forward_with_prefix_original_name_inaccessible__a" as a;
+
+        b {
+            color: a.$a;
+        }
+    "#;
+    tempfile!(
+        "forward_with_prefix_original_name_inaccessible__a.scss",
+        r#"@forward "forward_with_prefix_original_name_inaccessible__b" as foo-*;"#
+    );
+    tempfile!(
+        "forward_with_prefix_original_name_inaccessible__b.scss",
+        "$a: red;"
+    );
+
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn forward_show() {
+    let input = r#"
+        @use "forward_show__a" as a;
+
+        b {
+            color: a.$a;
+            width: a.b();
+        }
+    "#;
+    tempfile!(
+        "forward_show__a.scss",
+        r#"@forward "forward_show__b" show b, $a;"#
+    );
+    tempfile!(
+        "forward_show__b.scss",
+        "$a: red; $c: blue; @function b() { @return 5px; }"
+    );
+
+    assert_eq!(
+        "b {\n  color: red;\n  width: 5px;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_show_hides_unlisted() {
+    let input = r#"
+        @use "forward_show_hides_unlisted__a" as a;
+
+        b {
+            color: a.$c;
+        }
+    "#;
+    tempfile!(
+        "forward_show_hides_unlisted__a.scss",
+        r#"@forward "forward_show_hides_unlisted__b" show $a;"#
+    );
+    tempfile!("forward_show_hides_unlisted__b.scss", "$a: red; $c: blue;");
+
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn forward_hide() {
+    let input = r#"
+        @use "forward_hide__a" as a;
+
+        b {
+            color: a.$c;
+        }
+    "#;
+    tempfile!(
+        "forward_hide__a.scss",
+        r#"@forward "forward_hide__b" hide $c;"#
+    );
+    tempfile!("forward_hide__b.scss", "$a: red; $c: blue;");
+
+    assert_err!("Error: Undefined variable.", input);
+}
+
+#[test]
+fn forward_hide_variable_does_not_hide_function() {
+    let input = r#"
+        @use "forward_hide_variable_does_not_hide_function__a" as a;
+
+        b {
+            color: a.a();
+        }
+    "#;
+    tempfile!(
+        "forward_hide_variable_does_not_hide_function__a.scss",
+        r#"@forward "forward_hide_variable_does_not_hide_function__b" hide $a;"#
+    );
+    tempfile!(
+        "forward_hide_variable_does_not_hide_function__b.scss",
+        "$a: red; @function a() { @return blue; }"
+    );
+
+    assert_eq!(
+        "b {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_prefix_and_show() {
+    let input = r#"
+        @use "forward_prefix_and_show__a" as a;
+
+        b {
+            color: a.$foo-a;
+        }
+    "#;
+    tempfile!(
+        "forward_prefix_and_show__a.scss",
+        r#"@forward "forward_prefix_and_show__b" as foo-* show $foo-a;"#
+    );
+    tempfile!("forward_prefix_and_show__b.scss", "$a: red;");
+
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_private_members_not_forwarded() {
+    let input = r#"
+        @use "forward_private_members_not_forwarded__a" as a;
+
+        b {
+            color: meta.inspect(meta.module-variables(a));
+        }
+    "#;
+    tempfile!(
+        "forward_private_members_not_forwarded__a.scss",
+        r#"@forward "forward_private_members_not_forwarded__b";"#
+    );
+    tempfile!(
+        "forward_private_members_not_forwarded__b.scss",
+        "$-a: red; $b: blue;"
+    );
+
+    assert_eq!(
+        "b {\n  color: (\"b\": blue);\n}\n",
+        &grass::from_string(
+            format!("@use \"sass:meta\";\n{}", input),
+            &grass::Options::default()
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn forward_mixin_uses_scope_of_declaring_module() {
+    let input = r#"
+        @use "forward_mixin_uses_scope_of_declaring_module__a" as a;
+
+        b {
+            @include a.foo-bar;
+        }
+    "#;
+    tempfile!(
+        "forward_mixin_uses_scope_of_declaring_module__a.scss",
+        r#"@forward "forward_mixin_uses_scope_of_declaring_module__b" as foo-*;"#
+    );
+    tempfile!(
+        "forward_mixin_uses_scope_of_declaring_module__b.scss",
+        "$-a: red; @mixin bar { color: $-a; }"
+    );
+
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_function_uses_scope_of_declaring_module() {
+    let input = r#"
+        @use "forward_function_uses_scope_of_declaring_module__a" as a;
+
+        b {
+            color: a.bar();
+        }
+    "#;
+    tempfile!(
+        "forward_function_uses_scope_of_declaring_module__a.scss",
+        r#"@forward "forward_function_uses_scope_of_declaring_module__b";"#
+    );
+    tempfile!(
+        "forward_function_uses_scope_of_declaring_module__b.scss",
+        "$-a: red; @function bar() { @return $-a; }"
+    );
+
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_through_multiple_modules() {
+    let input = r#"
+        @use "forward_through_multiple_modules__a" as a;
+
+        b {
+            color: a.$x-y-c;
+        }
+    "#;
+    tempfile!(
+        "forward_through_multiple_modules__a.scss",
+        r#"@forward "forward_through_multiple_modules__b" as x-*;"#
+    );
+    tempfile!(
+        "forward_through_multiple_modules__b.scss",
+        r#"@forward "forward_through_multiple_modules__c" as y-*;"#
+    );
+    tempfile!("forward_through_multiple_modules__c.scss", "$c: red;");
+
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_use_as_star() {
+    let input = r#"
+        @use "forward_use_as_star__a" as *;
+
+        b {
+            color: $foo-a;
+        }
+    "#;
+    tempfile!(
+        "forward_use_as_star__a.scss",
+        r#"@forward "forward_use_as_star__b" as foo-*;"#
+    );
+    tempfile!("forward_use_as_star__b.scss", "$a: red;");
+
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_and_use_same_module() {
+    let input = r#"
+        @use "forward_and_use_same_module__a" as a;
+
+        b {
+            color: a.$b;
+        }
+    "#;
+    tempfile!(
+        "forward_and_use_same_module__a.scss",
+        r#"@forward "forward_and_use_same_module__c";
+        @use "forward_and_use_same_module__c" as c;
+        $b: c.$c;"#
+    );
+    tempfile!("forward_and_use_same_module__c.scss", "$c: red;");
+
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_builtin_module() {
+    let input = r#"
+        @use "forward_builtin_module__a" as a;
+
+        b {
+            color: a.div(4, 2);
+        }
+    "#;
+    tempfile!("forward_builtin_module__a.scss", r#"@forward "sass:math";"#);
+
+    assert_eq!(
+        "b {\n  color: 2;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_redeclare_variable() {
+    let input = r#"
+        @use "forward_redeclare_variable__a" as a;
+
+        a.$a: blue;
+
+        b {
+            color: a.$a;
+        }
+    "#;
+    tempfile!(
+        "forward_redeclare_variable__a.scss",
+        r#"@forward "forward_redeclare_variable__b";"#
+    );
+    tempfile!("forward_redeclare_variable__b.scss", "$a: red;");
+
+    assert_eq!(
+        "b {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_with_config() {
+    let input = r#"
+        @use "forward_with_config__a" as a;
+
+        b {
+            color: a.$a;
+        }
+    "#;
+    tempfile!(
+        "forward_with_config__a.scss",
+        r#"@forward "forward_with_config__b" with ($a: blue);"#
+    );
+    tempfile!("forward_with_config__b.scss", "$a: red !default;");
+
+    assert_eq!(
+        "b {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_config_passes_through() {
+    let input = r#"
+        @use "forward_config_passes_through__a" as a with ($a: green);
+
+        b {
+            color: a.$a;
+        }
+    "#;
+    tempfile!(
+        "forward_config_passes_through__a.scss",
+        r#"@forward "forward_config_passes_through__b";"#
+    );
+    tempfile!("forward_config_passes_through__b.scss", "$a: red !default;");
+
+    assert_eq!(
+        "b {\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_config_passes_through_prefix() {
+    let input = r#"
+        @use "forward_config_passes_through_prefix__a" as a with ($foo-a: green);
+
+        b {
+            color: a.$foo-a;
+        }
+    "#;
+    tempfile!(
+        "forward_config_passes_through_prefix__a.scss",
+        r#"@forward "forward_config_passes_through_prefix__b" as foo-*;"#
+    );
+    tempfile!(
+        "forward_config_passes_through_prefix__b.scss",
+        "$a: red !default;"
+    );
+
+    assert_eq!(
+        "b {\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_with_default_config_overridden_upstream() {
+    let input = r#"
+        @use "forward_with_default_config_overridden_upstream__a" as a with ($a: green);
+
+        b {
+            color: a.$a;
+        }
+    "#;
+    tempfile!(
+        "forward_with_default_config_overridden_upstream__a.scss",
+        r#"@forward "forward_with_default_config_overridden_upstream__b" with ($a: blue !default);"#
+    );
+    tempfile!(
+        "forward_with_default_config_overridden_upstream__b.scss",
+        "$a: red !default;"
+    );
+
+    assert_eq!(
+        "b {\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_with_default_config_not_overridden() {
+    let input = r#"
+        @use "forward_with_default_config_not_overridden__a" as a;
+
+        b {
+            color: a.$a;
+        }
+    "#;
+    tempfile!(
+        "forward_with_default_config_not_overridden__a.scss",
+        r#"@forward "forward_with_default_config_not_overridden__b" with ($a: blue !default);"#
+    );
+    tempfile!(
+        "forward_with_default_config_not_overridden__b.scss",
+        "$a: red !default;"
+    );
+
+    assert_eq!(
+        "b {\n  color: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_config_used_by_forwarding_module() {
+    let input = r#"
+        @use "forward_config_used_by_forwarding_module__a" as a with ($b: green);
+
+        b {
+            color: a.$b;
+        }
+    "#;
+    tempfile!(
+        "forward_config_used_by_forwarding_module__a.scss",
+        r#"@forward "forward_config_used_by_forwarding_module__b";
+        $b: red !default;"#
+    );
+    tempfile!(
+        "forward_config_used_by_forwarding_module__b.scss",
+        "$a: red;"
+    );
+
+    assert_eq!(
+        "b {\n  color: green;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn forward_with_config_variable_not_default() {
+    let input = r#"@use "forward_with_config_variable_not_default__a";"#;
+    tempfile!(
+        "forward_with_config_variable_not_default__a.scss",
+        r#"@forward "forward_with_config_variable_not_default__b" with ($a: blue);"#
+    );
+    tempfile!(
+        "forward_with_config_variable_not_default__b.scss",
+        "$a: red;"
+    );
+
+    assert_err!(
+        "Error: This variable was not declared with !default in the @used module.",
+        input
+    );
+}
+
+#[test]
+fn forward_config_hidden_variable_not_passed_through() {
+    let input = r#"@use "forward_config_hidden_variable_not_passed_through__a" with ($a: blue);"#;
+    tempfile!(
+        "forward_config_hidden_variable_not_passed_through__a.scss",
+        r#"@forward "forward_config_hidden_variable_not_passed_through__b" hide $a;"#
+    );
+    tempfile!(
+        "forward_config_hidden_variable_not_passed_through__b.scss",
+        "$a: red !default;"
+    );
+
+    assert_err!(
+        "Error: This variable was not declared with !default in the @used module.",
+        input
+    );
+}
@@ -0,0 +1,265 @@
+use std::{cell::RefCell, io::Write};
+
+use grass::{Importer, ImporterResult, Syntax};
+
+#[macro_use]
+mod macros;
+
+/// Serves stylesheets from memory, recording every request it receives
+#[derive(Debug, Default)]
+struct MemoryImporter {
+    files: Vec<(&'static str, &'static str, Syntax)>,
+    requests: RefCell<Vec<(String, Option<String>)>>,
+}
+
+impl MemoryImporter {
+    fn new(files: Vec<(&'static str, &'static str, Syntax)>) -> Self {
+        Self {
+            files,
+            requests: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Importer for MemoryImporter {
+    fn import(
+        &self,
+        url: &str,
+        containing_url: Option<&str>,
+    ) -> std::io::Result<Option<ImporterResult>> {
+        self.requests
+            .borrow_mut()
+            .push((url.to_owned(), containing_url.map(ToOwned::to_owned)));
+
+        // resolve urls relative to the `mem:` stylesheet containing them
+        let canonical = match containing_url {
+            Some(containing) if containing.starts_with("mem:") && !url.contains(':') => {
+                format!("mem:{}", url)
+            }
+            _ => url.to_owned(),
+        };
+
+        Ok(self.files.iter().find(|(name, ..)| *name == canonical).map(
+            |(name, contents, syntax)| {
+                ImporterResult::new((*name).to_owned(), (*contents).to_owned(), *syntax)
+            },
+        ))
+    }
+}
+
+#[derive(Debug)]
+struct FailingImporter;
+
+impl Importer for FailingImporter {
+    fn import(&self, _: &str, _: Option<&str>) -> std::io::Result<Option<ImporterResult>> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "importer failed",
+        ))
+    }
+}
+
+#[test]
+fn use_from_importer() {
+    let importer = MemoryImporter::new(vec![("mem:theme", "$a: red;", Syntax::Scss)]);
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            "@use \"mem:theme\";\na {\n  color: theme.$a;\n}\n".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn import_from_importer() {
+    let importer = MemoryImporter::new(vec![("mem:theme", "a {\n  color: red;\n}", Syntax::Scss)]);
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            "@import \"mem:theme\";".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn load_css_from_importer() {
+    let importer = MemoryImporter::new(vec![("mem:theme", "a {\n  color: red;\n}", Syntax::Scss)]);
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            "@use \"sass:meta\";\n@include meta.load-css(\"mem:theme\");".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn indented_syntax_from_importer() {
+    let importer = MemoryImporter::new(vec![("mem:theme", "a\n  color: red\n", Syntax::Indented)]);
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            "@import \"mem:theme\";".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn containing_url_is_canonical_url() {
+    let importer = MemoryImporter::new(vec![
+        (
+            "mem:theme",
+            "@use \"colors\";\n$a: colors.$b;",
+            Syntax::Scss,
+        ),
+        ("mem:colors", "$b: red;", Syntax::Scss),
+    ]);
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            "@use \"mem:theme\";\na {\n  color: theme.$a;\n}\n".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        vec![
+            ("mem:theme".to_owned(), None),
+            ("colors".to_owned(), Some("mem:theme".to_owned()))
+        ],
+        *importer.requests.borrow()
+    );
+}
+
+#[test]
+fn importers_are_chained_in_order() {
+    let first = MemoryImporter::new(vec![("mem:a", "$a: red;", Syntax::Scss)]);
+    let second = MemoryImporter::new(vec![
+        ("mem:a", "$a: blue;", Syntax::Scss),
+        ("mem:b", "$b: green;", Syntax::Scss),
+    ]);
+    assert_eq!(
+        "a {\n  color: red;\n  color: green;\n}\n",
+        grass::from_string(
+            "@use \"mem:a\";\n@use \"mem:b\";\na {\n  color: a.$a;\n  color: b.$b;\n}\n"
+                .to_string(),
+            &grass::Options::default().importer(&first).importer(&second)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn relative_file_takes_precedence_over_importer() {
+    tempfile!(
+        "relative_file_takes_precedence_over_importer.scss",
+        "$a: red;"
+    );
+    let importer = MemoryImporter::new(vec![(
+        "relative_file_takes_precedence_over_importer",
+        "$a: blue;",
+        Syntax::Scss,
+    )]);
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            "@use \"relative_file_takes_precedence_over_importer\" as b;\na {\n  color: b.$a;\n}\n"
+                .to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+    assert!(importer.requests.borrow().is_empty());
+}
+
+#[test]
+fn importer_error_is_propagated() {
+    match grass::from_string(
+        "@use \"mem:theme\";".to_string(),
+        &grass::Options::default().importer(&FailingImporter),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e.to_string().contains("importer failed")),
+    }
+}
+
+#[test]
+fn unknown_url_is_error() {
+    let importer = MemoryImporter::new(Vec::new());
+    match grass::from_string(
+        "@import \"mem:theme\";".to_string(),
+        &grass::Options::default().importer(&importer),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => assert!(e
+            .to_string()
+            .starts_with("Error: Can't find stylesheet to import.")),
+    }
+}
+
+#[test]
+fn importer_stylesheet_resolves_relative_urls_through_same_importer() {
+    tempfile!(
+        "importer_stylesheet_resolves_relative_urls_through_same_importer.scss",
+        "$b: red;"
+    );
+    let importer = MemoryImporter::new(vec![
+        (
+            "mem:theme",
+            "@use \"importer_stylesheet_resolves_relative_urls_through_same_importer\" as colors;\n$a: colors.$b;",
+            Syntax::Scss,
+        ),
+        (
+            "mem:importer_stylesheet_resolves_relative_urls_through_same_importer",
+            "$b: blue;",
+            Syntax::Scss,
+        ),
+    ]);
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        grass::from_string(
+            "@use \"mem:theme\";\na {\n  color: theme.$a;\n}\n".to_string(),
+            &grass::Options::default().importer(&importer)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn importer_stylesheet_tries_own_importer_first() {
+    let first = MemoryImporter::new(vec![("colors", "$b: red;", Syntax::Scss)]);
+    let second = MemoryImporter::new(vec![
+        (
+            "mem:theme",
+            "@use \"colors\";\n$a: colors.$b;",
+            Syntax::Scss,
+        ),
+        ("mem:colors", "$b: blue;", Syntax::Scss),
+    ]);
+    assert_eq!(
+        "a {\n  color: blue;\n}\n",
+        grass::from_string(
+            "@use \"mem:theme\";\na {\n  color: theme.$a;\n}\n".to_string(),
+            &grass::Options::default().importer(&first).importer(&second)
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        vec![
+            ("mem:theme".to_owned(), None),
+            ("colors".to_owned(), Some("mem:theme".to_owned()))
+        ],
+        *second.requests.borrow()
+    );
+    assert_eq!(
+        vec![("mem:theme".to_owned(), None)],
+        *first.requests.borrow()
+    );
+}
@@ -0,0 +1,229 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+test!(
+    basic_style,
+    "a\n  color: red\n",
+    "a {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    nested_rules,
+    "a\n  color: red\n  b\n    color: blue\n",
+    "a {\n  color: red;\n}\na b {\n  color: blue;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    tab_indentation,
+    "a\n\tcolor: red\n\tb\n\t\tcolor: blue\n",
+    "a {\n  color: red;\n}\na b {\n  color: blue;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    dedent_multiple_levels,
+    "a\n  b\n    c\n      color: red\nd\n  color: blue\n",
+    "a b c {\n  color: red;\n}\n\nd {\n  color: blue;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    variables,
+    "$a: red\na\n  color: $a\n",
+    "a {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    mixin_shorthand,
+    "=foo($a)\n  color: $a\na\n  +foo(red)\n",
+    "a {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    mixin_shorthand_with_content,
+    "=foo\n  b\n    @content\na\n  +foo\n    color: red\n",
+    "a b {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    full_at_rule_mixin,
+    "@mixin foo\n  color: red\na\n  @include foo\n",
+    "a {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    old_property_syntax,
+    "a\n  :color red\n",
+    "a {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    pseudo_class_selector_is_not_property,
+    "a\n  :hover\n    color: red\n",
+    "a :hover {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    nested_properties,
+    "a\n  font:\n    family: b\n    size: 12px\n",
+    "a {\n  font-family: b;\n  font-size: 12px;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    silent_comment_is_removed,
+    "// a\n  b\na\n  color: red // c\n",
+    "a {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    loud_comment_is_closed_implicitly,
+    "/* a\n   b\na\n  color: red\n",
+    "/* a\n   b */\na {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    url_is_not_silent_comment,
+    "a\n  color: url(http://a.com/b.png)\n",
+    "a {\n  color: url(http://a.com/b.png);\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    selector_continues_after_comma,
+    "a,\nb\n  color: red\n",
+    "a,\nb {\n  color: red;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    value_continues_inside_parens,
+    "a\n  color: foo(1,\n    2)\n",
+    "a {\n  color: foo(1, 2);\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    if_else,
+    "$a: 1\na\n  @if $a == 2\n    color: red\n  @else if $a == 1\n    color: green\n  @else\n    color: blue\n",
+    "a {\n  color: green;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    function_and_each,
+    "@function double($a)\n  @return $a * 2\n@each $i in 1, 2\n  .a-#{$i}\n    width: double($i)\n",
+    ".a-1 {\n  width: 2;\n}\n\n.a-2 {\n  width: 4;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    media_query,
+    "a\n  @media screen\n    color: red\n",
+    "@media screen {\n  a {\n    color: red;\n  }\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+test!(
+    blank_lines_are_ignored,
+    "a\n\n  color: red\n\n\n  b\n\n    color: blue\n",
+    "a {\n  color: red;\n}\na b {\n  color: blue;\n}\n",
+    grass::Options::default().indented_syntax(true)
+);
+
+fn indented_err(input: &str) -> String {
+    match grass::from_string(
+        input.to_string(),
+        &grass::Options::default().indented_syntax(true),
+    ) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e
+            .to_string()
+            .chars()
+            .take_while(|c| *c != '\n')
+            .collect::<String>(),
+    }
+}
+
+#[test]
+fn indented_first_line_is_error() {
+    assert_eq!(
+        "Error: Indenting at the beginning of the document is illegal.",
+        indented_err("  a\n    color: red\n")
+    );
+}
+
+#[test]
+fn inconsistent_indentation_is_error() {
+    assert_eq!(
+        "Error: Inconsistent indentation.",
+        indented_err("a\n    color: red\n  color: blue\n")
+    );
+}
+
+#[test]
+fn mixed_tabs_and_spaces_is_error() {
+    assert_eq!(
+        "Error: Tabs and spaces may not be mixed.",
+        indented_err("a\n \tcolor: red\n")
+    );
+}
+
+#[test]
+fn tabs_after_spaces_is_error() {
+    assert_eq!(
+        "Error: Expected spaces, was tabs.",
+        indented_err("a\n  color: red\nb\n\tcolor: blue\n")
+    );
+}
+
+#[test]
+fn spaces_after_tabs_is_error() {
+    assert_eq!(
+        "Error: Expected tabs, was spaces.",
+        indented_err("a\n\tcolor: red\nb\n  color: blue\n")
+    );
+}
+
+#[test]
+fn sass_extension_is_indented() {
+    tempfile!("sass_extension_is_indented.sass", "a\n  color: red\n");
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_path(
+            "sass_extension_is_indented.sass",
+            &grass::Options::default()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn import_sass_partial_from_scss() {
+    let input = "@import \"import_sass_partial_from_scss\";\nb {\n  color: $a;\n}\n";
+    tempfile!(
+        "_import_sass_partial_from_scss.sass",
+        "$a: red\na\n  color: $a\n"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n}\n\nb {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+#[test]
+fn unquoted_import_in_indented() {
+    let input = "@import unquoted_import_in_indented__a\nb\n  color: $a\n";
+    tempfile!("unquoted_import_in_indented__a.sass", "$a: red\n");
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(
+            input.to_string(),
+            &grass::Options::default().indented_syntax(true)
+        )
+        .expect(input)
+    );
+}
+
+#[test]
+fn use_sass_module() {
+    let input = "@use \"use_sass_module__a\" as a;\nb {\n  color: a.$a;\n}\n";
+    tempfile!("use_sass_module__a.sass", "$a: red\n");
+    assert_eq!(
+        "b {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
@@ -0,0 +1,149 @@
+#[macro_use]
+mod macros;
+
+test!(
+    positive_div_zero_is_infinity,
+    "a {\n  color: (1/0);\n}\n",
+    "a {\n  color: calc(infinity);\n}\n"
+);
+test!(
+    negative_div_zero_is_negative_infinity,
+    "a {\n  color: (-1/0);\n}\n",
+    "a {\n  color: calc(-infinity);\n}\n"
+);
+test!(
+    unit_div_zero_keeps_unit,
+    "a {\n  color: (1px/0);\n}\n",
+    "a {\n  color: calc(infinity * 1px);\n}\n"
+);
+test!(
+    math_div_by_zero,
+    "@use \"sass:math\";\na {\n  color: math.div(-1px, 0);\n}\n",
+    "a {\n  color: calc(-infinity * 1px);\n}\n"
+);
+test!(
+    infinity_compressed,
+    "a {\n  color: (1px/0);\n  color: (0/0);\n}\n",
+    "a{color:calc(infinity*1px);color:calc(NaN)}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    type_of_infinity,
+    "a {\n  color: type-of((1/0));\n}\n",
+    "a {\n  color: number;\n}\n"
+);
+test!(
+    inspect_infinity,
+    "a {\n  color: inspect((-1px/0));\n}\n",
+    "a {\n  color: calc(-infinity * 1px);\n}\n"
+);
+test!(
+    infinity_minus_infinity_is_nan,
+    "a {\n  color: (1/0) - (1/0);\n}\n",
+    "a {\n  color: calc(NaN);\n}\n"
+);
+test!(
+    infinity_times_zero_is_nan,
+    "a {\n  color: (1/0) * 0;\n}\n",
+    "a {\n  color: calc(NaN);\n}\n"
+);
+test!(
+    number_div_infinity_is_zero,
+    "a {\n  color: 1 / (1/0);\n}\n",
+    "a {\n  color: 0;\n}\n"
+);
+test!(
+    number_mod_infinity,
+    "a {\n  color: 1 % (1/0);\n  color: -1 % (1/0);\n}\n",
+    "a {\n  color: 1;\n  color: calc(infinity);\n}\n"
+);
+test!(
+    infinity_plus_string,
+    "a {\n  color: (1/0) + \"a\";\n}\n",
+    "a {\n  color: \"calc(infinity)a\";\n}\n"
+);
+test!(
+    infinity_comparisons,
+    "a {\n  color: (1/0) > 100000;\n  color: (-1/0) < -100000;\n  color: (1/0) == (2/0);\n}\n",
+    "a {\n  color: true;\n  color: true;\n  color: true;\n}\n"
+);
+test!(
+    nan_comparisons,
+    "a {\n  color: (0/0) == (0/0);\n  color: (0/0) < 1;\n  color: (0/0) >= 1;\n}\n",
+    "a {\n  color: false;\n  color: false;\n  color: false;\n}\n"
+);
+test!(
+    round_infinity,
+    "a {\n  color: round((1/0));\n}\n",
+    "a {\n  color: calc(infinity);\n}\n"
+);
+test!(
+    calc_infinity_constant,
+    "a {\n  color: calc(infinity);\n  color: calc(-infinity);\n  color: calc(NaN);\n}\n",
+    "a {\n  color: calc(infinity);\n  color: calc(-infinity);\n  color: calc(NaN);\n}\n"
+);
+test!(
+    calc_infinity_constant_case_insensitive,
+    "a {\n  color: calc(InFiNiTy);\n}\n",
+    "a {\n  color: calc(infinity);\n}\n"
+);
+test!(
+    calc_infinity_times_unit,
+    "a {\n  color: calc(infinity * 1px);\n}\n",
+    "a {\n  color: calc(infinity * 1px);\n}\n"
+);
+test!(
+    calc_infinity_in_unsimplified_calculation,
+    "a {\n  color: calc(1% + infinity * 1px);\n}\n",
+    "a {\n  color: calc(1% + infinity * 1px);\n}\n"
+);
+test!(
+    max_with_infinity,
+    "a {\n  color: max(1px, calc(infinity * 1px));\n}\n",
+    "a {\n  color: calc(infinity * 1px);\n}\n"
+);
+error!(
+    rgb_nan_channel,
+    "a {\n  color: rgb((0/0), 0, 0);\n}\n", "Error: $red: NaN is not a finite number."
+);
+error!(
+    rgba_infinite_alpha,
+    "a {\n  color: rgba(red, (1/0));\n}\n", "Error: $alpha: Infinity is not a finite number."
+);
+error!(
+    hsl_infinite_hue,
+    "a {\n  color: hsl((1/0), 50%, 50%);\n}\n", "Error: $hue: Infinity is not a finite number."
+);
+error!(
+    mix_nan_weight,
+    "a {\n  color: mix(red, blue, (0/0));\n}\n", "Error: $weight: NaN is not a finite number."
+);
+error!(
+    adjust_color_nan_channel,
+    "a {\n  color: adjust-color(red, $red: (0/0));\n}\n",
+    "Error: $red: NaN is not a finite number."
+);
+error!(
+    lighten_infinite_amount,
+    "a {\n  color: lighten(red, (-1/0));\n}\n", "Error: $amount: -Infinity is not a finite number."
+);
+error!(
+    nth_infinite_index,
+    "a {\n  color: nth(a b, (1/0));\n}\n",
+    "Error: $n: Invalid index Infinity for a list with 2 elements."
+);
+test!(
+    grayscale_infinite_number_is_plain_css,
+    "a {\n  color: grayscale((1px/0));\n}\n",
+    "a {\n  color: grayscale(calc(infinity * 1px));\n}\n"
+);
+test!(
+    saturate_infinite_number_is_plain_css,
+    "a {\n  color: saturate((-1px/0));\n}\n",
+    "a {\n  color: saturate(calc(-infinity * 1px));\n}\n"
+);
+test!(
+    invert_infinite_number_is_plain_css,
+    "a {\n  color: invert((1px/0));\n}\n",
+    "a {\n  color: invert(calc(infinity * 1px));\n}\n"
+);
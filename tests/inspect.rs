@@ -82,7 +82,6 @@ test!(
     "a {\n  color: (), ();\n}\n"
 );
 test!(
-    #[ignore]
     inspect_comma_separated_list_of_comma_separated_lists,
     "a {\n  color: inspect([(1, 2), (3, 4)]);\n}\n",
     "a {\n  color: [(1, 2), (3, 4)];\n}\n"
@@ -129,3 +128,48 @@ test!(
     }",
     "a {\n  color: ((a: b),);\n}\n"
 );
+test!(
+    inspect_comma_list_in_slash_list,
+    "@use \"sass:list\";\na {\n  color: inspect(list.slash((1, 2), 3));\n}\n",
+    "a {\n  color: (1, 2) / 3;\n}\n"
+);
+test!(
+    inspect_slash_list_in_slash_list,
+    "@use \"sass:list\";\na {\n  color: inspect(list.slash(list.slash(1, 2), 3));\n}\n",
+    "a {\n  color: (1 / 2) / 3;\n}\n"
+);
+test!(
+    inspect_space_list_in_slash_list,
+    "@use \"sass:list\";\na {\n  color: inspect(list.slash(1 2, 3));\n}\n",
+    "a {\n  color: 1 2 / 3;\n}\n"
+);
+test!(
+    inspect_comma_list_in_comma_list,
+    "@use \"sass:list\";\na {\n  color: inspect(((1, 2), 3));\n}\n",
+    "a {\n  color: (1, 2), 3;\n}\n"
+);
+test!(
+    inspect_space_list_in_comma_list,
+    "@use \"sass:list\";\na {\n  color: inspect((1 2, 3));\n}\n",
+    "a {\n  color: 1 2, 3;\n}\n"
+);
+test!(
+    inspect_space_list_in_space_list,
+    "@use \"sass:list\";\na {\n  color: inspect(((1 2) 3));\n}\n",
+    "a {\n  color: (1 2) 3;\n}\n"
+);
+test!(
+    inspect_comma_list_in_single_element_comma_list,
+    "@use \"sass:list\";\na {\n  color: inspect(((1, 2),));\n}\n",
+    "a {\n  color: ((1, 2),);\n}\n"
+);
+test!(
+    inspect_comma_list_in_bracketed_comma_list,
+    "@use \"sass:list\";\na {\n  color: inspect([(1, 2), 3]);\n}\n",
+    "a {\n  color: [(1, 2), 3];\n}\n"
+);
+test!(
+    inspect_bracketed_list_in_comma_list,
+    "@use \"sass:list\";\na {\n  color: inspect(([1, 2], 3));\n}\n",
+    "a {\n  color: [1, 2], 3;\n}\n"
+);
@@ -412,3 +412,54 @@ error!(
     "a {\n  color: set-nth([], 1px, a);\n}\n",
     "Error: $n: Invalid index 1px for a list with 0 elements."
 );
+test!(
+    slash_list_from_module_function,
+    "@use \"sass:list\";\na {\n  color: list.slash(1, 2, 3);\n}\n",
+    "a {\n  color: 1 / 2 / 3;\n}\n"
+);
+test!(
+    slash_list_compressed,
+    "@use \"sass:list\";\na {\n  color: list.slash(a, b);\n}\n",
+    "a{color:a/b}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
+test!(
+    separator_of_slash_list,
+    "@use \"sass:list\";\na {\n  color: list.separator(list.slash(a, b));\n}\n",
+    "a {\n  color: slash;\n}\n"
+);
+test!(
+    join_with_slash_separator,
+    "a {\n  color: join(a b, c, $separator: slash);\n}\n",
+    "a {\n  color: a / b / c;\n}\n"
+);
+test!(
+    append_with_slash_separator,
+    "a {\n  color: append(a, b, $separator: slash);\n}\n",
+    "a {\n  color: a / b;\n}\n"
+);
+test!(
+    append_keeps_slash_separator_on_auto,
+    "@use \"sass:list\";\na {\n  color: append(list.slash(a, b), c);\n}\n",
+    "a {\n  color: a / b / c;\n}\n"
+);
+test!(
+    inspect_single_element_slash_list,
+    "a {\n  color: inspect(append((), 1, slash));\n}\n",
+    "a {\n  color: (1/);\n}\n"
+);
+test!(
+    inspect_single_element_bracketed_slash_list,
+    "a {\n  color: inspect(join([], 1, slash));\n}\n",
+    "a {\n  color: [1/];\n}\n"
+);
+error!(
+    slash_list_with_one_element,
+    "@use \"sass:list\";\na {\n  color: list.slash(a);\n}\n",
+    "Error: At least two elements are required."
+);
+error!(
+    join_invalid_separator,
+    "a {\n  color: join(a, b, $separator: foo);\n}\n",
+    "Error: $separator: Must be \"space\", \"comma\", \"slash\", or \"auto\"."
+);
@@ -0,0 +1,107 @@
+use std::{io::Write, path::PathBuf};
+
+use grass::{Importer, ImporterResult, Syntax};
+
+#[macro_use]
+mod macros;
+
+fn loaded_files(input: &str, options: &grass::Options) -> Vec<PathBuf> {
+    grass::compile_string(input.to_string(), options)
+        .expect(input)
+        .loaded_files()
+        .to_vec()
+}
+
+#[test]
+fn string_input_is_not_listed() {
+    assert!(loaded_files("a {\n  color: red;\n}\n", &grass::Options::default()).is_empty());
+}
+
+#[test]
+fn path_input_is_listed() {
+    tempfile!("path_input_is_listed.scss", "a {\n  color: red;\n}\n");
+    let result =
+        grass::compile_path("path_input_is_listed.scss", &grass::Options::default()).unwrap();
+    assert_eq!(
+        &[PathBuf::from("path_input_is_listed.scss")],
+        result.loaded_files()
+    );
+}
+
+#[test]
+fn use_import_and_load_css_are_listed_in_order() {
+    tempfile!("loaded_files_use.scss", "$a: red;");
+    tempfile!("_loaded_files_import.scss", "b {\n  color: blue;\n}\n");
+    tempfile!("loaded_files_load_css.scss", "c {\n  color: green;\n}\n");
+    assert_eq!(
+        vec![
+            PathBuf::from("loaded_files_use.scss"),
+            PathBuf::from("_loaded_files_import.scss"),
+            PathBuf::from("loaded_files_load_css.scss"),
+        ],
+        loaded_files(
+            "@use \"sass:meta\";\n@use \"loaded_files_use\";\n@import \"loaded_files_import\";\n@include meta.load-css(\"loaded_files_load_css\");",
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn transitive_dependencies_are_listed() {
+    tempfile!(
+        "transitive_dependencies_a.scss",
+        "@forward \"transitive_dependencies_b\";"
+    );
+    tempfile!("transitive_dependencies_b.scss", "$b: red;");
+    assert_eq!(
+        vec![
+            PathBuf::from("transitive_dependencies_a.scss"),
+            PathBuf::from("transitive_dependencies_b.scss"),
+        ],
+        loaded_files(
+            "@use \"transitive_dependencies_a\";",
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn file_imported_twice_is_listed_once() {
+    tempfile!("file_imported_twice.scss", "a {\n  color: red;\n}\n");
+    assert_eq!(
+        vec![PathBuf::from("file_imported_twice.scss")],
+        loaded_files(
+            "@import \"file_imported_twice\";\n@import \"file_imported_twice\";",
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn plain_css_import_is_not_listed() {
+    assert!(loaded_files("@import \"foo.css\";", &grass::Options::default()).is_empty());
+}
+
+#[derive(Debug)]
+struct ThemeImporter;
+
+impl Importer for ThemeImporter {
+    fn import(&self, url: &str, _: Option<&str>) -> std::io::Result<Option<ImporterResult>> {
+        Ok(Some(ImporterResult::new(
+            format!("theme:{}", url),
+            "$a: red;".to_owned(),
+            Syntax::Scss,
+        )))
+    }
+}
+
+#[test]
+fn importer_canonical_url_is_listed() {
+    assert_eq!(
+        vec![PathBuf::from("theme:colors")],
+        loaded_files(
+            "@use \"colors\";",
+            &grass::Options::default().importer(&ThemeImporter)
+        )
+    );
+}
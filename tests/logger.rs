@@ -0,0 +1,189 @@
+use std::{cell::RefCell, io::Write};
+
+use grass::{Logger, SourceLocation};
+
+#[macro_use]
+mod macros;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Message {
+    Warn(String, String, usize, usize, bool),
+    Debug(String, String, usize, usize),
+}
+
+/// Records every message it receives
+#[derive(Debug, Default)]
+struct RecordingLogger {
+    messages: RefCell<Vec<Message>>,
+    /// The member and line of each frame of the stack trace of every warning
+    stack_traces: RefCell<Vec<Vec<(String, usize)>>>,
+}
+
+impl Logger for RecordingLogger {
+    fn warn(&self, message: &str, location: &SourceLocation, deprecation: bool) {
+        self.stack_traces.borrow_mut().push(
+            location
+                .stack_trace()
+                .iter()
+                .map(|frame| (frame.member().to_owned(), frame.line()))
+                .collect(),
+        );
+        self.messages.borrow_mut().push(Message::Warn(
+            message.to_owned(),
+            location.file().to_owned(),
+            location.line(),
+            location.column(),
+            deprecation,
+        ));
+    }
+
+    fn debug(&self, message: &str, location: &SourceLocation) {
+        self.messages.borrow_mut().push(Message::Debug(
+            message.to_owned(),
+            location.file().to_owned(),
+            location.line(),
+            location.column(),
+        ));
+    }
+}
+
+#[test]
+fn warn_is_logged() {
+    let logger = RecordingLogger::default();
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        grass::from_string(
+            "a {\n  @warn \"foo\" + bar;\n  color: red;\n}\n".to_string(),
+            &grass::Options::default().logger(&logger)
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        vec![Message::Warn(
+            "\"foobar\"".to_owned(),
+            "stdin".to_owned(),
+            2,
+            9,
+            false
+        )],
+        *logger.messages.borrow()
+    );
+}
+
+#[test]
+fn debug_is_logged() {
+    let logger = RecordingLogger::default();
+    grass::from_string(
+        "@debug (a: b);\n@debug \"foo\";".to_string(),
+        &grass::Options::default().logger(&logger),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![
+            Message::Debug("(a: b)".to_owned(), "stdin".to_owned(), 1, 8),
+            Message::Debug("\"foo\"".to_owned(), "stdin".to_owned(), 2, 8),
+        ],
+        *logger.messages.borrow()
+    );
+}
+
+#[test]
+fn warn_inside_function() {
+    let logger = RecordingLogger::default();
+    grass::from_string(
+        "@function foo() {\n  @warn bar;\n  @return 1;\n}\na {\n  b: foo();\n}\n".to_string(),
+        &grass::Options::default().logger(&logger),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![Message::Warn(
+            "bar".to_owned(),
+            "stdin".to_owned(),
+            2,
+            9,
+            false
+        )],
+        *logger.messages.borrow()
+    );
+}
+
+#[test]
+fn location_is_in_imported_file() {
+    tempfile!("location_is_in_imported_file.scss", "\n@debug 1;");
+    let logger = RecordingLogger::default();
+    grass::from_string(
+        "@import \"location_is_in_imported_file\";".to_string(),
+        &grass::Options::default().logger(&logger),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![Message::Debug(
+            "1".to_owned(),
+            "location_is_in_imported_file.scss".to_owned(),
+            2,
+            8
+        )],
+        *logger.messages.borrow()
+    );
+}
+
+#[test]
+fn quiet_suppresses_logger() {
+    let logger = RecordingLogger::default();
+    grass::from_string(
+        "@warn foo;\n@debug bar;".to_string(),
+        &grass::Options::default().logger(&logger).quiet(true),
+    )
+    .unwrap();
+    assert!(logger.messages.borrow().is_empty());
+}
+
+#[test]
+fn null_logger_discards_messages() {
+    assert_eq!(
+        "",
+        grass::from_string(
+            "@warn foo;\n@debug bar;".to_string(),
+            &grass::Options::default().logger(&grass::NullLogger)
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn warn_has_stack_trace() {
+    let logger = RecordingLogger::default();
+    grass::from_string(
+        "@function foo() {\n  @warn bar;\n  @return 1;\n}\n@mixin baz {\n  a {\n    b: foo();\n  }\n}\n@include baz;\n".to_string(),
+        &grass::Options::default().logger(&logger),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![vec![
+            ("foo()".to_owned(), 2),
+            ("baz()".to_owned(), 7),
+            ("root stylesheet".to_owned(), 10),
+        ]],
+        *logger.stack_traces.borrow()
+    );
+}
+
+#[test]
+fn global_declaring_new_variable_is_deprecated() {
+    let logger = RecordingLogger::default();
+    grass::from_string(
+        "$a: 1;\n@mixin foo {\n  $a: 2 !global;\n  $b: 3 !global;\n}\n@include foo;\n".to_string(),
+        &grass::Options::default().logger(&logger),
+    )
+    .unwrap();
+    assert_eq!(
+        vec![Message::Warn(
+            "As of Dart Sass 2.0.0, !global assignments won't be able to declare new variables.\n\nRecommendation: add `$b: null` at the stylesheet root.".to_owned(),
+            "stdin".to_owned(),
+            4,
+            3,
+            true
+        )],
+        *logger.messages.borrow()
+    );
+}
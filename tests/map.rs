@@ -314,3 +314,81 @@ error!(
     denies_comma_separated_list_without_parens_as_key,
     "$map: (a: 1, b, c, d: e);", "Error: expected \":\"."
 );
+test!(
+    map_get_nested,
+    "@use 'sass:map'; a {b: map.get((c: (d: (e: f))), c, d, e)}",
+    "a {\n  b: f;\n}\n"
+);
+test!(
+    map_get_nested_intermediate_is_not_map,
+    "@use 'sass:map'; a {b: inspect(map.get((c: 1), c, d))}",
+    "a {\n  b: null;\n}\n"
+);
+test!(
+    map_get_nested_missing_key,
+    "a {b: inspect(map-get((c: (d: e)), c, f))}",
+    "a {\n  b: null;\n}\n"
+);
+test!(
+    map_has_key_nested,
+    "@use 'sass:map'; a {b: map.has-key((c: (d: (e: f))), c, d, e)}",
+    "a {\n  b: true;\n}\n"
+);
+test!(
+    map_has_key_nested_missing_key,
+    "@use 'sass:map'; a {b: map.has-key((c: (d: (e: f))), c, d, f)}",
+    "a {\n  b: false;\n}\n"
+);
+test!(
+    map_has_key_nested_intermediate_is_not_map,
+    "a {b: map-has-key((c: 1), c, d)}",
+    "a {\n  b: false;\n}\n"
+);
+test!(
+    map_deep_merge_nested_maps,
+    "@use 'sass:map'; a {b: inspect(map.deep-merge((c: (d: e, f: g), h: i), (c: (f: 1, j: 2), k: 3)))}",
+    "a {\n  b: (c: (d: e, f: 1, j: 2), h: i, k: 3);\n}\n"
+);
+test!(
+    map_deep_merge_replaces_non_map_value,
+    "@use 'sass:map'; a {b: inspect(map.deep-merge((c: (d: e)), (c: 1)))}",
+    "a {\n  b: (c: 1);\n}\n"
+);
+test!(
+    map_deep_merge_map_replaces_non_map_value,
+    "@use 'sass:map'; a {b: inspect(map.deep-merge((c: 1), (c: (d: e))))}",
+    "a {\n  b: (c: (d: e));\n}\n"
+);
+test!(
+    map_deep_merge_empty_list_as_map,
+    "@use 'sass:map'; a {b: inspect(map.deep-merge((c: (d: e)), (c: ())))}",
+    "a {\n  b: (c: (d: e));\n}\n"
+);
+test!(
+    map_deep_merge_empty,
+    "@use 'sass:map'; a {b: inspect(map.deep-merge((), (c: d)))}",
+    "a {\n  b: (c: d);\n}\n"
+);
+test!(
+    map_deep_remove_top_level,
+    "@use 'sass:map'; a {b: inspect(map.deep-remove((c: d, e: f), c))}",
+    "a {\n  b: (e: f);\n}\n"
+);
+test!(
+    map_deep_remove_nested,
+    "@use 'sass:map'; a {b: inspect(map.deep-remove((c: (d: (e: f, g: h))), c, d, e))}",
+    "a {\n  b: (c: (d: (g: h)));\n}\n"
+);
+test!(
+    map_deep_remove_intermediate_is_not_map,
+    "@use 'sass:map'; a {b: inspect(map.deep-remove((c: 1), c, d))}",
+    "a {\n  b: (c: 1);\n}\n"
+);
+error!(
+    map_deep_merge_map2_non_map,
+    "@use 'sass:map'; a {b: map.deep-merge((c: d), foo)}", "Error: $map2: foo is not a map."
+);
+error!(
+    map_deep_remove_missing_key,
+    "@use 'sass:map'; a {b: map.deep-remove((c: d))}", "Error: Missing argument $key."
+);
@@ -244,6 +244,67 @@ test!(
     }"#,
     "@media foo {\n  a {\n    color: red;\n  }\n\n  @import \"foo.css\";\n}\n"
 );
+test!(
+    nested_media_is_merged,
+    "@media screen {\n  @media (min-width: 10px) {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    "@media screen and (min-width: 10px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_media_inside_style_rule_is_merged,
+    "a {\n  @media screen {\n    color: red;\n    @media (min-width: 10px) {\n      color: blue;\n    }\n  }\n}\n",
+    "@media screen {\n  a {\n    color: red;\n  }\n}\n@media screen and (min-width: 10px) {\n  a {\n    color: blue;\n  }\n}\n"
+);
+test!(
+    nested_media_splits_outer_media,
+    "@media screen, print {\n  a {\n    color: red;\n  }\n  @media (color) {\n    b {\n      color: red;\n    }\n  }\n  c {\n    color: red;\n  }\n}\n",
+    "@media screen, print {\n  a {\n    color: red;\n  }\n}\n@media screen and (color), print and (color) {\n  b {\n    color: red;\n  }\n}\n@media screen, print {\n  c {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_media_with_different_types_is_removed,
+    "@media print {\n  @media screen {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    ""
+);
+test!(
+    nested_media_with_negated_type_is_removed,
+    "@media not screen {\n  @media screen {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    ""
+);
+test!(
+    nested_media_keeps_more_specific_type,
+    "@media only screen {\n  @media not print {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    "@media only screen {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    nested_media_inside_supports_is_merged_in_place,
+    "@media screen {\n  @supports (a: b) {\n    @media (color) {\n      a {\n        color: red;\n      }\n    }\n  }\n}\n",
+    "@media screen {\n  @supports (a: b) {\n    @media screen and (color) {\n      a {\n        color: red;\n      }\n    }\n  }\n}\n"
+);
+test!(
+    disjunction_is_not_merged,
+    "@media (a) or (b) {\n  @media (c) {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    "@media (a) or (b) {\n  @media (c) {\n    a {\n      color: red;\n    }\n  }\n}\n"
+);
+test!(
+    negated_condition,
+    "@media not (color) {\n  a {\n    color: red;\n  }\n}\n",
+    "@media (not (color)) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    range_comparison,
+    "@media (width >= 600px) {\n  a {\n    color: red;\n  }\n}\n",
+    "@media (width >= 600px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    range_with_two_comparisons,
+    "@media (400px<width<=700px) {\n  a {\n    color: red;\n  }\n}\n",
+    "@media (400px < width <= 700px) {\n  a {\n    color: red;\n  }\n}\n"
+);
+test!(
+    merged_media_compressed,
+    "@media screen, print {\n  @media (color) {\n    a {\n      color: red;\n    }\n  }\n}\n",
+    "@media screen and (color),print and (color){a{color:red}}",
+    grass::Options::default().style(grass::OutputStyle::Compressed)
+);
 
 error!(
     media_feature_missing_closing_paren,
@@ -253,3 +314,17 @@ error!(
     media_feature_missing_curly_brace_after_hash,
     "@media foo and # {}", "Error: expected \"{\"."
 );
+error!(
+    mixed_and_or_in_condition,
+    "@media (a) and (b) or (c) {}", "Error: expected \"{\"."
+);
+test!(
+    media_with_only_null_styles_is_omitted,
+    "@media screen {\n  a {\n    color: null;\n  }\n}\n",
+    ""
+);
+test!(
+    media_with_only_empty_nested_style_rules_is_omitted,
+    "@media screen {\n  a {\n    b {}\n  }\n}\nc {\n  d: e;\n}\n",
+    "c {\n  d: e;\n}\n"
+);
@@ -6,7 +6,7 @@ mod macros;
 test!(
     module_functions_builtin,
     "@use 'sass:meta';\na {\n  color: inspect(meta.module-functions(meta));\n}\n",
-    "a {\n  color: (\"feature-exists\": get-function(\"feature-exists\"), \"inspect\": get-function(\"inspect\"), \"type-of\": get-function(\"type-of\"), \"keywords\": get-function(\"keywords\"), \"global-variable-exists\": get-function(\"global-variable-exists\"), \"variable-exists\": get-function(\"variable-exists\"), \"function-exists\": get-function(\"function-exists\"), \"mixin-exists\": get-function(\"mixin-exists\"), \"content-exists\": get-function(\"content-exists\"), \"module-variables\": get-function(\"module-variables\"), \"module-functions\": get-function(\"module-functions\"), \"get-function\": get-function(\"get-function\"), \"call\": get-function(\"call\"));\n}\n"
+    "a {\n  color: (\"feature-exists\": get-function(\"feature-exists\"), \"inspect\": get-function(\"inspect\"), \"type-of\": get-function(\"type-of\"), \"keywords\": get-function(\"keywords\"), \"global-variable-exists\": get-function(\"global-variable-exists\"), \"variable-exists\": get-function(\"variable-exists\"), \"function-exists\": get-function(\"function-exists\"), \"mixin-exists\": get-function(\"mixin-exists\"), \"content-exists\": get-function(\"content-exists\"), \"module-variables\": get-function(\"module-variables\"), \"module-functions\": get-function(\"module-functions\"), \"get-function\": get-function(\"get-function\"), \"call\": get-function(\"call\"), \"get-mixin\": get-function(\"get-mixin\"), \"accepts-content\": get-function(\"accepts-content\"));\n}\n"
 );
 test!(
     module_variables_builtin,
@@ -71,3 +71,80 @@ fn load_css_non_map_with() {
     let input = "@use \"sass:meta\";\na {\n @include meta.load-css(foo, 2);\n}";
     assert_err!("Error: $with: 2 is not a map.", input);
 }
+test!(
+    get_mixin_type_of,
+    "@use 'sass:meta';\n@mixin foo {}\na {\n  color: type-of(meta.get-mixin(foo));\n}\n",
+    "a {\n  color: mixin;\n}\n"
+);
+test!(
+    get_mixin_inspect,
+    "@use 'sass:meta';\n@mixin foo {}\na {\n  color: inspect(meta.get-mixin(\"foo\"));\n}\n",
+    "a {\n  color: get-mixin(\"foo\");\n}\n"
+);
+test!(
+    get_mixin_equality,
+    "@use 'sass:meta';\n@mixin foo {}\n@mixin bar {}\na {\n  color: meta.get-mixin(foo) == meta.get-mixin(foo);\n  color: meta.get-mixin(foo) == meta.get-mixin(bar);\n}\n",
+    "a {\n  color: true;\n  color: false;\n}\n"
+);
+test!(
+    apply_with_args,
+    "@use 'sass:meta';\n@mixin foo($a, $b: 2) {\n  a: $a;\n  b: $b;\n}\na {\n  @include meta.apply(meta.get-mixin(foo), 1, $b: 3);\n}\n",
+    "a {\n  a: 1;\n  b: 3;\n}\n"
+);
+test!(
+    apply_mixin_passed_as_argument,
+    "@use 'sass:meta';\n@mixin primary {\n  color: red;\n}\n@mixin button($theme) {\n  @include meta.apply($theme);\n}\na {\n  @include button(meta.get-mixin(primary));\n}\n",
+    "a {\n  color: red;\n}\n"
+);
+test!(
+    apply_forwards_content_block,
+    "@use 'sass:meta';\n@mixin wrap {\n  b {\n    @content;\n  }\n}\na {\n  @include meta.apply(meta.get-mixin(wrap)) {\n    color: red;\n  }\n}\n",
+    "a b {\n  color: red;\n}\n"
+);
+test!(
+    apply_forwards_content_block_args,
+    "@use 'sass:meta';\n@mixin wrap {\n  @content(1px);\n}\na {\n  @include meta.apply(meta.get-mixin(wrap)) using ($width) {\n    width: $width;\n  }\n}\n",
+    "a {\n  width: 1px;\n}\n"
+);
+test!(
+    accepts_content,
+    "@use 'sass:meta';\n@mixin foo {\n  a: \"@content\";\n}\n@mixin bar {\n  @content;\n}\na {\n  color: meta.accepts-content(meta.get-mixin(foo));\n  color: meta.accepts-content(meta.get-mixin(bar));\n  color: meta.accepts-content(meta.get-mixin(apply, meta));\n  color: meta.accepts-content(meta.get-mixin(load-css, meta));\n}\n",
+    "a {\n  color: false;\n  color: true;\n  color: true;\n  color: false;\n}\n"
+);
+error!(
+    get_mixin_not_found,
+    "@use 'sass:meta';\na {\n  color: meta.get-mixin(foo);\n}\n", "Error: Mixin not found: foo"
+);
+error!(
+    get_mixin_as_css_value,
+    "@use 'sass:meta';\n@mixin foo {}\na {\n  color: meta.get-mixin(foo);\n}\n",
+    "Error: get-mixin(\"foo\") isn't a valid CSS value."
+);
+error!(
+    apply_non_mixin,
+    "@use 'sass:meta';\na {\n  @include meta.apply(foo);\n}\n",
+    "Error: $mixin: foo is not a mixin reference."
+);
+error!(
+    apply_content_to_mixin_without_content,
+    "@use 'sass:meta';\n@mixin foo {}\na {\n  @include meta.apply(meta.get-mixin(foo)) {\n    color: red;\n  }\n}\n",
+    "Error: Mixin doesn't accept a content block."
+);
+error!(
+    accepts_content_non_mixin,
+    "@use 'sass:meta';\na {\n  color: meta.accepts-content(1);\n}\n",
+    "Error: $mixin: 1 is not a mixin reference."
+);
+
+#[test]
+fn get_mixin_from_module() {
+    let input = "@use \"sass:meta\";\n@use \"get_mixin_from_module\" as module;\na {\n  @include meta.apply(meta.get-mixin(foo, $module: module), red);\n}";
+    tempfile!(
+        "get_mixin_from_module.scss",
+        "$default: blue;\n@mixin foo($color) { color: $color; background: $default; }"
+    );
+    assert_eq!(
+        "a {\n  color: red;\n  background: blue;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
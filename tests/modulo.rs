@@ -29,37 +29,37 @@ test!(
 test!(
     zero_mod_zero,
     "a {\n  color: 0 % 0;\n}\n",
-    "a {\n  color: NaN;\n}\n"
+    "a {\n  color: calc(NaN);\n}\n"
 );
 test!(
     positive_mod_zero,
     "a {\n  color: 1 % 0;\n}\n",
-    "a {\n  color: NaN;\n}\n"
+    "a {\n  color: calc(NaN);\n}\n"
 );
 test!(
     positive_unit_mod_zero,
     "a {\n  color: 1px % 0;\n}\n",
-    "a {\n  color: NaNpx;\n}\n"
+    "a {\n  color: calc(NaN * 1px);\n}\n"
 );
 test!(
     positive_mod_zero_unit,
     "a {\n  color: 1 % 0px;\n}\n",
-    "a {\n  color: NaNpx;\n}\n"
+    "a {\n  color: calc(NaN * 1px);\n}\n"
 );
 test!(
     positive_unit_mod_zero_unit_same,
     "a {\n  color: 1px % 0px;\n}\n",
-    "a {\n  color: NaNpx;\n}\n"
+    "a {\n  color: calc(NaN * 1px);\n}\n"
 );
 test!(
     positive_unit_mod_zero_unit_different_compatible_takes_first_1,
     "a {\n  color: 1px % 0in;\n}\n",
-    "a {\n  color: NaNpx;\n}\n"
+    "a {\n  color: calc(NaN * 1px);\n}\n"
 );
 test!(
     positive_unit_mod_zero_unit_different_compatible_takes_first_2,
     "a {\n  color: 1in % 0px;\n}\n",
-    "a {\n  color: NaNin;\n}\n"
+    "a {\n  color: calc(NaN * 1in);\n}\n"
 );
 error!(
     positive_unit_mod_zero_unit_incompatible_units,
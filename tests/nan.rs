@@ -16,24 +16,27 @@ error!(
 test!(
     unitless_nan_percentage_number,
     "a {\n  color: percentage((0/0));\n}\n",
-    "a {\n  color: NaN%;\n}\n"
+    "a {\n  color: calc(NaN * 1%);\n}\n"
 );
 test!(
     unitless_nan_abs_number,
     "a {\n  color: abs((0/0));\n}\n",
-    "a {\n  color: NaN;\n}\n"
+    "a {\n  color: calc(NaN);\n}\n"
 );
-error!(
+test!(
     unitless_nan_round_number,
-    "a {\n  color: round((0/0));\n}\n", "Error: Infinity or NaN toInt"
+    "a {\n  color: round((0/0));\n}\n",
+    "a {\n  color: calc(NaN);\n}\n"
 );
-error!(
+test!(
     unitless_nan_ceil_number,
-    "a {\n  color: ceil((0/0));\n}\n", "Error: Infinity or NaN toInt"
+    "a {\n  color: ceil((0/0));\n}\n",
+    "a {\n  color: calc(NaN);\n}\n"
 );
-error!(
+test!(
     unitless_nan_floor_number,
-    "a {\n  color: floor((0/0));\n}\n", "Error: Infinity or NaN toInt"
+    "a {\n  color: floor((0/0));\n}\n",
+    "a {\n  color: calc(NaN);\n}\n"
 );
 error!(
     unitless_nan_random_limit,
@@ -50,7 +53,7 @@ error!(
 test!(
     unitless_nan_min_first_arg,
     "$n: (0/0);\na {\n  color: min($n, 1px);\n}\n",
-    "a {\n  color: NaN;\n}\n"
+    "a {\n  color: calc(NaN);\n}\n"
 );
 test!(
     unitless_nan_min_last_arg,
@@ -65,7 +68,7 @@ test!(
 test!(
     unitless_nan_max_first_arg,
     "$n: (0/0);\na {\n  color: max($n, 1px);\n}\n",
-    "a {\n  color: NaN;\n}\n"
+    "a {\n  color: calc(NaN);\n}\n"
 );
 test!(
     unitless_nan_max_last_arg,
@@ -80,74 +83,77 @@ test!(
 error!(
     unitful_nan_str_slice_start,
     "@use \"sass:math\";\na {\n  color: str-slice(\"\", math.acos(2));\n}\n",
-    "Error: $start: Expected NaNdeg to have no units."
+    "Error: $start: Expected calc(NaN * 1deg) to have no units."
 );
 error!(
     unitful_nan_str_slice_end,
     "@use \"sass:math\";\na {\n  color: str-slice(\"\", 0, math.acos(2));\n}\n",
-    "Error: $end: Expected NaNdeg to have no units."
+    "Error: $end: Expected calc(NaN * 1deg) to have no units."
 );
 error!(
     unitful_nan_str_insert_index,
     "@use \"sass:math\";\na {\n  color: str-insert(\"\", \"\", math.acos(2));\n}\n",
-    "Error: $index: Expected NaNdeg to have no units."
+    "Error: $index: Expected calc(NaN * 1deg) to have no units."
 );
 error!(
     unitful_nan_percentage,
     "@use \"sass:math\";\na {\n  color: percentage(math.acos(2));\n}\n",
-    "Error: $number: Expected NaNdeg to have no units."
+    "Error: $number: Expected calc(NaN * 1deg) to have no units."
 );
-error!(
+test!(
     unitful_nan_round,
-    "@use \"sass:math\";\na {\n  color: round(math.acos(2));\n}\n", "Error: Infinity or NaN toInt"
+    "@use \"sass:math\";\na {\n  color: round(math.acos(2));\n}\n",
+    "a {\n  color: calc(NaN * 1deg);\n}\n"
 );
-error!(
+test!(
     unitful_nan_ceil,
-    "@use \"sass:math\";\na {\n  color: ceil(math.acos(2));\n}\n", "Error: Infinity or NaN toInt"
+    "@use \"sass:math\";\na {\n  color: ceil(math.acos(2));\n}\n",
+    "a {\n  color: calc(NaN * 1deg);\n}\n"
 );
-error!(
+test!(
     unitful_nan_floor,
-    "@use \"sass:math\";\na {\n  color: floor(math.acos(2));\n}\n", "Error: Infinity or NaN toInt"
+    "@use \"sass:math\";\na {\n  color: floor(math.acos(2));\n}\n",
+    "a {\n  color: calc(NaN * 1deg);\n}\n"
 );
 test!(
     unitful_nan_abs,
     "@use \"sass:math\";\na {\n  color: abs(math.acos(2));\n}\n",
-    "a {\n  color: NaNdeg;\n}\n"
+    "a {\n  color: calc(NaN * 1deg);\n}\n"
 );
 error!(
     unitful_nan_random,
     "@use \"sass:math\";\na {\n  color: random(math.acos(2));\n}\n",
     "Error: $limit: NaNdeg is not an int."
 );
-test!(
+error!(
     unitful_nan_min_first_arg,
     "@use \"sass:math\";\na {\n  color: min(math.acos(2), 1px);\n}\n",
-    "a {\n  color: NaNdeg;\n}\n"
+    "Error: calc(NaN * 1deg) and 1px are incompatible."
 );
-test!(
+error!(
     unitful_nan_min_last_arg,
     "@use \"sass:math\";\na {\n  color: min(1px, math.acos(2));\n}\n",
-    "a {\n  color: 1px;\n}\n"
+    "Error: 1px and calc(NaN * 1deg) are incompatible."
 );
-test!(
+error!(
     unitful_nan_min_middle_arg,
     "@use \"sass:math\";\na {\n  color: min(1px, math.acos(2), 0);\n}\n",
-    "a {\n  color: 0;\n}\n"
+    "Error: 1px and calc(NaN * 1deg) are incompatible."
 );
-test!(
+error!(
     unitful_nan_max_first_arg,
     "@use \"sass:math\";\na {\n  color: max(math.acos(2), 1px);\n}\n",
-    "a {\n  color: NaNdeg;\n}\n"
+    "Error: calc(NaN * 1deg) and 1px are incompatible."
 );
-test!(
+error!(
     unitful_nan_max_last_arg,
     "@use \"sass:math\";\na {\n  color: max(1px, math.acos(2));\n}\n",
-    "a {\n  color: 1px;\n}\n"
+    "Error: 1px and calc(NaN * 1deg) are incompatible."
 );
-test!(
+error!(
     unitful_nan_max_middle_arg,
     "@use \"sass:math\";\na {\n  color: max(1px, math.acos(2), 0);\n}\n",
-    "a {\n  color: 1px;\n}\n"
+    "Error: 1px and calc(NaN * 1deg) are incompatible."
 );
 error!(
     unitful_nan_nth_n,
@@ -162,15 +168,15 @@ error!(
 test!(
     nan_unary_negative,
     "a {\n  color: -(0/0);\n}\n",
-    "a {\n  color: NaN;\n}\n"
+    "a {\n  color: calc(NaN);\n}\n"
 );
 test!(
     nan_unary_plus,
     "a {\n  color: +(0/0);\n}\n",
-    "a {\n  color: NaN;\n}\n"
+    "a {\n  color: calc(NaN);\n}\n"
 );
 test!(
     nan_unary_div,
     "a {\n  color: /(0/0);\n}\n",
-    "a {\n  color: /NaN;\n}\n"
+    "a {\n  color: /calc(NaN);\n}\n"
 );
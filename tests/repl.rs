@@ -0,0 +1,133 @@
+use std::io::Write;
+
+use grass::{Repl, SassValue};
+
+#[macro_use]
+mod macros;
+
+fn evaluate_all(lines: &[&str], options: &grass::Options) -> Vec<Option<String>> {
+    let mut repl = Repl::new(options).unwrap();
+    lines
+        .iter()
+        .map(|line| repl.evaluate(line).expect(line))
+        .collect()
+}
+
+fn error_message(lines: &[&str]) -> String {
+    let options = grass::Options::default();
+    let mut repl = Repl::new(&options).unwrap();
+    let (last, rest) = lines.split_last().unwrap();
+    for line in rest {
+        repl.evaluate(line).expect(line);
+    }
+    match repl.evaluate(last) {
+        Ok(..) => panic!("did not fail"),
+        Err(e) => e
+            .to_string()
+            .chars()
+            .take_while(|c| *c != '\n')
+            .collect::<String>(),
+    }
+}
+
+fn some(value: &str) -> Option<String> {
+    Some(value.to_owned())
+}
+
+#[test]
+fn expression_is_inspected() {
+    assert_eq!(
+        vec![some("3px"), some("\"ab\""), some("(a: 1)"), some("null")],
+        evaluate_all(
+            &["1px + 2", "\"a\" + b", "(a: 1)", "null"],
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn variables_persist() {
+    assert_eq!(
+        vec![some("2"), some("4"), some("2")],
+        evaluate_all(
+            &["$a: 2", "$a * 2", "$a: 3 !default"],
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn builtin_modules_persist() {
+    assert_eq!(
+        vec![None, some("2.5px"), None, some("18")],
+        evaluate_all(
+            &[
+                "@use \"sass:math\"",
+                "math.div(10px, 4)",
+                "@use \"sass:color\" as c;",
+                "c.red(#123456)"
+            ],
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn user_functions_persist() {
+    assert_eq!(
+        vec![None, some("4px")],
+        evaluate_all(
+            &["@function double($n) { @return $n * 2; }", "double(2px)"],
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn user_module_is_loaded() {
+    tempfile!("repl_user_module.scss", "$gutter: 8px;");
+    assert_eq!(
+        vec![None, some("16px")],
+        evaluate_all(
+            &["@use \"repl_user_module\" as m", "m.$gutter * 2"],
+            &grass::Options::default()
+        )
+    );
+}
+
+#[test]
+fn custom_function_is_callable() {
+    assert_eq!(
+        vec![some("a")],
+        evaluate_all(
+            &["identity(a)"],
+            &grass::Options::default().add_function("identity($a)", &|args| Ok(args[0].clone()))
+        )
+    );
+}
+
+#[test]
+fn state_survives_error() {
+    let options = grass::Options::default();
+    let mut repl = Repl::new(&options).unwrap();
+    repl.evaluate("$a: 1").unwrap();
+    assert!(repl.evaluate("$a +").is_err());
+    assert_eq!(some("1"), repl.evaluate("$a").unwrap());
+}
+
+#[test]
+fn undefined_variable() {
+    assert_eq!("Error: Undefined variable.", error_message(&["$a"]));
+}
+
+#[test]
+fn trailing_input() {
+    assert_eq!("Error: expected no more input.", error_message(&["1;2"]));
+}
+
+#[test]
+fn invalid_custom_function_signature() {
+    let options =
+        grass::Options::default().add_function("foo", &|_: &[SassValue]| Ok(SassValue::Null));
+    assert!(Repl::new(&options).is_err());
+}
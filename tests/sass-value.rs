@@ -0,0 +1,171 @@
+use grass::{ListSeparator, SassColor, SassNumber, SassValue};
+
+fn list(elements: Vec<SassValue>, separator: ListSeparator, bracketed: bool) -> SassValue {
+    SassValue::List {
+        elements,
+        separator,
+        bracketed,
+    }
+}
+
+#[test]
+fn compatible_units_are_equal() {
+    assert_eq!(
+        SassNumber::with_unit(1.0, "in"),
+        SassNumber::with_unit(96.0, "px")
+    );
+    assert_ne!(SassNumber::with_unit(1.0, "px"), SassNumber::new(1.0));
+}
+
+#[test]
+fn nan_is_not_equal_to_itself() {
+    assert_ne!(SassNumber::new(f64::NAN), SassNumber::new(f64::NAN));
+}
+
+#[test]
+fn quoted_and_unquoted_strings_are_equal() {
+    assert_eq!(
+        SassValue::quoted_string("a"),
+        SassValue::unquoted_string("a")
+    );
+}
+
+#[test]
+fn list_equality_respects_separator_and_brackets() {
+    let a = || SassValue::unquoted_string("a");
+    assert_eq!(
+        list(vec![a(), a()], ListSeparator::Comma, false),
+        list(vec![a(), a()], ListSeparator::Comma, false)
+    );
+    assert_ne!(
+        list(vec![a(), a()], ListSeparator::Comma, false),
+        list(vec![a(), a()], ListSeparator::Space, false)
+    );
+    assert_ne!(
+        list(vec![a(), a()], ListSeparator::Space, false),
+        list(vec![a(), a()], ListSeparator::Space, true)
+    );
+}
+
+#[test]
+fn map_equality_ignores_order() {
+    let a = SassValue::Map(vec![
+        (SassValue::unquoted_string("a"), SassValue::from(1.0)),
+        (SassValue::unquoted_string("b"), SassValue::from(2.0)),
+    ]);
+    let b = SassValue::Map(vec![
+        (SassValue::unquoted_string("b"), SassValue::from(2.0)),
+        (SassValue::unquoted_string("a"), SassValue::from(1.0)),
+    ]);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn colors_compare_by_channels() {
+    assert_eq!(
+        SassColor::rgba(255.0, 0.0, 0.0, 1.0),
+        SassColor::hsla(0.0, 100.0, 50.0, 1.0)
+    );
+}
+
+#[test]
+fn color_channels() {
+    let color = SassColor::rgba(300.0, 128.0, 0.0, 0.5);
+    assert_eq!(255.0, color.red());
+    assert_eq!(128.0, color.green());
+    assert_eq!(0.0, color.blue());
+    assert_eq!(0.5, color.alpha());
+}
+
+#[test]
+fn number_value_and_unit() {
+    let number = SassNumber::with_unit(1.5, "px");
+    assert_eq!(1.5, number.value());
+    assert_eq!("px", number.unit());
+    assert!(!number.is_unitless());
+    assert!(SassNumber::new(1.0).is_unitless());
+}
+
+#[test]
+fn inspect() {
+    assert_eq!("null", SassValue::Null.inspect().unwrap());
+    assert_eq!("\"a\"", SassValue::quoted_string("a").inspect().unwrap());
+    assert_eq!("1.5px", SassNumber::with_unit(1.5, "px").to_string());
+    assert_eq!(
+        "(1,)",
+        list(vec![SassValue::from(1.0)], ListSeparator::Comma, false)
+            .inspect()
+            .unwrap()
+    );
+    assert_eq!(
+        "[]",
+        list(Vec::new(), ListSeparator::Space, true)
+            .inspect()
+            .unwrap()
+    );
+    assert_eq!(
+        "(a: 1, b: 2)",
+        SassValue::Map(vec![
+            (SassValue::unquoted_string("a"), SassValue::from(1.0)),
+            (SassValue::unquoted_string("b"), SassValue::from(2.0)),
+        ])
+        .to_string()
+    );
+}
+
+#[test]
+fn to_css_string() {
+    assert_eq!(
+        "1px solid red",
+        list(
+            vec![
+                SassNumber::with_unit(1.0, "px").into(),
+                SassValue::unquoted_string("solid"),
+                SassColor::rgba(255.0, 0.0, 0.0, 1.0).into(),
+            ],
+            ListSeparator::Space,
+            false
+        )
+        .to_css_string(false)
+        .unwrap()
+    );
+    assert_eq!(
+        "a,b",
+        list(
+            vec![
+                SassValue::unquoted_string("a"),
+                SassValue::unquoted_string("b")
+            ],
+            ListSeparator::Comma,
+            false
+        )
+        .to_css_string(true)
+        .unwrap()
+    );
+    assert_eq!("", SassValue::Null.to_css_string(false).unwrap());
+}
+
+#[test]
+fn map_is_not_valid_css() {
+    assert_eq!(
+        "Error: (a: 1) isn't a valid CSS value.\n",
+        SassValue::Map(vec![(
+            SassValue::unquoted_string("a"),
+            SassValue::from(1.0)
+        )])
+        .to_css_string(false)
+        .unwrap_err()
+        .to_string()
+    );
+}
+
+#[test]
+fn type_name_and_truthiness() {
+    assert_eq!("number", SassValue::from(1.0).type_name());
+    assert_eq!("bool", SassValue::from(true).type_name());
+    assert_eq!("map", SassValue::Map(Vec::new()).type_name());
+    assert!(!SassValue::from(false).is_truthy());
+    assert!(!SassValue::Null.is_truthy());
+    assert!(SassValue::Null.is_null());
+    assert!(SassValue::unquoted_string("").is_truthy());
+}
@@ -0,0 +1,149 @@
+use std::io::Write;
+
+#[macro_use]
+mod macros;
+
+fn source_map(input: &str, options: &grass::Options) -> String {
+    grass::compile_string(input.to_string(), options)
+        .expect(input)
+        .source_map()
+        .expect("no source map was generated")
+        .to_owned()
+}
+
+#[test]
+fn no_source_map_by_default() {
+    let result = grass::compile_string(
+        "a {\n  color: red;\n}\n".to_string(),
+        &grass::Options::default(),
+    )
+    .unwrap();
+    assert_eq!("a {\n  color: red;\n}\n", result.css());
+    assert_eq!(None, result.source_map());
+}
+
+#[test]
+fn single_ruleset() {
+    assert_eq!(
+        r#"{"version":3,"sourceRoot":"","sources":["stdin"],"names":[],"mappings":"AAAA;EACE"}"#,
+        source_map(
+            "a {\n  color: red;\n}\n",
+            &grass::Options::default().source_map(true)
+        )
+    );
+}
+
+#[test]
+fn nested_ruleset() {
+    assert_eq!(
+        r#"{"version":3,"sourceRoot":"","sources":["stdin"],"names":[],"mappings":"AAAA;EACE;;AACA;EAAI"}"#,
+        source_map(
+            "a {\n  color: red;\n  b { width: 1px }\n}\n",
+            &grass::Options::default().source_map(true)
+        )
+    );
+}
+
+#[test]
+fn nested_property() {
+    assert_eq!(
+        r#"{"version":3,"sourceRoot":"","sources":["stdin"],"names":[],"mappings":"AAAA;EAEI"}"#,
+        source_map(
+            "a {\n  font: {\n    family: b;\n  }\n}\n",
+            &grass::Options::default().source_map(true)
+        )
+    );
+}
+
+#[test]
+fn compressed() {
+    assert_eq!(
+        r#"{"version":3,"sourceRoot":"","sources":["stdin"],"names":[],"mappings":"AAAA,EACE,UACA,IAAI"}"#,
+        source_map(
+            "a {\n  color: red;\n  b { width: 1px }\n}\n",
+            &grass::Options::default()
+                .source_map(true)
+                .style(grass::OutputStyle::Compressed)
+        )
+    );
+}
+
+#[test]
+fn charset_shifts_mappings() {
+    assert_eq!(
+        r#"{"version":3,"sourceRoot":"","sources":["stdin"],"names":[],"mappings":";AAAA;EACE"}"#,
+        source_map(
+            "a {\n  color: \"ä\";\n}\n",
+            &grass::Options::default().source_map(true)
+        )
+    );
+}
+
+#[test]
+fn columns_are_utf16() {
+    assert_eq!(
+        r#"{"version":3,"sourceRoot":"","sources":["stdin"],"names":[],"mappings":";AAAA;;EACW"}"#,
+        source_map(
+            "a {\n  /* 😀 */ color: red;\n}\n",
+            &grass::Options::default().source_map(true)
+        )
+    );
+}
+
+#[test]
+fn embed_sources() {
+    assert_eq!(
+        r#"{"version":3,"sourceRoot":"","sources":["stdin"],"sourcesContent":["a {\n  color: \"b\";\n}\n"],"names":[],"mappings":"AAAA;EACE"}"#,
+        source_map(
+            "a {\n  color: \"b\";\n}\n",
+            &grass::Options::default()
+                .source_map(true)
+                .embed_sources(true)
+        )
+    );
+}
+
+#[test]
+fn imported_file_is_relative_source() {
+    let input = "@import \"imported_file_is_relative_source__a\";\nb {\n  color: red;\n}\n";
+    tempfile!(
+        "imported_file_is_relative_source__a.scss",
+        "a {\n  color: red;\n}\n"
+    );
+    assert_eq!(
+        r#"{"version":3,"sourceRoot":"","sources":["imported_file_is_relative_source__a.scss","stdin"],"names":[],"mappings":"AAAA;EACE;;;ACAF;EACE"}"#,
+        source_map(input, &grass::Options::default().source_map(true))
+    );
+}
+
+#[test]
+fn source_map_path_changes_relative_urls() {
+    let input = "@import \"source_map_path_changes_relative_urls__a\";\n";
+    tempfile!(
+        "source_map_path_changes_relative_urls__a.scss",
+        "a {\n  color: red;\n}\n"
+    );
+    assert_eq!(
+        r#"{"version":3,"sourceRoot":"","sources":["../source_map_path_changes_relative_urls__a.scss"],"names":[],"mappings":"AAAA;EACE"}"#,
+        source_map(
+            input,
+            &grass::Options::default()
+                .source_map(true)
+                .source_map_path(std::path::Path::new("out/style.css.map"))
+        )
+    );
+}
+
+#[test]
+fn absolute_urls() {
+    let input = "@import \"absolute_urls__a\";\n";
+    tempfile!("absolute_urls__a.scss", "a {\n  color: red;\n}\n");
+    let map = source_map(
+        input,
+        &grass::Options::default()
+            .source_map(true)
+            .source_map_urls(grass::SourceMapUrls::Absolute),
+    );
+    assert!(map.starts_with(r#"{"version":3,"sourceRoot":"","sources":["file:///"#));
+    assert!(map.contains(r#"/absolute_urls__a.scss"]"#));
+}
@@ -4,7 +4,7 @@ mod macros;
 test!(
     calc_whitespace,
     "a {\n  color: calc(       1      );\n}\n",
-    "a {\n  color: calc( 1 );\n}\n"
+    "a {\n  color: 1;\n}\n"
 );
 test!(
     calc_newline,
@@ -17,9 +17,9 @@ test!(
     "a {\n  color: calc(1, 2, a, b, c);\n}\n"
 );
 test!(
-    calc_does_not_evaluate_arithmetic,
+    calc_evaluates_arithmetic,
     "a {\n  color: calc(1 + 2);\n}\n",
-    "a {\n  color: calc(1 + 2);\n}\n"
+    "a {\n  color: 3;\n}\n"
 );
 test!(
     calc_evaluates_interpolated_arithmetic,
@@ -42,9 +42,9 @@ test!(
     "a {\n  color: calc((((()))));\n}\n"
 );
 test!(
-    calc_invalid_arithmetic,
+    calc_partially_simplified_arithmetic,
     "a {\n  color: calc(2px + 2px + 5%);\n}\n",
-    "a {\n  color: calc(2px + 2px + 5%);\n}\n"
+    "a {\n  color: calc(4px + 5%);\n}\n"
 );
 test!(
     calc_uppercase,
@@ -244,13 +244,12 @@ test!(
     "a {\n  color: clamp((()));\n}\n",
     "a {\n  color: clamp((()));\n}\n"
 );
-test!(
+error!(
     clamp_single_arg,
-    "a {\n  color: clamp(1);\n}\n",
-    "a {\n  color: clamp(1);\n}\n"
+    "a {\n  color: clamp(1);\n}\n", "Error: 3 arguments required, but only 1 was passed."
 );
 test!(
     clamp_many_args,
     "a {\n  color: clamp(1, 2, 3);\n}\n",
-    "a {\n  color: clamp(1, 2, 3);\n}\n"
+    "a {\n  color: 2;\n}\n"
 );
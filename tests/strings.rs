@@ -239,3 +239,58 @@ test!(
     "a {\n  color: \"#foo\";\n}\n",
     "a {\n  color: \"#foo\";\n}\n"
 );
+test!(
+    str_index_counts_codepoints,
+    "a {\n  color: str-index(\"ébc\", \"c\");\n}\n",
+    "a {\n  color: 3;\n}\n"
+);
+test!(
+    string_split,
+    "@use \"sass:string\";\na {\n  color: string.split(\"spacing.lg\", \".\");\n}\n",
+    "a {\n  color: [\"spacing\", \"lg\"];\n}\n"
+);
+test!(
+    string_split_unquoted,
+    "@use \"sass:string\";\na {\n  color: string.split(a-b-c, \"-\");\n}\n",
+    "a {\n  color: [a, b, c];\n}\n"
+);
+test!(
+    string_split_with_limit,
+    "@use \"sass:string\";\na {\n  color: string.split(\"a b c d\", \" \", 2);\n}\n",
+    "a {\n  color: [\"a\", \"b\", \"c d\"];\n}\n"
+);
+test!(
+    string_split_empty_separator,
+    "@use \"sass:string\";\na {\n  color: string.split(\"aéb\", \"\");\n}\n",
+    "@charset \"UTF-8\";\na {\n  color: [\"a\", \"é\", \"b\"];\n}\n"
+);
+test!(
+    string_split_multibyte_separator,
+    "@use \"sass:string\";\na {\n  color: string.split(\"aébéc\", \"é\");\n}\n",
+    "a {\n  color: [\"a\", \"b\", \"c\"];\n}\n"
+);
+test!(
+    string_split_separator_not_found,
+    "@use \"sass:string\";\na {\n  color: string.split(\"a.b\", \"-\");\n}\n",
+    "a {\n  color: [\"a.b\"];\n}\n"
+);
+test!(
+    string_split_empty_string,
+    "@use \"sass:string\";\na {\n  color: inspect(string.split(\"\", \".\"));\n}\n",
+    "a {\n  color: [];\n}\n"
+);
+error!(
+    string_split_limit_zero,
+    "@use \"sass:string\";\na {\n  color: string.split(\"a.b\", \".\", 0);\n}\n",
+    "Error: $limit: Must be 1 or greater, was 0."
+);
+error!(
+    string_split_limit_decimal,
+    "@use \"sass:string\";\na {\n  color: string.split(\"a.b\", \".\", 1.5);\n}\n",
+    "Error: $limit: 1.5 is not an int."
+);
+error!(
+    string_split_separator_not_string,
+    "@use \"sass:string\";\na {\n  color: string.split(\"a.b\", 1);\n}\n",
+    "Error: $separator: 1 is not a string."
+);
@@ -63,3 +63,13 @@ test!(
     }",
     "@supports (position: sticky) {\n  a {\n    color: red;\n  }\n}\n\na {\n  color: red;\n}\n"
 );
+test!(
+    supports_with_only_empty_style_rules_is_omitted,
+    "@supports (a: b) {\n  c {}\n}\nd {\n  e: f;\n}\n",
+    "d {\n  e: f;\n}\n"
+);
+test!(
+    supports_emptied_by_at_root_is_omitted,
+    "@supports (a: b) {\n  c {\n    @at-root (without: supports) {\n      d: e;\n    }\n  }\n}\n",
+    "c {\n  d: e;\n}\n"
+);
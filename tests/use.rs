@@ -442,3 +442,38 @@ fn use_variable_declaration_between_use() {
         &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
     );
 }
+
+#[test]
+fn use_default_namespace_is_basename() {
+    let input = "@use \"use_default_namespace_is_basename/_a.scss\";\na {\n color: a.$a;\n}";
+    tempfile!(
+        "_a.scss",
+        "$a: red;",
+        dir = "use_default_namespace_is_basename"
+    );
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}
+
+error!(
+    use_default_namespace_not_identifier,
+    "@use \"foo/1-bar\";", "Error: The default namespace \"1-bar\" is not a valid Sass identifier."
+);
+#[test]
+fn use_default_namespace_not_identifier_with_as() {
+    let input =
+        "@use \"use_default_namespace_not_identifier_with_as/1-a\" as a;\na {\n color: a.$a;\n}";
+    tempfile!(
+        "_1-a.scss",
+        "$a: red;",
+        dir = "use_default_namespace_not_identifier_with_as"
+    );
+
+    assert_eq!(
+        "a {\n  color: red;\n}\n",
+        &grass::from_string(input.to_string(), &grass::Options::default()).expect(input)
+    );
+}